@@ -0,0 +1,440 @@
+//! Rules of connect four on boards of any size up to 128 cells, as played by the connect four
+//! pallet.
+//!
+//! Wallets, indexers and bots can replay and check games with the exact rules of the chain
+//! without pulling in FRAME. Players are `PLAYER_1` (red) and `PLAYER_2` (blue).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+pub mod notation;
+#[cfg(test)]
+mod tests;
+
+/// Red, usually the player moving first.
+pub const PLAYER_1: u8 = 1;
+
+/// Blue.
+pub const PLAYER_2: u8 = 2;
+
+/// Board cells column by column, each column ordered from the top row (`0`) down to the bottom
+/// row, `0` for an empty cell. Only used to show boards, see `Board::cells`.
+pub type Cells = Vec<Vec<u8>>;
+
+/// Bits available to a board, every column takes one more bit than it has rows.
+const BOARD_BITS: u32 = u128::BITS;
+
+/// Stones of both players as one bitboard each.
+///
+/// Every column takes `height + 1` bits, from the bottom row up. The extra bit on top always
+/// stays empty, so lines found by shifting the bitboard can't wrap into the next column.
+/// Rows are counted from the top everywhere outside of this module.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct Board {
+	width: u8,
+	height: u8,
+	/// Stones of `PLAYER_1` and `PLAYER_2`.
+	stones: [u128; 2],
+}
+
+impl Board {
+	/// A board of `width` columns and `height` rows fits into the bitboards.
+	pub fn fits(width: u8, height: u8) -> bool {
+		width as u32 * (height as u32 + 1) <= BOARD_BITS
+	}
+
+	/// Number of columns.
+	pub fn width(&self) -> u8 {
+		self.width
+	}
+
+	/// Number of rows.
+	pub fn height(&self) -> u8 {
+		self.height
+	}
+
+	/// Player owning the stone at `column` and `row` from the top, `0` if the cell is empty.
+	pub fn cell(&self, column: u8, row: u8) -> u8 {
+		if column >= self.width || row >= self.height {
+			return 0;
+		}
+		let bit = 1u128 << self.bit(column, self.height - 1 - row);
+		self.stones
+			.iter()
+			.position(|stones| stones & bit != 0)
+			.map_or(0, |i| i as u8 + 1)
+	}
+
+	/// Cells of the board, for events and clients.
+	pub fn cells(&self) -> Cells {
+		(0..self.width)
+			.map(|column| (0..self.height).map(|row| self.cell(column, row)).collect())
+			.collect()
+	}
+
+	/// Board holding `cells`, `None` if they don't fit or a stone floats above an empty cell.
+	pub fn from_cells<C: AsRef<[u8]>>(cells: &[C]) -> Option<Board> {
+		let height = cells.first().map_or(0, |column| column.as_ref().len());
+		let mut board = Logic::new_board(cells.len().try_into().ok()?, height.try_into().ok()?)?;
+		for (x, column) in cells.iter().enumerate() {
+			let column = column.as_ref();
+			if column.len() != height {
+				return None;
+			}
+			for player in column.iter().rev().take_while(|cell| **cell != 0) {
+				Logic::add_stone(&mut board, x as u8, *player)?;
+			}
+			let stones = column.iter().filter(|cell| **cell != 0).count();
+			if stones != Logic::stones_in(&board, x as u8) as usize {
+				return None;
+			}
+		}
+		Some(board)
+	}
+
+	/// Bit of `column` and `row`, rows counted from the bottom.
+	fn bit(&self, column: u8, row: u8) -> u32 {
+		column as u32 * (self.height as u32 + 1) + row as u32
+	}
+
+	/// Bits of the cells of `column`.
+	fn column_mask(&self, column: u8) -> u128 {
+		((1u128 << self.height) - 1) << self.bit(column, 0)
+	}
+
+	fn occupied(&self) -> u128 {
+		self.stones[0] | self.stones[1]
+	}
+}
+
+/// Bitboard of `player`, `None` for anything but `PLAYER_1` or `PLAYER_2`.
+fn player_index(player: u8) -> Option<usize> {
+	match player {
+		PLAYER_1 | PLAYER_2 => Some(player as usize - 1),
+		_ => None,
+	}
+}
+
+/// A move of a player.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum Move {
+	/// Drop a stone into a column.
+	Drop(u8),
+	/// Pop Out: take an own stone out of the bottom of a column.
+	Pop(u8),
+}
+
+/// How a game ended on the board, see `Logic::outcome`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum Outcome {
+	/// `PLAYER_1` or `PLAYER_2` connected a line.
+	Win(u8),
+	/// The board filled up without a line.
+	Draw,
+}
+
+/// Heuristic value of dropping a stone into a column, see `Logic::column_scores`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct ColumnScore {
+	pub column: u8,
+	/// The stone connects a line and wins the game.
+	pub wins: bool,
+	/// The stone takes the cell the opponent would win with.
+	pub blocks: bool,
+	/// Closeness to the center, from `0` for the outer columns up to `(width - 1) / 2`.
+	pub center: u8,
+	/// All of the above weighed into one number, higher is better.
+	pub score: u32,
+}
+
+/// Empty cells that would complete a line for each player, see `Logic::threat_counts`.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct ThreatCounts {
+	/// Threats of `PLAYER_1`.
+	pub red: u8,
+	/// Threats of `PLAYER_2`.
+	pub blue: u8,
+}
+
+/// Score of a winning column, outweighs everything else.
+pub const WIN_SCORE: u32 = 1_000;
+
+/// Score of a column that blocks a win of the opponent, outweighs the center preference.
+pub const BLOCK_SCORE: u32 = 100;
+
+/// Most stones a handicap may place before the first move.
+pub const MAX_HANDICAP_STONES: u8 = 2;
+
+pub struct Logic {}
+
+impl Logic {
+	/// Create an empty board with `width` columns and `height` rows, `None` if it doesn't
+	/// fit into the bitboards.
+	pub fn new_board(width: u8, height: u8) -> Option<Board> {
+		Board::fits(width, height).then(|| Board { width, height, stones: [0; 2] })
+	}
+
+	/// The board is full once every cell is taken.
+	pub fn full(board: &Board) -> bool {
+		Self::stones(board) == board.width as u32 * board.height as u32
+	}
+
+	/// Number of stones on the board, which is the number of moves played so far.
+	pub fn stones(board: &Board) -> u32 {
+		board.occupied().count_ones()
+	}
+
+	/// Number of stones `player` has on the board.
+	pub fn player_stones(board: &Board, player: u8) -> u32 {
+		player_index(player).map_or(0, |i| board.stones[i].count_ones())
+	}
+
+	/// Number of stones in `column`.
+	fn stones_in(board: &Board, column: u8) -> u32 {
+		(board.occupied() & board.column_mask(column)).count_ones()
+	}
+
+	/// Find `connect` stones of `player` in a row in any direction.
+	/// Returns the `(column, row)` cells of the first such line.
+	pub fn evaluate(board: &Board, player: u8, connect: u8) -> Option<Vec<(u8, u8)>> {
+		let stones = board.stones[player_index(player)?];
+		let column_bits = board.height as u32 + 1;
+		// Horizontal, vertical and both diagonals.
+		for shift in [column_bits, 1, column_bits + 1, column_bits - 1] {
+			// A bit survives if the `connect - 1` cells following it hold stones as well.
+			let line_starts = (1..connect as u32).fold(stones, |starts, step| {
+				starts & stones.checked_shr(step * shift).unwrap_or(0)
+			});
+			if line_starts != 0 {
+				let start = line_starts.trailing_zeros();
+				let line = (0..connect as u32)
+					.map(|step| {
+						let bit = start + step * shift;
+						let row = (bit % column_bits) as u8;
+						((bit / column_bits) as u8, board.height - 1 - row)
+					})
+					.collect();
+				return Some(line);
+			}
+		}
+		None
+	}
+
+	/// Drop a stone into `column`, it lands on the lowest free row.
+	/// Returns the row of the stone, `None` if the column doesn't exist or is already full.
+	pub fn add_stone(board: &mut Board, column: u8, player: u8) -> Option<u8> {
+		let row = Self::landing_row(board, column)?;
+		let bit = board.bit(column, board.height - 1 - row);
+		board.stones[player_index(player)?] |= 1u128 << bit;
+		Some(row)
+	}
+
+	/// Take the top stone out of `column`.
+	/// Returns the player the stone belonged to, `None` if the column doesn't exist or is empty.
+	pub fn remove_stone(board: &mut Board, column: u8) -> Option<u8> {
+		let player = Self::top_stone(board, column)?;
+		let stones = Self::stones_in(board, column) as u8;
+		board.stones[player_index(player)?] &= !(1u128 << board.bit(column, stones - 1));
+		Some(player)
+	}
+
+	/// Player owning the top stone of `column`, `None` if the column doesn't exist or is empty.
+	pub fn top_stone(board: &Board, column: u8) -> Option<u8> {
+		if column >= board.width {
+			return None;
+		}
+		let stones = Self::stones_in(board, column) as u8;
+		let row = board.height.checked_sub(stones)?;
+		match board.cell(column, row) {
+			0 => None,
+			player => Some(player),
+		}
+	}
+
+	/// Row a stone dropped into `column` lands on, `None` if the column is full or missing.
+	pub fn landing_row(board: &Board, column: u8) -> Option<u8> {
+		if column >= board.width {
+			return None;
+		}
+		let stones = Self::stones_in(board, column) as u8;
+		board.height.checked_sub(stones + 1)
+	}
+
+	/// Pop Out: take the bottom stone out of `column` if it belongs to `player`, the stones
+	/// above it drop down one row. Returns `false` if there's no such stone.
+	pub fn pop_stone(board: &mut Board, column: u8, player: u8) -> bool {
+		if player_index(player).is_none() ||
+			column >= board.width ||
+			board.cell(column, board.height - 1) != player
+		{
+			return false;
+		}
+		let mask = board.column_mask(column);
+		for stones in board.stones.iter_mut() {
+			*stones = (*stones & !mask) | (((*stones & mask) >> 1) & mask);
+		}
+		true
+	}
+
+	/// A handicap of `stones` pre-placed stones fits a board with `width` columns and can't
+	/// decide the game on its own.
+	pub fn valid_handicap(width: u8, connect: u8, stones: u8) -> bool {
+		stones > 0 && stones <= MAX_HANDICAP_STONES && stones < connect && stones <= width
+	}
+
+	/// Drop `stones` stones of `player` into the columns closest to the center, one per column.
+	/// Returns `false` if there isn't room for all of them.
+	pub fn place_handicap(board: &mut Board, player: u8, stones: u8) -> bool {
+		let width = board.width as usize;
+		let center = width / 2;
+		// Center column first, then alternating left and right of it.
+		let columns = (0..width)
+			.filter_map(|i| {
+				let offset = (i + 1) / 2;
+				if i % 2 == 1 {
+					center.checked_sub(offset)
+				} else {
+					Some(center + offset)
+				}
+			})
+			.filter(|column| *column < width)
+			.take(stones as usize)
+			.collect::<Vec<_>>();
+		columns.len() == stones as usize &&
+			columns
+				.into_iter()
+				.all(|column| Self::add_stone(board, column as u8, player).is_some())
+	}
+
+	/// Columns that still have room for a stone.
+	pub fn open_columns(board: &Board) -> Vec<u8> {
+		(0..board.width).filter(|x| Self::landing_row(board, *x).is_some()).collect()
+	}
+
+	/// Open columns as bits, the lowest bit for column `0`. Only the first 32 columns fit.
+	pub fn open_column_mask(board: &Board) -> u32 {
+		Self::open_columns(board)
+			.into_iter()
+			.fold(0, |mask, column| mask | 1u32.checked_shl(column as u32).unwrap_or(0))
+	}
+
+	/// Pick one of the open columns based on `seed`.
+	pub fn random_move(board: &Board, seed: u32) -> Option<u8> {
+		let columns = Self::open_columns(board);
+		if columns.is_empty() {
+			return None;
+		}
+		Some(columns[seed as usize % columns.len()])
+	}
+
+	/// Moves `player` may make, drops into every open column and, with `pop_out`, pops of
+	/// their own stones at the bottom of a column.
+	pub fn legal_moves(board: &Board, player: u8, pop_out: bool) -> Vec<Move> {
+		let mut moves: Vec<Move> = Self::open_columns(board).into_iter().map(Move::Drop).collect();
+		if pop_out && player_index(player).is_some() {
+			moves.extend(
+				(0..board.width)
+					.filter(|x| board.cell(*x, board.height - 1) == player)
+					.map(Move::Pop),
+			);
+		}
+		moves
+	}
+
+	/// Play `mv` for `player`, returns `false` if it isn't legal and leaves the board alone.
+	pub fn play(board: &mut Board, player: u8, mv: Move) -> bool {
+		match mv {
+			Move::Drop(column) => Self::add_stone(board, column, player).is_some(),
+			Move::Pop(column) => Self::pop_stone(board, column, player),
+		}
+	}
+
+	/// Player that connected `connect` stones and the cells of their line. Checks `PLAYER_1`
+	/// first, a Pop Out move can connect lines for both.
+	pub fn winner_line(board: &Board, connect: u8) -> Option<(u8, Vec<(u8, u8)>)> {
+		[PLAYER_1, PLAYER_2]
+			.into_iter()
+			.find_map(|player| Self::evaluate(board, player, connect).map(|line| (player, line)))
+	}
+
+	/// Result of the position, `None` while the game goes on. A line wins even on a full
+	/// board.
+	pub fn outcome(board: &Board, connect: u8) -> Option<Outcome> {
+		match Self::winner_line(board, connect) {
+			Some((player, _)) => Some(Outcome::Win(player)),
+			None => Self::full(board).then(|| Outcome::Draw),
+		}
+	}
+
+	/// First column in which a stone of `player` connects `connect` stones.
+	pub fn winning_move(board: &Board, player: u8, connect: u8) -> Option<u8> {
+		Self::open_columns(board)
+			.into_iter()
+			.find(|column| Self::connects(board, *column, player, connect))
+	}
+
+	/// Score every open column for the next stone of `player`: an immediate win comes first,
+	/// then blocking an immediate win of the opponent, then columns closer to the center.
+	pub fn column_scores(board: &Board, player: u8, connect: u8) -> Vec<ColumnScore> {
+		let opponent = if player == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+		let width = board.width as i16;
+		Self::open_columns(board)
+			.into_iter()
+			.map(|column| {
+				let wins = Self::connects(board, column, player, connect);
+				let blocks = Self::connects(board, column, opponent, connect);
+				// Half the distance to the middle of the board, both middle columns of an
+				// even width count as the center.
+				let distance = ((2 * column as i16 + 1 - width).abs() / 2) as u8;
+				let center = (board.width - 1) / 2 - distance;
+				let score = if wins { WIN_SCORE } else { 0 } +
+					if blocks { BLOCK_SCORE } else { 0 } +
+					center as u32;
+				ColumnScore { column, wins, blocks, center, score }
+			})
+			.collect()
+	}
+
+	/// Threats of both players: empty cells where a stone would connect `connect` stones,
+	/// whether or not a stone can land there yet. A cheap estimate of who's ahead, the side
+	/// with more threats usually wins.
+	pub fn threat_counts(board: &Board, connect: u8) -> ThreatCounts {
+		ThreatCounts {
+			red: Self::threats(board, PLAYER_1, connect),
+			blue: Self::threats(board, PLAYER_2, connect),
+		}
+	}
+
+	/// Empty cells completing a line of `player`, `0` once they already connected one.
+	fn threats(board: &Board, player: u8, connect: u8) -> u8 {
+		let index = match player_index(player) {
+			Some(index) => index,
+			None => return 0,
+		};
+		if Self::evaluate(board, player, connect).is_some() {
+			return 0;
+		}
+		let empty = (0..board.width)
+			.flat_map(|column| (0..board.height).map(move |row| 1u128 << board.bit(column, row)))
+			.filter(|bit| board.occupied() & *bit == 0);
+		empty
+			.filter(|bit| {
+				let mut trial = *board;
+				trial.stones[index] |= *bit;
+				Self::evaluate(&trial, player, connect).is_some()
+			})
+			.count() as u8
+	}
+
+	/// A stone of `player` dropped into `column` connects `connect` stones.
+	fn connects(board: &Board, column: u8, player: u8, connect: u8) -> bool {
+		let mut trial = *board;
+		Self::add_stone(&mut trial, column, player).is_some() &&
+			Self::evaluate(&trial, player, connect).is_some()
+	}
+}
@@ -0,0 +1,183 @@
+//! Compact notation of finished games, for exporting them to analysis tools.
+//!
+//! A game is a header of `HEADER_LEN` bytes followed by one byte per move:
+//!
+//! - `NOTATION_VERSION`
+//! - width, height and connect length of the board
+//! - flags: bit 7 for Pop Out, bits 4 and 5 for the player of a stones handicap and bits 0 to 3 for
+//!   the number of handicap stones, see `Logic::place_handicap`
+//!
+//! A move byte holds the column in bits 0 to 5, bit 6 is set for moves of `PLAYER_2` and bit 7
+//! for pops. The player is part of every move, as blitz games don't strictly alternate.
+
+use crate::{Board, Logic, Move, PLAYER_1, PLAYER_2};
+use alloc::{string::String, vec::Vec};
+
+/// Version of the notation, the first byte of every encoded game.
+pub const NOTATION_VERSION: u8 = 1;
+
+/// Bytes in front of the moves of an encoded game.
+pub const HEADER_LEN: u32 = 5;
+
+const POP_OUT_FLAG: u8 = 0x80;
+const COLUMN_MASK: u8 = 0x3f;
+const PLAYER_2_BIT: u8 = 0x40;
+const POP_BIT: u8 = 0x80;
+
+/// A game as played from the empty board.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GameRecord {
+	pub width: u8,
+	pub height: u8,
+	pub connect: u8,
+	pub pop_out: bool,
+	/// Player and number of stones placed before the first move.
+	pub handicap: Option<(u8, u8)>,
+	/// Moves in the order they were played.
+	pub moves: Vec<(u8, Move)>,
+}
+
+/// Header of a game on a `width` by `height` board.
+pub fn header(
+	width: u8,
+	height: u8,
+	connect: u8,
+	pop_out: bool,
+	handicap: Option<(u8, u8)>,
+) -> [u8; HEADER_LEN as usize] {
+	let mut flags = if pop_out { POP_OUT_FLAG } else { 0 };
+	if let Some((player, stones)) = handicap {
+		flags |= (player & 0x03) << 4 | (stones & 0x0f);
+	}
+	[NOTATION_VERSION, width, height, connect, flags]
+}
+
+/// Byte of `mv` played by `player`.
+pub fn encode_move(player: u8, mv: Move) -> u8 {
+	let player_bit = if player == PLAYER_2 { PLAYER_2_BIT } else { 0 };
+	match mv {
+		Move::Drop(column) => player_bit | (column & COLUMN_MASK),
+		Move::Pop(column) => POP_BIT | player_bit | (column & COLUMN_MASK),
+	}
+}
+
+/// Player and move of a move byte.
+pub fn decode_move(byte: u8) -> (u8, Move) {
+	let player = if byte & PLAYER_2_BIT != 0 { PLAYER_2 } else { PLAYER_1 };
+	let column = byte & COLUMN_MASK;
+	if byte & POP_BIT != 0 {
+		(player, Move::Pop(column))
+	} else {
+		(player, Move::Drop(column))
+	}
+}
+
+impl GameRecord {
+	/// The game in compact notation.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes =
+			header(self.width, self.height, self.connect, self.pop_out, self.handicap).to_vec();
+		bytes.extend(self.moves.iter().map(|(player, mv)| encode_move(*player, *mv)));
+		bytes
+	}
+
+	/// Read a game in compact notation, `None` for another version or a short header.
+	pub fn from_bytes(bytes: &[u8]) -> Option<GameRecord> {
+		if bytes.len() < HEADER_LEN as usize {
+			return None;
+		}
+		let (header, moves) = bytes.split_at(HEADER_LEN as usize);
+		let [version, width, height, connect, flags] = <[u8; 5]>::try_from(header).ok()?;
+		if version != NOTATION_VERSION {
+			return None;
+		}
+		let stones = flags & 0x0f;
+		Some(GameRecord {
+			width,
+			height,
+			connect,
+			pop_out: flags & POP_OUT_FLAG != 0,
+			handicap: (stones > 0).then(|| ((flags >> 4) & 0x03, stones)),
+			moves: moves.iter().map(|byte| decode_move(*byte)).collect(),
+		})
+	}
+
+	/// Board after all moves, `None` if the board doesn't fit or a move isn't legal.
+	pub fn replay(&self) -> Option<Board> {
+		self.position(self.moves.len(), &[], 0)
+	}
+
+	/// Board after every `interval`th move, so `position` doesn't have to replay the game
+	/// from the start. `None` if the board doesn't fit or a move isn't legal.
+	pub fn snapshots(&self, interval: usize) -> Option<Vec<Board>> {
+		let mut snapshots = Vec::new();
+		if interval == 0 {
+			return Some(snapshots);
+		}
+		let mut board = self.start()?;
+		for (chunk, moves) in self.moves.chunks(interval).enumerate() {
+			board = self.play_from(board, moves)?;
+			if (chunk + 1) * interval <= self.moves.len() {
+				snapshots.push(board);
+			}
+		}
+		Some(snapshots)
+	}
+
+	/// Board after the first `moves` moves, replayed from the latest of the `snapshots` taken
+	/// every `interval` moves. `None` if there aren't that many moves, the board doesn't fit
+	/// or a move isn't legal.
+	pub fn position(&self, moves: usize, snapshots: &[Board], interval: usize) -> Option<Board> {
+		let played = self.moves.get(..moves)?;
+		let snapshot = if interval == 0 { 0 } else { (moves / interval).min(snapshots.len()) };
+		let board = match snapshot.checked_sub(1) {
+			Some(latest) => snapshots[latest],
+			None => self.start()?,
+		};
+		self.play_from(board, &played[snapshot * interval..])
+	}
+
+	/// Empty board with the handicap stones placed.
+	fn start(&self) -> Option<Board> {
+		let mut board = Logic::new_board(self.width, self.height)?;
+		if let Some((player, stones)) = self.handicap {
+			if !Logic::place_handicap(&mut board, player, stones) {
+				return None;
+			}
+		}
+		Some(board)
+	}
+
+	/// `board` after `moves`.
+	fn play_from(&self, mut board: Board, moves: &[(u8, Move)]) -> Option<Board> {
+		for (player, mv) in moves {
+			if matches!(mv, Move::Pop(_)) && !self.pop_out {
+				return None;
+			}
+			if !Logic::play(&mut board, *player, *mv) {
+				return None;
+			}
+		}
+		Some(board)
+	}
+
+	/// Columns of the moves counted from 1, as read by most connect four solvers.
+	/// `None` unless it's a game of alternating drops from the empty board with at most 9
+	/// columns.
+	pub fn column_string(&self) -> Option<String> {
+		if self.handicap.is_some() || self.width > 9 {
+			return None;
+		}
+		let mut last = None;
+		self.moves
+			.iter()
+			.map(|(player, mv)| match mv {
+				Move::Drop(column) if last != Some(*player) => {
+					last = Some(*player);
+					char::from_digit(*column as u32 + 1, 10)
+				},
+				_ => None,
+			})
+			.collect()
+	}
+}
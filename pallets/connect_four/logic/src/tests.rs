@@ -0,0 +1,186 @@
+use crate::{
+	notation::GameRecord, Board, ColumnScore, Logic, Move, Outcome, ThreatCounts, PLAYER_1,
+	PLAYER_2,
+};
+use alloc::vec;
+
+#[test]
+fn bitboards_find_lines_in_every_direction() {
+	let mut board = Logic::new_board(5, 4).unwrap();
+	// Columns from the left, stones from the bottom up.
+	let columns = [vec![1, 2, 1, 1], vec![2, 1, 2], vec![2, 2, 1], vec![1, 2, 1, 1]];
+	for (column, stones) in columns.iter().enumerate() {
+		for player in stones {
+			assert!(Logic::add_stone(&mut board, column as u8, *player).is_some());
+		}
+	}
+	assert_eq!(Logic::evaluate(&board, PLAYER_1, 4), Some(vec![(0, 3), (1, 2), (2, 1), (3, 0)]));
+	assert_eq!(Logic::evaluate(&board, PLAYER_2, 3), None);
+	assert_eq!(Logic::landing_row(&board, 1), Some(0));
+	assert_eq!(Logic::open_columns(&board), vec![1, 2, 4]);
+
+	// Cells round trip, rows counted from the top.
+	let cells = board.cells();
+	assert_eq!(cells[0], vec![1, 1, 2, 1]);
+	assert_eq!(Board::from_cells(&cells), Some(board));
+	assert_eq!(Board::from_cells(&vec![vec![1u8, 0, 0, 0]; 4]), None);
+	assert!(!Board::fits(11, 11));
+
+	assert!(Logic::pop_stone(&mut board, 2, PLAYER_2));
+	assert_eq!(board.cells()[2], vec![0, 0, 1, 2]);
+	assert_eq!(Logic::remove_stone(&mut board, 0), Some(PLAYER_1));
+	assert_eq!(Logic::stones(&board), 12);
+}
+
+#[test]
+fn legal_moves_include_pops_of_own_stones() {
+	let mut board = Logic::new_board(4, 4).unwrap();
+	for _ in 0..2 {
+		assert!(Logic::play(&mut board, PLAYER_1, Move::Drop(0)));
+		assert!(Logic::play(&mut board, PLAYER_2, Move::Drop(0)));
+	}
+	assert!(Logic::play(&mut board, PLAYER_2, Move::Drop(1)));
+
+	assert_eq!(
+		Logic::legal_moves(&board, PLAYER_1, false),
+		vec![Move::Drop(1), Move::Drop(2), Move::Drop(3)]
+	);
+	assert_eq!(
+		Logic::legal_moves(&board, PLAYER_2, true),
+		vec![Move::Drop(1), Move::Drop(2), Move::Drop(3), Move::Pop(1)]
+	);
+	assert!(!Logic::play(&mut board, PLAYER_1, Move::Drop(0)));
+	assert!(!Logic::play(&mut board, PLAYER_1, Move::Pop(1)));
+
+	// Blue completes the falling diagonal from the top left corner.
+	for (column, player) in
+		[(1, PLAYER_1), (1, PLAYER_2), (2, PLAYER_1), (2, PLAYER_2), (3, PLAYER_2)]
+	{
+		assert!(Logic::add_stone(&mut board, column, player).is_some());
+	}
+	assert_eq!(
+		Logic::winner_line(&board, 4),
+		Some((PLAYER_2, vec![(0, 0), (1, 1), (2, 2), (3, 3)]))
+	);
+}
+
+#[test]
+fn outcomes_follow_lines_and_full_boards() {
+	let mut board = Logic::new_board(4, 2).unwrap();
+	for column in [0, 1, 2] {
+		assert!(Logic::add_stone(&mut board, column, PLAYER_1).is_some());
+		assert!(Logic::add_stone(&mut board, column, PLAYER_2).is_some());
+	}
+	assert_eq!(Logic::open_column_mask(&board), 0b1000);
+	assert_eq!(Logic::outcome(&board, 4), None);
+	assert_eq!(Logic::outcome(&board, 3), Some(Outcome::Win(PLAYER_1)));
+
+	assert!(Logic::add_stone(&mut board, 3, PLAYER_2).is_some());
+	assert!(Logic::add_stone(&mut board, 3, PLAYER_1).is_some());
+	assert_eq!(Logic::open_column_mask(&board), 0);
+	assert_eq!(Logic::outcome(&board, 4), Some(Outcome::Draw));
+}
+
+#[test]
+fn column_scores_rank_wins_over_blocks_over_the_center() {
+	let mut board = Logic::new_board(7, 6).unwrap();
+	for _ in 0..3 {
+		assert!(Logic::add_stone(&mut board, 0, PLAYER_1).is_some());
+		assert!(Logic::add_stone(&mut board, 6, PLAYER_2).is_some());
+	}
+
+	let scores = Logic::column_scores(&board, PLAYER_1, 4);
+	assert_eq!(
+		scores[0],
+		ColumnScore { column: 0, wins: true, blocks: false, center: 0, score: 1_000 }
+	);
+	assert_eq!(
+		scores[6],
+		ColumnScore { column: 6, wins: false, blocks: true, center: 0, score: 100 }
+	);
+	assert_eq!(scores.iter().map(|score| score.center).collect::<Vec<_>>(), [0, 1, 2, 3, 2, 1, 0]);
+
+	// Both middle columns of an even width are the center, full columns get no score.
+	let mut board = Logic::new_board(4, 2).unwrap();
+	assert!(Logic::add_stone(&mut board, 3, PLAYER_1).is_some());
+	assert!(Logic::add_stone(&mut board, 3, PLAYER_2).is_some());
+	let scores = Logic::column_scores(&board, PLAYER_2, 2);
+	assert_eq!(
+		scores.iter().map(|score| (score.column, score.center)).collect::<Vec<_>>(),
+		[(0, 0), (1, 1), (2, 1)]
+	);
+}
+
+#[test]
+fn threats_count_open_cells_completing_a_line() {
+	let mut board = Logic::new_board(7, 6).unwrap();
+	for column in 1..4 {
+		assert!(Logic::add_stone(&mut board, column, PLAYER_1).is_some());
+		assert!(Logic::add_stone(&mut board, column, PLAYER_2).is_some());
+	}
+	// Blue's threats float above empty cells, they count all the same.
+	assert_eq!(Logic::threat_counts(&board, 4), ThreatCounts { red: 2, blue: 2 });
+
+	assert!(Logic::add_stone(&mut board, 0, PLAYER_1).is_some());
+	assert_eq!(Logic::threat_counts(&board, 4), ThreatCounts { red: 0, blue: 2 });
+}
+
+#[test]
+fn game_records_round_trip_through_the_compact_notation() {
+	let mut record = GameRecord {
+		width: 7,
+		height: 6,
+		connect: 4,
+		pop_out: false,
+		handicap: None,
+		moves: [3, 3, 4, 2, 5, 6, 6]
+			.into_iter()
+			.zip([PLAYER_1, PLAYER_2].into_iter().cycle())
+			.map(|(column, player)| (player, Move::Drop(column)))
+			.collect(),
+	};
+	let bytes = record.to_bytes();
+	assert_eq!(bytes.len(), 12);
+	assert_eq!(GameRecord::from_bytes(&bytes), Some(record.clone()));
+	assert_eq!(record.column_string().as_deref(), Some("4453677"));
+	let board = record.replay().unwrap();
+	assert_eq!(Logic::stones(&board), 7);
+	assert_eq!(Logic::evaluate(&board, PLAYER_1, 4), None);
+
+	// Pops and moves out of turn are kept, but standard tools can't read them.
+	record.pop_out = true;
+	record.moves.push((PLAYER_1, Move::Pop(3)));
+	assert_eq!(GameRecord::from_bytes(&record.to_bytes()), Some(record.clone()));
+	assert_eq!(record.column_string(), None);
+	assert!(record.replay().is_some());
+	record.moves.push((PLAYER_1, Move::Pop(3)));
+	assert_eq!(record.replay(), None);
+
+	assert_eq!(GameRecord::from_bytes(&bytes[..4]), None);
+}
+
+#[test]
+fn snapshots_fast_forward_to_any_position() {
+	let record = GameRecord {
+		width: 7,
+		height: 6,
+		connect: 4,
+		pop_out: false,
+		handicap: Some((PLAYER_2, 1)),
+		moves: [3, 3, 4, 2, 5, 6, 6]
+			.into_iter()
+			.zip([PLAYER_1, PLAYER_2].into_iter().cycle())
+			.map(|(column, player)| (player, Move::Drop(column)))
+			.collect(),
+	};
+	let snapshots = record.snapshots(3).unwrap();
+	assert_eq!(snapshots.iter().map(Logic::stones).collect::<Vec<_>>(), vec![4, 7]);
+	assert_eq!(record.snapshots(0), Some(vec![]));
+
+	for moves in 0..=record.moves.len() {
+		let partial = GameRecord { moves: record.moves[..moves].to_vec(), ..record.clone() };
+		assert_eq!(record.position(moves, &snapshots, 3), partial.replay());
+		assert_eq!(record.position(moves, &[], 3), partial.replay());
+	}
+	assert_eq!(record.position(8, &snapshots, 3), None);
+}
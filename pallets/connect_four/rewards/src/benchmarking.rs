@@ -0,0 +1,23 @@
+//! Benchmarking setup for pallet-connectfour-rewards
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Rewards;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	claim {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance();
+		// Enough for the rewards and to keep the reward account alive.
+		T::Currency::make_free_balance_be(&Rewards::<T>::account_id(), amount + amount);
+		Unclaimed::<T>::insert(&caller, amount);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(T::Currency::free_balance(&caller), amount);
+	}
+
+	impl_benchmark_test_suite!(Rewards, crate::mock::new_test_ext(), crate::mock::Test);
+}
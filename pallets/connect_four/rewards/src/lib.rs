@@ -0,0 +1,160 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Token rewards for connect four win streaks.
+//!
+//! Plugged into the connect four pallet as its `Config::GameFinishedHandler`, every ranked win
+//! accrues `BaseReward` plus `StreakBonus` for each win in a row before it, counting at most
+//! `MaxBonusStreak` of them. A loss ends the streak, draws and void games don't touch it. Winners
+//! `claim` their rewards from the account of `PalletId`, which somebody, e.g. the treasury, has to
+//! keep funded.
+
+use frame_support::{
+	traits::{Currency, ExistenceRequirement, Get},
+	PalletId,
+};
+use pallet_connectfour::{FinishReason, GameKind, OnGameFinished};
+use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Balance of the currency rewards are paid in.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency the rewards are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Id of the account rewards are paid from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Reward of every ranked win.
+		#[pallet::constant]
+		type BaseReward: Get<BalanceOf<Self>>;
+
+		/// Added to the reward for every win in a row before the rewarded one.
+		#[pallet::constant]
+		type StreakBonus: Get<BalanceOf<Self>>;
+
+		/// Wins in a row that count towards the bonus at most.
+		#[pallet::constant]
+		type MaxBonusStreak: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Ranked wins in a row of an account, since its last ranked loss.
+	#[pallet::storage]
+	#[pallet::getter(fn streaks)]
+	pub type Streaks<T: Config> = StorageMap<_, Identity, T::AccountId, u32, ValueQuery>;
+
+	/// Rewards an account earned but didn't claim yet.
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed)]
+	pub type Unclaimed<T: Config> = StorageMap<_, Identity, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A ranked win earned a reward. \[winner, reward, streak\]
+		RewardAccrued(T::AccountId, BalanceOf<T>, u32),
+		/// Rewards got paid out. \[who, amount\]
+		RewardsClaimed(T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account has no unclaimed rewards.
+		NothingToClaim,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay out all unclaimed rewards of the sender. Fails while the reward account can't
+		/// cover them, the rewards stay unclaimed then.
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let amount = <Unclaimed<T>>::take(&sender);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&sender,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::RewardsClaimed(sender, amount));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account the rewards are paid from.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Reward of a win that extended a streak to `streak` wins.
+	pub fn reward(streak: u32) -> BalanceOf<T> {
+		let bonus_wins = streak.saturating_sub(1).min(T::MaxBonusStreak::get());
+		T::BaseReward::get().saturating_add(T::StreakBonus::get().saturating_mul(bonus_wins.into()))
+	}
+}
+
+/// Casual games are free to set up between two accounts of the same person, only ranked wins
+/// are rewarded.
+impl<T: Config> OnGameFinished<T::AccountId, T::Hash> for Pallet<T> {
+	fn on_game_finished(
+		_: T::Hash,
+		_: &T::AccountId,
+		_: &T::AccountId,
+		winner: Option<&T::AccountId>,
+		loser: Option<&T::AccountId>,
+		kind: GameKind,
+		_: FinishReason,
+	) {
+		let (winner, loser) = match (winner, loser) {
+			(Some(winner), Some(loser)) if kind == GameKind::Ranked => (winner, loser),
+			_ => return,
+		};
+		<Streaks<T>>::remove(loser);
+		let streak = <Streaks<T>>::mutate(winner, |streak| {
+			*streak = streak.saturating_add(1);
+			*streak
+		});
+		let reward = Self::reward(streak);
+		<Unclaimed<T>>::mutate(winner, |unclaimed| *unclaimed = unclaimed.saturating_add(reward));
+		Self::deposit_event(Event::RewardAccrued(winner.clone(), reward, streak));
+	}
+}
@@ -0,0 +1,230 @@
+use crate as pallet_connectfour_rewards;
+use frame_support::{
+	parameter_types,
+	traits::{ConstBool, ConstI32, ConstU16, ConstU32, ConstU64, ConstU8, Randomness},
+	PalletId,
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime with the connect four pallet reporting its results to this one.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		ConnectFour: pallet_connectfour::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+		Rewards: pallet_connectfour_rewards::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ConnectFour;
+	type OnKilledAccount = ConnectFour;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	Call: From<C>,
+{
+	type Extrinsic = TestXt<Call, ()>;
+	type OverarchingCall = Call;
+}
+
+/// Predictable randomness, the subject hashed together with the current block number.
+pub struct TestRandomness;
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		(BlakeTwo256::hash_of(&(subject, block_number)), block_number)
+	}
+}
+
+/// Test signatures are the signer's account and the message.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_connectfour::BenchmarkHelper<UintAuthorityId, u64, TestSignature, u64>
+	for TestBenchmarkHelper
+{
+	fn signer() -> (UintAuthorityId, u64) {
+		(UintAuthorityId(1), 1)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+
+	fn advance_clock(by: u64) {
+		System::set_block_number(System::block_number() + by);
+	}
+}
+
+parameter_types! {
+	pub const MatchmakingTimeBank: Option<u64> = None;
+	pub const SeasonScoreRetention: Perbill = Perbill::from_percent(50);
+	pub const DefaultMatchAward: pallet_connectfour::AwardState =
+		pallet_connectfour::AwardState { win: 10, lose: 5, draw: 2 };
+	pub const DefaultEventVerbosity: pallet_connectfour::EventVerbosity =
+		pallet_connectfour::EventVerbosity::Delta;
+	pub const ConnectFourPalletId: PalletId = PalletId(*b"py/cnct4");
+	pub const RewardsPalletId: PalletId = PalletId(*b"py/c4rwd");
+}
+
+impl pallet_connectfour::Config for Test {
+	type Proposal = Call;
+	type Event = Event;
+	type Randomness = TestRandomness;
+	type MaxBoardWidth = ConstU8<10>;
+	type MaxBoardHeight = ConstU8<10>;
+	type MaxQueueLength = ConstU32<16>;
+	type MaxMatchesPerBlock = ConstU32<4>;
+	type RematchPeriod = ConstU64<10>;
+	type MatchmakingTimeBank = MatchmakingTimeBank;
+	type Trophies = ();
+	type MaxSpectators = ConstU32<4>;
+	type Currency = Balances;
+	type MinRankedBalance = ConstU64<10>;
+	type QueueDeposit = ConstU64<5>;
+	type MinAccountAge = ConstU64<0>;
+	type SeasonLength = ConstU64<100>;
+	type SeasonScoreRetention = SeasonScoreRetention;
+	type MaxDecayPerBlock = ConstU32<4>;
+	type MaxSeasonRewardees = ConstU32<3>;
+	type SeasonRewardOrigin = frame_system::EnsureRoot<u64>;
+	type PalletId = ConnectFourPalletId;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type DefaultAcceptedDiff = ConstU8<10>;
+	type QueueWideningPeriod = ConstU64<5>;
+	type QueueWideningStep = ConstU8<10>;
+	type MaxWidenedDiff = ConstU8<30>;
+	type QueueTimeout = ConstU64<0>;
+	type DefaultMatchAward = DefaultMatchAward;
+	type DefaultMaxActiveBoards = ConstU32<100>;
+	type DefaultMaxGamesPerPlayer = ConstU32<1>;
+	type MaxLeaderboardSize = ConstU32<4>;
+	type GameClock = pallet_connectfour::BlockClock<Test>;
+	type GameFinishedHandler = Rewards;
+	type RemoteLocation = u64;
+	type RemoteOrigin = frame_system::EnsureNever<u64>;
+	type RemoteAccount = sp_runtime::traits::Identity;
+	type RemoteNotifier = ();
+	type ReportHandler = ();
+	type MaxOpenLobbies = ConstU32<4>;
+	type HandicapScoreGap = ConstU32<5>;
+	type ScoreFloor = ConstI32<0>;
+	type ProvisionalGames = ConstU32<0>;
+	type ProvisionalBoost = ConstU8<3>;
+	type MaxTournamentPlayers = ConstU32<8>;
+	type MaxTournamentRounds = ConstU32<4>;
+	type MaxTournamentsPerBlock = ConstU32<2>;
+	type StaleGameAge = ConstU64<20>;
+	type UnsignedPriority = ConstU64<100>;
+	type AwardCalculator = ();
+	type MaxFriends = ConstU32<4>;
+	type MaxBlocked = ConstU32<4>;
+	type ResultRetentionBlocks = ConstU64<10>;
+	type MaxArchivedMoves = ConstU32<64>;
+	type StallTurnTime = ConstU64<5>;
+	type MaxStalledTurns = ConstU8<2>;
+	type MaxDisplayNameLength = ConstU32<8>;
+	type MaxPredictions = ConstU32<4>;
+	type PredictionFeeDestination = ();
+	type StakeFeeDestination = ();
+	type MaxCleanupAccounts = ConstU32<4>;
+	type CleanupReward = ConstU64<1>;
+	type Identity = ();
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TestBenchmarkHelper;
+	type RequireJudgedIdentity = ConstBool<false>;
+	type MaxBoardCreationsPerBlock = ConstU32<2>;
+	type MaxMovesPerGame = ConstU32<1000>;
+	type RewardPointValue = ConstU64<2>;
+	type RewardVestingBlocks = ConstU64<5>;
+	type InactivityPeriod = ConstU64<0>;
+	type InactivityScoreRetention = SeasonScoreRetention;
+	type MaxInactivityChecks = ConstU32<2>;
+	type Assets = pallet_connectfour::NoAssets<u32, u64>;
+	type EmoteCooldown = ConstU64<3>;
+	type ChallengeLifetime = ConstU64<20>;
+	type PrivateGameLifetime = ConstU64<10>;
+	type MaxPrivateGamesPerBlock = ConstU32<2>;
+	type MaxScoreHistory = ConstU32<3>;
+	type MaxGuildMembers = ConstU32<3>;
+	type MaxGuildMatchPlayers = ConstU32<2>;
+	type SnapshotInterval = ConstU32<4>;
+	type PrizeClaimPeriod = ConstU64<10>;
+	type MaxPrizeSweeps = ConstU32<2>;
+	type UnclaimedPrizeDestination = ();
+	type ChallengeCooldown = ConstU64<0>;
+	type ChallengeEra = ConstU64<20>;
+	type MaxChallengesPerEra = ConstU32<0>;
+	type MaxSponsors = ConstU32<2>;
+	type AnnotationDeposit = ConstU64<3>;
+	type MaxAnnotations = ConstU32<2>;
+	type MaxAnnotationLength = ConstU32<8>;
+	type DefaultEventVerbosity = DefaultEventVerbosity;
+	type WeightInfo = ();
+}
+
+impl pallet_connectfour_rewards::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = RewardsPalletId;
+	type BaseReward = ConstU64<10>;
+	type StreakBonus = ConstU64<5>;
+	type MaxBonusStreak = ConstU32<2>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+}
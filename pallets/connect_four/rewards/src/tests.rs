@@ -0,0 +1,86 @@
+use crate::{mock::*, Error, Event as RewardsEvent, Streaks, Unclaimed};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use pallet_connectfour::{AwardState, BoardVariant, FirstMovePolicy, GameKind, PlayerBoard};
+
+const AWARD: AwardState = AwardState { win: 10, lose: 5, draw: 2 };
+
+/// `winner` beats `loser` in a `kind` game, connecting four in the first column.
+fn win(winner: u64, loser: u64, kind: GameKind) {
+	assert_ok!(ConnectFour::challenge(
+		Origin::signed(loser),
+		winner,
+		10,
+		5,
+		2,
+		BoardVariant::default(),
+		None,
+		None,
+		FirstMovePolicy::ResponderFirst,
+		kind,
+		None
+	));
+	assert_ok!(ConnectFour::resp_challenge(Origin::signed(winner), loser, true, AWARD));
+	for _ in 0..3 {
+		assert_ok!(ConnectFour::play_turn(Origin::signed(winner), 0, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(loser), 1, None));
+	}
+	assert_ok!(ConnectFour::play_turn(Origin::signed(winner), 0, None));
+	assert!(!PlayerBoard::<Test>::contains_key(winner));
+}
+
+#[test]
+fn ranked_wins_accrue_streak_rewards() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		win(1, 2, GameKind::Ranked);
+		System::assert_has_event(RewardsEvent::RewardAccrued(1, 10, 1).into());
+		win(1, 2, GameKind::Ranked);
+		assert_eq!(Rewards::unclaimed(1), 25);
+
+		// The bonus stops growing after `MaxBonusStreak` wins in a row.
+		win(1, 2, GameKind::Ranked);
+		win(1, 2, GameKind::Ranked);
+		System::assert_has_event(RewardsEvent::RewardAccrued(1, 20, 4).into());
+		assert_eq!(Rewards::streaks(1), 4);
+		assert_eq!(Rewards::unclaimed(1), 65);
+
+		// A loss ends the streak.
+		win(2, 1, GameKind::Ranked);
+		assert!(!Streaks::<Test>::contains_key(1));
+		assert_eq!(Rewards::streaks(2), 1);
+		assert_eq!(Rewards::unclaimed(2), 10);
+		assert_eq!(Rewards::unclaimed(1), 65);
+	});
+}
+
+#[test]
+fn casual_wins_earn_nothing() {
+	new_test_ext().execute_with(|| {
+		win(1, 2, GameKind::Casual);
+		assert!(!Streaks::<Test>::contains_key(1));
+		assert!(!Unclaimed::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn rewards_are_claimed_from_the_reward_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(Rewards::claim(Origin::signed(1)), Error::<Test>::NothingToClaim);
+		win(1, 2, GameKind::Ranked);
+		win(1, 2, GameKind::Ranked);
+
+		// The reward account has to stay alive, the rewards wait until it is funded.
+		Balances::make_free_balance_be(&Rewards::account_id(), 25);
+		assert!(Rewards::claim(Origin::signed(1)).is_err());
+		assert_eq!(Rewards::unclaimed(1), 25);
+
+		Balances::make_free_balance_be(&Rewards::account_id(), 100);
+		assert_ok!(Rewards::claim(Origin::signed(1)));
+		System::assert_last_event(RewardsEvent::RewardsClaimed(1, 25).into());
+		assert_eq!(Balances::free_balance(&1), 25);
+		assert_eq!(Balances::free_balance(&Rewards::account_id()), 75);
+		assert!(!Unclaimed::<Test>::contains_key(1));
+		assert_noop!(Rewards::claim(Origin::signed(1)), Error::<Test>::NothingToClaim);
+	});
+}
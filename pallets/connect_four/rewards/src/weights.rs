@@ -0,0 +1,41 @@
+//! Weights for pallet_connectfour_rewards
+//!
+//! These are conservative estimates until the benchmarks are run on reference hardware.
+//! Regenerate this file with:
+//!
+//! ./target/release/node-template benchmark pallet --chain dev --execution wasm --wasm-execution
+//! compiled --pallet pallet_connectfour_rewards --extrinsic '*' --steps 50 --repeat 20
+//! --output ./pallets/connect_four/rewards/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_connectfour_rewards.
+pub trait WeightInfo {
+	fn claim() -> Weight;
+}
+
+/// Weights for pallet_connectfour_rewards using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ConnectFourRewards Unclaimed (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn claim() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
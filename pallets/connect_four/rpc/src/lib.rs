@@ -0,0 +1,552 @@
+//! RPC interface for the connect four pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+pub use connectfour_runtime_api::ConnectFourApi as ConnectFourRuntimeApi;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_connectfour::{
+	gameplay::{Board, ColumnScore, Outcome},
+	BoardState, BoardStruct, BoardSummary, BoardVariant, GameKind, MoveError, QueueStatus,
+	ScoreChange,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Board of a running game in a form that is easy to render on a client.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardInfo<Hash, AccountId, Moment> {
+	pub id: Hash,
+	pub red: AccountId,
+	pub blue: AccountId,
+	/// Teammates of `red` and `blue` in doubles games.
+	pub red_mate: Option<AccountId>,
+	pub blue_mate: Option<AccountId>,
+	/// Cells row by row from the top, `0` empty, `1` red, `2` blue.
+	pub rows: Vec<Vec<u8>>,
+	pub width: u8,
+	pub height: u8,
+	pub connect: u8,
+	pub last_turn: Moment,
+	/// Last moment of the game clock the next stone may be played at, `None` without time
+	/// banks.
+	pub deadline: Option<Moment>,
+	/// Account that has to play the next stone.
+	pub next_player: AccountId,
+	/// Move index a relayed move has to sign, see `RelayedMove`.
+	pub turns: u32,
+	/// Winner of the game, only set once it's finished.
+	pub winner: Option<AccountId>,
+}
+
+impl<Hash, AccountId: Clone + PartialEq, Moment>
+	From<BoardStruct<Hash, AccountId, Moment, BoardState<AccountId>, Board>>
+	for BoardInfo<Hash, AccountId, Moment>
+{
+	fn from(
+		board: BoardStruct<Hash, AccountId, Moment, BoardState<AccountId>, Board>,
+	) -> Self {
+		let BoardVariant { width, height, connect, .. } = board.variant;
+		// The pallet stores bitboards, clients draw rows.
+		let rows = (0..height).map(|row| (0..width).map(|x| board.board.cell(x, row)).collect()).collect();
+		let next_player = board.account_at_turn(board.next_player).clone();
+		let winner = match board.board_state {
+			BoardState::Finished(winner) => winner,
+			_ => None,
+		};
+		BoardInfo {
+			id: board.id,
+			red: board.red,
+			blue: board.blue,
+			red_mate: board.red_mate,
+			blue_mate: board.blue_mate,
+			rows,
+			width,
+			height,
+			connect,
+			last_turn: board.last_turn,
+			deadline: board.deadline,
+			next_player,
+			turns: board.turns,
+			winner,
+		}
+	}
+}
+
+/// Overview of a running board, for indexers syncing many accounts at once.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardSummaryInfo<Hash, AccountId, Moment> {
+	pub id: Hash,
+	pub red: AccountId,
+	pub blue: AccountId,
+	/// Account that has to play the next stone.
+	pub next_player: AccountId,
+	pub moves_played: u16,
+	/// Last moment of the game clock the next stone may be played at, `None` without time
+	/// banks.
+	pub deadline: Option<Moment>,
+	/// Casual games leave the scores alone.
+	pub ranked: bool,
+}
+
+impl<Hash, AccountId, Moment> From<BoardSummary<Hash, AccountId, Moment>>
+	for BoardSummaryInfo<Hash, AccountId, Moment>
+{
+	fn from(summary: BoardSummary<Hash, AccountId, Moment>) -> Self {
+		BoardSummaryInfo {
+			id: summary.id,
+			red: summary.red,
+			blue: summary.blue,
+			next_player: summary.next_player,
+			moves_played: summary.moves_played,
+			deadline: summary.deadline,
+			ranked: summary.kind == GameKind::Ranked,
+		}
+	}
+}
+
+/// Match queue entry of a waiting player.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueInfo {
+	pub score: i32,
+	pub waiting: u32,
+}
+
+impl From<QueueStatus> for QueueInfo {
+	fn from(status: QueueStatus) -> Self {
+		QueueInfo { score: status.score, waiting: status.waiting }
+	}
+}
+
+/// Heuristic score of dropping the next stone into a column.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnScoreInfo {
+	pub column: u8,
+	/// The stone wins the game.
+	pub wins: bool,
+	/// The stone blocks a win of the opponent.
+	pub blocks: bool,
+	/// Closeness to the center, `0` for the outer columns.
+	pub center: u8,
+	/// All of the above weighed into one number, higher is better.
+	pub score: u32,
+}
+
+impl From<ColumnScore> for ColumnScoreInfo {
+	fn from(score: ColumnScore) -> Self {
+		let ColumnScore { column, wins, blocks, center, score } = score;
+		ColumnScoreInfo { column, wins, blocks, center, score }
+	}
+}
+
+/// Result of a finished position.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutcomeInfo {
+	/// Player `1` (red) or `2` (blue) connected a line.
+	Win(u8),
+	/// The board filled up without a line.
+	Draw,
+}
+
+impl From<Outcome> for OutcomeInfo {
+	fn from(outcome: Outcome) -> Self {
+		match outcome {
+			Outcome::Win(player) => OutcomeInfo::Win(player),
+			Outcome::Draw => OutcomeInfo::Draw,
+		}
+	}
+}
+
+/// Change of a player's score, to draw rating graphs from.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreChangeInfo<Hash, BlockNumber> {
+	pub block: BlockNumber,
+	pub delta: i32,
+	/// Game that changed the score, `None` for decays and resets.
+	pub board_id: Option<Hash>,
+}
+
+impl<Hash, BlockNumber> From<ScoreChange<BlockNumber, Hash>> for ScoreChangeInfo<Hash, BlockNumber> {
+	fn from(change: ScoreChange<BlockNumber, Hash>) -> Self {
+		ScoreChangeInfo { block: change.block, delta: change.delta, board_id: change.board_id }
+	}
+}
+
+/// Leaderboard entry with the identity display name of the account.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry<AccountId> {
+	pub account: AccountId,
+	pub score: i32,
+	/// Display name of the account's identity, `None` if it has none.
+	pub display_name: Option<String>,
+}
+
+impl<AccountId> From<(AccountId, i32, Option<Vec<u8>>)> for LeaderboardEntry<AccountId> {
+	fn from((account, score, display_name): (AccountId, i32, Option<Vec<u8>>)) -> Self {
+		let display_name = display_name.map(|name| String::from_utf8_lossy(&name).into_owned());
+		LeaderboardEntry { account, score, display_name }
+	}
+}
+
+#[rpc(client, server)]
+pub trait ConnectFourApi<BlockHash, AccountId, Hash, BlockNumber, Moment> {
+	/// Board with the given id, as long as it's being played.
+	#[method(name = "connectFour_board")]
+	fn board(
+		&self,
+		board_id: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BoardInfo<Hash, AccountId, Moment>>>;
+
+	/// Board an account is currently playing on.
+	#[method(name = "connectFour_playerBoard")]
+	fn player_board(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BoardInfo<Hash, AccountId, Moment>>>;
+
+	/// Boards the given accounts are playing on, in the same order, `None` for accounts that
+	/// aren't playing.
+	#[method(name = "connectFour_boardsOf")]
+	fn boards_of(
+		&self,
+		accounts: Vec<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Option<BoardSummaryInfo<Hash, AccountId, Moment>>>>;
+
+	/// Ids of up to `limit` running boards after `start_key`, at most `MAX_BOARD_IDS_PAGE`.
+	/// Pass the last id of a page to get the next one, an empty page ends the iteration.
+	#[method(name = "connectFour_activeBoardIds")]
+	fn active_board_ids(
+		&self,
+		start_key: Option<Hash>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Hash>>;
+
+	/// The `count` highest scores, best first.
+	#[method(name = "connectFour_leaderboard")]
+	fn leaderboard(&self, count: u32, at: Option<BlockHash>) -> RpcResult<Vec<(AccountId, i32)>>;
+
+	/// The `count` highest scores, best first, with the display names of the players.
+	#[method(name = "connectFour_namedLeaderboard")]
+	fn named_leaderboard(
+		&self,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<LeaderboardEntry<AccountId>>>;
+
+	/// Match queue entry of an account.
+	#[method(name = "connectFour_queueStatus")]
+	fn queue_status(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<QueueInfo>>;
+
+	/// Place of an account in the queue it waits in, `1` for the one waiting longest.
+	#[method(name = "connectFour_queuePosition")]
+	fn queue_position(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Option<u32>>;
+
+	/// Accounts waiting in the ranked and the casual queue together.
+	#[method(name = "connectFour_queueLength")]
+	fn queue_length(&self, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// Row a stone of `account` dropped into `column` would land on. Fails with the reason if
+	/// the move isn't legal right now.
+	#[method(name = "connectFour_checkMove")]
+	fn check_move(&self, account: AccountId, column: u8, at: Option<BlockHash>) -> RpcResult<u8>;
+
+	/// Finished game in the compact notation of `gameplay::notation`, decode it with
+	/// `GameRecord::from_bytes`.
+	#[method(name = "connectFour_archivedGame")]
+	fn archived_game(&self, board_id: Hash, at: Option<BlockHash>) -> RpcResult<Option<Vec<u8>>>;
+
+	/// Cells of an archived game after its first `moves` moves, row by row from the top as in
+	/// `BoardInfo::rows`.
+	#[method(name = "connectFour_archivedPosition")]
+	fn archived_position(
+		&self,
+		board_id: Hash,
+		moves: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Vec<Vec<u8>>>>;
+
+	/// Heuristic scores of the open columns for the player at turn, the same on every node.
+	#[method(name = "connectFour_evaluatePosition")]
+	fn evaluate_position(
+		&self,
+		board_id: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Vec<ColumnScoreInfo>>>;
+
+	/// Columns a stone can be dropped into as bits, the lowest for column `0`, by the rules of
+	/// the chain. `0` unless the board is being played.
+	#[method(name = "connectFour_legalMoves")]
+	fn legal_moves(&self, board_id: Hash, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// Result of the position on a running or archived board, `None` while the game goes on.
+	#[method(name = "connectFour_isTerminal")]
+	fn is_terminal(&self, board_id: Hash, at: Option<BlockHash>) -> RpcResult<Option<OutcomeInfo>>;
+
+	/// Latest score changes of an account, oldest first, at most `MaxScoreHistory` of them.
+	#[method(name = "connectFour_scoreHistory")]
+	fn score_history(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ScoreChangeInfo<Hash, BlockNumber>>>;
+}
+
+/// Provides RPC methods to query the connect four pallet.
+pub struct ConnectFour<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ConnectFour<C, B> {
+	/// Create new `ConnectFour` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(err: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query connect four state.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Error code returned when the checked move isn't legal.
+const ILLEGAL_MOVE: i32 = 2;
+
+fn illegal_move(err: MoveError) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		ILLEGAL_MOVE,
+		"Move is not legal.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+impl<C, Block, AccountId, Hash, BlockNumber, Moment>
+	ConnectFourApiServer<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber, Moment>
+	for ConnectFour<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ConnectFourRuntimeApi<Block, AccountId, Hash, BlockNumber, Moment>,
+	AccountId: Codec + Clone + PartialEq + Send + Sync + Serialize + 'static,
+	Hash: Codec + Send + Sync + Serialize + 'static,
+	BlockNumber: Codec + Send + Sync + Serialize + 'static,
+	Moment: Codec + Send + Sync + Serialize + 'static,
+{
+	fn board(
+		&self,
+		board_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BoardInfo<Hash, AccountId, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let board = api.get_board(&at, board_id).map_err(runtime_error)?;
+		Ok(board.map(Into::into))
+	}
+
+	fn player_board(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BoardInfo<Hash, AccountId, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let board = match api.get_player_board(&at, account).map_err(runtime_error)? {
+			Some(board_id) => api.get_board(&at, board_id).map_err(runtime_error)?,
+			None => None,
+		};
+		Ok(board.map(Into::into))
+	}
+
+	fn boards_of(
+		&self,
+		accounts: Vec<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Option<BoardSummaryInfo<Hash, AccountId, Moment>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let summaries = api.boards_of(&at, accounts).map_err(runtime_error)?;
+		Ok(summaries.into_iter().map(|summary| summary.map(Into::into)).collect())
+	}
+
+	fn active_board_ids(
+		&self,
+		start_key: Option<Hash>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.active_board_ids(&at, start_key, limit).map_err(runtime_error)
+	}
+
+	fn leaderboard(
+		&self,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(AccountId, i32)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_leaderboard(&at, count).map_err(runtime_error)
+	}
+
+	fn named_leaderboard(
+		&self,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<LeaderboardEntry<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let entries = api.get_named_leaderboard(&at, count).map_err(runtime_error)?;
+		Ok(entries.into_iter().map(Into::into).collect())
+	}
+
+	fn queue_status(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<QueueInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let status = api.get_queue_status(&at, account).map_err(runtime_error)?;
+		Ok(status.map(Into::into))
+	}
+
+	fn queue_position(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u32>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_queue_position(&at, account).map_err(runtime_error)
+	}
+
+	fn queue_length(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_queue_length(&at).map_err(runtime_error)
+	}
+
+	fn check_move(
+		&self,
+		account: AccountId,
+		column: u8,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<u8> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.check_move(&at, account, column)
+			.map_err(runtime_error)?
+			.map_err(illegal_move)
+	}
+
+	fn archived_game(
+		&self,
+		board_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<u8>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_archived_game(&at, board_id).map_err(runtime_error)
+	}
+
+	fn archived_position(
+		&self,
+		board_id: Hash,
+		moves: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<Vec<u8>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let board = api.get_archived_position(&at, board_id, moves).map_err(runtime_error)?;
+		Ok(board.map(|board| {
+			(0..board.height())
+				.map(|row| (0..board.width()).map(|x| board.cell(x, row)).collect())
+				.collect()
+		}))
+	}
+
+	fn evaluate_position(
+		&self,
+		board_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<ColumnScoreInfo>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let scores = api.evaluate_position(&at, board_id).map_err(runtime_error)?;
+		Ok(scores.map(|scores| scores.into_iter().map(Into::into).collect()))
+	}
+
+	fn legal_moves(&self, board_id: Hash, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.legal_moves(&at, board_id).map_err(runtime_error)
+	}
+
+	fn is_terminal(
+		&self,
+		board_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<OutcomeInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let outcome = api.is_terminal(&at, board_id).map_err(runtime_error)?;
+		Ok(outcome.map(Into::into))
+	}
+
+	fn score_history(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ScoreChangeInfo<Hash, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let history = api.get_score_history(&at, account).map_err(runtime_error)?;
+		Ok(history.into_iter().map(Into::into).collect())
+	}
+}
@@ -0,0 +1,79 @@
+//! Runtime API definition for the connect four pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_connectfour::{
+	gameplay::{Board, ColumnScore, Outcome},
+	BoardState, BoardStruct, BoardSummary, MoveError, QueueStatus, ScoreChange,
+};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to boards, scores and the match queue without decoding raw storage.
+	/// `Moment` is a point in time on the pallet's `GameClock`.
+	pub trait ConnectFourApi<AccountId, Hash, BlockNumber, Moment> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Board with the given id, as long as it's being played.
+		fn get_board(
+			board_id: Hash,
+		) -> Option<BoardStruct<Hash, AccountId, Moment, BoardState<AccountId>, Board>>;
+
+		/// Id of the board an account is playing on.
+		fn get_player_board(account: AccountId) -> Option<Hash>;
+
+		/// Summaries of the boards the given accounts are playing on, in the same order.
+		fn boards_of(
+			accounts: Vec<AccountId>,
+		) -> Vec<Option<BoardSummary<Hash, AccountId, Moment>>>;
+
+		/// Ids of up to `limit` running boards after `start_key` in storage order, pass the
+		/// last id of a page to get the next one.
+		fn active_board_ids(start_key: Option<Hash>, limit: u32) -> Vec<Hash>;
+
+		/// The `n` highest scores, best first.
+		fn get_leaderboard(n: u32) -> Vec<(AccountId, i32)>;
+
+		/// Same as `get_leaderboard`, with the identity display name of every account.
+		fn get_named_leaderboard(n: u32) -> Vec<(AccountId, i32, Option<Vec<u8>>)>;
+
+		/// Match queue entry of an account, `None` if it isn't waiting for a game.
+		fn get_queue_status(account: AccountId) -> Option<QueueStatus>;
+
+		/// Place of an account in the queue it waits in, `1` for the one waiting longest.
+		fn get_queue_position(account: AccountId) -> Option<u32>;
+
+		/// Accounts waiting in the ranked and the casual queue together.
+		fn get_queue_length() -> u32;
+
+		/// Row a stone of `account` dropped into `column` would land on, or why the move isn't
+		/// legal right now.
+		fn check_move(account: AccountId, column: u8) -> Result<u8, MoveError>;
+
+		/// Finished game in `gameplay::notation`, as long as its result is kept.
+		fn get_archived_game(board_id: Hash) -> Option<Vec<u8>>;
+
+		/// Board of an archived game after its first `moves` moves, restored from the latest
+		/// snapshot in `ArchivedSnapshots` before them.
+		fn get_archived_position(board_id: Hash, moves: u32) -> Option<Board>;
+
+		/// Heuristic scores of the open columns for the player at turn, `None` unless the board
+		/// is being played.
+		fn evaluate_position(board_id: Hash) -> Option<Vec<ColumnScore>>;
+
+		/// Columns a stone can be dropped into as bits, the lowest for column `0`. `0` unless
+		/// the board is being played.
+		fn legal_moves(board_id: Hash) -> u32;
+
+		/// Result of the position on a running or archived board, `None` while the game goes
+		/// on or if it ended on time, by forfeit or by a ruling.
+		fn is_terminal(board_id: Hash) -> Option<Outcome>;
+
+		/// Latest score changes of an account, oldest first.
+		fn get_score_history(account: AccountId) -> Vec<ScoreChange<BlockNumber, Hash>>;
+	}
+}
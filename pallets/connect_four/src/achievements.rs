@@ -0,0 +1,89 @@
+//! Milestones players unlock by winning games.
+//!
+//! Every account keeps the achievements it unlocked as bits of an `AchievementSet`, each
+//! achievement can only be unlocked once.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+use crate::{
+	gameplay::{notation::GameRecord, Board, Logic},
+	GameStats,
+};
+
+/// Unlocked achievements of an account, see `Achievement::bit`.
+pub type AchievementSet = u32;
+
+/// Wins in a row that unlock `Achievement::WinStreak`.
+pub const WIN_STREAK: u32 = 10;
+
+/// Full columns on the final board that unlock `Achievement::FullColumns`.
+pub const FULL_COLUMNS: usize = 4;
+
+/// Milestone of a player.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum Achievement {
+	/// Won a game for the first time.
+	FirstWin,
+	/// Won `WIN_STREAK` games in a row.
+	WinStreak,
+	/// Won with at least `FULL_COLUMNS` columns filled to the top.
+	FullColumns,
+	/// Won a game the opponent could have won with one of their moves.
+	Comeback,
+}
+
+impl Achievement {
+	/// Bit of the achievement in an `AchievementSet`.
+	pub fn bit(self) -> AchievementSet {
+		1 << self as u32
+	}
+}
+
+/// Achievements the winning `player` earned with the game ending on `board`. `stats` already
+/// count the game, `game` is its move history if it got logged.
+pub fn earned(
+	stats: &GameStats,
+	board: &Board,
+	game: Option<&GameRecord>,
+	player: u8,
+) -> Vec<Achievement> {
+	let mut earned = Vec::new();
+	if stats.wins == 1 {
+		earned.push(Achievement::FirstWin);
+	}
+	if stats.current_streak >= WIN_STREAK {
+		earned.push(Achievement::WinStreak);
+	}
+	if board.width() as usize - Logic::open_columns(board).len() >= FULL_COLUMNS {
+		earned.push(Achievement::FullColumns);
+	}
+	if game.map_or(false, |game| opponent_missed_a_win(game, player)) {
+		earned.push(Achievement::Comeback);
+	}
+	earned
+}
+
+/// The opponent of `player` could have won with their next drop at some point of `game`. As
+/// `player` won in the end, the opponent let that chance slip.
+fn opponent_missed_a_win(game: &GameRecord, player: u8) -> bool {
+	let mut board = match Logic::new_board(game.width, game.height) {
+		Some(board) => board,
+		None => return false,
+	};
+	if let Some((handicapped, stones)) = game.handicap {
+		if !Logic::place_handicap(&mut board, handicapped, stones) {
+			return false
+		}
+	}
+	for (mover, mv) in &game.moves {
+		if *mover != player && Logic::winning_move(&board, *mover, game.connect).is_some() {
+			return true
+		}
+		if !Logic::play(&mut board, *mover, *mv) {
+			return false
+		}
+	}
+	false
+}
@@ -1,20 +1,1743 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-connectfour
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as ConnectFour;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
 
+const SEED: u32 = 0;
+
+/// Give an account enough free balance to join ranked matchmaking.
+fn fund_ranked<T: Config>(who: &T::AccountId) {
+	let balance =
+		T::MinRankedBalance::get() + T::QueueDeposit::get() + T::Currency::minimum_balance();
+	T::Currency::make_free_balance_be(who, balance);
+}
+
+/// Staked private game on the largest board.
+fn private_settings<T: Config>() -> PrivateGameSettingsOf<T> {
+	PrivateGameSettings {
+		award: AwardState { win: 10, lose: 5, draw: 2 },
+		variant: max_variant::<T>(),
+		time_bank: None,
+		kind: GameKind::Ranked,
+		stake: Some(T::Currency::minimum_balance()),
+	}
+}
+
+/// Largest board the runtime allows, so board evaluation hits its worst case.
+fn max_variant<T: Config>() -> BoardVariant {
+	let width = T::MaxBoardWidth::get();
+	let height = T::MaxBoardHeight::get();
+	BoardVariant { width, height, connect: width.min(height), pop_out: true }
+}
+
+/// Preset of timed games on the largest board, staked up to the minimum balance.
+fn game_preset<T: Config>() -> GamePresetOf<T> {
+	GamePreset {
+		variant: max_variant::<T>(),
+		time_bank: Some(100u32.into()),
+		award: AwardState { win: 10, lose: 5, draw: 2 },
+		min_stake: Zero::zero(),
+		max_stake: T::Currency::minimum_balance(),
+	}
+}
+
+/// Fill the leaderboard, so moving an account on it shifts every entry.
+fn fill_leaderboard<T: Config>() {
+	for i in 0..T::MaxLeaderboardSize::get() {
+		ConnectFour::<T>::set_score(&account("leader", i, SEED), Some(1_000 + i as i32));
+	}
+}
+
+/// Fill the friend and block lists of `who` up to the last free slot, so looking through
+/// them hits the worst case.
+fn fill_social_lists<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	let origin = || RawOrigin::Signed(who.clone());
+	for i in 1..T::MaxFriends::get() {
+		ConnectFour::<T>::add_friend(origin().into(), account("friend", i, SEED))?;
+	}
+	for i in 1..T::MaxBlocked::get() {
+		ConnectFour::<T>::block_player(origin().into(), account("blocked", i, SEED))?;
+	}
+	Ok(())
+}
+
+/// Open a tournament of `rounds` rounds for `players` registered accounts, check-in opens
+/// in the next block and closes in the one after.
+fn open_tournament<T: Config>(
+	creator: &T::AccountId,
+	rounds: u8,
+	players: u32,
+) -> Result<u32, &'static str> {
+	let id = NextTournamentId::<T>::get();
+	let award = AwardState { win: 10, lose: 5, draw: 2 };
+	let now = frame_system::Pallet::<T>::block_number();
+	let entry_fee = T::Currency::minimum_balance();
+	ConnectFour::<T>::create_tournament(
+		RawOrigin::Signed(creator.clone()).into(),
+		rounds,
+		award.win,
+		award.lose,
+		award.draw,
+		max_variant::<T>(),
+		Some(100u32.into()),
+		entry_fee,
+		now + 1u32.into(),
+		now + 2u32.into(),
+		false,
+	)?;
+	for i in 0..players {
+		let player: T::AccountId = account("player", i, SEED);
+		T::Currency::make_free_balance_be(&player, entry_fee + entry_fee);
+		ConnectFour::<T>::join_tournament(RawOrigin::Signed(player).into(), id)?;
+	}
+	Ok(id)
+}
+
+/// Check every registered player but the last `no_shows` in and close the check-in. The
+/// seeds get snapshot when registration closes, as in `on_initialize`.
+fn check_in_players<T: Config>(id: u32, no_shows: u32) -> Result<(), &'static str> {
+	let tournament = Tournaments::<T>::get(id).ok_or("tournament not found")?;
+	frame_system::Pallet::<T>::set_block_number(tournament.check_in);
+	ConnectFour::<T>::snapshot_seeds(tournament.check_in);
+	for i in 0..tournament.players.saturating_sub(no_shows) {
+		let player: T::AccountId = account("player", i, SEED);
+		ConnectFour::<T>::check_in(RawOrigin::Signed(player).into(), id)?;
+	}
+	frame_system::Pallet::<T>::set_block_number(tournament.start);
+	Ok(())
+}
+
+/// Finish a tournament of `players` registered players, the first one won a prize pool of
+/// the minimum balance and nobody claimed their prize yet.
+fn finished_tournament<T: Config>(players: u32) -> Result<u32, &'static str> {
+	let creator: T::AccountId = account("creator", 0, SEED);
+	let id = open_tournament::<T>(&creator, 1, players)?;
+	let prize = T::Currency::minimum_balance();
+	let pallet = ConnectFour::<T>::bot_account();
+	T::Currency::make_free_balance_be(&pallet, prize + prize);
+	T::Currency::reserve(&pallet, prize)?;
+	Tournaments::<T>::mutate(id, |tournament| {
+		if let Some(tournament) = tournament {
+			tournament.state = TournamentState::Finished;
+			tournament.prize_pool = prize;
+			tournament.claim_deadline = frame_system::Pallet::<T>::block_number();
+		}
+	});
+	for i in 0..players {
+		let player: T::AccountId = account("player", i, SEED);
+		TournamentPrizes::<T>::insert(id, player, if i == 0 { prize } else { Zero::zero() });
+	}
+	Ok(id)
+}
+
+/// Start a game of `joiner` against `creator` at a public table staked with the minimum
+/// balance, both players can afford to double the stake.
+fn staked_game<T: Config>(
+	creator: &T::AccountId,
+	joiner: &T::AccountId,
+) -> Result<T::Hash, &'static str> {
+	let stake = T::Currency::minimum_balance();
+	for player in [creator, joiner] {
+		fund_ranked::<T>(player);
+		let _ = T::Currency::deposit_creating(player, stake + stake);
+	}
+	ConnectFour::<T>::create_open_game(
+		RawOrigin::Signed(creator.clone()).into(),
+		AwardState { win: 10, lose: 5, draw: 2 },
+		stake,
+		None,
+	)?;
+	let lobby_id = NextLobbyId::<T>::get() - 1;
+	ConnectFour::<T>::join_open_game(RawOrigin::Signed(joiner.clone()).into(), lobby_id, None)?;
+	Ok(PlayerBoard::<T>::get(joiner))
+}
+
+/// Found a guild of `members` accounts named `name`, the first of them being its founder.
+fn found_guild<T: Config>(
+	name: &'static str,
+	members: u32,
+) -> Result<(u32, Vec<T::AccountId>), &'static str> {
+	let id = NextGuildId::<T>::get();
+	let members: Vec<T::AccountId> = (0..members).map(|i| account(name, i, SEED)).collect();
+	for (i, member) in members.iter().enumerate() {
+		let origin = RawOrigin::Signed(member.clone()).into();
+		if i == 0 {
+			ConnectFour::<T>::create_guild(origin)?;
+		} else {
+			ConnectFour::<T>::join_guild(origin, id)?;
+		}
+	}
+	Ok((id, members))
+}
+
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	find_game {
+		// The caller joins an almost full bucket.
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+		ScoringBoard::<T>::insert(&caller, 5);
+		for i in 1 .. T::MaxQueueLength::get() {
+			let queued: T::AccountId = account("queued", i, SEED);
+			ConnectFour::<T>::enqueue(&queued, 5)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), GameKind::Ranked)
+	verify {
+		assert!(QueuedPlayers::<T>::contains_key(&caller));
+	}
+
+	find_preset_game {
+		// Same as `find_game`, the preset gets looked up and remembered on top.
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+		ScoringBoard::<T>::insert(&caller, 5);
+		for i in 1 .. T::MaxQueueLength::get() {
+			let queued: T::AccountId = account("queued", i, SEED);
+			ConnectFour::<T>::enqueue(&queued, 5)?;
+		}
+		Presets::<T>::insert(0, game_preset::<T>());
+	}: _(RawOrigin::Signed(caller.clone()), GameKind::Ranked, 0)
+	verify {
+		assert_eq!(QueuedPresets::<T>::get(&caller), Some(0));
+	}
+
+	expire_queue {
+		let n in 0 .. T::MaxQueueLength::get();
+		// All players wait in the same bucket, which is rewritten for every one of them.
+		for i in 0 .. n {
+			let queued: T::AccountId = account("queued", i, SEED);
+			ConnectFour::<T>::enqueue(&queued, 5)?;
+		}
+		let expires = frame_system::Pallet::<T>::block_number() + T::QueueTimeout::get();
+	}: {
+		ConnectFour::<T>::expire_queue(expires);
+	}
+	verify {
+		if !T::QueueTimeout::get().is_zero() {
+			assert_eq!(QueueLength::<T>::get(), 0);
+		}
+	}
+
+	match_queued_players {
+		let c in 0 .. T::MaxMatchesPerBlock::get();
+		// Every newcomer finds a waiting opponent, so each check creates a game.
+		for i in 0 .. c {
+			let waiting: T::AccountId = account("waiting", i, SEED);
+			ConnectFour::<T>::enqueue(&waiting, i as i32 * 100)?;
+		}
+		NewlyQueued::<T>::kill();
+		for i in 0 .. c {
+			let newcomer: T::AccountId = account("newcomer", i, SEED);
+			ConnectFour::<T>::enqueue(&newcomer, i as i32 * 100)?;
+		}
+	}: {
+		ConnectFour::<T>::match_queued_players(frame_system::Pallet::<T>::block_number());
+	}
+	verify {
+		assert!(NewlyQueued::<T>::get().is_empty());
+		assert_eq!(QueuedPlayers::<T>::iter().count(), 0);
+	}
+
+	cancel_queue {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+		ConnectFour::<T>::find_game(RawOrigin::Signed(caller.clone()).into(), GameKind::Ranked)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!QueuedPlayers::<T>::contains_key(&caller));
+	}
+
+	challenge {
+		// The opponent accepts the challenge right away.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		ConnectFour::<T>::set_profile(
+			RawOrigin::Signed(opponent.clone()).into(),
+			None,
+			None,
+			true,
+			Some(100),
+			Vec::new(),
+		)?;
+		let variant = max_variant::<T>();
+		let time_bank = Some(100u32.into());
+		let handicap = Some(Handicap::Stones(2));
+		let first_move = FirstMovePolicy::AlternateFromLastGame;
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		opponent,
+		10,
+		5,
+		2,
+		variant,
+		time_bank,
+		handicap,
+		first_move,
+		GameKind::Casual,
+		None
+	)
+	verify {
+		assert!(!Challenges::<T>::contains_key(&caller));
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	challenge_preset {
+		// The opponent accepts the challenge right away.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		ConnectFour::<T>::set_profile(
+			RawOrigin::Signed(opponent.clone()).into(),
+			None,
+			None,
+			true,
+			Some(100),
+			Vec::new(),
+		)?;
+		Presets::<T>::insert(0, game_preset::<T>());
+		let first_move = FirstMovePolicy::AlternateFromLastGame;
+	}: _(RawOrigin::Signed(caller.clone()), opponent, 0, first_move, GameKind::Casual, None)
+	verify {
+		assert!(!Challenges::<T>::contains_key(&caller));
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	resp_challenge {
+		// Both players stake on the game.
+		let caller: T::AccountId = whitelisted_caller();
+		let challenger: T::AccountId = account("challenger", 0, SEED);
+		for player in [&caller, &challenger] {
+			fund_ranked::<T>(player);
+		}
+		ConnectFour::<T>::challenge(
+			RawOrigin::Signed(challenger.clone()).into(),
+			caller.clone(),
+			10,
+			5,
+			2,
+			max_variant::<T>(),
+			Some(100u32.into()),
+			Some(Handicap::Stones(2)),
+			FirstMovePolicy::AlternateFromLastGame,
+			GameKind::Ranked,
+			Some(T::Currency::minimum_balance()),
+		)?;
+		let award = AwardState { win: 10, lose: 5, draw: 2 };
+	}: _(RawOrigin::Signed(caller.clone()), challenger.clone(), true, award)
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+		assert!(BoardStakes::<T>::contains_key(PlayerBoard::<T>::get(&caller)));
+		assert!(!Challenges::<T>::contains_key(&challenger));
+	}
+
+	cancel_challenge {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		ConnectFour::<T>::challenge(
+			RawOrigin::Signed(caller.clone()).into(),
+			opponent,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Challenges::<T>::contains_key(&caller));
+	}
+
+	withdraw_all {
+		// Every pending state at once: rematch, ranked queue, staked challenge and table.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		fund_ranked::<T>(&caller);
+		// Room for both stakes on top of the queue deposit.
+		let stakes = T::Currency::minimum_balance() * 2u32.into();
+		let _ = T::Currency::deposit_creating(&caller, stakes);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		ConnectFour::<T>::finish_game(board, FinishReason::BoardFull);
+		ConnectFour::<T>::find_game(RawOrigin::Signed(caller.clone()).into(), GameKind::Ranked)?;
+		ConnectFour::<T>::challenge(
+			RawOrigin::Signed(caller.clone()).into(),
+			opponent,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			Some(T::Currency::minimum_balance()),
+		)?;
+		ConnectFour::<T>::create_open_game(
+			RawOrigin::Signed(caller.clone()).into(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			T::Currency::minimum_balance(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!QueuedPlayers::<T>::contains_key(&caller));
+		assert!(!Challenges::<T>::contains_key(&caller));
+		assert!(OpenLobbies::<T>::get().is_empty());
+		assert!(!Rematches::<T>::contains_key(&caller));
+	}
+
+	play_turn {
+		// Winning move on the largest board, which also settles scores and cleans up.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let variant = max_variant::<T>();
+		let award = AwardState { win: 10, lose: 5, draw: 2 };
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			award,
+			variant,
+			Some(100u32.into()),
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_1;
+				for _ in 1 .. variant.connect {
+					Logic::add_stone(&mut board.board, 0, PLAYER_1);
+				}
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()), 0, Some(0))
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+		assert!(!PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	pop_stone {
+		// Popping ends the game, which also settles scores and cleans up.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let variant = max_variant::<T>();
+		let award = AwardState { win: 10, lose: 5, draw: 2 };
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			award,
+			variant,
+			Some(100u32.into()),
+		)?;
+		// Popping column 0 completes a red line in the second row and a blue one in the
+		// bottom row, so both sides get checked.
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_1;
+				for column in 1 .. variant.connect {
+					Logic::add_stone(&mut board.board, column, PLAYER_2);
+					Logic::add_stone(&mut board.board, column, PLAYER_1);
+				}
+				for player in [PLAYER_1, PLAYER_2, PLAYER_1] {
+					Logic::add_stone(&mut board.board, 0, player);
+				}
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+	}
+
+	play_turn_mid_game {
+		// First move of a matched game on the largest board, which hands back the deposit. It's
+		// spectated, so the move is streamed to the game trie.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			Some(100u32.into()),
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_1;
+			}
+		});
+		FirstMoveDeposits::<T>::insert(&caller, T::QueueDeposit::get());
+		let spectator: T::AccountId = account("spectator", 0, SEED);
+		ConnectFour::<T>::watch_board(RawOrigin::Signed(spectator).into(), board_id)?;
+	}: play_turn(RawOrigin::Signed(caller.clone()), 0, Some(0))
+	verify {
+		assert_eq!(Boards::<T>::get(board_id).map(|board| board.last_move), Some(Some(0)));
+		assert!(ConnectFour::<T>::streamed_move(board_id, 1).is_some());
+	}
+
+	pop_stone_mid_game {
+		// Popping leaves no line on the largest board.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			Some(100u32.into()),
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_1;
+				for player in [PLAYER_1, PLAYER_2, PLAYER_1] {
+					Logic::add_stone(&mut board.board, 0, player);
+				}
+			}
+		});
+	}: pop_stone(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert_eq!(Boards::<T>::get(board_id).map(|board| board.last_move), Some(None));
+	}
+
+	claim_timeout {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			Some(1u32.into()),
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_2;
+			}
+		});
+		T::BenchmarkHelper::advance_clock(10u32.into());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+	}
+
+	request_rematch {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		ConnectFour::<T>::finish_game(board, FinishReason::BoardFull);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Rematches::<T>::get(&caller).map_or(false, |rematch| rematch.requested));
+	}
+
+	accept_rematch {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		ConnectFour::<T>::finish_game(board, FinishReason::BoardFull);
+		ConnectFour::<T>::request_rematch(RawOrigin::Signed(opponent.clone()).into())?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+		assert!(!Rematches::<T>::contains_key(&opponent));
+	}
+
+	watch_board {
+		// The caller takes the last free spectator slot.
+		let caller: T::AccountId = whitelisted_caller();
+		let board_id = ConnectFour::<T>::create_game(
+			account("red", 0, SEED),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		for i in 1 .. T::MaxSpectators::get() {
+			let spectator: T::AccountId = account("spectator", i, SEED);
+			ConnectFour::<T>::watch_board(RawOrigin::Signed(spectator).into(), board_id)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), board_id)
+	verify {
+		assert!(Spectators::<T>::get(board_id).contains(&caller));
+	}
+
+	unwatch_board {
+		// The caller is the last of a full list of spectators.
+		let caller: T::AccountId = whitelisted_caller();
+		let board_id = ConnectFour::<T>::create_game(
+			account("red", 0, SEED),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		for i in 1 .. T::MaxSpectators::get() {
+			let spectator: T::AccountId = account("spectator", i, SEED);
+			ConnectFour::<T>::watch_board(RawOrigin::Signed(spectator).into(), board_id)?;
+		}
+		ConnectFour::<T>::watch_board(RawOrigin::Signed(caller.clone()).into(), board_id)?;
+	}: _(RawOrigin::Signed(caller.clone()), board_id)
+	verify {
+		assert!(!Spectators::<T>::get(board_id).contains(&caller));
+	}
+
+	set_event_verbosity {
+		let caller: T::AccountId = whitelisted_caller();
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller), board_id, EventVerbosity::FullBoard)
+	verify {
+		let verbosity = Boards::<T>::get(board_id).map(|board| board.verbosity);
+		assert_eq!(verbosity, Some(EventVerbosity::FullBoard));
+	}
+
+	send_emote {
+		// The caller is the last of a full list of spectators.
+		let caller: T::AccountId = whitelisted_caller();
+		let board_id = ConnectFour::<T>::create_game(
+			account("red", 0, SEED),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		for i in 1 .. T::MaxSpectators::get() {
+			let spectator: T::AccountId = account("spectator", i, SEED);
+			ConnectFour::<T>::watch_board(RawOrigin::Signed(spectator).into(), board_id)?;
+		}
+		ConnectFour::<T>::watch_board(RawOrigin::Signed(caller.clone()).into(), board_id)?;
+	}: _(RawOrigin::Signed(caller.clone()), board_id, Emote::GoodGame)
+	verify {
+		assert!(LastEmote::<T>::contains_key(&caller));
+	}
+
+	decay_scores {
+		let c in 0 .. T::MaxDecayPerBlock::get();
+		// Every decayed score also enters the standings, the worst case for ranking.
+		for i in 0 .. c {
+			let player: T::AccountId = account("player", i, SEED);
+			ScoringBoard::<T>::insert(&player, i as i32);
+		}
+		PendingDecay::<T>::put((0, None::<T::AccountId>));
+	}: {
+		ConnectFour::<T>::decay_scores();
+	}
+	verify {
+		assert!(PendingDecay::<T>::get().map_or(true, |(_, last)| last.is_some()));
+	}
+
+	decay_inactive_scores {
+		let c in 0 .. T::MaxInactivityChecks::get();
+		// Every checked account has been away long enough and its score decays.
+		for i in 0 .. c {
+			let player: T::AccountId = account("player", i, SEED);
+			ScoringBoard::<T>::insert(&player, 1_000 + i as i32);
+			LastActive::<T>::insert(&player, T::BlockNumber::zero());
+		}
+		let now = T::InactivityPeriod::get();
+	}: {
+		ConnectFour::<T>::decay_inactive_scores(now);
+	}
+	verify {
+		assert_eq!(InactivityCursor::<T>::exists(), c > 0);
+	}
+
+	prune_results {
+		let n in 0 .. 100;
+		let finished = T::BlockNumber::from(1u32);
+		for i in 0 .. n {
+			let result = GameResult {
+				red: account("red", i, SEED),
+				blue: account("blue", i, SEED),
+				winner: None,
+				reason: FinishReason::BoardFull,
+				moves: 42,
+			};
+			ResultsByBlock::<T>::insert(finished, T::Hashing::hash_of(&i), result);
+		}
+		let now = finished + T::ResultRetentionBlocks::get();
+	}: {
+		ConnectFour::<T>::prune_results(now);
+	}
+	verify {
+		assert_eq!(ResultsByBlock::<T>::iter_prefix(finished).count(), 0);
+	}
+
+	distribute_season_rewards {
+		let n in 1 .. T::MaxSeasonRewardees::get();
+		let standings: Vec<_> = (0 .. n).map(|i| (account("player", i, SEED), i as i32)).collect();
+		let standings = BoundedVec::try_from(standings).map_err(|_| "too many standings")?;
+		SeasonStandings::<T>::insert(0, standings);
+		let origin = T::SeasonRewardOrigin::successful_origin();
+		let reward_pool = T::Currency::minimum_balance() * 100u32.into();
+	}: _<T::Origin>(origin, 0, reward_pool)
+	verify {
+		assert!(!SeasonStandings::<T>::contains_key(0));
+	}
+
+	play_vs_bot {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), BotDifficulty::Hard)
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	play_turn_vs_bot {
+		// The hard bot finds no winning or blocking move on the largest board and falls back to
+		// a random one, after checking every column twice.
+		let caller: T::AccountId = whitelisted_caller();
+		ConnectFour::<T>::play_vs_bot(RawOrigin::Signed(caller.clone()).into(), BotDifficulty::Hard)?;
+		let board_id = PlayerBoard::<T>::get(&caller);
+		let variant = max_variant::<T>();
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.variant = variant;
+				if let Some(cells) = ConnectFour::<T>::empty_board(&variant) {
+					board.board = cells;
+				}
+			}
+		});
+	}: play_turn(RawOrigin::Signed(caller.clone()), 0, None)
+	verify {
+		assert!(Boards::<T>::contains_key(board_id));
+	}
+
+	challenge_team {
+		let caller: T::AccountId = whitelisted_caller();
+		let teammate: T::AccountId = account("teammate", 0, SEED);
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let opponent_mate: T::AccountId = account("opponent_mate", 0, SEED);
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		teammate,
+		opponent,
+		opponent_mate,
+		10,
+		5,
+		2,
+		max_variant::<T>(),
+		Some(100u32.into())
+	)
+	verify {
+		assert!(TeamChallenges::<T>::contains_key(&caller));
+	}
+
+	resp_team_challenge {
+		// Last acceptance, which starts the game.
+		let caller: T::AccountId = whitelisted_caller();
+		let challenger: T::AccountId = account("challenger", 0, SEED);
+		let teammate: T::AccountId = account("teammate", 0, SEED);
+		let opponent_mate: T::AccountId = account("opponent_mate", 0, SEED);
+		ConnectFour::<T>::challenge_team(
+			RawOrigin::Signed(challenger.clone()).into(),
+			teammate.clone(),
+			caller.clone(),
+			opponent_mate.clone(),
+			10,
+			5,
+			2,
+			max_variant::<T>(),
+			Some(100u32.into()),
+		)?;
+		ConnectFour::<T>::resp_team_challenge(
+			RawOrigin::Signed(teammate).into(),
+			challenger.clone(),
+			true,
+		)?;
+		ConnectFour::<T>::resp_team_challenge(
+			RawOrigin::Signed(opponent_mate).into(),
+			challenger.clone(),
+			true,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), challenger.clone(), true)
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+		assert!(!TeamChallenges::<T>::contains_key(&challenger));
+	}
+
+	force_end_game {
+		fill_leaderboard::<T>();
+		let winner: T::AccountId = account("winner", 0, SEED);
+		let loser: T::AccountId = account("loser", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			winner.clone(),
+			loser,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, board_id, Some(winner.clone()))
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+		assert_eq!(ScoringBoard::<T>::get(&winner), Some(10));
+	}
+
+	abort_game {
+		// Both players staked at a public table and get their stakes back.
+		let caller: T::AccountId = whitelisted_caller();
+		let creator: T::AccountId = account("creator", 0, SEED);
+		for player in [&caller, &creator] {
+			fund_ranked::<T>(player);
+		}
+		ConnectFour::<T>::create_open_game(
+			RawOrigin::Signed(creator).into(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			T::Currency::minimum_balance(),
+			None,
+		)?;
+		let lobby_id = NextLobbyId::<T>::get() - 1;
+		ConnectFour::<T>::join_open_game(RawOrigin::Signed(caller.clone()).into(), lobby_id, None)?;
+	}: _(RawOrigin::Signed(caller.clone()), AbortReason::WrongOpponent)
+	verify {
+		assert!(!PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	offer_double {
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let board_id = staked_game::<T>(&creator, &whitelisted_caller())?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		let caller = board.account_at_turn(board.next_player).clone();
+	}: _(RawOrigin::Signed(caller), board_id)
+	verify {
+		let board = Boards::<T>::get(board_id).ok_or("board not found")?;
+		assert!(matches!(board.cube, DoublingCube::Offered(_)));
+	}
+
+	respond_double {
+		// Declining ends the game.
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let board_id = staked_game::<T>(&creator, &whitelisted_caller())?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		let offerer = board.account_at_turn(board.next_player).clone();
+		let caller = if offerer == board.red { board.blue.clone() } else { board.red.clone() };
+		ConnectFour::<T>::offer_double(RawOrigin::Signed(offerer).into(), board_id)?;
+	}: _(RawOrigin::Signed(caller), board_id, false)
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+	}
+
+	commit_first_move {
+		let caller: T::AccountId = whitelisted_caller();
+		let commitment = T::Hashing::hash_of(&[0u8; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), commitment)
+	verify {
+		assert_eq!(FirstMoveCommits::<T>::get(&caller), Some(commitment));
+	}
+
+	reveal_first_move {
+		// Second reveal, which draws the first mover.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		FirstMoveCommits::<T>::insert(&caller, T::Hashing::hash_of(&[0u8; 32]));
+		FirstMoveCommits::<T>::insert(&opponent, T::Hashing::hash_of(&[1u8; 32]));
+		let board_id = ConnectFour::<T>::new_game(
+			caller.clone(),
+			opponent.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+			GameMode::Standard,
+			FirstMovePolicy::CommitReveal,
+			GameKind::Ranked,
+		)?;
+		let origin = RawOrigin::Signed(opponent).into();
+		ConnectFour::<T>::reveal_first_move(origin, board_id, [1u8; 32])?;
+	}: _(RawOrigin::Signed(caller), board_id, [0u8; 32])
+	verify {
+		assert!(!FirstMoveDraws::<T>::contains_key(board_id));
+	}
+
+	swap_sides {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::new_game(
+			caller,
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			Some(100u32.into()),
+			GameMode::Standard,
+			FirstMovePolicy::PieRule,
+			GameKind::Ranked,
+		)?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		let first = board.account_at_turn(board.next_player).clone();
+		let second = if first == board.red { board.blue.clone() } else { board.red.clone() };
+		ConnectFour::<T>::play_turn(RawOrigin::Signed(first).into(), 0, None)?;
+	}: _(RawOrigin::Signed(second), board_id)
+	verify {
+		assert!(Boards::<T>::get(board_id).map_or(false, |board| board.sides_swapped));
+	}
+
+	report_opponent {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller), board_id, ReportReason::EngineAssistance)
+	verify {
+		assert!(Reports::<T>::contains_key(board_id));
+	}
+
+	resolve_report {
+		// The report is upheld against the winner of a ranked game, who loses it instead.
+		fill_leaderboard::<T>();
+		let reporter: T::AccountId = account("reporter", 0, SEED);
+		let reported: T::AccountId = account("reported", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			reporter.clone(),
+			reported.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+		ConnectFour::<T>::report_opponent(
+			RawOrigin::Signed(reporter.clone()).into(),
+			board_id,
+			ReportReason::EngineAssistance,
+		)?;
+		let origin = T::AdminOrigin::successful_origin();
+		ConnectFour::<T>::force_end_game(origin.clone(), board_id, Some(reported))?;
+	}: _<T::Origin>(origin, board_id, Verdict::Upheld)
+	verify {
+		assert!(!Disputes::<T>::contains_key(board_id));
+		assert_eq!(ScoringBoard::<T>::get(&reporter), Some(10));
+	}
+
+	reset_score {
+		fill_leaderboard::<T>();
+		let player: T::AccountId = account("leader", 0, SEED);
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, player.clone())
+	verify {
+		assert!(!ScoringBoard::<T>::contains_key(&player));
+	}
+
+	set_matchmaking_params {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 20, AwardState { win: 20, lose: 10, draw: 4 })
+	verify {
+		assert_eq!(AcceptedDiff::<T>::get(), 20);
+	}
+
+	set_tier_boundaries {
+		let origin = T::AdminOrigin::successful_origin();
+		let tiers = 1..=MAX_TIER_BOUNDARIES as i32;
+		let boundaries: Vec<i32> = tiers.map(|tier| tier * 100).collect();
+		let boundaries: TierBoundariesOf = boundaries.try_into().unwrap();
+	}: _<T::Origin>(origin, boundaries.clone(), true)
+	verify {
+		assert_eq!(TierBoundaries::<T>::get(), boundaries);
+	}
+
+	create_game_deterministic {
+		let origin = T::AdminOrigin::successful_origin();
+		let red: T::AccountId = account("red", 0, SEED);
+		let blue: T::AccountId = account("blue", 0, SEED);
+		let award = AwardState { win: 10, lose: 5, draw: 2 };
+		let (variant, seed) = (max_variant::<T>(), T::Hashing::hash_of(&0u32));
+		let first_move = FirstMovePolicy::Random;
+	}: _<T::Origin>(origin, red.clone(), blue, award, variant, seed, first_move, GameKind::Ranked)
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&red));
+	}
+
+	set_paused {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, true)
+	verify {
+		assert!(Paused::<T>::get());
+	}
+
+	commit_move {
+		// Second commitment of the round, which starts the reveals.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::new_game(
+			caller.clone(),
+			opponent.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			Some(100u32.into()),
+			GameMode::Blitz,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+		)?;
+		let commitment = T::Hashing::hash_of(&(0u8, [0u8; 32]));
+		ConnectFour::<T>::commit_move(RawOrigin::Signed(opponent).into(), commitment)?;
+	}: _(RawOrigin::Signed(caller.clone()), commitment)
+	verify {
+		let round = Boards::<T>::get(board_id).and_then(|board| board.blitz);
+		assert_eq!(round.map(|round| round.phase), Some(BlitzPhase::Reveal));
+	}
+
+	reveal_move {
+		// Second reveal of the round, red drops first and wins.
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let variant = max_variant::<T>();
+		let board_id = ConnectFour::<T>::new_game(
+			caller.clone(),
+			opponent.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			variant,
+			Some(100u32.into()),
+			GameMode::Blitz,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+		)?;
+		let salt = [0u8; 32];
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_1;
+				for _ in 1 .. variant.connect {
+					Logic::add_stone(&mut board.board, 0, PLAYER_1);
+				}
+			}
+		});
+		ConnectFour::<T>::commit_move(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Hashing::hash_of(&(0u8, salt)),
+		)?;
+		ConnectFour::<T>::commit_move(
+			RawOrigin::Signed(opponent.clone()).into(),
+			T::Hashing::hash_of(&(1u8, salt)),
+		)?;
+		ConnectFour::<T>::reveal_move(RawOrigin::Signed(opponent).into(), 1, salt)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, salt)
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+	}
+
+	request_undo {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent,
+			AwardState { win: 0, lose: 0, draw: 0 },
+			BoardVariant::default(),
+			None,
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				Logic::add_stone(&mut board.board, 0, PLAYER_1);
+				board.last_move = Some(0);
+				board.next_player = PLAYER_2;
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(UndoRequests::<T>::get(board_id), Some(caller));
+	}
+
+	respond_undo {
+		let caller: T::AccountId = whitelisted_caller();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			caller.clone(),
+			opponent.clone(),
+			AwardState { win: 0, lose: 0, draw: 0 },
+			BoardVariant::default(),
+			None,
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				Logic::add_stone(&mut board.board, 0, PLAYER_2);
+				board.last_move = Some(0);
+				board.next_player = PLAYER_1;
+			}
+		});
+		ConnectFour::<T>::request_undo(RawOrigin::Signed(opponent).into())?;
+	}: _(RawOrigin::Signed(caller.clone()), true)
+	verify {
+		assert_eq!(Boards::<T>::get(board_id).map(|board| board.next_player), Some(PLAYER_2));
+	}
+
+	create_open_game {
+		// Every other table is taken, so the whole list gets checked.
+		for i in 1 .. T::MaxOpenLobbies::get() {
+			let creator: T::AccountId = account("creator", i, SEED);
+			fund_ranked::<T>(&creator);
+			ConnectFour::<T>::create_open_game(
+				RawOrigin::Signed(creator).into(),
+				AwardState { win: 10, lose: 5, draw: 2 },
+				T::Currency::minimum_balance(),
+				None,
+			)?;
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+		let award = AwardState { win: 10, lose: 5, draw: 2 };
+	}: _(RawOrigin::Signed(caller.clone()), award, T::Currency::minimum_balance(), None)
+	verify {
+		assert!(OpenLobbies::<T>::get().iter().any(|lobby| lobby.creator == caller));
+	}
+
+	join_open_game {
+		// The newest of a full list of tables.
+		let max = T::MaxOpenLobbies::get();
+		for i in 0 .. max {
+			let creator: T::AccountId = account("creator", i, SEED);
+			fund_ranked::<T>(&creator);
+			ConnectFour::<T>::create_open_game(
+				RawOrigin::Signed(creator).into(),
+				AwardState { win: 10, lose: 5, draw: 2 },
+				T::Currency::minimum_balance(),
+				None,
+			)?;
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), max - 1, None)
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	close_open_game {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+		ConnectFour::<T>::create_open_game(
+			RawOrigin::Signed(caller.clone()).into(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			T::Currency::minimum_balance(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(OpenLobbies::<T>::get().is_empty());
+	}
+
+	create_private_game {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+		let code_hash = T::Hashing::hash(b"invite");
+		let settings = private_settings::<T>();
+	}: _(RawOrigin::Signed(caller), code_hash, settings)
+	verify {
+		assert!(PrivateGames::<T>::contains_key(code_hash));
+	}
+
+	join_private_game {
+		let creator: T::AccountId = account("creator", 0, SEED);
+		fund_ranked::<T>(&creator);
+		let code = b"invite".to_vec();
+		ConnectFour::<T>::create_private_game(
+			RawOrigin::Signed(creator).into(),
+			T::Hashing::hash(&code),
+			private_settings::<T>(),
+		)?;
+		let caller: T::AccountId = whitelisted_caller();
+		fund_ranked::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), code)
+	verify {
+		assert!(PlayerBoard::<T>::contains_key(&caller));
+	}
+
+	expire_private_games {
+		let n in 0 .. T::MaxPrivateGamesPerBlock::get();
+		for i in 0 .. n {
+			let creator: T::AccountId = account("creator", i, SEED);
+			fund_ranked::<T>(&creator);
+			ConnectFour::<T>::create_private_game(
+				RawOrigin::Signed(creator).into(),
+				T::Hashing::hash_of(&i),
+				private_settings::<T>(),
+			)?;
+		}
+		let expires = frame_system::Pallet::<T>::block_number() + T::PrivateGameLifetime::get();
+	}: {
+		ConnectFour::<T>::expire_private_games(expires);
+	}
+	verify {
+		assert_eq!(PrivateGames::<T>::iter().count(), 0);
+	}
+
+	create_tournament {
+		let caller: T::AccountId = whitelisted_caller();
+		let rounds = T::MaxTournamentRounds::get() as u8;
+		let variant = max_variant::<T>();
+		let bank = Some(100u32.into());
+		let fee = T::Currency::minimum_balance();
+		let now = frame_system::Pallet::<T>::block_number();
+		let (check_in, start) = (now + 1u32.into(), now + 2u32.into());
+	}: _(RawOrigin::Signed(caller), rounds, 10, 5, 2, variant, bank, fee, check_in, start, true)
+	verify {
+		assert!(Tournaments::<T>::contains_key(0));
+	}
+
+	join_tournament {
+		let caller: T::AccountId = whitelisted_caller();
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let id = open_tournament::<T>(&creator, 1, T::MaxTournamentPlayers::get() - 1)?;
+		let entry_fee = T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(&caller, entry_fee + entry_fee);
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert!(TournamentPlayers::<T>::contains_key(id, &caller));
+	}
+
+	check_in {
+		let caller: T::AccountId = account("player", 0, SEED);
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let id = open_tournament::<T>(&creator, 1, 1)?;
+		let tournament = Tournaments::<T>::get(id).ok_or("tournament not found")?;
+		frame_system::Pallet::<T>::set_block_number(tournament.check_in);
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		let standing = TournamentPlayers::<T>::get(id, &caller).ok_or("not registered")?;
+		assert!(standing.checked_in);
+	}
+
+	start_tournament {
+		// Half of the players didn't check in and lose their entry fee.
+		let n in 4 .. T::MaxTournamentPlayers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let id = open_tournament::<T>(&caller, 1, n)?;
+		check_in_players::<T>(id, n / 2)?;
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Tournaments::<T>::get(id).map(|tournament| tournament.round), Some(1));
+		assert_eq!(TournamentPlayers::<T>::iter_prefix(id).count() as u32, n - n / 2);
+	}
+
+	snapshot_seeds {
+		let n in 2 .. T::MaxTournamentPlayers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let id = open_tournament::<T>(&caller, 1, n)?;
+		let check_in = Tournaments::<T>::get(id).ok_or("tournament not found")?.check_in;
+	}: {
+		ConnectFour::<T>::snapshot_seeds(check_in);
+	}
+	verify {
+		assert_eq!(TournamentSeeds::<T>::get(id).len() as u32, n);
+	}
+
+	advance_tournament {
+		// Every game of the first round finished, the second round gets paired.
+		let n in 3 .. T::MaxTournamentPlayers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let id = open_tournament::<T>(&caller, 2, n)?;
+		check_in_players::<T>(id, 0)?;
+		ConnectFour::<T>::start_tournament(RawOrigin::Signed(caller.clone()).into(), id)?;
+		PendingBoardCreations::<T>::remove(id);
+		Tournaments::<T>::mutate(id, |tournament| {
+			if let Some(tournament) = tournament {
+				tournament.pending = 0;
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Tournaments::<T>::get(id).map(|tournament| tournament.round), Some(2));
+	}
+
+	claim_prize {
+		// The winner gets the prize pool on top of their entry fee.
+		let id = finished_tournament::<T>(2)?;
+		let caller: T::AccountId = account("player", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert!(!TournamentPrizes::<T>::contains_key(id, &caller));
+	}
+
+	sweep_prizes {
+		let n in 1 .. T::MaxPrizeSweeps::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let id = finished_tournament::<T>(n)?;
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert_eq!(TournamentPrizes::<T>::iter_prefix(id).count(), 0);
+	}
+
+	create_guild {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(PlayerGuild::<T>::contains_key(&caller));
+	}
+
+	join_guild {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, _) = found_guild::<T>("member", T::MaxGuildMembers::get() - 1)?;
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(PlayerGuild::<T>::get(&caller), Some(id));
+	}
+
+	leave_guild {
+		// The founder leaves and the next member takes over.
+		let (id, members) = found_guild::<T>("member", T::MaxGuildMembers::get())?;
+		let founder = members[0].clone();
+	}: _(RawOrigin::Signed(founder.clone()))
+	verify {
+		assert_eq!(Guilds::<T>::get(id).map(|guild| guild.founder), Some(members[1].clone()));
+	}
+
+	challenge_guild {
+		let players = T::MaxGuildMatchPlayers::get();
+		let (id, members) = found_guild::<T>("member", players)?;
+		let (opponent, _) = found_guild::<T>("opponent", players)?;
+		let founder = members[0].clone();
+	}: _(RawOrigin::Signed(founder), opponent, 10, 5, 2, members)
+	verify {
+		assert!(GuildChallenges::<T>::contains_key(id));
+	}
+
+	resp_guild_challenge {
+		// Every player of both lineups gets a board.
+		let n in 1 .. T::MaxGuildMatchPlayers::get();
+		let (challenger, members) = found_guild::<T>("member", n)?;
+		let (id, opponents) = found_guild::<T>("opponent", n)?;
+		let founder = opponents[0].clone();
+		ConnectFour::<T>::challenge_guild(
+			RawOrigin::Signed(members[0].clone()).into(),
+			id,
+			10,
+			5,
+			2,
+			members,
+		)?;
+	}: _(RawOrigin::Signed(founder), challenger, true, opponents)
+	verify {
+		assert!(GuildMatches::<T>::contains_key(0));
+	}
+
+	expire_stale_game {
+		let red: T::AccountId = account("red", 0, SEED);
+		let blue: T::AccountId = account("blue", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			red,
+			blue,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		T::BenchmarkHelper::advance_clock(T::StaleGameAge::get());
+	}: _(RawOrigin::None, board_id)
+	verify {
+		assert!(!Boards::<T>::contains_key(board_id));
+	}
+
+	challenge_friend {
+		let caller: T::AccountId = whitelisted_caller();
+		let friend: T::AccountId = account("friend", 0, SEED);
+		fill_social_lists::<T>(&caller)?;
+		fill_social_lists::<T>(&friend)?;
+		ConnectFour::<T>::add_friend(RawOrigin::Signed(caller.clone()).into(), friend.clone())?;
+		ConnectFour::<T>::add_friend(RawOrigin::Signed(friend.clone()).into(), caller.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), friend, GameKind::Ranked)
+	verify {
+		assert!(Challenges::<T>::contains_key(&caller));
+	}
+
+	add_friend {
+		let caller: T::AccountId = whitelisted_caller();
+		let friend: T::AccountId = account("friend", 0, SEED);
+		fill_social_lists::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), friend.clone())
+	verify {
+		assert!(Friends::<T>::get(&caller).contains(&friend));
+	}
+
+	remove_friend {
+		// The friend is the last of a full list.
+		let caller: T::AccountId = whitelisted_caller();
+		let friend: T::AccountId = account("friend", 0, SEED);
+		fill_social_lists::<T>(&caller)?;
+		ConnectFour::<T>::add_friend(RawOrigin::Signed(caller.clone()).into(), friend.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), friend.clone())
+	verify {
+		assert!(!Friends::<T>::get(&caller).contains(&friend));
+	}
+
+	block_player {
+		// The blocked account is the last of a full friend list.
+		let caller: T::AccountId = whitelisted_caller();
+		let player: T::AccountId = account("friend", 0, SEED);
+		fill_social_lists::<T>(&caller)?;
+		ConnectFour::<T>::add_friend(RawOrigin::Signed(caller.clone()).into(), player.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), player.clone())
+	verify {
+		assert!(Blocked::<T>::get(&caller).contains(&player));
+		assert!(!Friends::<T>::get(&caller).contains(&player));
+	}
+
+	unblock_player {
+		// The blocked account is the last of a full list.
+		let caller: T::AccountId = whitelisted_caller();
+		let player: T::AccountId = account("blocked", 0, SEED);
+		fill_social_lists::<T>(&caller)?;
+		ConnectFour::<T>::block_player(RawOrigin::Signed(caller.clone()).into(), player.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), player.clone())
+	verify {
+		assert!(!Blocked::<T>::get(&caller).contains(&player));
+	}
+
+	set_profile {
+		let caller: T::AccountId = whitelisted_caller();
+		let display_name = vec![b'a'; T::MaxDisplayNameLength::get() as usize];
+		let variant = Some(max_variant::<T>());
+		let max_stake = Some(T::Currency::minimum_balance());
+	}: _(RawOrigin::Signed(caller.clone()), variant, max_stake, false, Some(100), display_name)
+	verify {
+		assert!(PlayerProfile::<T>::contains_key(&caller));
+	}
+
+	predict {
+		// The caller takes the last free prediction slot.
+		let caller: T::AccountId = whitelisted_caller();
+		let red: T::AccountId = account("red", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			red.clone(),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		let stake = T::Currency::minimum_balance();
+		for i in 1 .. T::MaxPredictions::get() {
+			let predictor: T::AccountId = account("predictor", i, SEED);
+			T::Currency::make_free_balance_be(&predictor, stake + stake);
+			ConnectFour::<T>::predict(
+				RawOrigin::Signed(predictor).into(),
+				board_id,
+				Some(red.clone()),
+				stake,
+			)?;
+		}
+		T::Currency::make_free_balance_be(&caller, stake + stake);
+	}: _(RawOrigin::Signed(caller.clone()), board_id, Some(red), stake)
+	verify {
+		assert!(Predictions::<T>::get(board_id).iter().any(|p| p.predictor == caller));
+	}
+
+	set_prediction_fee {
+		let origin = T::AdminOrigin::successful_origin();
+		let fee = Perbill::from_percent(5);
+	}: _<T::Origin>(origin, fee)
+	verify {
+		assert_eq!(PredictionFee::<T>::get(), fee);
+	}
+
+	set_stake_fee {
+		let origin = T::AdminOrigin::successful_origin();
+		let fee = Perbill::from_percent(5);
+	}: _<T::Origin>(origin, fee)
+	verify {
+		assert_eq!(StakeFee::<T>::get(), fee);
+	}
+
+	set_max_active_boards {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 500)
+	verify {
+		assert_eq!(MaxActiveBoards::<T>::get(), 500);
+	}
+
+	set_max_games_per_player {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 2)
+	verify {
+		assert_eq!(MaxGamesPerPlayer::<T>::get(), 2);
+	}
+
+	set_preset {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 0, Some(game_preset::<T>()))
+	verify {
+		assert!(Presets::<T>::contains_key(0));
+	}
+
+	set_abandon_penalty {
+		let origin = T::AdminOrigin::successful_origin();
+		let penalty = Perbill::from_percent(50);
+	}: _<T::Origin>(origin, GameKind::Ranked, penalty)
+	verify {
+		assert_eq!(AbandonPenalty::<T>::get(GameKind::Ranked), penalty);
+	}
+
+	settle_predictions {
+		let p in 2 .. T::MaxPredictions::get();
+		// Half of the predictions are right, so both stakes are slashed and payouts are made.
+		let red: T::AccountId = account("red", 0, SEED);
+		let blue: T::AccountId = account("blue", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			red.clone(),
+			blue.clone(),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		PredictionFee::<T>::put(Perbill::from_percent(5));
+		let stake = T::Currency::minimum_balance();
+		for i in 0 .. p {
+			let predictor: T::AccountId = account("predictor", i, SEED);
+			let winner = if i % 2 == 0 { red.clone() } else { blue.clone() };
+			T::Currency::make_free_balance_be(&predictor, stake + stake);
+			ConnectFour::<T>::predict(
+				RawOrigin::Signed(predictor).into(),
+				board_id,
+				Some(winner),
+				stake,
+			)?;
+		}
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+	}: {
+		ConnectFour::<T>::settle_predictions(&board, Some(&red), FinishReason::Connected);
+	}
+	verify {
+		assert!(!Predictions::<T>::contains_key(board_id));
+	}
+
+	sponsor_game {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		let board_id = ConnectFour::<T>::create_game(
+			account("red", 0, SEED),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+		let amount = T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(&caller, amount + amount);
+	}: _(RawOrigin::Signed(caller), SponsoredGame::Board(board_id), amount)
+	verify {
+		assert_eq!(BoardSponsors::<T>::get(board_id).len(), 1);
+	}
+
+	settle_sponsorships {
+		let s in 1 .. T::MaxSponsors::get();
+		// Every sponsorship goes to the winner.
+		let red: T::AccountId = account("red", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			red.clone(),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			BoardVariant::default(),
+			None,
+		)?;
+		let amount = T::Currency::minimum_balance();
+		for i in 0 .. s {
+			let sponsor: T::AccountId = account("sponsor", i, SEED);
+			T::Currency::make_free_balance_be(&sponsor, amount + amount);
+			let game = SponsoredGame::Board(board_id);
+			ConnectFour::<T>::sponsor_game(RawOrigin::Signed(sponsor).into(), game, amount)?;
+		}
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+	}: {
+		ConnectFour::<T>::settle_sponsorships(&board, Some(&red));
+	}
+	verify {
+		assert!(!BoardSponsors::<T>::contains_key(board_id));
+	}
+
+	annotate_game {
+		let caller: T::AccountId = whitelisted_caller();
+		let board_id = T::Hashing::hash_of(&0u32);
+		ArchivedGames::<T>::insert(board_id, ArchivedGameOf::<T>::default());
+		let balance = T::AnnotationDeposit::get() + T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(&caller, balance);
+		let annotation = vec![b'a'; T::MaxAnnotationLength::get() as usize];
+	}: _(RawOrigin::Signed(caller), board_id, annotation)
+	verify {
+		assert_eq!(GameAnnotations::<T>::get(board_id).len(), 1);
+	}
+
+	release_annotations {
+		let a in 1 .. T::MaxAnnotations::get();
+		// A single pruned game, its annotations make up the difference to `prune_results`.
+		let finished = T::BlockNumber::from(1u32);
+		let board_id = T::Hashing::hash_of(&0u32);
+		let result = GameResult {
+			red: account("red", 0, SEED),
+			blue: account("blue", 0, SEED),
+			winner: None,
+			reason: FinishReason::BoardFull,
+			moves: 42,
+		};
+		ResultsByBlock::<T>::insert(finished, board_id, result);
+		ArchivedGames::<T>::insert(board_id, ArchivedGameOf::<T>::default());
+		let balance = T::AnnotationDeposit::get() + T::Currency::minimum_balance();
+		for i in 0 .. a {
+			let annotator: T::AccountId = account("annotator", i, SEED);
+			T::Currency::make_free_balance_be(&annotator, balance);
+			let origin = RawOrigin::Signed(annotator).into();
+			ConnectFour::<T>::annotate_game(origin, board_id, vec![b'a'])?;
+		}
+		let now = finished + T::ResultRetentionBlocks::get();
+	}: {
+		ConnectFour::<T>::prune_results(now);
+	}
+	verify {
+		assert!(!GameAnnotations::<T>::contains_key(board_id));
+	}
+
+	check_move {
+		let board_id = ConnectFour::<T>::create_game(
+			account("red", 0, SEED),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+		let board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		let caller = board.account_at_turn(board.next_player).clone();
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(Boards::<T>::get(board_id).map_or(false, |board| board.last_move.is_none()));
+	}
+
+	set_play_proxy {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), delegate.clone())
+	verify {
+		assert_eq!(PlayProxies::<T>::get(&delegate), Some(caller));
+	}
+
+	remove_play_proxy {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		PlayProxies::<T>::insert(&delegate, &caller);
+	}: _(RawOrigin::Signed(caller), delegate.clone())
+	verify {
+		assert!(!PlayProxies::<T>::contains_key(&delegate));
+	}
+
+	cleanup {
+		let n in 1 .. T::MaxCleanupAccounts::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let mut accounts = Vec::new();
+		for i in 0 .. n {
+			let orphan: T::AccountId = account("orphan", i, SEED);
+			PlayerBoard::<T>::insert(&orphan, T::Hash::default());
+			accounts.push(orphan);
+		}
+		let accounts = BoundedVec::try_from(accounts).map_err(|_| "too many accounts")?;
+	}: _(RawOrigin::Signed(caller), accounts.clone())
+	verify {
+		assert!(accounts.iter().all(|orphan| !PlayerBoard::<T>::contains_key(orphan)));
+	}
+
+	create_pending_boards {
+		// A round of `c` games waits for its boards.
+		let c in 1 .. T::MaxBoardCreationsPerBlock::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let id = open_tournament::<T>(&caller, 1, 2 * c)?;
+		check_in_players::<T>(id, 0)?;
+		ConnectFour::<T>::start_tournament(RawOrigin::Signed(caller).into(), id)?;
+	}: {
+		ConnectFour::<T>::create_pending_boards();
+	}
+	verify {
+		assert!(!PendingBoardCreations::<T>::contains_key(id));
+		assert_eq!(TournamentBoards::<T>::iter().count() as u32, c);
+	}
+
+	verify_relayed_move {
+		// The signer plays red in a casual game on the largest board.
+		let (signer, player) = T::BenchmarkHelper::signer();
+		let opponent: T::AccountId = account("opponent", 0, SEED);
+		let board_id = ConnectFour::<T>::create_game(
+			player.clone(),
+			opponent,
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+		Boards::<T>::mutate(board_id, |board| {
+			if let Some(board) = board {
+				board.next_player = PLAYER_1;
+				board.kind = GameKind::Casual;
+			}
+		});
+		let payload = RelayedMove { board_id, column: 0, move_index: 0 };
+		let signature = T::BenchmarkHelper::sign(&signer, &payload.encode());
+		let mut relayed = None;
+	}: {
+		relayed = Some(ConnectFour::<T>::relayed_player(&payload, &signature)?);
+	}
+	verify {
+		assert_eq!(relayed, Some(player));
+	}
+
+	board_snapshot {
+		// Events are only kept from the first block on.
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		let board_id = ConnectFour::<T>::create_game(
+			account("red", 0, SEED),
+			account("blue", 0, SEED),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			max_variant::<T>(),
+			None,
+		)?;
+		let mut board = Boards::<T>::get(board_id).ok_or("board not created")?;
+		board.verbosity = EventVerbosity::FullBoard;
+	}: {
+		ConnectFour::<T>::snapshot_board(&board);
+	}
+	verify {
+		let event: <T as Config>::Event = Event::BoardSnapshot(board_id, board).into();
+		frame_system::Pallet::<T>::assert_last_event(event.into());
+	}
+
+	claim_rewards {
+		let caller: T::AccountId = whitelisted_caller();
+		let rewards = RewardBalance { points: 100, ..Default::default() };
+		RewardPoints::<T>::insert(&caller, rewards);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!RewardPoints::<T>::contains_key(&caller));
+	}
+
+	clear_game_trie {
+		let k in 1 .. T::MaxMovesPerGame::get();
+		let board_id = T::Hashing::hash_of(&k);
+		for move_number in 1 ..= k as u16 {
+			child::put(&ConnectFour::<T>::game_trie(board_id), &move_number.encode(), &move_number);
+		}
+		GameTrieCleanups::<T>::insert(board_id, ());
+	}: {
+		ConnectFour::<T>::clear_game_tries(Weight::MAX);
+	}
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(!GameTrieCleanups::<T>::contains_key(board_id));
+		assert!(ConnectFour::<T>::streamed_move(board_id, 1).is_none());
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(ConnectFour, crate::mock::new_test_ext(), crate::mock::Test);
 }
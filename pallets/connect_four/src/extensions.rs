@@ -0,0 +1,97 @@
+//! Transaction extensions checking moves before they reach a block.
+
+use codec::{Decode, Encode};
+use frame_support::traits::IsSubType;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{fmt, marker::PhantomData};
+
+use crate::{Boards, Call, Config, MoveError, Pallet};
+
+/// Rejects `play_turn` transactions that can't be played at all: the sender has no running
+/// board, it isn't their turn, the column is full or outside the board, or the move is stale
+/// by its `expected_move_index`. These are dropped from the pool and never take block space
+/// or fees. `InvalidTransaction::Custom` carries the index of the `MoveError` then, a stale
+/// move is `InvalidTransaction::Stale`.
+///
+/// A move of a player out of time is let through, playing it ends the game on time.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckMove<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckMove<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckMove<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for CheckMove<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckMove")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckMove<T>
+where
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckMove";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_: &DispatchInfoOf<Self::Call>,
+		_: usize,
+	) -> TransactionValidity {
+		if let Some(Call::play_turn { column, expected_move_index }) = call.is_sub_type() {
+			let mover = Pallet::<T>::mover(who.clone());
+			match Pallet::<T>::dry_run_move(&mover, *column) {
+				Ok(_) | Err(MoveError::OutOfTime) => (),
+				Err(error) => return Err(InvalidTransaction::Custom(error as u8).into()),
+			}
+			if let Some(index) = expected_move_index {
+				let board = <Boards<T>>::get(Pallet::<T>::player_board(&mover));
+				if board.map_or(true, |board| board.moves_played != *index) {
+					return Err(InvalidTransaction::Stale.into())
+				}
+			}
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
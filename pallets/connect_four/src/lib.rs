@@ -4,13 +4,35 @@
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// <https://substrate.dev/docs/en/knowledgebase/runtime/frame>
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::traits::Randomness;
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	ensure,
+	storage::{
+		bounded_vec::BoundedVec,
+		child::{self, ChildInfo},
+		with_transaction, TransactionOutcome,
+	},
+	traits::{
+		tokens::fungibles::{self, MutateHold},
+		BalanceStatus, ConstU32, Currency, Get, Imbalance, OnKilledAccount, OnNewAccount,
+		OnUnbalanced, Randomness, ReservableCurrency, StorageVersion, Time,
+	},
+	PalletId,
+};
+use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
 
-use sp_runtime::traits::{Dispatchable, Hash, TrailingZeroInput};
+use sp_runtime::{
+	traits::{
+		AccountIdConversion, CheckedDiv, Dispatchable, Hash, IdentifyAccount, SaturatedConversion,
+		Saturating, TrailingZeroInput, Verify, Zero,
+	},
+	transaction_validity::TransactionPriority,
+	DispatchError, DispatchResult, Perbill,
+};
 
 use scale_info::TypeInfo;
 
-use sp_std::{prelude::*, vec::Vec};
+use sp_std::{cmp::Ordering, marker::PhantomData, prelude::*, vec, vec::Vec};
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
@@ -24,52 +46,1006 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-/// Implementations of some helper traits passed into runtime modules as associated types.
-pub mod gameplay;
-use gameplay::Logic;
+pub mod extensions;
+pub use extensions::CheckMove;
+
+pub mod traits;
+#[cfg(feature = "runtime-benchmarks")]
+pub use traits::BenchmarkHelper;
+pub use traits::{
+	AwardCalculator, BlockClock, IdentityProvider, NoAssets, NotifyRemotePlayer, OnGameFinished,
+	OnGameReported, ScoreWeightedAward, TrophyMinter,
+};
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub mod migrations;
+
+/// Rules of the game, shared with clients through the `connectfour-logic` crate.
+pub use connectfour_logic as gameplay;
+use gameplay::{
+	notation, Board, ColumnScore, Logic, Move, Outcome, ThreatCounts, PLAYER_1, PLAYER_2,
+};
+
+pub mod swiss;
+use swiss::Swiss;
+
+pub mod achievements;
+use achievements::{Achievement, AchievementSet};
+
+/// Balance of the currency used for the ranked queue deposit.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Point in time on the game clock, see `Config::GameClock`.
+pub type MomentOf<T> = <<T as Config>::GameClock as Time>::Moment;
+
+/// Imbalance of the currency, used to pay out prediction pools.
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Id of an asset public tables can be played for besides the native token.
+pub type AssetIdOf<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
 
 /// Game challenge
-#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
 pub struct AwardState {
-	win: u32,
-	lose: u32,
+	pub win: u32,
+	pub lose: u32,
+	/// Added to every player's score when a ranked game ends on a full board.
+	pub draw: u32,
+}
+
+/// Board dimensions and the number of stones in a row needed to win.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct BoardVariant {
+	pub width: u8,
+	pub height: u8,
+	pub connect: u8,
+	/// Pop Out: instead of dropping a stone, players may take one of their own out of the
+	/// bottom row. Only in games of two players moving in turns.
+	pub pop_out: bool,
+}
+
+impl Default for BoardVariant {
+	/// Classic connect four, 7 columns by 6 rows.
+	fn default() -> Self {
+		Self { width: 7, height: 6, connect: 4, pop_out: false }
+	}
+}
+
+impl BoardVariant {
+	/// Board fits in the configured limits and the connect length fits on the board.
+	fn is_valid(&self, max_width: u8, max_height: u8) -> bool {
+		(MIN_BOARD_SIZE..=max_width).contains(&self.width) &&
+			(MIN_BOARD_SIZE..=max_height).contains(&self.height) &&
+			(MIN_CONNECT..=self.width.min(self.height)).contains(&self.connect)
+	}
 }
 
+/// Pending challenge, agreed on by both players when the game gets created. It's emitted
+/// whole in `ChallengeCreated`, so the responder sees what they would accept.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct ChallengeStruct<AccountId, Balance, BlockNumber, Moment> {
+	/// Stable id of the challenge, see `NextChallengeId`.
+	pub id: u32,
+	/// Only account allowed to respond, `None` for an open challenge anyone may accept.
+	pub opponent: Option<AccountId>,
+	pub award: AwardState,
+	pub variant: BoardVariant,
+	/// Time each player may spend on their moves on the `GameClock`, `None` for no clock.
+	pub time_bank: Option<Moment>,
+	/// Play in blitz mode, see `BlitzRound`.
+	pub blitz: bool,
+	/// Head start granted to the player accepting the challenge.
+	pub handicap: Option<Handicap>,
+	pub first_move: FirstMovePolicy,
+	pub kind: GameKind,
+	/// Reserved from both players, the winner takes the loser's stake.
+	pub stake: Option<Balance>,
+	/// Last block the challenge can be accepted in, see `ChallengeLifetime`.
+	pub expires: BlockNumber,
+}
+
+pub type ChallengeOf<T> = ChallengeStruct<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	MomentOf<T>,
+>;
+
+/// Pending doubles challenge, the game starts once all three invited players accepted.
 #[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct TeamChallengeStruct<AccountId, Moment> {
+	/// Stable id of the challenge, see `NextChallengeId`.
+	id: u32,
+	teammate: AccountId,
+	opponent: AccountId,
+	opponent_mate: AccountId,
+	award: AwardState,
+	variant: BoardVariant,
+	/// Time each team may spend on its moves on the `GameClock`, `None` for no clock.
+	time_bank: Option<Moment>,
+	/// Invited players that accepted so far.
+	accepted: BoundedVec<AccountId, ConstU32<3>>,
+}
+
+pub type TeamChallengeOf<T> =
+	TeamChallengeStruct<<T as frame_system::Config>::AccountId, MomentOf<T>>;
+
+/// Chess-clock style time banks, on the `GameClock`. Each move is charged to the mover's bank.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct TimeBanks<Moment> {
+	/// Budget both players started with.
+	pub initial: Moment,
+	pub red: Moment,
+	pub blue: Moment,
+}
+
+/// Results of all finished games of a player.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, Debug, TypeInfo)]
+pub struct GameStats {
+	pub played: u32,
+	pub wins: u32,
+	pub losses: u32,
+	pub draws: u32,
+	/// Games won in a row up to the last one.
+	pub current_streak: u32,
+	pub best_streak: u32,
+	/// Ranked games finished, the first `ProvisionalGames` of them are placement games.
+	pub ranked: u32,
+}
+
+/// Change of a player's score, see `ScoreHistory`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct ScoreChange<BlockNumber, Hash> {
+	pub block: BlockNumber,
+	pub delta: i32,
+	/// Game that changed the score, `None` for decays and resets.
+	pub board_id: Option<Hash>,
+}
+
+pub type ScoreChangeOf<T> =
+	ScoreChange<<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::Hash>;
+
+/// Reward points of a player, apart from the score that only counts as reputation.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, Debug, TypeInfo)]
+pub struct RewardBalance<BlockNumber> {
+	/// Points that can be claimed.
+	pub points: u32,
+	/// Points that can be claimed from `vests_at` on. Points earned in the meantime join them.
+	pub pending: u32,
+	pub vests_at: BlockNumber,
+}
+
+pub type RewardBalanceOf<T> = RewardBalance<<T as frame_system::Config>::BlockNumber>;
+
+/// Match queue entry of a waiting player.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct QueueStatus {
+	/// Score the player got queued with.
+	pub score: i32,
+	/// Number of players waiting in the same score bucket, or in the casual queue, including
+	/// this player.
+	pub waiting: u32,
+}
+
+/// Overview of a running board for indexers, see `Pallet::boards_of`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct BoardSummary<Hash, AccountId, Moment> {
+	pub id: Hash,
+	pub red: AccountId,
+	pub blue: AccountId,
+	/// Account that has to play the next stone.
+	pub next_player: AccountId,
+	pub moves_played: u16,
+	/// Last moment the next stone may be played at, `None` without time banks.
+	pub deadline: Option<Moment>,
+	pub kind: GameKind,
+}
+
+pub type BoardSummaryOf<T> = BoardSummary<
+	<T as frame_system::Config>::Hash,
+	<T as frame_system::Config>::AccountId,
+	MomentOf<T>,
+>;
+
+/// Why a stone can't be dropped into a column right now, see `Pallet::dry_run_move`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub enum MoveError {
+	/// The account isn't playing on a board.
+	NoPlayerBoard,
+	/// The game is already over.
+	GameNotRunning,
+	/// Blitz moves go through `commit_move` and `reveal_move`.
+	BlitzGame,
+	/// Another account has to play the next stone.
+	NotPlayerTurn,
+	/// The column is outside the board.
+	ColumnOutOfRange,
+	/// The column has no room left.
+	ColumnFull,
+	/// The player ran out of time or stalled too many turns, any move loses the game.
+	OutOfTime,
+}
+
+impl<T: Config> From<MoveError> for Error<T> {
+	fn from(error: MoveError) -> Self {
+		match error {
+			MoveError::NoPlayerBoard => Error::<T>::NoPlayerBoard,
+			MoveError::GameNotRunning => Error::<T>::GameNotRunning,
+			MoveError::BlitzGame => Error::<T>::BlitzGame,
+			MoveError::NotPlayerTurn => Error::<T>::NotPlayerTurn,
+			MoveError::ColumnOutOfRange => Error::<T>::ColumnOutOfRange,
+			MoveError::ColumnFull => Error::<T>::ColumnFull,
+			MoveError::OutOfTime => Error::<T>::OutOfTime,
+		}
+	}
+}
+
+/// Score range `[bucket * QUEUE_BUCKET_SIZE, (bucket + 1) * QUEUE_BUCKET_SIZE)` of the match queue.
+pub type ScoreBucket = i32;
+
+/// Rating pool of a score, `0` for the lowest one, e.g. bronze, silver and gold for two
+/// `TierBoundaries`.
+pub type Tier = u8;
+
+/// Lowest scores of the tiers above the lowest one, ascending.
+pub type TierBoundariesOf = BoundedVec<i32, ConstU32<MAX_TIER_BOUNDARIES>>;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
 pub enum BoardState<AccountId> {
 	None,
 	Running,
 	Finished(Option<AccountId>),
 }
 
+/// Why a game came to an end.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum FinishReason {
+	/// The winner connected enough stones in a row.
+	Connected,
+	/// The board filled up without a winner.
+	BoardFull,
+	/// The player to move ran out of time.
+	Timeout,
+	/// Ended by the admin origin.
+	Forced,
+	/// The loser took too long for `MaxStalledTurns` turns.
+	Stalled,
+	/// Nobody won within `MaxMovesPerGame` moves.
+	MoveLimit,
+	/// A player called the game off before the second move, see `abort_game`.
+	Aborted,
+	/// The loser declined a double of the stakes, see `offer_double`.
+	DoubleDeclined,
+}
+
+impl FinishReason {
+	/// Games ended this way are a draw, games ended without a winner otherwise are void.
+	pub fn is_draw(self) -> bool {
+		matches!(self, FinishReason::BoardFull | FinishReason::MoveLimit)
+	}
+}
+
+/// Why a player called off a game, see `abort_game`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum AbortReason {
+	/// Matchmaking or a table paired the player with someone they didn't mean to play.
+	WrongOpponent,
+	/// The challenge was accepted by mistake.
+	MistakenChallenge,
+	/// The board variant, award or clock isn't what the player expected.
+	WrongSettings,
+	Other,
+}
+
+/// Why a player reported their opponent, see `report_opponent`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum ReportReason {
+	/// The moves looked like they came from an engine.
+	EngineAssistance,
+	/// The opponent plays together with someone else, e.g. to lose on purpose.
+	Collusion,
+	/// Abusive emotes or deliberate stalling.
+	Abuse,
+	Other,
+}
+
+/// Ruling on a reported game, see `resolve_report`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum Verdict {
+	/// The game stands as played.
+	Dismissed,
+	/// The reported player forfeits, the reporter wins the game.
+	Upheld,
+}
+
+/// Fair-play report against the opponent on a running board.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct Report<AccountId> {
+	pub reporter: AccountId,
+	pub reason: ReportReason,
+}
+
+pub type ReportOf<T> = Report<<T as frame_system::Config>::AccountId>;
+
+/// A reported game that finished, its scores and stakes are settled by the verdict.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct Dispute<Board, AccountId, Balance, AssetId> {
+	/// The board as it finished.
+	pub board: Board,
+	pub report: Report<AccountId>,
+	/// How the game ended as played.
+	pub finish: FinishReason,
+	/// Stake each player still has reserved.
+	pub stake: Option<Balance>,
+	/// Asset of the stakes, `None` for the native token.
+	pub asset: Option<AssetId>,
+}
+
+pub type DisputeOf<T> =
+	Dispute<BoardOf<T>, <T as frame_system::Config>::AccountId, BalanceOf<T>, AssetIdOf<T>>;
+
+/// Summary of a finished game, kept in `ResultsByBlock` for `ResultRetentionBlocks` blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct GameResult<AccountId> {
+	pub red: AccountId,
+	pub blue: AccountId,
+	/// `None` for a draw.
+	pub winner: Option<AccountId>,
+	pub reason: FinishReason,
+	/// Stones on the board when the game ended.
+	pub moves: u32,
+}
+
+pub type GameResultOf<T> = GameResult<<T as frame_system::Config>::AccountId>;
+
+/// Position after a move of a streamed game, kept in the game's child trie under the encoded
+/// move number, see `Pallet::game_trie`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct StreamedMove<Moment> {
+	/// The move in `notation`, along with the color that played it.
+	pub notation: u8,
+	/// Time banks of red and blue after the move, `None` without a clock.
+	pub clocks: Option<(Moment, Moment)>,
+	/// Threats of both sides after the move, see `Logic::threat_counts`.
+	pub evaluation: ThreatCounts,
+}
+
+pub type StreamedMoveOf<T> = StreamedMove<MomentOf<T>>;
+
+/// Length of a game in `notation`, the header and up to `MaxArchivedMoves` moves.
+pub struct ArchivedGameLen<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for ArchivedGameLen<T> {
+	fn get() -> u32 {
+		T::MaxArchivedMoves::get().saturating_add(notation::HEADER_LEN)
+	}
+}
+
+pub type ArchivedGameOf<T> = BoundedVec<u8, ArchivedGameLen<T>>;
+
+/// Reference to an analysis of an archived game, such as the CID of a file on IPFS.
+pub type AnnotationOf<T> = BoundedVec<u8, <T as Config>::MaxAnnotationLength>;
+
+/// Snapshots of an archived game, one every `SnapshotInterval` of its `MaxArchivedMoves`.
+pub struct ArchivedSnapshotsLen<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for ArchivedSnapshotsLen<T> {
+	fn get() -> u32 {
+		T::MaxArchivedMoves::get().checked_div(T::SnapshotInterval::get()).unwrap_or(0)
+	}
+}
+
+pub type ArchivedSnapshotsOf<T> = BoundedVec<Board, ArchivedSnapshotsLen<T>>;
+
+/// Strength of the pallet's own player.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum BotDifficulty {
+	/// Plays a random open column.
+	Easy,
+	/// Takes a winning move, else blocks the opponent's winning move, else plays randomly.
+	Hard,
+}
+
+/// Predefined message players and spectators can send to a board, see `send_emote`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum Emote {
+	GoodLuck,
+	WellPlayed,
+	Thinking,
+	Oops,
+	Wow,
+	Thanks,
+	GoodGame,
+}
+
+/// How the player making the first move of a challenged game is picked.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum FirstMovePolicy {
+	/// Decided by the random board id.
+	Random,
+	/// The player who sent the challenge starts.
+	ChallengerFirst,
+	/// The player who accepted the challenge starts.
+	ResponderFirst,
+	/// Whoever moved second in the last game between both players starts, random if they
+	/// haven't played each other within the rematch period.
+	AlternateFromLastGame,
+	/// Drawn from salts both players committed to before the game, see `FirstMoveDraw`.
+	CommitReveal,
+	/// Random, after the first move the other player may take over the first mover's side
+	/// instead of replying, see `swap_sides`.
+	PieRule,
+}
+
+impl Default for FirstMovePolicy {
+	fn default() -> Self {
+		FirstMovePolicy::Random
+	}
+}
+
+/// What the events of a board tell about a move.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum EventVerbosity {
+	/// Only `TurnPlayed` and `StonePopped`, the move itself.
+	Delta,
+	/// Every move is followed by a `BoardSnapshot` of the whole board. The movers pay the
+	/// extra weight, see `set_event_verbosity`.
+	FullBoard,
+}
+
+impl Default for EventVerbosity {
+	fn default() -> Self {
+		EventVerbosity::Delta
+	}
+}
+
+/// Whether a game counts towards the scores.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum GameKind {
+	/// Settled on `ScoringBoard` and the leaderboard.
+	Ranked,
+	/// Played for fun, scores are left alone.
+	Casual,
+}
+
+impl Default for GameKind {
+	fn default() -> Self {
+		GameKind::Ranked
+	}
+}
+
+/// Head start of the weaker player of a game.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum Handicap {
+	/// The weaker player moves first.
+	FirstMove,
+	/// The weaker player starts with this many stones around the center column, and the
+	/// stronger player moves first.
+	Stones(u8),
+}
+
+impl Handicap {
+	/// Whether the handicap can be given on a board of the variant.
+	pub fn is_valid(&self, variant: &BoardVariant) -> bool {
+		match self {
+			Handicap::FirstMove => true,
+			Handicap::Stones(stones) =>
+				Logic::valid_handicap(variant.width, variant.connect, *stones),
+		}
+	}
+}
+
+/// Doubling cube of a staked game, see `offer_double`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum DoublingCube {
+	/// Either player may double on their turn.
+	Centered,
+	/// Only this color may double, it accepted the last double.
+	Owned(u8),
+	/// This color offered a double and holds the doubled stake, the opponent has to answer
+	/// before the game goes on.
+	Offered(u8),
+}
+
+impl Default for DoublingCube {
+	fn default() -> Self {
+		DoublingCube::Centered
+	}
+}
+
+/// Phase of a blitz round.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum BlitzPhase {
+	/// Both players commit to a hidden column.
+	Commit,
+	/// Both players reveal the column they committed to.
+	Reveal,
+}
+
+/// Round of a blitz game, where both players pick their column at the same time.
+///
+/// Players first commit to `hash((column, salt))` and reveal once both commitments are in.
+/// The stones are dropped once both columns are revealed, the player at turn first, so they win
+/// a column both picked. The turn passes on every round.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct BlitzRound<Hash> {
+	pub phase: BlitzPhase,
+	pub red_commit: Option<Hash>,
+	pub blue_commit: Option<Hash>,
+	pub red_column: Option<u8>,
+	pub blue_column: Option<u8>,
+}
+
+impl<Hash> Default for BlitzRound<Hash> {
+	fn default() -> Self {
+		BlitzRound {
+			phase: BlitzPhase::Commit,
+			red_commit: None,
+			blue_commit: None,
+			red_column: None,
+			blue_column: None,
+		}
+	}
+}
+
+impl<Hash> BlitzRound<Hash> {
+	/// `player` already committed or revealed in the current phase.
+	pub fn has_moved(&self, player: u8) -> bool {
+		match (self.phase, player) {
+			(BlitzPhase::Commit, PLAYER_1) => self.red_commit.is_some(),
+			(BlitzPhase::Commit, _) => self.blue_commit.is_some(),
+			(BlitzPhase::Reveal, PLAYER_1) => self.red_column.is_some(),
+			(BlitzPhase::Reveal, _) => self.blue_column.is_some(),
+		}
+	}
+}
+
+/// Game a third account adds tokens to the winner's payout of, see `sponsor_game`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum SponsoredGame<Hash> {
+	/// Running board, e.g. of an accepted challenge.
+	Board(Hash),
+	/// Tournament, the sponsorship goes to its prize pool.
+	Tournament(u32),
+}
+
+/// Challenges an account created lately, see `Config::ChallengeCooldown`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct ChallengeRate<BlockNumber> {
+	/// Block of the last challenge.
+	pub last: BlockNumber,
+	/// Era of the last challenge, the block number divided by `ChallengeEra`.
+	pub era: BlockNumber,
+	/// Challenges created in `era`.
+	pub count: u32,
+}
+
+/// Draw of the first mover of a `FirstMovePolicy::CommitReveal` game.
+///
+/// Both players commit to `hash(salt)` with `commit_first_move` before the game starts and
+/// reveal their salt once it started, see `reveal_first_move`. The first mover follows from
+/// `hash((red_salt, blue_salt))`, which neither player nor the block author can bias alone.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct FirstMoveDraw<Hash> {
+	pub red_commit: Hash,
+	pub blue_commit: Hash,
+	pub red_salt: Option<[u8; 32]>,
+	pub blue_salt: Option<[u8; 32]>,
+}
+
+impl<Hash> FirstMoveDraw<Hash> {
+	/// `player` revealed their salt.
+	pub fn has_revealed(&self, player: u8) -> bool {
+		if player == PLAYER_1 {
+			self.red_salt.is_some()
+		} else {
+			self.blue_salt.is_some()
+		}
+	}
+}
+
+/// Move signed off-chain by the player at turn and submitted by a relayer, see
+/// `play_turn_signed`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RelayedMove<Hash> {
+	pub board_id: Hash,
+	pub column: u8,
+	/// `BoardStruct::turns` before the move, a signed move can only be played once.
+	pub move_index: u32,
+}
+
+pub type RelayedMoveOf<T> = RelayedMove<<T as frame_system::Config>::Hash>;
+
+/// Kind of game `new_game` sets up.
+enum GameMode<AccountId> {
+	/// One player on each side, taking turns.
+	Standard,
+	/// Blue is played by the pallet.
+	Bot(BotDifficulty),
+	/// Second red and blue player of a doubles game.
+	Doubles(AccountId, AccountId),
+	/// Both players move at once, see `BlitzRound`.
+	Blitz,
+	/// One player on each side, the color gets a head start.
+	Handicapped(u8, Handicap),
+}
+
 /// Connect four board structure containing two players and the board
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct BoardStruct<Hash, AccountId, Moment, BoardState, Cells> {
+	pub id: Hash,
+	pub red: AccountId,
+	pub blue: AccountId,
+	pub board: Cells,
+	pub variant: BoardVariant,
+	pub last_turn: Moment,
+	pub time_banks: Option<TimeBanks<Moment>>,
+	/// Last moment on the `GameClock` `next_player` may move at before running out of time,
+	/// `None` without time banks.
+	pub deadline: Option<Moment>,
+	pub next_player: u8,
+	pub board_state: BoardState,
+	pub award: AwardState,
+	/// Set when blue is played by the pallet itself.
+	pub bot: Option<BotDifficulty>,
+	/// Second red player in doubles, teammates take turns placing the red stones.
+	pub red_mate: Option<AccountId>,
+	/// Second blue player in doubles.
+	pub blue_mate: Option<AccountId>,
+	/// Current round of a blitz game, `None` for games played in turns.
+	pub blitz: Option<BlitzRound<Hash>>,
+	/// Column of the last stone, `None` before the first move and after an undo.
+	pub last_move: Option<u8>,
+	/// Color that got a head start and how, `None` for an even game.
+	pub handicap: Option<(u8, Handicap)>,
+	/// Policy that picked `first_player`, a handicap takes precedence over it.
+	pub first_move: FirstMovePolicy,
+	/// Color that made the first move.
+	pub first_player: u8,
+	/// Matchmade game, `award` is weighed by `Config::AwardCalculator` when settled.
+	pub weighted_award: bool,
+	/// Turns red and blue took at least `StallTurnTime` for.
+	pub stalled_turns: (u8, u8),
+	/// Hash of `board` and `next_player`, see `Pallet::position_hash`.
+	pub position: Hash,
+	/// Casual games leave the scores alone, bot games are always casual.
+	pub kind: GameKind,
+	/// Stones dropped or popped so far, undone moves included, see `RelayedMove`.
+	pub turns: u32,
+	/// Who may double the stakes, only used by staked games.
+	pub cube: DoublingCube,
+	/// Moves on the board, unlike `turns` without the undone ones. Handicap stones don't
+	/// count, so it has the parity of the other stones on the board.
+	pub moves_played: u16,
+	/// Pie rule: the second player took over the side of the first mover, see `swap_sides`.
+	pub sides_swapped: bool,
+	/// Whether moves are followed by a `BoardSnapshot` event.
+	pub verbosity: EventVerbosity,
+}
+
+impl<Hash, AccountId: PartialEq, Moment, BoardState, Cells>
+	BoardStruct<Hash, AccountId, Moment, BoardState, Cells>
+{
+	/// Color of the team `who` plays on, `None` if they don't play on this board.
+	pub fn team_of(&self, who: &AccountId) -> Option<u8> {
+		if *who == self.red || self.red_mate.as_ref() == Some(who) {
+			Some(PLAYER_1)
+		} else if *who == self.blue || self.blue_mate.as_ref() == Some(who) {
+			Some(PLAYER_2)
+		} else {
+			None
+		}
+	}
+}
+
+impl<Hash, AccountId, Moment, BoardState> BoardStruct<Hash, AccountId, Moment, BoardState, Board> {
+	/// Account placing the next stone of `player`'s color. In doubles the team lead plays
+	/// the team's first stone and the teammates alternate from there.
+	pub fn account_at_turn(&self, player: u8) -> &AccountId {
+		let (lead, mate) = if player == PLAYER_1 {
+			(&self.red, &self.red_mate)
+		} else {
+			(&self.blue, &self.blue_mate)
+		};
+		match mate {
+			Some(mate) if Logic::player_stones(&self.board, player) % 2 == 1 => mate,
+			_ => lead,
+		}
+	}
+}
+
+/// Board as stored by the pallet for a given runtime.
+pub type BoardOf<T> = BoardStruct<
+	<T as frame_system::Config>::Hash,
+	<T as frame_system::Config>::AccountId,
+	MomentOf<T>,
+	BoardState<<T as frame_system::Config>::AccountId>,
+	Board,
+>;
+
+/// Rematch entry kept for each player of a finished game until it expires.
 #[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
-pub struct BoardStruct<Hash, AccountId, BlockNumber, BoardState> {
-	id: Hash,
-	red: AccountId,
-	blue: AccountId,
-	board: [[u8; 6]; 7],
-	last_turn: BlockNumber,
-	next_player: u8,
-	board_state: BoardState,
+pub struct RematchStruct<AccountId, BlockNumber, Moment> {
+	opponent: AccountId,
 	award: AwardState,
+	variant: BoardVariant,
+	/// Color of this player in the finished game, the rematch swaps colors.
+	was_red: bool,
+	/// This player made the first move of the finished game.
+	moved_first: bool,
+	time_bank: Option<Moment>,
+	kind: GameKind,
+	/// This player offered the rematch to the opponent.
+	requested: bool,
+	expires: BlockNumber,
+}
+
+/// Game offered at a public table, the first account to join plays it.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct LobbyStruct<AccountId, Balance, AssetId> {
+	pub id: u32,
+	pub creator: AccountId,
+	pub award: AwardState,
+	/// Reserved from both players, the winner takes the loser's stake.
+	pub stake: Balance,
+	/// Asset of the stake, `None` for the native token.
+	pub asset: Option<AssetId>,
+}
+
+pub type LobbyOf<T> =
+	LobbyStruct<<T as frame_system::Config>::AccountId, BalanceOf<T>, AssetIdOf<T>>;
+
+/// Game a private game is played with, see `Pallet::create_private_game`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct PrivateGameSettings<Balance, Moment> {
+	pub award: AwardState,
+	pub variant: BoardVariant,
+	/// Time bank of each player, `None` for no clock.
+	pub time_bank: Option<Moment>,
+	pub kind: GameKind,
+	/// Reserved from both players, the winner takes the loser's stake.
+	pub stake: Option<Balance>,
+}
+
+pub type PrivateGameSettingsOf<T> = PrivateGameSettings<BalanceOf<T>, MomentOf<T>>;
+
+/// Game waiting for the holder of its invite code.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct PrivateGameStruct<AccountId, Balance, BlockNumber, Moment> {
+	pub creator: AccountId,
+	pub settings: PrivateGameSettings<Balance, Moment>,
+	/// Block the game gets dropped in if nobody joined it.
+	pub expires: BlockNumber,
+}
+
+pub type PrivateGameOf<T> = PrivateGameStruct<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	MomentOf<T>,
+>;
+
+/// Game settings vetted by governance, see `Pallet::set_preset`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct GamePreset<Balance, Moment> {
+	pub variant: BoardVariant,
+	/// Time bank of each player, `None` for no clock.
+	pub time_bank: Option<Moment>,
+	pub award: AwardState,
+	/// Smallest stake of a challenge, no stake counts as zero.
+	pub min_stake: Balance,
+	/// Largest stake of a challenge, zero for unstaked games only.
+	pub max_stake: Balance,
+}
+
+pub type GamePresetOf<T> = GamePreset<BalanceOf<T>, MomentOf<T>>;
+
+/// Players sharing a rating, see `create_guild`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct GuildStruct<AccountId, Members> {
+	/// Member picking the lineups of guild matches, the oldest member takes over once they
+	/// leave.
+	pub founder: AccountId,
+	/// Members in the order they joined, the founder included.
+	pub members: Members,
+	/// Sum of the score changes of the members' games, plus the awards of guild matches.
+	pub rating: i32,
+}
+
+pub type GuildOf<T> = GuildStruct<
+	<T as frame_system::Config>::AccountId,
+	BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxGuildMembers>,
+>;
+
+/// Pending guild match, see `challenge_guild`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct GuildChallengeStruct<Lineup> {
+	/// Challenged guild.
+	pub opponent: u32,
+	/// Award of the guild winning the majority of the boards.
+	pub award: AwardState,
+	/// Members of the challenging guild, one board each.
+	pub lineup: Lineup,
+}
+
+pub type GuildChallengeOf<T> = GuildChallengeStruct<
+	BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxGuildMatchPlayers>,
+>;
+
+/// Running guild match, won by the guild winning the majority of its boards.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct GuildMatch {
+	pub challenger: u32,
+	pub opponent: u32,
+	pub award: AwardState,
+	/// Boards of the match that haven't finished yet.
+	pub pending: u32,
+	/// Boards won by the members of the challenging guild.
+	pub challenger_wins: u32,
+	/// Boards won by the members of the challenged guild.
+	pub opponent_wins: u32,
+}
+
+/// Game preferences of an account, see `set_profile`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct ProfileStruct<Balance, DisplayName> {
+	/// Board matchmaking pairs the account on, `None` for any.
+	pub preferred_variant: Option<BoardVariant>,
+	/// Highest stake in the native token the account puts up when joining a public table,
+	/// `None` for any.
+	pub max_stake: Option<Balance>,
+	/// Whether the account joins open challenges and public tables.
+	pub allow_open_challenges: bool,
+	/// Challenges with nothing at stake from players whose score is at most this far off
+	/// are accepted right away, `None` to answer every challenge yourself.
+	pub auto_accept_within: Option<u32>,
+	pub display_name: DisplayName,
+}
+
+impl<Balance, DisplayName: Default> Default for ProfileStruct<Balance, DisplayName> {
+	fn default() -> Self {
+		Self {
+			preferred_variant: None,
+			max_stake: None,
+			allow_open_challenges: true,
+			auto_accept_within: None,
+			display_name: DisplayName::default(),
+		}
+	}
+}
+
+pub type ProfileOf<T> =
+	ProfileStruct<BalanceOf<T>, BoundedVec<u8, <T as Config>::MaxDisplayNameLength>>;
+
+/// Stake of a spectator on the outcome of a board, see `predict`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct PredictionStruct<AccountId, Balance> {
+	pub predictor: AccountId,
+	/// Player predicted to win, `None` for a draw. Teammates count as the same prediction.
+	pub winner: Option<AccountId>,
+	/// Reserved until the game ends.
+	pub stake: Balance,
+}
+
+pub type PredictionOf<T> = PredictionStruct<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// Phase of a tournament.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, Debug, TypeInfo)]
+pub enum TournamentState {
+	/// Players can join until `check_in`, then check in until `start`, when the creator can
+	/// start the first round.
+	Registration,
+	/// Rounds are being played.
+	Running,
+	/// All rounds are played, standings hold the final tie-breaks.
+	Finished,
+}
+
+/// Swiss-system tournament, every round pairs players with similar points, see `swiss`.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo)]
+pub struct TournamentStruct<AccountId, Balance, BlockNumber, Moment> {
+	pub creator: AccountId,
+	/// Award of every game of the tournament.
+	pub award: AwardState,
+	pub variant: BoardVariant,
+	/// Time each player may spend on their moves in every game on the `GameClock`, `None`
+	/// for no clock.
+	pub time_bank: Option<Moment>,
+	/// Reserved from every registered player, claimed back once the tournament finished.
+	pub entry_fee: Balance,
+	/// Players that didn't check in get their entry fee back, otherwise it goes to the prize
+	/// pool.
+	pub refund_no_shows: bool,
+	/// Registration closes and check-in opens at this block.
+	pub check_in: BlockNumber,
+	/// Check-in closes at this block, players that didn't check in are dropped at the start.
+	pub start: BlockNumber,
+	/// Number of rounds to play.
+	pub rounds: u8,
+	/// Round being played, `0` before the first round.
+	pub round: u8,
+	pub state: TournamentState,
+	/// Number of registered players.
+	pub players: u32,
+	/// Games of the current round that haven't finished yet.
+	pub pending: u32,
+	/// Entry fees of the no-shows, held by the pallet account until the winner claims them.
+	pub prize_pool: Balance,
+	/// Prizes not claimed before this block can be swept, set once the tournament finished.
+	pub claim_deadline: BlockNumber,
+}
+
+pub type TournamentOf<T> = TournamentStruct<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	MomentOf<T>,
+>;
+
+/// Standing of a player in a Swiss tournament, points are kept in half points.
+#[derive(Encode, Decode, Clone, PartialEq, MaxEncodedLen, Debug, TypeInfo, Default)]
+pub struct SwissStanding<Opponents> {
+	pub points: u32,
+	/// Opponents met so far, rematches are avoided.
+	pub opponents: Opponents,
+	/// Sat out a round, a player only gets one bye.
+	pub had_bye: bool,
+	/// Buchholz score, set once the tournament finished.
+	pub tie_break: u32,
+	/// Checked in before the tournament started, see `check_in`.
+	pub checked_in: bool,
 }
 
-const PLAYER_1: u8 = 1;
-const PLAYER_2: u8 = 2;
-const ACCEPTED_DIFF: u8 = 10;
+pub type SwissStandingOf<T> = SwissStanding<
+	BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxTournamentRounds>,
+>;
+
+pub type RematchOf<T> = RematchStruct<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	MomentOf<T>,
+>;
+
+/// Version of the storage layout, see `migrations`.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+const QUEUE_BUCKET_SIZE: i32 = 10;
+const MIN_BOARD_SIZE: u8 = 4;
+/// Most stale games a single off-chain worker run submits expiries for.
+const MAX_STALE_EXPIRIES: usize = 16;
+/// Blocks an expiry of a stale game stays valid in the transaction pool.
+const STALE_EXPIRY_LONGEVITY: u64 = 5;
+const MIN_CONNECT: u8 = 3;
+/// Most score boundaries between matchmaking tiers.
+pub const MAX_TIER_BOUNDARIES: u32 = 7;
+/// Most board ids a single `Pallet::active_board_ids` call returns.
+pub const MAX_BOARD_IDS_PAGE: u32 = 1_000;
+/// Prefix of the child trie of a streamed game, followed by the board id.
+pub const GAME_TRIE_PREFIX: &[u8] = b"ConnectFourGame";
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
-	use frame_system::pallet_prelude::*;
+	use frame_support::{
+		dispatch::{DispatchErrorWithPostInfo, DispatchResult},
+		pallet_prelude::*,
+		traits::UnfilteredDispatchable,
+		weights::GetDispatchInfo,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_runtime::traits::Convert;
 
 	// important to use outside structs and consts
 	use super::*;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 		type Proposal: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>>;
 
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
@@ -77,78 +1053,1350 @@ pub mod pallet {
 
 		/// The generator used to supply randomness to contracts through `seal_random`.
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-	}
 
-	#[pallet::pallet]
-	#[pallet::without_storage_info]
-	#[pallet::generate_store(pub(super) trait Store)]
-	pub struct Pallet<T>(_);
+		/// Maximum number of columns a board variant may have.
+		#[pallet::constant]
+		type MaxBoardWidth: Get<u8>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn challenges)]
-	/// Store players active board, currently only one board per player allowed.
-	pub type Challenges<T: Config> = StorageMap<_, Identity, T::AccountId, AwardState, OptionQuery>;
+		/// Maximum number of rows a board variant may have.
+		#[pallet::constant]
+		type MaxBoardHeight: Get<u8>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn boards)]
-	/// Store all boards that are currently being played.
-	pub type Boards<T: Config> = StorageMap<
-		_,
-		Identity,
-		T::Hash,
-		BoardStruct<T::Hash, T::AccountId, T::BlockNumber, BoardState<T::AccountId>>,
-		OptionQuery,
-	>;
+		/// Maximum number of players waiting in a single score bucket of the match queue.
+		#[pallet::constant]
+		type MaxQueueLength: Get<u32>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn scoring_board)]
-	/// Store all boards that are currently being played.
-	pub type ScoringBoard<T: Config> = StorageMap<_, Identity, T::AccountId, i32, OptionQuery>;
+		/// Number of blocks after a game ends during which the players can agree on a rematch.
+		#[pallet::constant]
+		type RematchPeriod: Get<Self::BlockNumber>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn match_queue)]
-	/// Store all boards that are currently being played.
-	pub type MatchQueue<T: Config> = StorageMap<_, Identity, T::AccountId, i32, OptionQuery>;
+		/// Number of blocks a challenge can be accepted in, afterwards it can only be cancelled.
+		#[pallet::constant]
+		type ChallengeLifetime: Get<Self::BlockNumber>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn player_board)]
-	/// Store players active board, currently only one board per player allowed.
-	pub type PlayerBoard<T: Config> = StorageMap<_, Identity, T::AccountId, T::Hash, ValueQuery>;
+		/// Blocks an account has to wait between two challenges, `0` for no cooldown.
+		#[pallet::constant]
+		type ChallengeCooldown: Get<Self::BlockNumber>;
 
-	// Default value for Nonce
-	#[pallet::type_value]
-	pub fn NonceDefault<T: Config>() -> u64 {
-		0
-	}
-	// Nonce used for generating a different seed each time.
-	#[pallet::storage]
-	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery, NonceDefault<T>>;
+		/// Length of the eras `MaxChallengesPerEra` counts challenges in.
+		#[pallet::constant]
+		type ChallengeEra: Get<Self::BlockNumber>;
 
-	// Pallets use events to inform users when important changes are made.
-	// https://substrate.dev/docs/en/knowledgebase/runtime/events
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Accept challenge
-		AcceptChallenge(T::AccountId, T::AccountId, AwardState),
-		/// Reject challenge
-		RejectChallenge(T::AccountId, T::AccountId, AwardState),
-		/// Cancel challenge
-		CancelChallenge(T::AccountId),
-		/// Cancel challenge
-		CancelQueue(T::AccountId),
-		/// A new board got created.
-		NewBoard(T::Hash),
-		/// Current state of the game.
-		GameState(BoardStruct<T::Hash, T::AccountId, T::BlockNumber, BoardState<T::AccountId>>),
-	}
+		/// Maximum number of challenges an account creates per `ChallengeEra`, `0` for no
+		/// limit.
+		#[pallet::constant]
+		type MaxChallengesPerEra: Get<u32>;
 
-	// Errors inform users that something went wrong.
-	#[pallet::error]
-	pub enum Error<T> {
-		/// Can't find element to remove
-		NotFound,
-		/// Player already has a board which is being played.
+		/// Number of blocks a private game waits for its invite code, afterwards it's dropped
+		/// and the stake is returned.
+		#[pallet::constant]
+		type PrivateGameLifetime: Get<Self::BlockNumber>;
+
+		/// Maximum number of private games expiring in the same block.
+		#[pallet::constant]
+		type MaxPrivateGamesPerBlock: Get<u32>;
+
+		/// Time bank of each player in games created by matchmaking, `None` for no clock.
+		#[pallet::constant]
+		type MatchmakingTimeBank: Get<Option<MomentOf<Self>>>;
+
+		/// Score gap from which matchmaking lets the weaker player move first, from twice the
+		/// gap they start with two stones instead. `0` disables handicaps in matchmaking.
+		#[pallet::constant]
+		type HandicapScoreGap: Get<u32>;
+
+		/// Lowest score a lost game can leave a player with, so scores can't spiral down and
+		/// score gaps stay meaningful to matchmaking.
+		#[pallet::constant]
+		type ScoreFloor: Get<i32>;
+
+		/// Number of ranked placement games a new player's score is provisional for. Players
+		/// only enter the leaderboard once they completed them.
+		#[pallet::constant]
+		type ProvisionalGames: Get<u32>;
+
+		/// Factor the awards of the first placement game are multiplied by, to place new
+		/// players near their strength quickly. It tapers off with every placement game.
+		#[pallet::constant]
+		type ProvisionalBoost: Get<u8>;
+
+		/// Mints a trophy to the winner of every game, `()` to disable trophies.
+		type Trophies: TrophyMinter<Self::AccountId, Self::Hash>;
+
+		/// Clock turns are timed with: `BlockClock` counts blocks, `pallet_timestamp` times
+		/// games in milliseconds. Time banks, deadlines, `StaleGameAge` and `StallTurnTime`
+		/// are moments of this clock.
+		type GameClock: Time;
+
+		/// Told about the result of every game, e.g. pallets paying rewards, `()` if nobody
+		/// needs to know. Reported games are told once the verdict is in.
+		type GameFinishedHandler: OnGameFinished<Self::AccountId, Self::Hash>;
+
+		/// Where a player on another chain plays from, e.g. an XCM `MultiLocation`.
+		type RemoteLocation: Parameter + MaxEncodedLen;
+
+		/// Origin of the calls players on other chains send, e.g. `pallet_xcm::EnsureXcm`,
+		/// resolving to their location. `EnsureNever` without remote players.
+		type RemoteOrigin: EnsureOrigin<Self::Origin, Success = Self::RemoteLocation>;
+
+		/// Local account a player on another chain plays as, e.g. a hash of their location.
+		type RemoteAccount: Convert<Self::RemoteLocation, Self::AccountId>;
+
+		/// Sends the results of their games to players on other chains, e.g. as XCM messages.
+		type RemoteNotifier: NotifyRemotePlayer<Self::RemoteLocation, Self::Hash>;
+
+		/// Told about every fair-play report, e.g. an oracle that rules on it through
+		/// `Pallet::rule_on_report`. `()` leaves the verdict to the admin origin.
+		type ReportHandler: OnGameReported<Self::AccountId, Self::Hash>;
+
+		/// Weighs the award of matchmade games by the players' scores, `()` for the flat
+		/// `DefaultAward`.
+		type AwardCalculator: AwardCalculator;
+
+		/// Maximum number of games matchmaking creates at the start of a block.
+		#[pallet::constant]
+		type MaxMatchesPerBlock: Get<u32>;
+
+		/// Maximum number of games waiting at public tables.
+		#[pallet::constant]
+		type MaxOpenLobbies: Get<u32>;
+
+		/// Maximum number of accounts watching a single board.
+		#[pallet::constant]
+		type MaxSpectators: Get<u32>;
+
+		/// Blocks an account has to wait between two emotes.
+		#[pallet::constant]
+		type EmoteCooldown: Get<Self::BlockNumber>;
+
+		/// Maximum number of players registering for a tournament.
+		#[pallet::constant]
+		type MaxTournamentPlayers: Get<u32>;
+
+		/// Maximum number of rounds of a tournament.
+		#[pallet::constant]
+		type MaxTournamentRounds: Get<u32>;
+
+		/// Maximum number of tournaments closing registration in the same block.
+		#[pallet::constant]
+		type MaxTournamentsPerBlock: Get<u32>;
+
+		/// Blocks players have to claim their prizes once a tournament finished, see
+		/// `claim_prize`.
+		#[pallet::constant]
+		type PrizeClaimPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of unclaimed prizes `sweep_prizes` takes at once.
+		#[pallet::constant]
+		type MaxPrizeSweeps: Get<u32>;
+
+		/// Maximum number of tournament boards created at the start of a block, the rest of a
+		/// round waits for the next blocks.
+		#[pallet::constant]
+		type MaxBoardCreationsPerBlock: Get<u32>;
+
+		/// Maximum number of accounts on a friend list.
+		#[pallet::constant]
+		type MaxFriends: Get<u32>;
+
+		/// Maximum number of accounts on a block list.
+		#[pallet::constant]
+		type MaxBlocked: Get<u32>;
+
+		/// Maximum length of a display name, in bytes.
+		#[pallet::constant]
+		type MaxDisplayNameLength: Get<u32>;
+
+		/// Maximum number of predictions on a single board, `0` disables predictions.
+		#[pallet::constant]
+		type MaxPredictions: Get<u32>;
+
+		/// Maximum number of sponsors of a single board.
+		#[pallet::constant]
+		type MaxSponsors: Get<u32>;
+
+		/// Deposit reserved for an annotation of an archived game, returned when the archive
+		/// is pruned.
+		#[pallet::constant]
+		type AnnotationDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of annotations of a single archived game.
+		#[pallet::constant]
+		type MaxAnnotations: Get<u32>;
+
+		/// Maximum length of an annotation, in bytes.
+		#[pallet::constant]
+		type MaxAnnotationLength: Get<u32>;
+
+		/// Event verbosity new boards start with, players can change it with
+		/// `set_event_verbosity`.
+		#[pallet::constant]
+		type DefaultEventVerbosity: Get<EventVerbosity>;
+
+		/// Receives the house fee of prediction pools, usually the treasury.
+		type PredictionFeeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Receives the fee taken from the losing stake of public table games, usually the
+		/// treasury.
+		type StakeFeeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Receives the prizes nobody claimed in time, usually the treasury.
+		type UnclaimedPrizeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Assets public tables can be played for besides the native token, `NoAssets` if the
+		/// chain has none.
+		type Assets: fungibles::Inspect<Self::AccountId, Balance = BalanceOf<Self>>
+			+ MutateHold<Self::AccountId>;
+
+		/// Maximum number of accounts `cleanup` looks at in one call.
+		#[pallet::constant]
+		type MaxCleanupAccounts: Get<u32>;
+
+		/// Minted for every orphaned entry `cleanup` removes.
+		#[pallet::constant]
+		type CleanupReward: Get<BalanceOf<Self>>;
+
+		/// Minted for every reward point claimed with `claim_rewards`.
+		#[pallet::constant]
+		type RewardPointValue: Get<BalanceOf<Self>>;
+
+		/// Blocks until reward points earned in a ranked game can be claimed.
+		#[pallet::constant]
+		type RewardVestingBlocks: Get<Self::BlockNumber>;
+
+		/// Identities of players, shown on the leaderboard. `()` if the chain has none.
+		type Identity: IdentityProvider<Self::AccountId>;
+
+		/// Signature players put on moves relayed by `play_turn_signed`.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// Key relayed moves are signed with, identifying the player's account.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Signs relayed moves and winds the game clock in the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<
+			Self::OffchainPublic,
+			Self::AccountId,
+			Self::OffchainSignature,
+			MomentOf<Self>,
+		>;
+
+		/// Ranked matchmaking only takes players with a judged identity, which makes it harder
+		/// to farm scores with throwaway accounts.
+		#[pallet::constant]
+		type RequireJudgedIdentity: Get<bool>;
+
+		/// Currency checked and reserved when joining ranked matchmaking.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Free balance an account needs to join ranked matchmaking.
+		#[pallet::constant]
+		type MinRankedBalance: Get<BalanceOf<Self>>;
+
+		/// Deposit reserved while waiting in the match queue. It's returned on leaving the queue
+		/// or after the first move of the matched game, and slashed if that move times out.
+		#[pallet::constant]
+		type QueueDeposit: Get<BalanceOf<Self>>;
+
+		/// Number of blocks an account has to exist before it may join ranked matchmaking.
+		/// Only accounts created after this pallet was added are tracked, others always pass.
+		#[pallet::constant]
+		type MinAccountAge: Get<Self::BlockNumber>;
+
+		/// Time on the `GameClock` without a move after which the off-chain worker ends a game
+		/// as a timeout of the player at turn. `0` keeps abandoned games around.
+		#[pallet::constant]
+		type StaleGameAge: Get<MomentOf<Self>>;
+
+		/// Priority of the unsigned transactions expiring stale games.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Number of blocks a season lasts, zero to play a single endless season.
+		#[pallet::constant]
+		type SeasonLength: Get<Self::BlockNumber>;
+
+		/// Time on the `GameClock` a turn may take before it counts as stalling, zero to allow
+		/// any pace.
+		#[pallet::constant]
+		type StallTurnTime: Get<MomentOf<Self>>;
+
+		/// Number of stalled turns a player forfeits the game with.
+		#[pallet::constant]
+		type MaxStalledTurns: Get<u8>;
+
+		/// Number of blocks game results stay in `ResultsByBlock`, zero to not record them.
+		#[pallet::constant]
+		type ResultRetentionBlocks: Get<Self::BlockNumber>;
+
+		/// Most moves of a game kept for `ArchivedGames`, longer games aren't archived.
+		#[pallet::constant]
+		type MaxArchivedMoves: Get<u32>;
+
+		/// Moves between two board snapshots of an archived game in `ArchivedSnapshots`, `0`
+		/// to not take any.
+		#[pallet::constant]
+		type SnapshotInterval: Get<u32>;
+
+		/// Moves after which a game without a winner ends in a draw, so pop out games on large
+		/// boards can't occupy storage forever. Undone moves count as well.
+		#[pallet::constant]
+		type MaxMovesPerGame: Get<u32>;
+
+		/// Part of their score players keep into the next season.
+		#[pallet::constant]
+		type SeasonScoreRetention: Get<Perbill>;
+
+		/// Maximum number of scores decayed per block after a season ended.
+		#[pallet::constant]
+		type MaxDecayPerBlock: Get<u32>;
+
+		/// Blocks without a finished game after which a score decays, zero turns inactivity
+		/// decay off.
+		#[pallet::constant]
+		type InactivityPeriod: Get<Self::BlockNumber>;
+
+		/// Part of their score inactive players keep for every `InactivityPeriod` away.
+		#[pallet::constant]
+		type InactivityScoreRetention: Get<Perbill>;
+
+		/// Maximum number of scores checked for inactivity per block.
+		#[pallet::constant]
+		type MaxInactivityChecks: Get<u32>;
+
+		/// Number of best players of a season that share the season rewards.
+		#[pallet::constant]
+		type MaxSeasonRewardees: Get<u32>;
+
+		/// Origin allowed to pay out season rewards.
+		type SeasonRewardOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of accounts kept on the on-chain leaderboard.
+		#[pallet::constant]
+		type MaxLeaderboardSize: Get<u32>;
+
+		/// Number of score changes kept per account, the oldest get dropped first.
+		#[pallet::constant]
+		type MaxScoreHistory: Get<u32>;
+
+		/// Maximum number of members of a guild.
+		#[pallet::constant]
+		type MaxGuildMembers: Get<u32>;
+
+		/// Maximum number of players each guild fields in a guild match.
+		#[pallet::constant]
+		type MaxGuildMatchPlayers: Get<u32>;
+
+		/// Score tolerance of matchmaking until `set_matchmaking_params` changes it.
+		#[pallet::constant]
+		type DefaultAcceptedDiff: Get<u8>;
+
+		/// Blocks a ranked player has to wait for their score tolerance to widen by
+		/// `QueueWideningStep`, `0` disables widening.
+		#[pallet::constant]
+		type QueueWideningPeriod: Get<Self::BlockNumber>;
+
+		/// Score difference added to the tolerance for every `QueueWideningPeriod` waited.
+		#[pallet::constant]
+		type QueueWideningStep: Get<u8>;
+
+		/// Score tolerance widening stops at.
+		#[pallet::constant]
+		type MaxWidenedDiff: Get<u8>;
+
+		/// Blocks a player waits in the match queue before their entry expires, `0` keeps them
+		/// queued until they're matched or leave.
+		#[pallet::constant]
+		type QueueTimeout: Get<Self::BlockNumber>;
+
+		/// Award of matched games until `set_matchmaking_params` changes it.
+		#[pallet::constant]
+		type DefaultMatchAward: Get<AwardState>;
+
+		/// Boards that may be played at once until `set_max_active_boards` changes it, bounding
+		/// the storage taken by running games.
+		#[pallet::constant]
+		type DefaultMaxActiveBoards: Get<u32>;
+
+		/// Boards an account may play at once until `set_max_games_per_player` changes it.
+		#[pallet::constant]
+		type DefaultMaxGamesPerPlayer: Get<u32>;
+
+		/// Origin allowed to resolve stuck games, reset scores and tune matchmaking.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Id of the pallet, its account plays bot games.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenges)]
+	/// Store players active board, currently only one board per player allowed.
+	pub type Challenges<T: Config> =
+		StorageMap<_, Identity, T::AccountId, ChallengeOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn team_challenges)]
+	/// Pending doubles challenges, by the account that issued them.
+	pub type TeamChallenges<T: Config> =
+		StorageMap<_, Identity, T::AccountId, TeamChallengeOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn boards)]
+	/// Store all boards that are currently being played.
+	pub type Boards<T: Config> = StorageMap<_, Identity, T::Hash, BoardOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn scoring_board)]
+	/// Store all boards that are currently being played.
+	pub type ScoringBoard<T: Config> = StorageMap<_, Identity, T::AccountId, i32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn top_scores)]
+	/// Highest scores, best first. Kept up to date whenever a score changes.
+	pub type Leaderboard<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, i32), T::MaxLeaderboardSize>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn score_history)]
+	/// Latest score changes of an account, oldest first, to draw rating graphs from.
+	pub type ScoreHistory<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::AccountId,
+		BoundedVec<ScoreChangeOf<T>, T::MaxScoreHistory>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn match_queue)]
+	/// Players waiting for a game, bucketed by score so matching only reads nearby buckets.
+	/// Each entry holds the score, the block the player joined at and their ticket from
+	/// `NextQueueTicket`, which orders the whole ranked queue.
+	pub type MatchQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		ScoreBucket,
+		BoundedVec<(T::AccountId, i32, T::BlockNumber, u64), T::MaxQueueLength>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn queued_players)]
+	/// Score each queued player was enqueued with, used to locate their bucket.
+	pub type QueuedPlayers<T: Config> = StorageMap<_, Identity, T::AccountId, i32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn casual_queue)]
+	/// Players waiting for a casual game, oldest first. They are paired regardless of score.
+	pub type CasualQueue<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxQueueLength>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn newly_queued)]
+	/// Players that joined the match queue since the last matchmaking pass, oldest first.
+	pub type NewlyQueued<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxQueueLength>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_queue_ticket)]
+	/// Ticket of the next player joining the ranked queue, counting up.
+	pub type NextQueueTicket<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn queue_length)]
+	/// Players waiting in the ranked and the casual queue together.
+	pub type QueueLength<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn queue_rechecks)]
+	/// Ranked players to match again at a block, once their score tolerance widened.
+	pub type QueueRechecks<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<T::AccountId, T::MaxQueueLength>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn casual_queued_at)]
+	/// Block each casual player joined the queue at, ranked players keep it in `MatchQueue`.
+	pub type CasualQueuedAt<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	/// Players whose queue entry expires in a block. Players that left the queue, or left and
+	/// joined it again, stay listed until then.
+	pub type QueueExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<T::AccountId, T::MaxQueueLength>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// Preset a queued player asked for, they are only paired with players asking for the
+	/// same one.
+	pub type QueuedPresets<T: Config> = StorageMap<_, Identity, T::AccountId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn player_board)]
+	/// Store players active board, currently only one board per player allowed.
+	pub type PlayerBoard<T: Config> = StorageMap<_, Identity, T::AccountId, T::Hash, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn play_proxies)]
+	/// Account every play proxy moves for, see `set_play_proxy`.
+	pub type PlayProxies<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rematches)]
+	/// Players of recently finished games, who can still agree on a rematch.
+	pub type Rematches<T: Config> =
+		StorageMap<_, Identity, T::AccountId, RematchOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn friends)]
+	/// Accounts a player added as friends.
+	pub type Friends<T: Config> =
+		StorageMap<_, Identity, T::AccountId, BoundedVec<T::AccountId, T::MaxFriends>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn blocked)]
+	/// Accounts a player never wants to be paired with or challenged by.
+	pub type Blocked<T: Config> =
+		StorageMap<_, Identity, T::AccountId, BoundedVec<T::AccountId, T::MaxBlocked>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn player_profile)]
+	/// Game preferences of an account, accounts without an entry use the default profile.
+	pub type PlayerProfile<T: Config> =
+		StorageMap<_, Identity, T::AccountId, ProfileOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn spectators)]
+	/// Accounts following a running board.
+	pub type Spectators<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<T::AccountId, T::MaxSpectators>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn remote_player)]
+	/// Location of the players on other chains an account plays for, see `remote_call`.
+	pub type RemotePlayers<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::RemoteLocation, OptionQuery>;
+
+	#[pallet::storage]
+	/// Boards with moves in their game trie, see `Pallet::game_trie`.
+	pub type StreamedGames<T: Config> = StorageMap<_, Identity, T::Hash, (), OptionQuery>;
+
+	#[pallet::storage]
+	/// Finished games whose game trie is still to be cleared, `on_idle` works through them.
+	pub type GameTrieCleanups<T: Config> = StorageMap<_, Identity, T::Hash, (), OptionQuery>;
+
+	#[pallet::storage]
+	/// Block of the last emote of an account, for the `EmoteCooldown`.
+	pub type LastEmote<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenge_rates)]
+	/// Challenges an account created lately, for the `ChallengeCooldown` and
+	/// `MaxChallengesPerEra`.
+	pub type ChallengeRates<T: Config> =
+		StorageMap<_, Identity, T::AccountId, ChallengeRate<T::BlockNumber>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reports)]
+	/// Fair-play report filed on a running board.
+	pub type Reports<T: Config> = StorageMap<_, Identity, T::Hash, ReportOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn disputes)]
+	/// Reported games that finished, waiting for `resolve_report`.
+	pub type Disputes<T: Config> = StorageMap<_, Identity, T::Hash, DisputeOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn achievements)]
+	/// Achievements an account unlocked, one bit per `Achievement`.
+	pub type Achievements<T: Config> =
+		StorageMap<_, Identity, T::AccountId, AchievementSet, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn undo_requests)]
+	/// Player asking to take back the last move of a board.
+	pub type UndoRequests<T: Config> = StorageMap<_, Identity, T::Hash, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn account_created)]
+	/// Block in which an account came into existence, used to gate ranked matchmaking.
+	pub type AccountCreated<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn current_season)]
+	/// Index of the season being played.
+	pub type CurrentSeason<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn season_start)]
+	/// Block in which the current season started.
+	pub type SeasonStart<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_decay)]
+	/// Ended season whose scores are still being decayed, with the last account decayed so far.
+	pub type PendingDecay<T: Config> = StorageValue<_, (u32, Option<T::AccountId>), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn last_active)]
+	/// Block of the last finished game of an account, or of its last inactivity decay.
+	pub type LastActive<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	/// Last account checked for inactivity, the next block continues after it.
+	pub type InactivityCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn first_move_deposits)]
+	/// Queue deposits of matched players that haven't made their first move yet.
+	pub type FirstMoveDeposits<T: Config> =
+		StorageMap<_, Identity, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn first_move_commits)]
+	/// Salt commitments for the next `FirstMovePolicy::CommitReveal` game of an account.
+	pub type FirstMoveCommits<T: Config> =
+		StorageMap<_, Identity, T::AccountId, T::Hash, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn first_move_draws)]
+	/// Boards waiting for their players to reveal the salts that draw the first mover.
+	pub type FirstMoveDraws<T: Config> =
+		StorageMap<_, Identity, T::Hash, FirstMoveDraw<T::Hash>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn open_lobbies)]
+	/// Games waiting at public tables, oldest first.
+	pub type OpenLobbies<T: Config> =
+		StorageValue<_, BoundedVec<LobbyOf<T>, T::MaxOpenLobbies>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_lobby_id)]
+	/// Id of the next public table.
+	pub type NextLobbyId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn private_games)]
+	/// Private games by the hash of their invite code.
+	pub type PrivateGames<T: Config> =
+		StorageMap<_, Identity, T::Hash, PrivateGameOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	/// Invite code hashes of the private games expiring in a block. Games that got joined
+	/// stay listed until then.
+	pub type PrivateGameExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<T::Hash, T::MaxPrivateGamesPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_challenge_id)]
+	/// Id of the next challenge, shared by single and doubles challenges.
+	pub type NextChallengeId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn board_stakes)]
+	/// Stake each player of a board has reserved.
+	pub type BoardStakes<T: Config> = StorageMap<_, Identity, T::Hash, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn board_stake_asset)]
+	/// Asset the stakes of a board are in, boards staked in the native token have no entry.
+	pub type BoardStakeAssets<T: Config> =
+		StorageMap<_, Identity, T::Hash, AssetIdOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn predictions)]
+	/// Stakes of spectators on the outcome of a board, settled when the game ends.
+	pub type Predictions<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::Hash,
+		BoundedVec<PredictionOf<T>, T::MaxPredictions>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn board_sponsors)]
+	/// Sponsorships of a board, reserved from the sponsors until the game ends.
+	pub type BoardSponsors<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::Hash,
+		BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxSponsors>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn prediction_fee)]
+	/// Part of the losing predictions that goes to `PredictionFeeDestination`.
+	pub type PredictionFee<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stake_fee)]
+	/// Part of the loser's stake that goes to `StakeFeeDestination` instead of the winner.
+	pub type StakeFee<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn abandon_penalty)]
+	/// Part of the queue deposit of a player that abandoned a game of the given kind that goes
+	/// to the waiting player, the rest is slashed.
+	pub type AbandonPenalty<T: Config> = StorageMap<_, Twox64Concat, GameKind, Perbill, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn player_stats)]
+	/// Game results of every account that finished a game.
+	pub type PlayerStats<T: Config> = StorageMap<_, Identity, T::AccountId, GameStats, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reward_points)]
+	/// Reward points earned in ranked games, the winner earns the win award and both players
+	/// of a draw the draw award. Unlike `ScoringBoard` they're converted to tokens when claimed.
+	pub type RewardPoints<T: Config> =
+		StorageMap<_, Identity, T::AccountId, RewardBalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_tournament_id)]
+	/// Id of the next tournament.
+	pub type NextTournamentId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournaments)]
+	/// Tournaments by id, kept after they finished for their results.
+	pub type Tournaments<T: Config> =
+		StorageMap<_, Twox64Concat, u32, TournamentOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournament_players)]
+	/// Standing of every player registered for a tournament.
+	pub type TournamentPlayers<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Identity,
+		T::AccountId,
+		SwissStandingOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Tournaments whose registration closes in a block, the scores of their players get
+	/// snapshot for seeding then.
+	pub type TournamentCheckIns<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<u32, T::MaxTournamentsPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournament_seeds)]
+	/// Scores of the players of a tournament when registration closed, best first. The first
+	/// round is seeded from them, so losing games on purpose afterwards doesn't pay off.
+	pub type TournamentSeeds<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<(T::AccountId, i32), T::MaxTournamentPlayers>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournament_rounds)]
+	/// Pairings of every round of a tournament, red first.
+	pub type TournamentRounds<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Twox64Concat,
+		u8,
+		BoundedVec<(T::AccountId, T::AccountId), T::MaxTournamentPlayers>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournament_boards)]
+	/// Tournament of every running tournament game.
+	pub type TournamentBoards<T: Config> = StorageMap<_, Identity, T::Hash, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournament_prizes)]
+	/// Unclaimed prizes of finished tournaments, paid on top of the entry fee of the player.
+	pub type TournamentPrizes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Identity, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn guilds)]
+	/// Guilds by id.
+	pub type Guilds<T: Config> = StorageMap<_, Twox64Concat, u32, GuildOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	/// Id of the next guild.
+	pub type NextGuildId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn player_guild)]
+	/// Guild every guild member belongs to.
+	pub type PlayerGuild<T: Config> = StorageMap<_, Identity, T::AccountId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn guild_challenges)]
+	/// Pending guild matches, by challenging guild.
+	pub type GuildChallenges<T: Config> =
+		StorageMap<_, Twox64Concat, u32, GuildChallengeOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn guild_matches)]
+	/// Running guild matches by id.
+	pub type GuildMatches<T: Config> = StorageMap<_, Twox64Concat, u32, GuildMatch, OptionQuery>;
+
+	#[pallet::storage]
+	/// Id of the next guild match.
+	pub type NextGuildMatchId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn guild_match_boards)]
+	/// Guild match of every running guild match game.
+	pub type GuildMatchBoards<T: Config> = StorageMap<_, Identity, T::Hash, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_board_creations)]
+	/// Paired tournament games whose boards aren't created yet, by tournament. Drained by
+	/// `on_initialize`, `MaxBoardCreationsPerBlock` boards at a time.
+	pub type PendingBoardCreations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<(T::AccountId, T::AccountId), T::MaxTournamentPlayers>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn results_by_block)]
+	/// Results of the games finished in a block, dropped after `ResultRetentionBlocks`.
+	pub type ResultsByBlock<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Identity,
+		T::Hash,
+		GameResultOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Moves of the running games in `notation`, dropped once a game has more than
+	/// `MaxArchivedMoves`.
+	pub type MoveLogs<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, T::MaxArchivedMoves>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn archived_game)]
+	/// Finished games in `notation`, kept as long as their result in `ResultsByBlock`.
+	pub type ArchivedGames<T: Config> =
+		StorageMap<_, Identity, T::Hash, ArchivedGameOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn archived_snapshots)]
+	/// Board after every `SnapshotInterval`th move of an archived game, so a position can be
+	/// restored without replaying the game from the start, see `GameRecord::position`.
+	pub type ArchivedSnapshots<T: Config> =
+		StorageMap<_, Identity, T::Hash, ArchivedSnapshotsOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn game_annotations)]
+	/// Annotations of an archived game and who submitted them, dropped with the archive when
+	/// the deposits are returned.
+	pub type GameAnnotations<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::Hash,
+		BoundedVec<(T::AccountId, AnnotationOf<T>), T::MaxAnnotations>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn season_standings)]
+	/// Best players of an ended season, best first, until their rewards are paid out.
+	pub type SeasonStandings<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<(T::AccountId, i32), T::MaxSeasonRewardees>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_trophy_id)]
+	/// Id of the next trophy minted to a winner.
+	pub type NextTrophyId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	// Default value for Nonce
+	#[pallet::type_value]
+	pub fn AcceptedDiffDefault<T: Config>() -> u8 {
+		T::DefaultAcceptedDiff::get()
+	}
+	#[pallet::storage]
+	#[pallet::getter(fn accepted_diff)]
+	/// Largest score difference between two players paired by matchmaking.
+	pub type AcceptedDiff<T: Config> = StorageValue<_, u8, ValueQuery, AcceptedDiffDefault<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	/// Set by the admin origin to stop new games, running games can still be played out.
+	pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultAwardDefault<T: Config>() -> AwardState {
+		T::DefaultMatchAward::get()
+	}
+	#[pallet::storage]
+	#[pallet::getter(fn default_award)]
+	/// Award of games created by matchmaking.
+	pub type DefaultAward<T: Config> =
+		StorageValue<_, AwardState, ValueQuery, DefaultAwardDefault<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn presets)]
+	/// Game settings players can pick by id instead of passing them, see `challenge_preset`
+	/// and `find_preset_game`.
+	pub type Presets<T: Config> = StorageMap<_, Twox64Concat, u32, GamePresetOf<T>, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn MaxActiveBoardsDefault<T: Config>() -> u32 {
+		T::DefaultMaxActiveBoards::get()
+	}
+	#[pallet::storage]
+	#[pallet::getter(fn max_active_boards)]
+	/// Boards that may be played at once, new games fail with `BoardLimitReached` beyond it.
+	pub type MaxActiveBoards<T: Config> =
+		StorageValue<_, u32, ValueQuery, MaxActiveBoardsDefault<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn active_boards)]
+	/// Boards being played, counted against `MaxActiveBoards`.
+	pub type ActiveBoards<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn MaxGamesPerPlayerDefault<T: Config>() -> u32 {
+		T::DefaultMaxGamesPerPlayer::get()
+	}
+	#[pallet::storage]
+	#[pallet::getter(fn max_games_per_player)]
+	/// Boards an account may play at once, new games fail with `TooManyGames` beyond it.
+	/// `PlayerBoard` keeps players on one board at a time, so only a cap of 0 is stricter.
+	pub type MaxGamesPerPlayer<T: Config> =
+		StorageValue<_, u32, ValueQuery, MaxGamesPerPlayerDefault<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tier_boundaries)]
+	/// Lowest score of every matchmaking tier above the lowest one, ascending. Ranked players
+	/// are only paired within their tier.
+	pub type TierBoundaries<T: Config> = StorageValue<_, TierBoundariesOf, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn adjacent_tier_fallback)]
+	/// Pair ranked players with the next tier up or down if nobody in their own tier fits.
+	pub type AdjacentTierFallback<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn NonceDefault<T: Config>() -> u64 {
+		0
+	}
+	// Nonce used for generating a different seed each time.
+	#[pallet::storage]
+	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery, NonceDefault<T>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initial scores, e.g. carried over from a previous chain.
+		pub scores: Vec<(T::AccountId, i32)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { scores: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (account, score) in &self.scores {
+				assert!(
+					!<ScoringBoard<T>>::contains_key(account),
+					"Account has more than one genesis score."
+				);
+				Pallet::<T>::set_score(account, Some(*score));
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Expire stale queue entries, pair queued players, create tournament boards and keep
+		/// the seasons going at the start of every block.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = Self::expire_queue(n);
+			weight = weight.saturating_add(Self::match_queued_players(n));
+			weight = weight.saturating_add(Self::create_pending_boards());
+			weight = weight.saturating_add(Self::roll_season(n));
+			weight = weight.saturating_add(Self::prune_results(n));
+			weight = weight.saturating_add(Self::expire_private_games(n));
+			weight = weight.saturating_add(Self::snapshot_seeds(n));
+			weight = weight.saturating_add(Self::decay_scores());
+			weight.saturating_add(Self::decay_inactive_scores(n))
+		}
+
+		/// Clear the game tries of finished games with the weight left in the block.
+		fn on_idle(_: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::clear_game_tries(remaining_weight)
+		}
+
+		fn integrity_test() {
+			// Matchmaking always plays the default variant.
+			assert!(
+				BoardVariant::default().is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+				"The default board variant must fit the board size limits."
+			);
+			assert!(
+				Board::fits(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+				"The board size limits must fit into a bitboard."
+			);
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		/// Submit expiries for abandoned games, so block hooks don't have to scan the boards.
+		fn offchain_worker(_: T::BlockNumber) {
+			Self::submit_stale_expiries();
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_upgrade::<T>()?;
+			Self::do_try_state()
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let board_id = match call {
+				Call::expire_stale_game { board_id } => board_id,
+				_ => return InvalidTransaction::Call.into(),
+			};
+			// Any node may relay an expiry, it only passes once the board is stale.
+			match <Boards<T>>::get(board_id) {
+				Some(board) if Self::is_stale(&board) =>
+					ValidTransaction::with_tag_prefix("ConnectFourStaleGame")
+						.priority(T::UnsignedPriority::get())
+						.and_provides(board_id)
+						.longevity(STALE_EXPIRY_LONGEVITY)
+						.propagate(true)
+						.build(),
+				Some(_) => InvalidTransaction::Future.into(),
+				None => InvalidTransaction::Stale.into(),
+			}
+		}
+	}
+
+	// Pallets use events to inform users when important changes are made.
+	// https://substrate.dev/docs/en/knowledgebase/runtime/events
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A challenge got placed with all its settings, its `opponent` is `None` for an open
+		/// challenge any account may accept.
+		ChallengeCreated { challenger: T::AccountId, challenge: ChallengeOf<T> },
+		/// A challenge got accepted and its game created.
+		AcceptChallenge {
+			challenge_id: u32,
+			challenger: T::AccountId,
+			opponent: T::AccountId,
+			board_id: T::Hash,
+		},
+		/// Doubles challenge.
+		/// \[challenge_id, challenger, teammate, opponent, opponent_mate\]
+		TeamChallenge(u32, T::AccountId, T::AccountId, T::AccountId, T::AccountId),
+		/// An invited player rejected a challenge, which got removed.
+		RejectChallenge { challenge_id: u32, challenger: T::AccountId, responder: T::AccountId },
+		/// The challenger withdrew their challenge.
+		CancelChallenge { challenge_id: u32, challenger: T::AccountId },
+		/// Cancel challenge
+		CancelQueue(T::AccountId),
+		/// A new board got created. \[board_id, deadline\], the last moment for the first move
+		/// of a game with time banks.
+		NewBoard(T::Hash, Option<MomentOf<T>>),
+		/// A stone got played.
+		/// `position` is the hash of the new position, see `Pallet::position_hash`, and
+		/// `deadline` the last moment for the next move of a game with time banks.
+		/// `moves_played` counts the moves on the board including this one. `evaluation` holds
+		/// the threats of both sides in spectated and tournament games, see
+		/// `Logic::threat_counts`.
+		TurnPlayed {
+			board_id: T::Hash,
+			player: T::AccountId,
+			column: u8,
+			row: u8,
+			position: T::Hash,
+			deadline: Option<MomentOf<T>>,
+			moves_played: u16,
+			evaluation: Option<ThreatCounts>,
+		},
+		/// A player of a Pop Out game took their stone out of the bottom of `column`.
+		StonePopped {
+			board_id: T::Hash,
+			player: T::AccountId,
+			column: u8,
+			position: T::Hash,
+			moves_played: u16,
+		},
+		/// A game came to an end, `winner` is `None` for a draw. `win_line` holds the
+		/// `(column, row)` cells of the connected stones, empty unless the game was won that way.
+		/// `stake_fee` is the part of the loser's stake that went to `StakeFeeDestination`.
+		GameFinished {
+			board_id: T::Hash,
+			winner: Option<T::AccountId>,
+			reason: FinishReason,
+			win_line: Vec<(u8, u8)>,
+			stake_fee: BalanceOf<T>,
+		},
+		/// A trophy got minted to the winner of a board. \[winner, board_id, trophy_id\]
+		TrophyAwarded(T::AccountId, T::Hash, u32),
+		/// A player offered a rematch to their last opponent.
+		RematchRequested(T::AccountId, T::AccountId),
+		/// A season ended, scores get decayed over the next blocks. \[season\]
+		SeasonEnded(u32),
+		/// Rewards of a season got paid out to its best players. \[season, reward_pool\]
+		SeasonRewarded(u32, BalanceOf<T>),
+		/// A game is waiting at a public table. \[lobby_id, creator, award, stake, asset\]
+		LobbyOpened(u32, T::AccountId, AwardState, BalanceOf<T>, Option<AssetIdOf<T>>),
+		/// A public table got taken or closed. \[lobby_id\]
+		LobbyClosed(u32),
+		/// The game results of a player changed.
+		StatsUpdated(T::AccountId, GameStats),
+		/// A player asked to take back the last move. \[board_id, player\]
+		UndoRequested(T::Hash, T::AccountId),
+		/// The last move got taken back. \[board_id, column\]
+		MoveUndone(T::Hash, u8),
+		/// The opponent refused to take back the last move. \[board_id, opponent\]
+		UndoRejected(T::Hash, T::AccountId),
+		/// A blitz player committed to their move. \[board_id, player\]
+		MoveCommitted(T::Hash, T::AccountId),
+		/// A blitz player revealed their column. \[board_id, player, column\]
+		MoveRevealed(T::Hash, T::AccountId, u8),
+		/// An account moved on the leaderboard. \[account, rank\], `None` if it dropped off.
+		LeaderboardUpdated(T::AccountId, Option<u32>),
+		/// An admin reset the score of an account.
+		ScoreReset(T::AccountId),
+		/// Matchmaking settings changed. \[accepted_diff, default_award\]
+		MatchmakingParamsSet(u8, AwardState),
+		/// New games are blocked until the pallet is unpaused.
+		PalletPaused,
+		/// New games can be started again.
+		PalletUnpaused,
+		/// A tournament opened for registration. \[tournament_id, creator, rounds\]
+		TournamentCreated(u32, T::AccountId, u8),
+		/// A player registered for a tournament. \[tournament_id, player\]
+		TournamentJoined(u32, T::AccountId),
+		/// A registered player checked in. \[tournament_id, player\]
+		TournamentCheckedIn(u32, T::AccountId),
+		/// A player that didn't check in got dropped from the tournament.
+		/// \[tournament_id, player, refunded\]
+		TournamentNoShow(u32, T::AccountId, bool),
+		/// The games of a tournament round started. \[tournament_id, round, bye\]
+		TournamentRoundStarted(u32, u8, Option<T::AccountId>),
+		/// All rounds of a tournament are played. \[tournament_id, winner\]
+		TournamentFinished(u32, T::AccountId),
+		/// An account changed its game preferences.
+		ProfileUpdated(T::AccountId),
+		/// A challenge got accepted by the opponent's profile.
+		/// \[challenge_id, challenger, opponent, board_id\]
+		AutoAccepted(u32, T::AccountId, T::AccountId, T::Hash),
+		/// A spectator staked on the outcome of a board. \[board_id, predictor, winner, stake\]
+		Predicted(T::Hash, T::AccountId, Option<T::AccountId>, BalanceOf<T>),
+		/// The predictions of a finished board got paid out. \[board_id, pool, fee\]
+		PredictionsSettled(T::Hash, BalanceOf<T>, BalanceOf<T>),
+		/// The predictions of a board got refunded, the game had no result or nobody was right.
+		PredictionsRefunded(T::Hash),
+		/// The house fee of prediction pools changed.
+		PredictionFeeSet(Perbill),
+		/// The fee on the losing stake of public table games changed.
+		StakeFeeSet(Perbill),
+		/// A stone could be dropped into the column. \[player, column, row\]
+		MoveChecked(T::AccountId, u8, u8),
+		/// Orphaned entries got removed. \[caller, entries, reward\]
+		CleanedUp(T::AccountId, u32, BalanceOf<T>),
+		/// The part of an abandoner's deposit paid to the waiting player changed.
+		AbandonPenaltySet(GameKind, Perbill),
+		/// A player that abandoned a game paid part of their deposit to the waiting player.
+		AbandonCompensated {
+			board_id: T::Hash,
+			abandoner: T::AccountId,
+			player: T::AccountId,
+			compensation: BalanceOf<T>,
+		},
+		/// The matchmaking tiers changed. \[boundaries, adjacent_fallback\]
+		TierBoundariesSet(TierBoundariesOf, bool),
+		/// A score change moved an account into another tier. \[account, from, to\]
+		TierChanged(T::AccountId, Tier, Tier),
+		/// Reward points got converted to tokens. \[account, points, payout\]
+		RewardsClaimed(T::AccountId, u32, BalanceOf<T>),
+		/// The score of an inactive account decayed. \[account, old_score, new_score\]
+		ScoreDecayed(T::AccountId, i32, i32),
+		/// A player or spectator sent an emote to a board. \[board_id, sender, emote\]
+		BoardEmote(T::Hash, T::AccountId, Emote),
+		/// A player called off a game before the second move. \[board_id, player, reason\]
+		GameAborted(T::Hash, T::AccountId, AbortReason),
+		/// A player reported their opponent, the game waits for a verdict once it ends.
+		/// \[board_id, reporter, reason\]
+		GameReported(T::Hash, T::AccountId, ReportReason),
+		/// A reported game got settled by the verdict. \[board_id, verdict, winner, stake_fee\]
+		ReportResolved(T::Hash, Verdict, Option<T::AccountId>, BalanceOf<T>),
+		/// A player unlocked an achievement. \[player, achievement\]
+		AchievementUnlocked(T::AccountId, Achievement),
+		/// A private game waits for its invite code. \[code_hash, creator, expires\]
+		PrivateGameCreated(T::Hash, T::AccountId, T::BlockNumber),
+		/// A private game got joined. \[code_hash, board_id\]
+		PrivateGameJoined(T::Hash, T::Hash),
+		/// Nobody joined a private game in time, its stake is returned. \[code_hash, creator\]
+		PrivateGameExpired(T::Hash, T::AccountId),
+		/// An account left all its pending states at once, see `withdraw_all`.
+		WithdrawnFromAll {
+			account: T::AccountId,
+			/// The account left the match queue.
+			queue: bool,
+			/// Challenge the account withdrew.
+			challenge_id: Option<u32>,
+			/// Public table the account left.
+			lobby_id: Option<u32>,
+			/// The account declined the rematch of its last game.
+			rematch: bool,
+		},
+		/// A guild got founded. \[guild_id, founder\]
+		GuildCreated(u32, T::AccountId),
+		/// An account joined a guild. \[guild_id, member\]
+		GuildJoined(u32, T::AccountId),
+		/// An account left a guild, the last member leaving disbands it. \[guild_id, member\]
+		GuildLeft(u32, T::AccountId),
+		/// A guild challenged another guild to a match. \[challenger, opponent, players\]
+		GuildChallenged(u32, u32, u32),
+		/// A guild rejected a guild match. \[challenger, opponent\]
+		GuildChallengeRejected(u32, u32),
+		/// Both lineups of a guild match got their boards. \[match_id, challenger, opponent\]
+		GuildMatchStarted(u32, u32, u32),
+		/// All boards of a guild match finished. \[match_id, winner\], `None` for a draw.
+		GuildMatchFinished(u32, Option<u32>),
+		/// An account let another account play its moves. \[account, delegate\]
+		PlayProxySet(T::AccountId, T::AccountId),
+		/// An account took back the moves of its play proxy. \[account, delegate\]
+		PlayProxyRemoved(T::AccountId, T::AccountId),
+		/// A player offered to double the stakes of their game. \[board_id, player, stake\]
+		DoubleOffered(T::Hash, T::AccountId, BalanceOf<T>),
+		/// The opponent accepted the double and owns the cube. \[board_id, player, stake\]
+		DoubleAccepted(T::Hash, T::AccountId, BalanceOf<T>),
+		/// The opponent declined the double and lost the game. \[board_id, player\]
+		DoubleDeclined(T::Hash, T::AccountId),
+		/// A player claimed their entry fee and prize. \[tournament_id, player, prize\]
+		PrizeClaimed(u32, T::AccountId, BalanceOf<T>),
+		/// Unclaimed entry fees and prizes went to `UnclaimedPrizeDestination`.
+		/// \[tournament_id, players, amount\]
+		PrizesSwept(u32, u32, BalanceOf<T>),
+		/// An account committed to a salt for its next commit-reveal game. \[account\]
+		FirstMoveCommitted(T::AccountId),
+		/// A player revealed their salt for the first mover draw. \[board_id, player\]
+		FirstMoveRevealed(T::Hash, T::AccountId),
+		/// Both salts are revealed and the first mover is drawn. \[board_id, player\]
+		FirstMoveDrawn(T::Hash, T::AccountId),
+		/// An account sponsored a board or a tournament. \[game, sponsor, amount\]
+		GameSponsored(SponsoredGame<T::Hash>, T::AccountId, BalanceOf<T>),
+		/// The winner of a board got a sponsorship. \[board_id, sponsor, winner, amount\]
+		SponsorshipPaid(T::Hash, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A board ended without a winner and the sponsor got their tokens back.
+		/// \[board_id, sponsor, amount\]
+		SponsorshipRefunded(T::Hash, T::AccountId, BalanceOf<T>),
+		/// A player waited `QueueTimeout` blocks without a match and left the queue, their
+		/// deposit is returned. \[player\]
+		QueueExpired(T::AccountId),
+		/// The number of boards that may be played at once changed.
+		MaxActiveBoardsSet(u32),
+		/// The number of boards an account may play at once changed.
+		MaxGamesPerPlayerSet(u32),
+		/// The second player of a pie rule game took over the first mover's side, the first
+		/// mover plays next. \[board_id, player, deadline\]
+		SidesSwapped(T::Hash, T::AccountId, Option<MomentOf<T>>),
+		/// An account attached an annotation to an archived game.
+		/// \[board_id, annotator, annotation\]
+		GameAnnotated(T::Hash, T::AccountId, AnnotationOf<T>),
+		/// The first round of a tournament got seeded from the scores snapshot when
+		/// registration closed, the best seed meets the worst. \[tournament_id, pairings\]
+		TournamentSeeded(u32, BoundedVec<(T::AccountId, T::AccountId), T::MaxTournamentPlayers>),
+		/// A game preset got added or changed. \[preset_id\]
+		PresetSet(u32),
+		/// A game preset got removed. \[preset_id\]
+		PresetRemoved(u32),
+		/// A player changed what the events of their board tell. \[board_id, verbosity\]
+		EventVerbositySet(T::Hash, EventVerbosity),
+		/// The whole board after a move on a board with `EventVerbosity::FullBoard`.
+		/// \[board_id, board\]
+		BoardSnapshot(T::Hash, BoardOf<T>),
+		/// A player finished their placement games, their score is no longer provisional.
+		/// \[player, score\]
+		PlacementCompleted(T::AccountId, i32),
+		/// A player on another chain played for the first time, as the local account.
+		/// \[account, location\]
+		RemotePlayerJoined(T::AccountId, T::RemoteLocation),
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Can't find element to remove
+		NotFound,
+		/// The account has no pending challenge.
+		ChallengeNotFound,
+		/// No new games can be started while the pallet is paused.
+		Paused,
+		/// Player already has a board which is being played.
 		PlayerBoardExists,
 		/// Player board doesn't exist for this player.
 		NoPlayerBoard,
@@ -168,286 +2416,5302 @@ pub mod pallet {
 		ReChallengeError,
 		/// Failed to access match queue
 		MatchQueueError,
+		/// Board dimensions or connect length are outside the allowed limits.
+		InvalidVariant,
+		/// Too many players are waiting in this score range, try again later.
+		QueueFull,
+		/// No recently finished game to replay.
+		NoRematch,
+		/// The time to agree on a rematch has passed.
+		RematchExpired,
+		/// The opponent hasn't asked for a rematch.
+		RematchNotRequested,
+		/// A time bank has to allow at least one tick of the game clock.
+		InvalidTimeBank,
+		/// The handicap doesn't fit the board or would decide the game on its own.
+		InvalidHandicap,
+		/// The game is played without a clock.
+		NoTimeControl,
+		/// The player to move still has time left.
+		ClockNotExpired,
+		/// Only the waiting player can claim that the opponent ran out of time.
+		CannotClaimOwnTimeout,
+		/// There is no running board with this id.
+		BoardNotFound,
+		/// The account doesn't play on this board.
+		NotOnBoard,
+		/// Blitz games are played with `commit_move` and `reveal_move`.
+		BlitzGame,
+		/// The board isn't a blitz game.
+		NotBlitzGame,
+		/// The blitz round is in the other phase.
+		WrongBlitzPhase,
+		/// The player already committed or revealed in this phase.
+		AlreadyMoved,
+		/// The revealed column and salt don't match the commitment.
+		CommitmentMismatch,
+		/// Moves can only be taken back in casual games between people.
+		UndoNotAllowed,
+		/// Only the last move can be taken back, by the player that made it.
+		NothingToUndo,
+		/// Nobody asked to take back a move on this board.
+		NoUndoRequest,
+		/// Taking back the last move was already requested.
+		UndoAlreadyRequested,
+		/// All public tables are taken.
+		TooManyLobbies,
+		/// The account already waits at a public table.
+		AlreadyInLobby,
+		/// There is no public table with this id.
+		LobbyNotFound,
+		/// The board had a move within `StaleGameAge`.
+		GameNotStale,
+		/// The account is already on the friend list.
+		AlreadyFriends,
+		/// The accounts aren't on each other's friend list.
+		NotFriends,
+		/// The friend list is full.
+		TooManyFriends,
+		/// The account is already on the block list.
+		AlreadyBlocked,
+		/// The account isn't on the block list.
+		NotBlocked,
+		/// The block list is full.
+		TooManyBlocked,
+		/// One of the players blocked the other.
+		PlayerBlocked,
+		/// The display name is longer than `MaxDisplayNameLength`.
+		DisplayNameTooLong,
+		/// The player doesn't join open challenges or public tables, see `set_profile`.
+		OpenChallengesDisallowed,
+		/// The stake of the table is above the player's `max_stake`.
+		StakeTooHigh,
+		/// The board isn't a Pop Out game of two players.
+		NotPopOut,
+		/// The bottom stone of the column isn't one of yours.
+		CannotPop,
+		/// No tournament with this id.
+		TournamentNotFound,
+		/// A tournament needs at least one round and fewer rounds than players.
+		InvalidRounds,
+		/// The tournament has no room for more players.
+		TournamentFull,
+		/// The account is already registered for the tournament.
+		AlreadyRegistered,
+		/// The tournament isn't in the phase this needs.
+		WrongTournamentState,
+		/// Only the creator can start the tournament.
+		NotTournamentCreator,
+		/// Games of the current round are still being played.
+		RoundNotFinished,
+		/// A tournament needs at least two players.
+		NotEnoughPlayers,
+		/// The account is already watching this board.
+		AlreadyWatching,
+		/// The account isn't watching this board.
+		NotWatching,
+		/// The board has reached the maximum number of spectators.
+		TooManySpectators,
+		/// The challenge is meant for another account, or open challenges can only be accepted.
+		NotChallenged,
+		/// Free balance is below the minimum for ranked matchmaking.
+		InsufficientBalance,
+		/// Account is too new for ranked matchmaking.
+		AccountTooNew,
+		/// Ranked matchmaking requires an identity judged by a registrar.
+		IdentityNotJudged,
+		/// The scores of this season are still being decayed.
+		SeasonNotSettled,
+		/// There are no standings to reward for this season.
+		NoSeasonStandings,
+		/// All four players of a doubles game have to be different accounts.
+		DuplicateTeamPlayer,
+		/// The invited player already accepted this challenge.
+		AlreadyAccepted,
+		/// Predictions close with the first move.
+		PredictionsClosed,
+		/// The account already predicted the outcome of this board.
+		AlreadyPredicted,
+		/// The board has reached the maximum number of predictions.
+		TooManyPredictions,
+		/// Players can't predict the outcome of their own game.
+		PlayerCannotPredict,
+		/// The stake is below the existential deposit.
+		StakeTooLow,
+		/// The game is already over.
+		GameNotRunning,
+		/// The column has no room left.
+		ColumnFull,
+		/// The player ran out of time or stalled too many turns, any move loses the game.
+		OutOfTime,
+		/// The challenge asks for another award than the one accepted.
+		AwardMismatch,
+		/// None of the accounts has an orphaned entry.
+		NothingToCleanUp,
+		/// Moves can only be relayed in casual games.
+		NotCasualGame,
+		/// The board moved on since the move was signed: the `move_index` of a relayed move
+		/// doesn't match the turns played, or the `expected_move_index` of `play_turn` doesn't
+		/// match the moves played.
+		StaleMove,
+		/// The move isn't signed by the player at turn.
+		InvalidSignature,
+		/// Check-in has to open after the current block and close after it opened.
+		InvalidCheckInWindow,
+		/// Registration for the tournament closed, check-in is open or over.
+		RegistrationClosed,
+		/// The check-in window of the tournament isn't open.
+		CheckInClosed,
+		/// The account isn't registered for the tournament.
+		NotRegistered,
+		/// The account already checked in.
+		AlreadyCheckedIn,
+		/// The tournament can't start before check-in closes.
+		CheckInNotOver,
+		/// Tier boundaries have to be strictly ascending.
+		InvalidTierBoundaries,
+		/// There are no vested reward points to claim.
+		NoRewardsToClaim,
+		/// The table is played for a stake in another asset.
+		StakeAssetMismatch,
+		/// The account neither plays on nor watches this board.
+		NotAtBoard,
+		/// The account sent an emote less than `EmoteCooldown` blocks ago.
+		EmoteTooSoon,
+		/// Games can only be aborted before the second move, and tournament games not at all.
+		AbortNotAllowed,
+		/// The game was reported already.
+		AlreadyReported,
+		/// There is no finished reported game with this id waiting for a verdict.
+		DisputeNotFound,
+		/// The challenge is past its `ChallengeLifetime` and can't be accepted anymore.
+		ChallengeExpired,
+		/// A private game with this invite code is already waiting.
+		PrivateGameExists,
+		/// No private game waits for this invite code.
+		PrivateGameNotFound,
+		/// Too many private games expire in this block, try again in the next one.
+		TooManyPrivateGames,
+		/// The account has nothing pending to withdraw from.
+		NothingToWithdraw,
+		/// The account is already a member of a guild.
+		AlreadyInGuild,
+		/// The account isn't a member of any guild.
+		NotInGuild,
+		/// There's no guild with this id.
+		GuildNotFound,
+		/// The guild has `MaxGuildMembers` members already.
+		GuildFull,
+		/// Only the founder of a guild can do this.
+		OnlyGuildFounder,
+		/// Lineups need between one and `MaxGuildMatchPlayers` distinct members of the guild,
+		/// the same number for both guilds.
+		InvalidLineup,
+		/// An account can't be its own play proxy.
+		InvalidPlayProxy,
+		/// The delegate already plays the moves of an account.
+		PlayProxyTaken,
+		/// The delegate doesn't play the moves of the sender.
+		NoPlayProxy,
+		/// Only games with a stake can be doubled.
+		NotStaked,
+		/// Doubles are offered on your turn of a running game, by the owner of the cube or by
+		/// either player while it's centered.
+		CannotDouble,
+		/// The game waits for the answer to an offered double.
+		DoublePending,
+		/// The opponent didn't offer a double.
+		NoDoubleOffer,
+		/// There's no unclaimed prize of the account in this tournament.
+		NoPrize,
+		/// Prizes can only be swept once `PrizeClaimPeriod` passed after the tournament.
+		ClaimPeriodNotOver,
+		/// Commit-reveal games need a salt commitment of both players, see `commit_first_move`.
+		FirstMoveNotCommitted,
+		/// The game waits for both players to reveal the salts drawing the first mover.
+		FirstMoveNotDrawn,
+		/// The board doesn't wait for a first mover draw.
+		NoFirstMoveDraw,
+		/// The account challenged less than `ChallengeCooldown` blocks ago, or already created
+		/// `MaxChallengesPerEra` challenges this era.
+		ChallengeRateLimited,
+		/// Bot games and finished tournaments can't be sponsored.
+		CannotSponsor,
+		/// The board has `MaxSponsors` sponsors already.
+		TooManySponsors,
+		/// `MaxActiveBoards` boards are being played already.
+		BoardLimitReached,
+		/// A player is on `MaxGamesPerPlayer` boards already.
+		TooManyGames,
+		/// Sides can only be swapped once, right after the first move of a pie rule game
+		/// between two players.
+		CannotSwapSides,
+		/// Only games kept in `ArchivedGames` can be annotated.
+		GameNotArchived,
+		/// The annotation is longer than `MaxAnnotationLength`.
+		AnnotationTooLong,
+		/// The game has `MaxAnnotations` annotations already.
+		TooManyAnnotations,
+		/// Too many tournaments close registration in this block, pick another check-in block.
+		TooManyTournaments,
+		/// There is no preset with this id.
+		PresetNotFound,
+		/// The stake doesn't fit the bounds of the preset.
+		StakeOutOfBounds,
+		/// The column is outside the board, see `Logic::legal_moves`.
+		ColumnOutOfRange,
+		/// Only finding, challenging and playing games can be sent from another chain.
+		NotRemotePlayable,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Find randome game. Ranked players are paired by score, casual players with whoever
+		/// else waits for a casual game.
+		#[pallet::weight(T::WeightInfo::find_game())]
+		pub fn find_game(origin: OriginFor<T>, kind: GameKind) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+
+			// Make sure players have no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			// Make sure not a challenger
+			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::ReChallengeError);
+			// Make sure gamer is not available
+			ensure!(!Self::is_queued(&sender), Error::<T>::MatchQueueError);
+			if kind == GameKind::Ranked {
+				// Keep throwaway accounts out of ranked play.
+				Self::ensure_ranked_eligible(&sender)?;
+			}
+			T::Currency::reserve(&sender, T::QueueDeposit::get())?;
+
+			// Pairing happens at the start of the next block.
+			match kind {
+				GameKind::Ranked => {
+					let finder_score = <ScoringBoard<T>>::get(&sender).unwrap_or(0);
+					Self::enqueue(&sender, finder_score)
+				},
+				GameKind::Casual => Self::enqueue_casual(&sender),
+			}
+		}
+
+		/// Find a random game played with the settings of a preset. Only players asking for
+		/// the same preset are paired.
+		#[pallet::weight(T::WeightInfo::find_preset_game())]
+		pub fn find_preset_game(
+			origin: OriginFor<T>,
+			kind: GameKind,
+			preset_id: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin.clone())?;
+			ensure!(<Presets<T>>::contains_key(preset_id), Error::<T>::PresetNotFound);
+
+			Self::find_game(origin, kind)?;
+			<QueuedPresets<T>>::insert(&sender, preset_id);
+			Ok(())
+		}
+
+		/// Cancel Challenge
+		#[pallet::weight(T::WeightInfo::cancel_queue())]
+		pub fn cancel_queue(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			// Make sure players have no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			// Make sure challenger in the storage
+			ensure!(Self::dequeue(&sender), Error::<T>::NotFound);
+			T::Currency::unreserve(&sender, T::QueueDeposit::get());
+
+			Self::deposit_event(Event::CancelQueue(sender));
+			Ok(())
+		}
+
+		/// Challenge player, optionally granting them a `handicap`. `first_move` picks who starts.
+		/// A `stake` is reserved from both players once accepted, the winner takes the loser's.
+		/// The game starts right away if the challenge meets the opponent's auto-accept
+		/// settings, see `set_profile`.
+		#[pallet::weight(T::WeightInfo::challenge())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn challenge(
+			origin: OriginFor<T>,
+			opponent: T::AccountId,
+			win: u32,
+			lose: u32,
+			draw: u32,
+			variant: BoardVariant,
+			time_bank: Option<MomentOf<T>>,
+			handicap: Option<Handicap>,
+			first_move: FirstMovePolicy,
+			kind: GameKind,
+			stake: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Don't allow playing against yourself.
+			ensure!(sender != opponent, Error::<T>::NoFakePlay);
+
+			// Make sure opponent has no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&opponent), Error::<T>::PlayerBoardExists);
+			// Make sure responder is not also a challenger
+			ensure!(!<Challenges<T>>::contains_key(&opponent), Error::<T>::WrongChallengeTurn);
+			ensure!(
+				first_move != FirstMovePolicy::CommitReveal ||
+					<FirstMoveCommits<T>>::contains_key(&sender),
+				Error::<T>::FirstMoveNotCommitted
+			);
+
+			let award = AwardState { win, lose, draw };
+			let challenge = ChallengeStruct {
+				id: Self::take_challenge_id(),
+				opponent: Some(opponent.clone()),
+				award,
+				variant,
+				time_bank,
+				blitz: false,
+				handicap,
+				first_move,
+				kind,
+				stake,
+				expires: Self::challenge_expiry(),
+			};
+			Self::place_challenge(&sender, challenge.clone())?;
+
+			if Self::auto_accepts(&opponent, &sender, &challenge) {
+				<Challenges<T>>::remove(&sender);
+				let challenge_id = challenge.id;
+				let board_id =
+					Self::start_challenge_game(opponent.clone(), sender.clone(), challenge)?;
+				Self::deposit_event(Event::AutoAccepted(challenge_id, sender, opponent, board_id));
+			}
+			Ok(())
+		}
+
+		/// Challenge player to a game with the settings of a preset. The `stake` has to fit
+		/// the bounds of the preset.
+		#[pallet::weight(T::WeightInfo::challenge_preset())]
+		pub fn challenge_preset(
+			origin: OriginFor<T>,
+			opponent: T::AccountId,
+			preset_id: u32,
+			first_move: FirstMovePolicy,
+			kind: GameKind,
+			stake: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let preset = <Presets<T>>::get(preset_id).ok_or(Error::<T>::PresetNotFound)?;
+			let staked = stake.unwrap_or_else(Zero::zero);
+			ensure!(
+				preset.min_stake <= staked && staked <= preset.max_stake,
+				Error::<T>::StakeOutOfBounds
+			);
+
+			let AwardState { win, lose, draw } = preset.award;
+			Self::challenge(
+				origin,
+				opponent,
+				win,
+				lose,
+				draw,
+				preset.variant,
+				preset.time_bank,
+				None,
+				first_move,
+				kind,
+				stake,
+			)
+		}
+
+		/// Challenge player to a blitz game, where both players pick their column at the same
+		/// time. With a clock, `time_bank` is the time each player has for every phase.
+		#[pallet::weight(T::WeightInfo::challenge())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn challenge_blitz(
+			origin: OriginFor<T>,
+			opponent: T::AccountId,
+			win: u32,
+			lose: u32,
+			draw: u32,
+			variant: BoardVariant,
+			time_bank: Option<MomentOf<T>>,
+			kind: GameKind,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Don't allow playing against yourself.
+			ensure!(sender != opponent, Error::<T>::NoFakePlay);
+
+			// Make sure opponent has no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&opponent), Error::<T>::PlayerBoardExists);
+			// Make sure responder is not also a challenger
+			ensure!(!<Challenges<T>>::contains_key(&opponent), Error::<T>::WrongChallengeTurn);
+
+			let award = AwardState { win, lose, draw };
+			Self::place_challenge(
+				&sender,
+				ChallengeStruct {
+					id: Self::take_challenge_id(),
+					opponent: Some(opponent.clone()),
+					award,
+					variant,
+					time_bank,
+					blitz: true,
+					handicap: None,
+					first_move: FirstMovePolicy::Random,
+					kind,
+					stake: None,
+					expires: Self::challenge_expiry(),
+				},
+			)?;
+			Ok(())
+		}
+
+		/// Challenge that any player may accept.
+		#[pallet::weight(T::WeightInfo::challenge())]
+		pub fn open_challenge(
+			origin: OriginFor<T>,
+			win: u32,
+			lose: u32,
+			draw: u32,
+			variant: BoardVariant,
+			time_bank: Option<MomentOf<T>>,
+			kind: GameKind,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let award = AwardState { win, lose, draw };
+			Self::place_challenge(
+				&sender,
+				ChallengeStruct {
+					id: Self::take_challenge_id(),
+					opponent: None,
+					award,
+					variant,
+					time_bank,
+					blitz: false,
+					handicap: None,
+					first_move: FirstMovePolicy::Random,
+					kind,
+					stake: None,
+					expires: Self::challenge_expiry(),
+				},
+			)?;
+			Ok(())
+		}
+
+		/// Challenge a friend to a game with the matchmaking award, board and clock, no
+		/// matter how far apart the scores are. Both players need each other on their friend
+		/// list.
+		#[pallet::weight(T::WeightInfo::challenge_friend())]
+		pub fn challenge_friend(
+			origin: OriginFor<T>,
+			friend: T::AccountId,
+			kind: GameKind,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Don't allow playing against yourself.
+			ensure!(sender != friend, Error::<T>::NoFakePlay);
+			ensure!(
+				<Friends<T>>::get(&sender).contains(&friend) &&
+					<Friends<T>>::get(&friend).contains(&sender),
+				Error::<T>::NotFriends
+			);
+
+			// Make sure friend has no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&friend), Error::<T>::PlayerBoardExists);
+			// Make sure responder is not also a challenger
+			ensure!(!<Challenges<T>>::contains_key(&friend), Error::<T>::WrongChallengeTurn);
+
+			let award = <DefaultAward<T>>::get();
+			let variant = BoardVariant::default();
+			Self::place_challenge(
+				&sender,
+				ChallengeStruct {
+					id: Self::take_challenge_id(),
+					opponent: Some(friend.clone()),
+					award,
+					variant,
+					time_bank: T::MatchmakingTimeBank::get(),
+					blitz: false,
+					handicap: None,
+					first_move: FirstMovePolicy::Random,
+					kind,
+					stake: None,
+					expires: Self::challenge_expiry(),
+				},
+			)?;
+			Ok(())
+		}
+
+		/// Response hallenge player. Accepting takes the `award` the responder agreed to, so
+		/// the challenger can't swap in worse terms right before the response.
+		#[pallet::weight(T::WeightInfo::resp_challenge())]
+		pub fn resp_challenge(
+			origin: OriginFor<T>,
+			opponent: T::AccountId,
+			accepted: bool,
+			award: AwardState,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Don't allow playing against yourself.
+			ensure!(sender != opponent, Error::<T>::NoFakePlay);
+
+			// Make sure players have no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			ensure!(!PlayerBoard::<T>::contains_key(&opponent), Error::<T>::PlayerBoardExists);
+
+			// Make sure responder is not also a challenger
+			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::WrongChallengeTurn);
+
+			let challenge =
+				Self::challenges(opponent.clone()).ok_or(Error::<T>::ChallengeNotFound)?;
+			// A targeted challenge is only for its opponent, an open one can't be rejected.
+			match &challenge.opponent {
+				Some(target) => ensure!(*target == sender, Error::<T>::NotChallenged),
+				None => {
+					ensure!(accepted, Error::<T>::NotChallenged);
+					ensure!(
+						<PlayerProfile<T>>::get(&sender).allow_open_challenges,
+						Error::<T>::OpenChallengesDisallowed
+					);
+				},
+			}
+
+			if accepted {
+				ensure!(challenge.award == award, Error::<T>::AwardMismatch);
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() <= challenge.expires,
+					Error::<T>::ChallengeExpired
+				);
+				let max_stake = <PlayerProfile<T>>::get(&sender).max_stake;
+				ensure!(
+					challenge.stake.zip(max_stake).map_or(true, |(stake, max)| stake <= max),
+					Error::<T>::StakeTooHigh
+				);
+				// Create new game
+				let challenge_id = challenge.id;
+				let board_id =
+					Self::start_challenge_game(sender.clone(), opponent.clone(), challenge)?;
+				Self::deposit_event(Event::AcceptChallenge {
+					challenge_id,
+					challenger: opponent.clone(),
+					opponent: sender,
+					board_id,
+				});
+			} else {
+				// Remove challenge
+				Self::release_challenge_stake(&opponent, &challenge);
+				Self::deposit_event(Event::RejectChallenge {
+					challenge_id: challenge.id,
+					challenger: opponent.clone(),
+					responder: sender,
+				});
+			}
+			<Challenges<T>>::remove(opponent);
+
+			Ok(())
+		}
+
+		/// Cancel Challenge
+		#[pallet::weight(T::WeightInfo::cancel_challenge())]
+		pub fn cancel_challenge(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			// Make sure players have no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			// Make sure challenger in the storage
+			let challenge_id = <Challenges<T>>::take(&sender)
+				.map(|challenge| {
+					Self::release_challenge_stake(&sender, &challenge);
+					challenge.id
+				})
+				.or_else(|| <TeamChallenges<T>>::take(&sender).map(|challenge| challenge.id))
+				.ok_or(Error::<T>::ChallengeNotFound)?;
+
+			Self::deposit_event(Event::CancelChallenge { challenge_id, challenger: sender });
+			Ok(())
+		}
+
+		/// Leave everything pending at once: the match queue, the caller's challenge, their
+		/// public table and the rematch of their last game. Deposits and stakes are released.
+		#[pallet::weight(T::WeightInfo::withdraw_all())]
+		pub fn withdraw_all(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			// Make sure players have no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			let queue = Self::dequeue(&sender);
+			if queue {
+				T::Currency::unreserve(&sender, T::QueueDeposit::get());
+			}
+			let challenge_id = <Challenges<T>>::take(&sender)
+				.map(|challenge| {
+					Self::release_challenge_stake(&sender, &challenge);
+					challenge.id
+				})
+				.or_else(|| <TeamChallenges<T>>::take(&sender).map(|challenge| challenge.id));
+			let lobby_id = <OpenLobbies<T>>::mutate(|lobbies| {
+				let index = lobbies.iter().position(|lobby| lobby.creator == sender)?;
+				let lobby = lobbies.remove(index);
+				Self::release_stake(lobby.asset, &sender, lobby.stake);
+				Some(lobby.id)
+			});
+			// The opponent can't accept a rematch without this player's entry.
+			let rematch = <Rematches<T>>::take(&sender).is_some();
+			ensure!(
+				queue || challenge_id.is_some() || lobby_id.is_some() || rematch,
+				Error::<T>::NothingToWithdraw
+			);
+
+			Self::deposit_event(Event::WithdrawnFromAll {
+				account: sender,
+				queue,
+				challenge_id,
+				lobby_id,
+				rematch,
+			});
+			Ok(())
+		}
+
+		/// Create game for two players
+		/// Moves that don't end the game are refunded down to the weight of a plain move.
+		/// Wallets can pass the `BoardStruct::moves_played` they saw as `expected_move_index`,
+		/// so a retried transaction fails with `StaleMove` if the first one already landed.
+		#[pallet::weight(
+			T::WeightInfo::play_turn().max(T::WeightInfo::play_turn_vs_bot())
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+				.saturating_add(T::WeightInfo::board_snapshot().saturating_mul(2))
+		)]
+		pub fn play_turn(
+			origin: OriginFor<T>,
+			column: u8,
+			expected_move_index: Option<u16>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::take_turn(Self::mover(sender), column, false, expected_move_index)
+		}
+
+		/// Play a move the player at turn signed off-chain, so a relayer such as a sponsor can
+		/// pay the fees for them. The signature covers the SCALE encoded `payload`, which has
+		/// to carry the current `BoardStruct::turns`. Casual games only.
+		#[pallet::weight(
+			T::WeightInfo::play_turn().max(T::WeightInfo::play_turn_vs_bot())
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+				.saturating_add(T::WeightInfo::board_snapshot().saturating_mul(2))
+				.saturating_add(T::WeightInfo::verify_relayed_move())
+		)]
+		pub fn play_turn_signed(
+			origin: OriginFor<T>,
+			payload: RelayedMoveOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let player = Self::relayed_player(&payload, &signature)?;
+			let mut post_info = Self::take_turn(player, payload.column, false, None)?;
+			post_info.actual_weight = post_info
+				.actual_weight
+				.map(|weight| weight.saturating_add(T::WeightInfo::verify_relayed_move()));
+			Ok(post_info)
+		}
+
+		/// Pop Out: take one of your stones out of the bottom of `column` instead of dropping
+		/// one. If that connects lines for both players, the opponent wins.
+		#[pallet::weight(
+			T::WeightInfo::pop_stone()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+				.saturating_add(T::WeightInfo::board_snapshot())
+		)]
+		pub fn pop_stone(origin: OriginFor<T>, column: u8) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::take_turn(Self::mover(sender), column, true, None)
+		}
+
+		/// Let `delegate`, e.g. a hot session key, play the moves of the sender's games with
+		/// `play_turn` and `pop_stone`. Stakes, challenges and everything else stay with the
+		/// sender. A delegate moves for one account at a time.
+		#[pallet::weight(T::WeightInfo::set_play_proxy())]
+		pub fn set_play_proxy(origin: OriginFor<T>, delegate: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender != delegate, Error::<T>::InvalidPlayProxy);
+			ensure!(!<PlayProxies<T>>::contains_key(&delegate), Error::<T>::PlayProxyTaken);
+
+			<PlayProxies<T>>::insert(&delegate, &sender);
+			Self::deposit_event(Event::PlayProxySet(sender, delegate));
+			Ok(())
+		}
+
+		/// Stop `delegate` from playing the sender's moves.
+		#[pallet::weight(T::WeightInfo::remove_play_proxy())]
+		pub fn remove_play_proxy(origin: OriginFor<T>, delegate: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				<PlayProxies<T>>::get(&delegate) == Some(sender.clone()),
+				Error::<T>::NoPlayProxy
+			);
+
+			<PlayProxies<T>>::remove(&delegate);
+			Self::deposit_event(Event::PlayProxyRemoved(sender, delegate));
+			Ok(())
+		}
+
+		/// Check that dropping a stone into `column` is a legal move for you right now, without
+		/// playing it. The row it would land on is in the `MoveChecked` event. Failed checks
+		/// are free, the `ConnectFourApi::check_move` runtime API answers without a transaction.
+		#[pallet::weight(T::WeightInfo::check_move())]
+		pub fn check_move(origin: OriginFor<T>, column: u8) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let row =
+				Self::dry_run_move(&sender, column).map_err(|error| DispatchErrorWithPostInfo {
+					post_info: Pays::No.into(),
+					error: Error::<T>::from(error).into(),
+				})?;
+			Self::deposit_event(Event::MoveChecked(sender, column, row));
+			Ok(().into())
+		}
+
+		/// Commit to the next column of a blitz game, as `hash((column, salt))`.
+		#[pallet::weight(T::WeightInfo::commit_move())]
+		pub fn commit_move(origin: OriginFor<T>, commitment: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board_id = Self::player_board_id(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+			let mut board = Self::boards(&board_id).ok_or(Error::<T>::NoPlayerBoard)?;
+			let round = board.blitz.as_mut().ok_or(Error::<T>::NotBlitzGame)?;
+			ensure!(round.phase == BlitzPhase::Commit, Error::<T>::WrongBlitzPhase);
+
+			let commit =
+				if sender == board.red { &mut round.red_commit } else { &mut round.blue_commit };
+			ensure!(commit.is_none(), Error::<T>::AlreadyMoved);
+			*commit = Some(commitment);
+
+			// Reveals start once both players committed.
+			if round.red_commit.is_some() && round.blue_commit.is_some() {
+				round.phase = BlitzPhase::Reveal;
+				Self::start_turn(&mut board);
+			}
+			<Boards<T>>::insert(board_id, board);
+
+			Self::deposit_event(Event::MoveCommitted(board_id, sender));
+			Ok(())
+		}
+
+		/// Reveal the column committed to in this blitz round. The stones are dropped once
+		/// both players revealed.
+		#[pallet::weight(
+			T::WeightInfo::reveal_move()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+				.saturating_add(T::WeightInfo::board_snapshot().saturating_mul(2))
+		)]
+		pub fn reveal_move(origin: OriginFor<T>, column: u8, salt: [u8; 32]) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board_id = Self::player_board_id(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+			let mut board = Self::boards(&board_id).ok_or(Error::<T>::NoPlayerBoard)?;
+			ensure!(column < board.variant.width, Error::<T>::ColumnOutOfRange);
+			let round = board.blitz.as_mut().ok_or(Error::<T>::NotBlitzGame)?;
+			ensure!(round.phase == BlitzPhase::Reveal, Error::<T>::WrongBlitzPhase);
+
+			let (commit, revealed) = if sender == board.red {
+				(round.red_commit, &mut round.red_column)
+			} else {
+				(round.blue_commit, &mut round.blue_column)
+			};
+			ensure!(revealed.is_none(), Error::<T>::AlreadyMoved);
+			ensure!(
+				commit == Some(T::Hashing::hash_of(&(column, salt))),
+				Error::<T>::CommitmentMismatch
+			);
+			*revealed = Some(column);
+			Self::deposit_event(Event::MoveRevealed(board_id, sender, column));
+
+			match (round.red_column, round.blue_column) {
+				(Some(red_column), Some(blue_column)) =>
+					Self::play_blitz_round(board, red_column, blue_column),
+				_ => {
+					<Boards<T>>::insert(board_id, board);
+					Ok(())
+				},
+			}
+		}
+
+		/// Win a game whose opponent ran out of time on their clock, or is stalling the last
+		/// turn `MaxStalledTurns` allows them to.
+		#[pallet::weight(
+			T::WeightInfo::claim_timeout()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+		)]
+		pub fn claim_timeout(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(PlayerBoard::<T>::contains_key(&sender), Error::<T>::NoPlayerBoard);
+			let board_id = Self::player_board(&sender);
+			let mut board = Self::boards(&board_id).ok_or(Error::<T>::NoPlayerBoard)?;
+			ensure!(
+				board.board_state == BoardState::Running,
+				"Board is not running, check if already finished."
+			);
+
+			// In blitz games the late player is the one still to commit or reveal.
+			let late_player = match &board.blitz {
+				Some(round) => {
+					let player = board.team_of(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+					let opponent = if player == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+					ensure!(
+						round.has_moved(player) && !round.has_moved(opponent),
+						Error::<T>::CannotClaimOwnTimeout
+					);
+					opponent
+				},
+				// An offered double waits for the opponent's answer.
+				None => match (board.cube, <FirstMoveDraws<T>>::get(board_id)) {
+					(DoublingCube::Offered(PLAYER_1), _) => PLAYER_2,
+					(DoublingCube::Offered(_), _) => PLAYER_1,
+					// Before the first move, the late player is the one still to reveal.
+					(_, Some(draw)) => {
+						let player = board.team_of(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+						let opponent = if player == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+						ensure!(
+							draw.has_revealed(player) && !draw.has_revealed(opponent),
+							Error::<T>::CannotClaimOwnTimeout
+						);
+						opponent
+					},
+					_ => board.next_player,
+				},
+			};
+			ensure!(board.team_of(&sender) != Some(late_player), Error::<T>::CannotClaimOwnTimeout);
+
+			let elapsed = T::GameClock::now().saturating_sub(board.last_turn);
+			// A player on their last allowed stall loses without running out of time.
+			let reason = if Self::stalls_out(&board, late_player, elapsed) {
+				FinishReason::Stalled
+			} else {
+				let time_banks = board.time_banks.ok_or(Error::<T>::NoTimeControl)?;
+				let bank = if late_player == PLAYER_1 { time_banks.red } else { time_banks.blue };
+				ensure!(elapsed > bank, Error::<T>::ClockNotExpired);
+				FinishReason::Timeout
+			};
+
+			board.board_state = BoardState::Finished(Some(sender));
+			Self::finish_game(board, reason);
+			Ok(())
+		}
+
+		/// Offer to double the stakes of your staked game on your turn, holding the doubled
+		/// stake right away. The opponent either accepts and owns the cube from then on, or
+		/// declines and loses at the current stakes, see `respond_double`. Either player may
+		/// double while the cube is centered, afterwards only its owner.
+		#[pallet::weight(T::WeightInfo::offer_double())]
+		pub fn offer_double(origin: OriginFor<T>, board_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			let player = board.team_of(&sender).ok_or(Error::<T>::NotOnBoard)?;
+			let stake = <BoardStakes<T>>::get(board_id).ok_or(Error::<T>::NotStaked)?;
+			ensure!(
+				board.board_state == BoardState::Running &&
+					board.blitz.is_none() &&
+					player == board.next_player &&
+					(board.cube == DoublingCube::Centered ||
+						board.cube == DoublingCube::Owned(player)),
+				Error::<T>::CannotDouble
+			);
+
+			Self::hold_stake(<BoardStakeAssets<T>>::get(board_id), &sender, stake)?;
+			board.cube = DoublingCube::Offered(player);
+			<Boards<T>>::insert(board_id, board);
+			Self::deposit_event(Event::DoubleOffered(
+				board_id,
+				sender,
+				stake.saturating_add(stake),
+			));
+			Ok(())
+		}
+
+		/// Answer the opponent's offer to double the stakes. Accepting holds the doubled stake
+		/// and hands you the cube, declining loses the game at the current stakes.
+		#[pallet::weight(
+			T::WeightInfo::respond_double()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+		)]
+		pub fn respond_double(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			accepted: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			let player = board.team_of(&sender).ok_or(Error::<T>::NotOnBoard)?;
+			let opponent = if player == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+			ensure!(board.cube == DoublingCube::Offered(opponent), Error::<T>::NoDoubleOffer);
+			let stake = <BoardStakes<T>>::get(board_id).ok_or(Error::<T>::NotStaked)?;
+
+			if !accepted {
+				let winner = board.account_at_turn(opponent).clone();
+				board.board_state = BoardState::Finished(Some(winner));
+				Self::deposit_event(Event::DoubleDeclined(board_id, sender));
+				Self::finish_game(board, FinishReason::DoubleDeclined);
+				return Ok(())
+			}
+
+			Self::hold_stake(<BoardStakeAssets<T>>::get(board_id), &sender, stake)?;
+			let doubled = stake.saturating_add(stake);
+			<BoardStakes<T>>::insert(board_id, doubled);
+			board.cube = DoublingCube::Owned(player);
+			<Boards<T>>::insert(board_id, board);
+			Self::deposit_event(Event::DoubleAccepted(board_id, sender, doubled));
+			Ok(())
+		}
+
+		/// Commit to `hash(salt)` for the first mover draw of your next commit-reveal game,
+		/// replacing an earlier commitment. Challengers commit before challenging, their
+		/// opponents before accepting, see `FirstMovePolicy::CommitReveal`.
+		#[pallet::weight(T::WeightInfo::commit_first_move())]
+		pub fn commit_first_move(origin: OriginFor<T>, commitment: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<FirstMoveCommits<T>>::insert(&sender, commitment);
+			Self::deposit_event(Event::FirstMoveCommitted(sender));
+			Ok(())
+		}
+
+		/// Reveal the salt committed to for the first mover draw of a commit-reveal game. The
+		/// game starts once both salts are revealed, a player not revealing in time loses like
+		/// one running out of time.
+		#[pallet::weight(T::WeightInfo::reveal_first_move())]
+		pub fn reveal_first_move(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			let player = board.team_of(&sender).ok_or(Error::<T>::NotOnBoard)?;
+			let mut draw = <FirstMoveDraws<T>>::get(board_id).ok_or(Error::<T>::NoFirstMoveDraw)?;
+			let (commit, revealed) = if player == PLAYER_1 {
+				(draw.red_commit, &mut draw.red_salt)
+			} else {
+				(draw.blue_commit, &mut draw.blue_salt)
+			};
+			ensure!(revealed.is_none(), Error::<T>::AlreadyMoved);
+			ensure!(commit == T::Hashing::hash_of(&salt), Error::<T>::CommitmentMismatch);
+			*revealed = Some(salt);
+			Self::deposit_event(Event::FirstMoveRevealed(board_id, sender));
+
+			let (red_salt, blue_salt) = match (draw.red_salt, draw.blue_salt) {
+				(Some(red_salt), Some(blue_salt)) => (red_salt, blue_salt),
+				_ => {
+					<FirstMoveDraws<T>>::insert(board_id, draw);
+					return Ok(())
+				},
+			};
+			let seed = T::Hashing::hash_of(&(red_salt, blue_salt));
+			board.next_player = if seed.as_ref()[0] < 128 { PLAYER_1 } else { PLAYER_2 };
+			board.first_player = board.next_player;
+			board.position = Self::position_hash(&board.board, board.next_player);
+			Self::start_turn(&mut board);
+			let first = board.account_at_turn(board.next_player).clone();
+			<FirstMoveDraws<T>>::remove(board_id);
+			<Boards<T>>::insert(board_id, board);
+			Self::deposit_event(Event::FirstMoveDrawn(board_id, first));
+			Ok(())
+		}
+
+		/// Pie rule: take over the side of the first mover instead of replying to their
+		/// opening move, they play the next stone with the other color. Open to the player at
+		/// turn after the first move of a `FirstMovePolicy::PieRule` game, once per game. The
+		/// time taken to decide is charged to the caller, whose time bank moves along.
+		#[pallet::weight(T::WeightInfo::swap_sides())]
+		pub fn swap_sides(origin: OriginFor<T>, board_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			ensure!(board.board_state == BoardState::Running, Error::<T>::GameNotRunning);
+			// Teams, bots, blitz rounds and handicaps have no plain opening to take over, and a
+			// doubled cube was offered for the sides as they were.
+			ensure!(
+				board.first_move == FirstMovePolicy::PieRule &&
+					board.moves_played == 1 &&
+					!board.sides_swapped &&
+					board.cube == DoublingCube::Centered &&
+					board.bot.is_none() &&
+					board.red_mate.is_none() &&
+					board.blitz.is_none() &&
+					board.handicap.is_none(),
+				Error::<T>::CannotSwapSides
+			);
+			ensure!(*board.account_at_turn(board.next_player) == sender, Error::<T>::NotPlayerTurn);
+
+			let elapsed = T::GameClock::now().saturating_sub(board.last_turn);
+			if let Some(time_banks) = board.time_banks.as_mut() {
+				let bank = if board.next_player == PLAYER_1 {
+					&mut time_banks.red
+				} else {
+					&mut time_banks.blue
+				};
+				ensure!(elapsed <= *bank, Error::<T>::OutOfTime);
+				*bank = bank.saturating_sub(elapsed);
+				sp_std::mem::swap(&mut time_banks.red, &mut time_banks.blue);
+			}
+			sp_std::mem::swap(&mut board.red, &mut board.blue);
+			board.stalled_turns = (board.stalled_turns.1, board.stalled_turns.0);
+			board.sides_swapped = true;
+			Self::start_turn(&mut board);
+			let deadline = board.deadline;
+			<Boards<T>>::insert(board_id, board);
+			Self::deposit_event(Event::SidesSwapped(board_id, sender, deadline));
+			Ok(())
+		}
+
+		/// Call off your game before its second move, e.g. after being paired with the wrong
+		/// opponent. Stakes, deposits and predictions are returned and scores are left alone.
+		#[pallet::weight(
+			T::WeightInfo::abort_game()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+		)]
+		pub fn abort_game(origin: OriginFor<T>, reason: AbortReason) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board_id = Self::player_board_id(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+			let mut board = Self::boards(board_id).ok_or(Error::<T>::NoPlayerBoard)?;
+			ensure!(
+				board.moves_played < 2 && !<TournamentBoards<T>>::contains_key(board_id),
+				Error::<T>::AbortNotAllowed
+			);
+
+			board.board_state = BoardState::Finished(None);
+			Self::finish_game(board, FinishReason::Aborted);
+			Self::deposit_event(Event::GameAborted(board_id, sender, reason));
+			Ok(())
+		}
+
+		/// Report the opponent on a running board for unfair play. Once the game ends, its
+		/// scores and stakes are held back until the admin origin, or an oracle told through
+		/// `ReportHandler`, rules on it.
+		#[pallet::weight(T::WeightInfo::report_opponent())]
+		pub fn report_opponent(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			reason: ReportReason,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			let team = board.team_of(&sender).ok_or(Error::<T>::NotOnBoard)?;
+			ensure!(!<Reports<T>>::contains_key(board_id), Error::<T>::AlreadyReported);
+
+			let reported = if team == PLAYER_1 { &board.blue } else { &board.red };
+			T::ReportHandler::on_game_reported(board_id, &sender, reported, reason);
+			<Reports<T>>::insert(board_id, Report { reporter: sender.clone(), reason });
+			Self::deposit_event(Event::GameReported(board_id, sender, reason));
+			Ok(())
+		}
+
+		/// End a game nobody moved on for `StaleGameAge` as a timeout of the player at
+		/// turn. Submitted unsigned by the off-chain worker.
+		#[pallet::weight(
+			T::WeightInfo::expire_stale_game()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+		)]
+		pub fn expire_stale_game(origin: OriginFor<T>, board_id: T::Hash) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let mut board = Self::boards(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			ensure!(Self::is_stale(&board), Error::<T>::GameNotStale);
+
+			let winner = Self::stalling_player(&board).map(|player| {
+				if player == PLAYER_1 {
+					board.blue.clone()
+				} else {
+					board.red.clone()
+				}
+			});
+			board.board_state = BoardState::Finished(winner);
+			Self::finish_game(board, FinishReason::Timeout);
+			Ok(())
+		}
+
+		/// Challenge two players to a doubles game, played together with `teammate`.
+		/// The game starts once the teammate and both opponents accepted.
+		#[pallet::weight(T::WeightInfo::challenge_team())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn challenge_team(
+			origin: OriginFor<T>,
+			teammate: T::AccountId,
+			opponent: T::AccountId,
+			opponent_mate: T::AccountId,
+			win: u32,
+			lose: u32,
+			draw: u32,
+			variant: BoardVariant,
+			time_bank: Option<MomentOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+			ensure!(
+				sender != teammate &&
+					sender != opponent &&
+					sender != opponent_mate &&
+					teammate != opponent &&
+					teammate != opponent_mate &&
+					opponent != opponent_mate,
+				Error::<T>::DuplicateTeamPlayer
+			);
+			ensure!(
+				variant.is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+				Error::<T>::InvalidVariant
+			);
+			ensure!(time_bank.map_or(true, |bank| !bank.is_zero()), Error::<T>::InvalidTimeBank);
+
+			// Make sure challenger has no board open and doesn't re-challenge.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::ReChallengeError);
+			ensure!(!<TeamChallenges<T>>::contains_key(&sender), Error::<T>::ReChallengeError);
+
+			let id = Self::take_challenge_id();
+			let challenge = TeamChallengeStruct {
+				id,
+				teammate: teammate.clone(),
+				opponent: opponent.clone(),
+				opponent_mate: opponent_mate.clone(),
+				award: AwardState { win, lose, draw },
+				variant,
+				time_bank,
+				accepted: Default::default(),
+			};
+			<TeamChallenges<T>>::insert(&sender, challenge);
+			Self::deposit_event(Event::TeamChallenge(
+				id,
+				sender,
+				teammate,
+				opponent,
+				opponent_mate,
+			));
+			Ok(())
+		}
+
+		/// Respond to a doubles challenge, rejecting it cancels the challenge for everyone.
+		#[pallet::weight(T::WeightInfo::resp_team_challenge())]
+		pub fn resp_team_challenge(
+			origin: OriginFor<T>,
+			challenger: T::AccountId,
+			accepted: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut challenge =
+				<TeamChallenges<T>>::get(&challenger).ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(
+				[&challenge.teammate, &challenge.opponent, &challenge.opponent_mate]
+					.contains(&&sender),
+				Error::<T>::NotChallenged
+			);
+
+			if !accepted {
+				<TeamChallenges<T>>::remove(&challenger);
+				Self::deposit_event(Event::RejectChallenge {
+					challenge_id: challenge.id,
+					challenger,
+					responder: sender,
+				});
+				return Ok(())
+			}
+
+			ensure!(!challenge.accepted.contains(&sender), Error::<T>::AlreadyAccepted);
+			challenge.accepted.try_push(sender).map_err(|_| Error::<T>::AlreadyAccepted)?;
+			if challenge.accepted.len() < 3 {
+				<TeamChallenges<T>>::insert(&challenger, challenge);
+				return Ok(())
+			}
+
+			// Everybody agreed, all four players need to be free to play.
+			for player in
+				[&challenger, &challenge.teammate, &challenge.opponent, &challenge.opponent_mate]
+			{
+				ensure!(!PlayerBoard::<T>::contains_key(player), Error::<T>::PlayerBoardExists);
+			}
+			<TeamChallenges<T>>::remove(&challenger);
+			let board_id = Self::new_game(
+				challenger.clone(),
+				challenge.opponent.clone(),
+				challenge.award,
+				challenge.variant,
+				challenge.time_bank,
+				GameMode::Doubles(challenge.teammate, challenge.opponent_mate),
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+			)?;
+			Self::deposit_event(Event::AcceptChallenge {
+				challenge_id: challenge.id,
+				challenger,
+				opponent: challenge.opponent,
+				board_id,
+			});
+			Ok(())
+		}
+
+		/// Start an unranked game against the pallet, which answers every move right away.
+		#[pallet::weight(T::WeightInfo::play_vs_bot())]
+		pub fn play_vs_bot(origin: OriginFor<T>, difficulty: BotDifficulty) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			// Make sure player has no board open.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			// Make sure not a challenger
+			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::ReChallengeError);
+			// Make sure player isn't waiting for a game
+			ensure!(!Self::is_queued(&sender), Error::<T>::MatchQueueError);
+
+			let award = AwardState { win: 0, lose: 0, draw: 0 };
+			let _board_id = Self::new_game(
+				sender,
+				Self::bot_account(),
+				award,
+				BoardVariant::default(),
+				None,
+				GameMode::Bot(difficulty),
+				FirstMovePolicy::Random,
+				GameKind::Casual,
+			)?;
+			Ok(())
+		}
+
+		/// Follow a running board.
+		#[pallet::weight(T::WeightInfo::watch_board())]
+		pub fn watch_board(origin: OriginFor<T>, board_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Boards<T>>::contains_key(board_id), Error::<T>::BoardNotFound);
+			<Spectators<T>>::try_mutate(board_id, |spectators| {
+				ensure!(!spectators.contains(&sender), Error::<T>::AlreadyWatching);
+				spectators.try_push(sender).map_err(|_| Error::<T>::TooManySpectators)
+			})?;
+			Ok(())
+		}
+
+		/// Stop following a board.
+		#[pallet::weight(T::WeightInfo::unwatch_board())]
+		pub fn unwatch_board(origin: OriginFor<T>, board_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<Spectators<T>>::try_mutate_exists(board_id, |maybe_spectators| {
+				let spectators = maybe_spectators.as_mut().ok_or(Error::<T>::NotWatching)?;
+				let index = spectators
+					.iter()
+					.position(|spectator| *spectator == sender)
+					.ok_or(Error::<T>::NotWatching)?;
+				spectators.remove(index);
+				if spectators.is_empty() {
+					*maybe_spectators = None;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			Ok(())
+		}
+
+		/// Choose what the events of a board you play on tell about its moves. With
+		/// `EventVerbosity::FullBoard` every move also emits a `BoardSnapshot`, which the
+		/// player making the move pays for with extra weight.
+		#[pallet::weight(T::WeightInfo::set_event_verbosity())]
+		pub fn set_event_verbosity(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			verbosity: EventVerbosity,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<Boards<T>>::try_mutate(board_id, |maybe_board| {
+				let board = maybe_board.as_mut().ok_or(Error::<T>::BoardNotFound)?;
+				ensure!(board.team_of(&sender).is_some(), Error::<T>::NotAtBoard);
+				board.verbosity = verbosity;
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::EventVerbositySet(board_id, verbosity));
+			Ok(())
+		}
+
+		/// Send a predefined message to a running board you play on or watch. Nothing is
+		/// stored but the time of the emote, UIs pick it up from the `BoardEmote` event.
+		#[pallet::weight(T::WeightInfo::send_emote())]
+		pub fn send_emote(origin: OriginFor<T>, board_id: T::Hash, emote: Emote) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board = Self::boards(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			ensure!(
+				board.team_of(&sender).is_some() ||
+					<Spectators<T>>::get(board_id).contains(&sender),
+				Error::<T>::NotAtBoard
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				<LastEmote<T>>::get(&sender)
+					.map_or(true, |last| now >= last.saturating_add(T::EmoteCooldown::get())),
+				Error::<T>::EmoteTooSoon
+			);
+			<LastEmote<T>>::insert(&sender, now);
+
+			Self::deposit_event(Event::BoardEmote(board_id, sender, emote));
+			Ok(())
+		}
+
+		/// Stake on the outcome of a board before its first move, `winner` is `None` to
+		/// predict a draw. When the game ends, correct predictions share the losing stakes by
+		/// their own stake, less the house fee. Everyone gets their stake back if nobody was
+		/// right or the game ended without a result.
+		#[pallet::weight(T::WeightInfo::predict())]
+		pub fn predict(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			winner: Option<T::AccountId>,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			ensure!(board.team_of(&sender).is_none(), Error::<T>::PlayerCannotPredict);
+			if let Some(winner) = &winner {
+				ensure!(board.team_of(winner).is_some(), Error::<T>::NotOnBoard);
+			}
+			ensure!(board.moves_played == 0, Error::<T>::PredictionsClosed);
+			ensure!(stake >= T::Currency::minimum_balance(), Error::<T>::StakeTooLow);
+
+			<Predictions<T>>::try_mutate(board_id, |predictions| {
+				ensure!(
+					!predictions.iter().any(|prediction| prediction.predictor == sender),
+					Error::<T>::AlreadyPredicted
+				);
+				predictions
+					.try_push(PredictionStruct {
+						predictor: sender.clone(),
+						winner: winner.clone(),
+						stake,
+					})
+					.map_err(|_| Error::<T>::TooManyPredictions)?;
+				T::Currency::reserve(&sender, stake)
+			})?;
+
+			Self::deposit_event(Event::Predicted(board_id, sender, winner, stake));
+			Ok(())
+		}
+
+		/// Add an account to your friend list.
+		#[pallet::weight(T::WeightInfo::add_friend())]
+		pub fn add_friend(origin: OriginFor<T>, friend: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender != friend, Error::<T>::NoFakePlay);
+			ensure!(!<Blocked<T>>::get(&sender).contains(&friend), Error::<T>::PlayerBlocked);
+
+			<Friends<T>>::try_mutate(&sender, |friends| {
+				ensure!(!friends.contains(&friend), Error::<T>::AlreadyFriends);
+				friends.try_push(friend).map_err(|_| Error::<T>::TooManyFriends)
+			})?;
+			Ok(())
+		}
+
+		/// Remove an account from your friend list.
+		#[pallet::weight(T::WeightInfo::remove_friend())]
+		pub fn remove_friend(origin: OriginFor<T>, friend: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<Friends<T>>::try_mutate_exists(&sender, |maybe_friends| {
+				let friends = maybe_friends.as_mut().ok_or(Error::<T>::NotFriends)?;
+				let index = friends
+					.iter()
+					.position(|account| *account == friend)
+					.ok_or(Error::<T>::NotFriends)?;
+				friends.remove(index);
+				if friends.is_empty() {
+					*maybe_friends = None;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			Ok(())
+		}
+
+		/// Never get paired with or challenged by an account again. It's removed from your
+		/// friend list.
+		#[pallet::weight(T::WeightInfo::block_player())]
+		pub fn block_player(origin: OriginFor<T>, player: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender != player, Error::<T>::NoFakePlay);
+
+			<Blocked<T>>::try_mutate(&sender, |blocked| {
+				ensure!(!blocked.contains(&player), Error::<T>::AlreadyBlocked);
+				blocked.try_push(player.clone()).map_err(|_| Error::<T>::TooManyBlocked)
+			})?;
+			<Friends<T>>::mutate_exists(&sender, |maybe_friends| {
+				if let Some(friends) = maybe_friends {
+					friends.retain(|account| *account != player);
+					if friends.is_empty() {
+						*maybe_friends = None;
+					}
+				}
+			});
+			Ok(())
+		}
+
+		/// Remove an account from your block list.
+		#[pallet::weight(T::WeightInfo::unblock_player())]
+		pub fn unblock_player(origin: OriginFor<T>, player: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<Blocked<T>>::try_mutate_exists(&sender, |maybe_blocked| {
+				let blocked = maybe_blocked.as_mut().ok_or(Error::<T>::NotBlocked)?;
+				let index = blocked
+					.iter()
+					.position(|account| *account == player)
+					.ok_or(Error::<T>::NotBlocked)?;
+				blocked.remove(index);
+				if blocked.is_empty() {
+					*maybe_blocked = None;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			Ok(())
+		}
+
+		/// Set the game preferences of the sender: the board variant matchmaking pairs them on,
+		/// the highest stake they put up at a public table, whether they join open challenges
+		/// and public tables at all, the score gap up to which they accept challenges right
+		/// away, and a display name. The default profile clears the entry.
+		#[pallet::weight(T::WeightInfo::set_profile())]
+		pub fn set_profile(
+			origin: OriginFor<T>,
+			preferred_variant: Option<BoardVariant>,
+			max_stake: Option<BalanceOf<T>>,
+			allow_open_challenges: bool,
+			auto_accept_within: Option<u32>,
+			display_name: Vec<u8>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Matchmaking creates games on the preferred variant.
+			ensure!(
+				preferred_variant.map_or(true, |variant| variant
+					.is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get())),
+				Error::<T>::InvalidVariant
+			);
+			let display_name =
+				BoundedVec::try_from(display_name).map_err(|_| Error::<T>::DisplayNameTooLong)?;
+
+			let profile = ProfileStruct {
+				preferred_variant,
+				max_stake,
+				allow_open_challenges,
+				auto_accept_within,
+				display_name,
+			};
+			if profile == ProfileOf::<T>::default() {
+				<PlayerProfile<T>>::remove(&sender);
+			} else {
+				<PlayerProfile<T>>::insert(&sender, profile);
+			}
+
+			Self::deposit_event(Event::ProfileUpdated(sender));
+			Ok(())
+		}
+
+		/// Ask the opponent to take back the last move, which has to be one of your own.
+		/// Only possible between people in casual games or games with no award at stake.
+		#[pallet::weight(T::WeightInfo::request_undo())]
+		pub fn request_undo(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board_id = Self::player_board_id(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+			let board = Self::boards(&board_id).ok_or(Error::<T>::NoPlayerBoard)?;
+			ensure!(Self::undo_allowed(&board), Error::<T>::UndoNotAllowed);
+			ensure!(!<UndoRequests<T>>::contains_key(board_id), Error::<T>::UndoAlreadyRequested);
+
+			// The last stone has to be one of the sender's team.
+			let column = board.last_move.ok_or(Error::<T>::NothingToUndo)?;
+			let last_player = Logic::top_stone(&board.board, column);
+			ensure!(last_player == board.team_of(&sender), Error::<T>::NothingToUndo);
+
+			<UndoRequests<T>>::insert(board_id, &sender);
+			Self::deposit_event(Event::UndoRequested(board_id, sender));
+			Ok(())
+		}
+
+		/// Answer the opponent's request to take back their last move.
+		#[pallet::weight(T::WeightInfo::respond_undo())]
+		pub fn respond_undo(origin: OriginFor<T>, accept: bool) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let board_id = Self::player_board_id(&sender).ok_or(Error::<T>::NoPlayerBoard)?;
+			let mut board = Self::boards(&board_id).ok_or(Error::<T>::NoPlayerBoard)?;
+			let requester = <UndoRequests<T>>::get(board_id).ok_or(Error::<T>::NoUndoRequest)?;
+			ensure!(board.team_of(&sender) != board.team_of(&requester), Error::<T>::NoUndoRequest);
+			<UndoRequests<T>>::remove(board_id);
+
+			if !accept {
+				Self::deposit_event(Event::UndoRejected(board_id, sender));
+				return Ok(())
+			}
+
+			let column = board.last_move.take().ok_or(Error::<T>::NothingToUndo)?;
+			let player =
+				Logic::remove_stone(&mut board.board, column).ok_or(Error::<T>::WrongLogic)?;
+			<MoveLogs<T>>::mutate(board_id, |log| {
+				if let Some(moves) = log {
+					moves.pop();
+				}
+			});
+			child::kill(&Self::game_trie(board_id), &board.moves_played.encode());
+			// The player that made the move is at turn again.
+			board.next_player = player;
+			board.moves_played = board.moves_played.saturating_sub(1);
+			board.position = Self::position_hash(&board.board, board.next_player);
+			Self::start_turn(&mut board);
+			<Boards<T>>::insert(board_id, board);
+
+			Self::deposit_event(Event::MoveUndone(board_id, column));
+			Ok(())
+		}
+
+		/// Offer a game at a public table, any account may join it. `stake` is reserved from
+		/// both players and goes to the winner. It's in `asset`, or in the native token for
+		/// `None`.
+		#[pallet::weight(T::WeightInfo::create_open_game())]
+		pub fn create_open_game(
+			origin: OriginFor<T>,
+			award: AwardState,
+			stake: BalanceOf<T>,
+			asset: Option<AssetIdOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+			// Make sure player has no board open and doesn't wait at another table.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			let mut lobbies = <OpenLobbies<T>>::get();
+			ensure!(
+				!lobbies.iter().any(|lobby| lobby.creator == sender),
+				Error::<T>::AlreadyInLobby
+			);
+
+			let id = <NextLobbyId<T>>::get();
+			let lobby =
+				LobbyStruct { id, creator: sender.clone(), award: award.clone(), stake, asset };
+			lobbies.try_push(lobby).map_err(|_| Error::<T>::TooManyLobbies)?;
+			Self::hold_stake(asset, &sender, stake)?;
+			<OpenLobbies<T>>::put(lobbies);
+			<NextLobbyId<T>>::put(id.wrapping_add(1));
+
+			Self::deposit_event(Event::LobbyOpened(id, sender, award, stake, asset));
+			Ok(())
+		}
+
+		/// Join the game waiting at a public table, which starts right away. `asset` is the
+		/// asset the caller stakes, `None` for the native token, and has to be the table's.
+		#[pallet::weight(T::WeightInfo::join_open_game())]
+		pub fn join_open_game(
+			origin: OriginFor<T>,
+			lobby_id: u32,
+			asset: Option<AssetIdOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut lobbies = <OpenLobbies<T>>::get();
+			let index = lobbies
+				.iter()
+				.position(|lobby| lobby.id == lobby_id)
+				.ok_or(Error::<T>::LobbyNotFound)?;
+			let lobby = lobbies.remove(index);
+			// Don't allow playing against yourself.
+			ensure!(sender != lobby.creator, Error::<T>::NoFakePlay);
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			ensure!(!PlayerBoard::<T>::contains_key(&lobby.creator), Error::<T>::PlayerBoardExists);
+			let profile = <PlayerProfile<T>>::get(&sender);
+			ensure!(profile.allow_open_challenges, Error::<T>::OpenChallengesDisallowed);
+			ensure!(lobby.asset == asset, Error::<T>::StakeAssetMismatch);
+			ensure!(
+				asset.is_some() ||
+					profile.max_stake.map_or(true, |max_stake| lobby.stake <= max_stake),
+				Error::<T>::StakeTooHigh
+			);
+
+			Self::hold_stake(asset, &sender, lobby.stake)?;
+			let board_id = Self::create_game(
+				lobby.creator,
+				sender,
+				lobby.award,
+				BoardVariant::default(),
+				T::MatchmakingTimeBank::get(),
+			)?;
+			if !lobby.stake.is_zero() {
+				<BoardStakes<T>>::insert(board_id, lobby.stake);
+				if let Some(asset) = asset {
+					<BoardStakeAssets<T>>::insert(board_id, asset);
+				}
+			}
+			<OpenLobbies<T>>::put(lobbies);
+
+			Self::deposit_event(Event::LobbyClosed(lobby_id));
+			Ok(())
+		}
+
+		/// Leave the public table, the stake is released.
+		#[pallet::weight(T::WeightInfo::close_open_game())]
+		pub fn close_open_game(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut lobbies = <OpenLobbies<T>>::get();
+			let index = lobbies
+				.iter()
+				.position(|lobby| lobby.creator == sender)
+				.ok_or(Error::<T>::LobbyNotFound)?;
+			let lobby = lobbies.remove(index);
+			Self::release_stake(lobby.asset, &sender, lobby.stake);
+			<OpenLobbies<T>>::put(lobbies);
+
+			Self::deposit_event(Event::LobbyClosed(lobby.id));
+			Ok(())
+		}
+
+		/// Create a game only the holder of the invite code can join, given by its `code_hash`
+		/// under the runtime's hashing. It waits `PrivateGameLifetime` blocks, the stake of the
+		/// `settings` is reserved until then.
+		#[pallet::weight(T::WeightInfo::create_private_game())]
+		pub fn create_private_game(
+			origin: OriginFor<T>,
+			code_hash: T::Hash,
+			settings: PrivateGameSettingsOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+			ensure!(
+				settings.variant.is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+				Error::<T>::InvalidVariant
+			);
+			ensure!(
+				settings.time_bank.map_or(true, |bank| !bank.is_zero()),
+				Error::<T>::InvalidTimeBank
+			);
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			ensure!(!<PrivateGames<T>>::contains_key(code_hash), Error::<T>::PrivateGameExists);
+
+			let expires = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::PrivateGameLifetime::get());
+			<PrivateGameExpiries<T>>::try_mutate(expires, |expiring| {
+				expiring.try_push(code_hash).map_err(|_| Error::<T>::TooManyPrivateGames)
+			})?;
+			if let Some(stake) = settings.stake {
+				T::Currency::reserve(&sender, stake)?;
+			}
+			let game = PrivateGameStruct { creator: sender.clone(), settings, expires };
+			<PrivateGames<T>>::insert(code_hash, game);
+
+			Self::deposit_event(Event::PrivateGameCreated(code_hash, sender, expires));
+			Ok(())
+		}
+
+		/// Join the private game of the invite `code`, which starts right away with the caller
+		/// playing red.
+		#[pallet::weight(T::WeightInfo::join_private_game())]
+		pub fn join_private_game(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let code_hash = T::Hashing::hash(&code);
+			let game = <PrivateGames<T>>::get(code_hash).ok_or(Error::<T>::PrivateGameNotFound)?;
+			// Don't allow playing against yourself.
+			ensure!(sender != game.creator, Error::<T>::NoFakePlay);
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			ensure!(!PlayerBoard::<T>::contains_key(&game.creator), Error::<T>::PlayerBoardExists);
+			ensure!(!Self::is_blocked(&game.creator, &sender), Error::<T>::PlayerBlocked);
+			let settings = game.settings;
+			let stake = settings.stake.filter(|stake| !stake.is_zero());
+			if let Some(stake) = stake {
+				let max_stake = <PlayerProfile<T>>::get(&sender).max_stake;
+				ensure!(
+					max_stake.map_or(true, |max_stake| stake <= max_stake),
+					Error::<T>::StakeTooHigh
+				);
+				T::Currency::reserve(&sender, stake)?;
+			}
+
+			let board_id = Self::new_game(
+				sender,
+				game.creator,
+				settings.award,
+				settings.variant,
+				settings.time_bank,
+				GameMode::Standard,
+				FirstMovePolicy::Random,
+				settings.kind,
+			)?;
+			if let Some(stake) = stake {
+				<BoardStakes<T>>::insert(board_id, stake);
+			}
+			<PrivateGames<T>>::remove(code_hash);
+
+			Self::deposit_event(Event::PrivateGameJoined(code_hash, board_id));
+			Ok(())
+		}
+
+		/// Open a Swiss-system tournament of `rounds` rounds for registration. Players register
+		/// until `check_in` and have to check in before `start`, players that don't are dropped
+		/// and their `entry_fee` is refunded if `refund_no_shows` is set, slashed otherwise.
+		#[pallet::weight(T::WeightInfo::create_tournament())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn create_tournament(
+			origin: OriginFor<T>,
+			rounds: u8,
+			win: u32,
+			lose: u32,
+			draw: u32,
+			variant: BoardVariant,
+			time_bank: Option<MomentOf<T>>,
+			entry_fee: BalanceOf<T>,
+			check_in: T::BlockNumber,
+			start: T::BlockNumber,
+			refund_no_shows: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+			ensure!(
+				rounds > 0 && rounds as u32 <= T::MaxTournamentRounds::get(),
+				Error::<T>::InvalidRounds
+			);
+			ensure!(
+				variant.is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+				Error::<T>::InvalidVariant
+			);
+			ensure!(time_bank.map_or(true, |bank| !bank.is_zero()), Error::<T>::InvalidTimeBank);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < check_in && check_in < start, Error::<T>::InvalidCheckInWindow);
+
+			let id = <NextTournamentId<T>>::get();
+			<TournamentCheckIns<T>>::try_mutate(check_in, |closing| {
+				closing.try_push(id).map_err(|_| Error::<T>::TooManyTournaments)
+			})?;
+			<Tournaments<T>>::insert(
+				id,
+				TournamentStruct {
+					creator: sender.clone(),
+					award: AwardState { win, lose, draw },
+					variant,
+					time_bank,
+					entry_fee,
+					refund_no_shows,
+					check_in,
+					start,
+					rounds,
+					round: 0,
+					state: TournamentState::Registration,
+					players: 0,
+					pending: 0,
+					prize_pool: Zero::zero(),
+					claim_deadline: Zero::zero(),
+				},
+			);
+			<NextTournamentId<T>>::put(id.wrapping_add(1));
+
+			Self::deposit_event(Event::TournamentCreated(id, sender, rounds));
+			Ok(())
+		}
+
+		/// Register for a tournament before its check-in opens, reserving the entry fee.
+		#[pallet::weight(T::WeightInfo::join_tournament())]
+		pub fn join_tournament(origin: OriginFor<T>, tournament_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut tournament =
+				<Tournaments<T>>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(
+				tournament.state == TournamentState::Registration,
+				Error::<T>::WrongTournamentState
+			);
+			ensure!(
+				!<TournamentPlayers<T>>::contains_key(tournament_id, &sender),
+				Error::<T>::AlreadyRegistered
+			);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < tournament.check_in,
+				Error::<T>::RegistrationClosed
+			);
+			ensure!(
+				tournament.players < T::MaxTournamentPlayers::get(),
+				Error::<T>::TournamentFull
+			);
+
+			T::Currency::reserve(&sender, tournament.entry_fee)?;
+			tournament.players += 1;
+			<TournamentPlayers<T>>::insert(tournament_id, &sender, SwissStandingOf::<T>::default());
+			<Tournaments<T>>::insert(tournament_id, tournament);
+
+			Self::deposit_event(Event::TournamentJoined(tournament_id, sender));
+			Ok(())
+		}
+
+		/// Confirm taking part in a tournament, between its `check_in` and `start` blocks.
+		#[pallet::weight(T::WeightInfo::check_in())]
+		pub fn check_in(origin: OriginFor<T>, tournament_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let tournament =
+				<Tournaments<T>>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(
+				tournament.state == TournamentState::Registration,
+				Error::<T>::WrongTournamentState
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				tournament.check_in <= now && now < tournament.start,
+				Error::<T>::CheckInClosed
+			);
+
+			<TournamentPlayers<T>>::try_mutate(tournament_id, &sender, |standing| {
+				let standing = standing.as_mut().ok_or(Error::<T>::NotRegistered)?;
+				ensure!(!standing.checked_in, Error::<T>::AlreadyCheckedIn);
+				standing.checked_in = true;
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::TournamentCheckedIn(tournament_id, sender));
+			Ok(())
+		}
+
+		/// Drop the players that didn't check in and start the first round once check-in
+		/// closed, only the creator may do this.
+		#[pallet::weight(T::WeightInfo::start_tournament(T::MaxTournamentPlayers::get()))]
+		pub fn start_tournament(origin: OriginFor<T>, tournament_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut tournament =
+				<Tournaments<T>>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(tournament.creator == sender, Error::<T>::NotTournamentCreator);
+			ensure!(
+				tournament.state == TournamentState::Registration,
+				Error::<T>::WrongTournamentState
+			);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= tournament.start,
+				Error::<T>::CheckInNotOver
+			);
+			let (checked_in, no_shows): (Vec<_>, Vec<_>) =
+				<TournamentPlayers<T>>::iter_prefix(tournament_id)
+					.partition(|(_, standing)| standing.checked_in);
+			let players = checked_in.len() as u32;
+			ensure!(players >= 2, Error::<T>::NotEnoughPlayers);
+			// Rematches can only be avoided while there are opponents left to meet.
+			ensure!((tournament.rounds as u32) < players, Error::<T>::InvalidRounds);
+
+			// The bracket is seeded from the players that checked in.
+			for (player, _) in no_shows {
+				<TournamentPlayers<T>>::remove(tournament_id, &player);
+				if tournament.refund_no_shows {
+					T::Currency::unreserve(&player, tournament.entry_fee);
+				} else {
+					let unpaid = T::Currency::repatriate_reserved(
+						&player,
+						&Self::bot_account(),
+						tournament.entry_fee,
+						BalanceStatus::Reserved,
+					)
+					.unwrap_or(tournament.entry_fee);
+					tournament.prize_pool = tournament
+						.prize_pool
+						.saturating_add(tournament.entry_fee.saturating_sub(unpaid));
+				}
+				Self::deposit_event(Event::TournamentNoShow(
+					tournament_id,
+					player,
+					tournament.refund_no_shows,
+				));
+			}
+			tournament.players = players;
+			tournament.state = TournamentState::Running;
+			Self::start_round(tournament_id, &mut tournament)?;
+			<Tournaments<T>>::insert(tournament_id, tournament);
+			Ok(())
+		}
+
+		/// Pair the next round once every game of the current round finished, or settle the
+		/// final standings after the last round. Anyone may advance a tournament.
+		#[pallet::weight(T::WeightInfo::advance_tournament(T::MaxTournamentPlayers::get()))]
+		pub fn advance_tournament(origin: OriginFor<T>, tournament_id: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut tournament =
+				<Tournaments<T>>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(tournament.state == TournamentState::Running, Error::<T>::WrongTournamentState);
+			ensure!(tournament.pending == 0, Error::<T>::RoundNotFinished);
+
+			if tournament.round < tournament.rounds {
+				Self::start_round(tournament_id, &mut tournament)?;
+			} else {
+				Self::finish_tournament(tournament_id, &mut tournament);
+			}
+			<Tournaments<T>>::insert(tournament_id, tournament);
+			Ok(())
+		}
+
+		/// Get back the entry fee of a finished tournament, the winner gets the prize pool on
+		/// top. Prizes have to be claimed within `PrizeClaimPeriod`.
+		#[pallet::weight(T::WeightInfo::claim_prize())]
+		pub fn claim_prize(origin: OriginFor<T>, tournament_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let tournament =
+				<Tournaments<T>>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			let prize =
+				<TournamentPrizes<T>>::take(tournament_id, &sender).ok_or(Error::<T>::NoPrize)?;
+			T::Currency::unreserve(&sender, tournament.entry_fee);
+			let unpaid = T::Currency::repatriate_reserved(
+				&Self::bot_account(),
+				&sender,
+				prize,
+				BalanceStatus::Free,
+			)
+			.unwrap_or(prize);
+
+			Self::deposit_event(Event::PrizeClaimed(
+				tournament_id,
+				sender,
+				prize.saturating_sub(unpaid),
+			));
+			Ok(())
+		}
+
+		/// Send up to `MaxPrizeSweeps` prizes that weren't claimed within `PrizeClaimPeriod`,
+		/// entry fees included, to `UnclaimedPrizeDestination`. Anyone may sweep prizes, large
+		/// tournaments take several calls.
+		#[pallet::weight(T::WeightInfo::sweep_prizes(T::MaxPrizeSweeps::get()))]
+		pub fn sweep_prizes(
+			origin: OriginFor<T>,
+			tournament_id: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let tournament =
+				<Tournaments<T>>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(
+				tournament.state == TournamentState::Finished,
+				Error::<T>::WrongTournamentState
+			);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= tournament.claim_deadline,
+				Error::<T>::ClaimPeriodNotOver
+			);
+			let prizes: Vec<(T::AccountId, BalanceOf<T>)> =
+				<TournamentPrizes<T>>::iter_prefix(tournament_id)
+					.take(T::MaxPrizeSweeps::get() as usize)
+					.collect();
+			ensure!(!prizes.is_empty(), Error::<T>::NoPrize);
+
+			let mut swept = NegativeImbalanceOf::<T>::zero();
+			for (player, prize) in &prizes {
+				<TournamentPrizes<T>>::remove(tournament_id, player);
+				swept.subsume(T::Currency::slash_reserved(player, tournament.entry_fee).0);
+				swept.subsume(T::Currency::slash_reserved(&Self::bot_account(), *prize).0);
+			}
+			let amount = swept.peek();
+			T::UnclaimedPrizeDestination::on_unbalanced(swept);
+
+			Self::deposit_event(Event::PrizesSwept(tournament_id, prizes.len() as u32, amount));
+			Ok(Some(T::WeightInfo::sweep_prizes(prizes.len() as u32)).into())
+		}
+
+		/// Add `amount` to the payout of the winner of a running board or a tournament. Board
+		/// sponsorships stay reserved until the game ends and go back to the sponsor if it ends
+		/// without a winner, tournament sponsorships join the prize pool right away.
+		#[pallet::weight(T::WeightInfo::sponsor_game())]
+		pub fn sponsor_game(
+			origin: OriginFor<T>,
+			game: SponsoredGame<T::Hash>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(amount >= T::Currency::minimum_balance(), Error::<T>::StakeTooLow);
+
+			match game {
+				SponsoredGame::Board(board_id) => {
+					let board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+					ensure!(board.bot.is_none(), Error::<T>::CannotSponsor);
+					<BoardSponsors<T>>::try_mutate(board_id, |sponsors| {
+						sponsors
+							.try_push((sender.clone(), amount))
+							.map_err(|_| Error::<T>::TooManySponsors)?;
+						T::Currency::reserve(&sender, amount)
+					})?;
+				},
+				SponsoredGame::Tournament(tournament_id) => {
+					let mut tournament = <Tournaments<T>>::get(tournament_id)
+						.ok_or(Error::<T>::TournamentNotFound)?;
+					ensure!(
+						tournament.state != TournamentState::Finished,
+						Error::<T>::CannotSponsor
+					);
+					// The pallet account holds the prize pool.
+					T::Currency::reserve(&sender, amount)?;
+					T::Currency::repatriate_reserved(
+						&sender,
+						&Self::bot_account(),
+						amount,
+						BalanceStatus::Reserved,
+					)?;
+					tournament.prize_pool = tournament.prize_pool.saturating_add(amount);
+					<Tournaments<T>>::insert(tournament_id, tournament);
+				},
+			}
+
+			Self::deposit_event(Event::GameSponsored(game, sender, amount));
+			Ok(())
+		}
+
+		/// Attach an annotation, such as the CID of an analysis file, to an archived game.
+		/// `AnnotationDeposit` is reserved until the archive of the game is pruned.
+		#[pallet::weight(T::WeightInfo::annotate_game())]
+		pub fn annotate_game(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			annotation: Vec<u8>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(<ArchivedGames<T>>::contains_key(board_id), Error::<T>::GameNotArchived);
+			let annotation: AnnotationOf<T> =
+				BoundedVec::try_from(annotation).map_err(|_| Error::<T>::AnnotationTooLong)?;
+
+			<GameAnnotations<T>>::try_mutate(board_id, |annotations| {
+				annotations
+					.try_push((sender.clone(), annotation.clone()))
+					.map_err(|_| Error::<T>::TooManyAnnotations)?;
+				T::Currency::reserve(&sender, T::AnnotationDeposit::get())
+			})?;
+
+			Self::deposit_event(Event::GameAnnotated(board_id, sender, annotation));
+			Ok(())
+		}
+
+		/// Found a guild, the sender becomes its first member.
+		#[pallet::weight(T::WeightInfo::create_guild())]
+		pub fn create_guild(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<PlayerGuild<T>>::contains_key(&sender), Error::<T>::AlreadyInGuild);
+
+			let id = <NextGuildId<T>>::get();
+			let mut members = BoundedVec::default();
+			members.try_push(sender.clone()).map_err(|_| Error::<T>::GuildFull)?;
+			<Guilds<T>>::insert(id, GuildStruct { founder: sender.clone(), members, rating: 0 });
+			<NextGuildId<T>>::put(id.wrapping_add(1));
+			<PlayerGuild<T>>::insert(&sender, id);
+
+			Self::deposit_event(Event::GuildCreated(id, sender));
+			Ok(())
+		}
+
+		/// Join a guild, an account is a member of one guild at most.
+		#[pallet::weight(T::WeightInfo::join_guild())]
+		pub fn join_guild(origin: OriginFor<T>, guild_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<PlayerGuild<T>>::contains_key(&sender), Error::<T>::AlreadyInGuild);
+
+			<Guilds<T>>::try_mutate(guild_id, |guild| {
+				let guild = guild.as_mut().ok_or(Error::<T>::GuildNotFound)?;
+				guild.members.try_push(sender.clone()).map_err(|_| Error::<T>::GuildFull)
+			})?;
+			<PlayerGuild<T>>::insert(&sender, guild_id);
+
+			Self::deposit_event(Event::GuildJoined(guild_id, sender));
+			Ok(())
+		}
+
+		/// Leave the guild of the sender. The oldest member takes over from a leaving founder,
+		/// the last member leaving disbands the guild along with its pending guild match.
+		#[pallet::weight(T::WeightInfo::leave_guild())]
+		pub fn leave_guild(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let guild_id = <PlayerGuild<T>>::take(&sender).ok_or(Error::<T>::NotInGuild)?;
+
+			let mut guild = <Guilds<T>>::get(guild_id).ok_or(Error::<T>::GuildNotFound)?;
+			guild.members.retain(|member| member != &sender);
+			match guild.members.first() {
+				Some(oldest) => {
+					if guild.founder == sender {
+						guild.founder = oldest.clone();
+					}
+					<Guilds<T>>::insert(guild_id, guild);
+				},
+				None => {
+					<Guilds<T>>::remove(guild_id);
+					<GuildChallenges<T>>::remove(guild_id);
+				},
+			}
+
+			Self::deposit_event(Event::GuildLeft(guild_id, sender));
+			Ok(())
+		}
+
+		/// Challenge another guild to a match, fielding the members of `lineup`. Every player
+		/// of the lineup plays a casual game against a member of the other guild and the guild
+		/// winning the majority of the boards takes the award. Only the founder may challenge.
+		#[pallet::weight(T::WeightInfo::challenge_guild())]
+		pub fn challenge_guild(
+			origin: OriginFor<T>,
+			opponent: u32,
+			win: u32,
+			lose: u32,
+			draw: u32,
+			lineup: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+			let guild_id = Self::founded_guild(&sender)?;
+			ensure!(
+				guild_id != opponent && <Guilds<T>>::contains_key(opponent),
+				Error::<T>::GuildNotFound
+			);
+			ensure!(!<GuildChallenges<T>>::contains_key(guild_id), Error::<T>::ReChallengeError);
+			let lineup = Self::guild_lineup(guild_id, lineup)?;
+
+			let players = lineup.len() as u32;
+			let award = AwardState { win, lose, draw };
+			<GuildChallenges<T>>::insert(
+				guild_id,
+				GuildChallengeStruct { opponent, award, lineup },
+			);
+			Self::deposit_event(Event::GuildChallenged(guild_id, opponent, players));
+			Ok(())
+		}
+
+		/// Respond to a guild match as the founder of the challenged guild. Accepting fields
+		/// as many members as the challenging guild and starts all boards at once, the
+		/// challenging guild's players play red.
+		#[pallet::weight(T::WeightInfo::resp_guild_challenge(T::MaxGuildMatchPlayers::get()))]
+		pub fn resp_guild_challenge(
+			origin: OriginFor<T>,
+			challenger: u32,
+			accepted: bool,
+			lineup: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let guild_id = Self::founded_guild(&sender)?;
+			let challenge =
+				<GuildChallenges<T>>::get(challenger).ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(challenge.opponent == guild_id, Error::<T>::NotChallenged);
+
+			if !accepted {
+				<GuildChallenges<T>>::remove(challenger);
+				Self::deposit_event(Event::GuildChallengeRejected(challenger, guild_id));
+				return Ok(())
+			}
+
+			ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+			let lineup = Self::guild_lineup(guild_id, lineup)?;
+			ensure!(lineup.len() == challenge.lineup.len(), Error::<T>::InvalidLineup);
+			// Members may have left since the challenge.
+			for red in &challenge.lineup {
+				ensure!(<PlayerGuild<T>>::get(red) == Some(challenger), Error::<T>::InvalidLineup);
+			}
+			for player in challenge.lineup.iter().chain(lineup.iter()) {
+				ensure!(!PlayerBoard::<T>::contains_key(player), Error::<T>::PlayerBoardExists);
+			}
+			<GuildChallenges<T>>::remove(challenger);
+
+			let match_id = <NextGuildMatchId<T>>::get();
+			<NextGuildMatchId<T>>::put(match_id.wrapping_add(1));
+			let pending = lineup.len() as u32;
+			for (red, blue) in challenge.lineup.into_iter().zip(lineup) {
+				let board_id = Self::new_game(
+					red,
+					blue,
+					challenge.award,
+					BoardVariant::default(),
+					T::MatchmakingTimeBank::get(),
+					GameMode::Standard,
+					FirstMovePolicy::Random,
+					GameKind::Casual,
+				)?;
+				<GuildMatchBoards<T>>::insert(board_id, match_id);
+			}
+			<GuildMatches<T>>::insert(
+				match_id,
+				GuildMatch {
+					challenger,
+					opponent: guild_id,
+					award: challenge.award,
+					pending,
+					challenger_wins: 0,
+					opponent_wins: 0,
+				},
+			);
+
+			Self::deposit_event(Event::GuildMatchStarted(match_id, challenger, guild_id));
+			Ok(())
+		}
+
+		/// Pay out the rewards of an ended season, split evenly among its best players.
+		/// The reward pool is newly issued.
+		#[pallet::weight(T::WeightInfo::distribute_season_rewards(T::MaxSeasonRewardees::get()))]
+		pub fn distribute_season_rewards(
+			origin: OriginFor<T>,
+			season: u32,
+			reward_pool: BalanceOf<T>,
+		) -> DispatchResult {
+			T::SeasonRewardOrigin::ensure_origin(origin)?;
+
+			// Standings are final once the scores of the season are decayed.
+			ensure!(
+				<PendingDecay<T>>::get().map_or(true, |(pending, _)| pending != season),
+				Error::<T>::SeasonNotSettled
+			);
+			let standings = <SeasonStandings<T>>::take(season);
+			ensure!(!standings.is_empty(), Error::<T>::NoSeasonStandings);
+
+			let share = reward_pool / (standings.len() as u32).into();
+			for (player, _) in standings {
+				let _ = T::Currency::deposit_creating(&player, share);
+			}
+
+			Self::deposit_event(Event::SeasonRewarded(season, reward_pool));
+			Ok(())
+		}
+
+		/// End a running game, e.g. one that got stuck or is being abused. Scores are
+		/// settled as usual if `winner` is set.
+		#[pallet::weight(
+			T::WeightInfo::force_end_game()
+				.saturating_add(T::WeightInfo::settle_predictions(T::MaxPredictions::get()))
+				.saturating_add(T::WeightInfo::settle_sponsorships(T::MaxSponsors::get()))
+		)]
+		pub fn force_end_game(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			winner: Option<T::AccountId>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			let mut board = <Boards<T>>::get(board_id).ok_or(Error::<T>::BoardNotFound)?;
+			if let Some(winner) = &winner {
+				ensure!(board.team_of(winner).is_some(), Error::<T>::NotOnBoard);
+			}
+
+			board.board_state = BoardState::Finished(winner);
+			Self::finish_game(board, FinishReason::Forced);
+			Ok(())
+		}
+
+		/// Rule on a finished reported game and settle its scores and stakes by the verdict.
+		#[pallet::weight(T::WeightInfo::resolve_report())]
+		pub fn resolve_report(
+			origin: OriginFor<T>,
+			board_id: T::Hash,
+			verdict: Verdict,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			Self::rule_on_report(board_id, verdict)
+		}
+
+		/// Reset the score of an account back to zero.
+		#[pallet::weight(T::WeightInfo::reset_score())]
+		pub fn reset_score(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			Self::set_score(&account, None);
+			Self::deposit_event(Event::ScoreReset(account));
+			Ok(())
+		}
+
+		/// Change the score tolerance of matchmaking and the award of matched games.
+		#[pallet::weight(T::WeightInfo::set_matchmaking_params())]
+		pub fn set_matchmaking_params(
+			origin: OriginFor<T>,
+			accepted_diff: u8,
+			default_award: AwardState,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<AcceptedDiff<T>>::put(accepted_diff);
+			<DefaultAward<T>>::put(default_award.clone());
+			Self::deposit_event(Event::MatchmakingParamsSet(accepted_diff, default_award));
+			Ok(())
+		}
+
+		/// Split ranked matchmaking into tiers starting at the `boundaries` scores. With
+		/// `adjacent_fallback` players nobody in their tier fits may be paired one tier up or down.
+		#[pallet::weight(T::WeightInfo::set_tier_boundaries())]
+		pub fn set_tier_boundaries(
+			origin: OriginFor<T>,
+			boundaries: TierBoundariesOf,
+			adjacent_fallback: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+			ensure!(
+				boundaries.windows(2).all(|pair| pair[0] < pair[1]),
+				Error::<T>::InvalidTierBoundaries
+			);
+
+			<TierBoundaries<T>>::put(boundaries.clone());
+			<AdjacentTierFallback<T>>::put(adjacent_fallback);
+			Self::deposit_event(Event::TierBoundariesSet(boundaries, adjacent_fallback));
+			Ok(())
+		}
+
+		/// Set up a game from `seed` instead of `Randomness`, for tests and dev chains that need
+		/// reproducible games. The board id is derived from the seed and the players, and
+		/// `first_move` counts red as the responder, so `Random` picks by the board id.
+		#[pallet::weight(T::WeightInfo::create_game_deterministic())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn create_game_deterministic(
+			origin: OriginFor<T>,
+			red: T::AccountId,
+			blue: T::AccountId,
+			award: AwardState,
+			variant: BoardVariant,
+			seed: T::Hash,
+			first_move: FirstMovePolicy,
+			kind: GameKind,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+			ensure!(red != blue, Error::<T>::NoFakePlay);
+
+			Self::seeded_game(
+				red,
+				blue,
+				award,
+				variant,
+				None,
+				GameMode::Standard,
+				first_move,
+				kind,
+				Some(seed),
+			)?;
+			Ok(())
+		}
+
+		/// Stop or allow new games. Running games, challenges and queue entries can still be
+		/// played out or cancelled while paused.
+		#[pallet::weight(T::WeightInfo::set_paused())]
+		pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<Paused<T>>::put(paused);
+			Self::deposit_event(if paused { Event::PalletPaused } else { Event::PalletUnpaused });
+			Ok(())
+		}
+
+		/// Change the part of the losing predictions that goes to the house.
+		#[pallet::weight(T::WeightInfo::set_prediction_fee())]
+		pub fn set_prediction_fee(origin: OriginFor<T>, fee: Perbill) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<PredictionFee<T>>::put(fee);
+			Self::deposit_event(Event::PredictionFeeSet(fee));
+			Ok(())
+		}
+
+		/// Change the part of the loser's stake at public tables that goes to the treasury.
+		#[pallet::weight(T::WeightInfo::set_stake_fee())]
+		pub fn set_stake_fee(origin: OriginFor<T>, fee: Perbill) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<StakeFee<T>>::put(fee);
+			Self::deposit_event(Event::StakeFeeSet(fee));
+			Ok(())
+		}
+
+		/// Change the number of boards that may be played at once. Running games go on when
+		/// it's lowered, new ones wait until enough of them finished.
+		#[pallet::weight(T::WeightInfo::set_max_active_boards())]
+		pub fn set_max_active_boards(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<MaxActiveBoards<T>>::put(limit);
+			Self::deposit_event(Event::MaxActiveBoardsSet(limit));
+			Ok(())
+		}
+
+		/// Change the number of boards an account may play at once. Running games go on when
+		/// it's lowered.
+		#[pallet::weight(T::WeightInfo::set_max_games_per_player())]
+		pub fn set_max_games_per_player(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<MaxGamesPerPlayer<T>>::put(limit);
+			Self::deposit_event(Event::MaxGamesPerPlayerSet(limit));
+			Ok(())
+		}
+
+		/// Add, change or, with `None`, remove a game preset. Players already queued for a
+		/// removed preset aren't paired until they leave the queue.
+		#[pallet::weight(T::WeightInfo::set_preset())]
+		pub fn set_preset(
+			origin: OriginFor<T>,
+			preset_id: u32,
+			preset: Option<GamePresetOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			match preset {
+				Some(preset) => {
+					ensure!(
+						preset.variant.is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+						Error::<T>::InvalidVariant
+					);
+					ensure!(
+						preset.time_bank.map_or(true, |bank| !bank.is_zero()),
+						Error::<T>::InvalidTimeBank
+					);
+					ensure!(preset.min_stake <= preset.max_stake, Error::<T>::StakeOutOfBounds);
+					<Presets<T>>::insert(preset_id, preset);
+					Self::deposit_event(Event::PresetSet(preset_id));
+				},
+				None => {
+					ensure!(<Presets<T>>::contains_key(preset_id), Error::<T>::PresetNotFound);
+					<Presets<T>>::remove(preset_id);
+					Self::deposit_event(Event::PresetRemoved(preset_id));
+				},
+			}
+			Ok(())
+		}
+
+		/// Change the part of the deposit a player abandoning a game of `kind` pays to the
+		/// waiting player.
+		#[pallet::weight(T::WeightInfo::set_abandon_penalty())]
+		pub fn set_abandon_penalty(
+			origin: OriginFor<T>,
+			kind: GameKind,
+			penalty: Perbill,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::OnlyFounderAllowed)?;
+
+			<AbandonPenalty<T>>::insert(kind, penalty);
+			Self::deposit_event(Event::AbandonPenaltySet(kind, penalty));
+			Ok(())
+		}
+
+		/// Offer a rematch to the opponent of the last finished game.
+		#[pallet::weight(T::WeightInfo::request_rematch())]
+		pub fn request_rematch(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut rematch = Self::valid_rematch(&sender)?;
+			let opponent = rematch.opponent.clone();
+			rematch.requested = true;
+			<Rematches<T>>::insert(&sender, rematch);
+
+			Self::deposit_event(Event::RematchRequested(sender, opponent));
+			Ok(())
+		}
+
+		/// Accept the rematch offered by the opponent of the last finished game.
+		/// The new game keeps the award, board variant and kind, with colors swapped.
+		#[pallet::weight(T::WeightInfo::accept_rematch())]
+		pub fn accept_rematch(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let rematch = Self::valid_rematch(&sender)?;
+			let opponent = rematch.opponent;
+			let offer = Self::valid_rematch(&opponent)?;
+			ensure!(offer.requested && offer.opponent == sender, Error::<T>::RematchNotRequested);
+
+			// Make sure both players are still free to play.
+			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
+			ensure!(!PlayerBoard::<T>::contains_key(&opponent), Error::<T>::PlayerBoardExists);
+			ensure!(!Self::is_queued(&sender), Error::<T>::MatchQueueError);
+			ensure!(!Self::is_queued(&opponent), Error::<T>::MatchQueueError);
+
+			<Rematches<T>>::remove(&sender);
+			<Rematches<T>>::remove(&opponent);
+
+			let (red, blue) = if rematch.was_red { (opponent, sender) } else { (sender, opponent) };
+			let _board_id = Self::new_game(
+				red,
+				blue,
+				rematch.award,
+				rematch.variant,
+				rematch.time_bank,
+				GameMode::Standard,
+				FirstMovePolicy::Random,
+				rematch.kind,
+			)?;
+			Ok(())
+		}
+
+		/// Remove orphaned entries of `accounts`: boards that no longer exist, queue entries of
+		/// players that are already on a board, rematch offers past the `RematchPeriod` and
+		/// challenges past the `ChallengeLifetime`.
+		/// Anyone may call this and earns `CleanupReward` for every entry removed.
+		#[pallet::weight(T::WeightInfo::cleanup(accounts.len() as u32))]
+		pub fn cleanup(
+			origin: OriginFor<T>,
+			accounts: BoundedVec<T::AccountId, T::MaxCleanupAccounts>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut cleaned = 0u32;
+			for account in &accounts {
+				if let Some(board_id) = Self::player_board_id(account) {
+					if !<Boards<T>>::contains_key(board_id) {
+						<PlayerBoard<T>>::remove(account);
+						cleaned += 1;
+					} else if Self::dequeue(account) {
+						T::Currency::unreserve(account, T::QueueDeposit::get());
+						cleaned += 1;
+					}
+				}
+				if <Rematches<T>>::get(account).map_or(false, |rematch| rematch.expires < now) {
+					<Rematches<T>>::remove(account);
+					cleaned += 1;
+				}
+				if let Some(challenge) = <Challenges<T>>::get(account) {
+					if challenge.expires < now {
+						<Challenges<T>>::remove(account);
+						Self::release_challenge_stake(account, &challenge);
+						cleaned += 1;
+					}
+				}
+			}
+			ensure!(cleaned > 0, Error::<T>::NothingToCleanUp);
+
+			let reward = T::CleanupReward::get().saturating_mul(cleaned.into());
+			let _ = T::Currency::deposit_creating(&sender, reward);
+			Self::deposit_event(Event::CleanedUp(sender, cleaned, reward));
+			Ok(())
+		}
+
+		/// Convert all vested reward points of the caller into tokens, `RewardPointValue` is
+		/// minted for every point.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut rewards =
+				<RewardPoints<T>>::get(&sender).ok_or(Error::<T>::NoRewardsToClaim)?;
+			Self::vest_rewards(&mut rewards);
+			ensure!(rewards.points > 0, Error::<T>::NoRewardsToClaim);
+			let points = sp_std::mem::take(&mut rewards.points);
+			if rewards.pending == 0 {
+				<RewardPoints<T>>::remove(&sender);
+			} else {
+				<RewardPoints<T>>::insert(&sender, rewards);
+			}
+
+			let payout = T::RewardPointValue::get().saturating_mul(points.into());
+			let _ = T::Currency::deposit_creating(&sender, payout);
+			Self::deposit_event(Event::RewardsClaimed(sender, points, payout));
+			Ok(())
+		}
+
+		/// Dispatch `call` for a player on another chain, as the local account of their
+		/// location, see `Config::RemoteAccount`. They can find, challenge and play games this
+		/// way, the results of their games are sent back through `Config::RemoteNotifier`.
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			(info.weight.saturating_add(T::DbWeight::get().reads_writes(1, 1)), info.class)
+		})]
+		pub fn remote_call(origin: OriginFor<T>, call: Box<Call<T>>) -> DispatchResultWithPostInfo {
+			let location = T::RemoteOrigin::ensure_origin(origin)?;
+			ensure!(
+				matches!(
+					*call,
+					Call::find_game { .. } |
+						Call::cancel_queue { .. } |
+						Call::challenge { .. } |
+						Call::open_challenge { .. } |
+						Call::resp_challenge { .. } |
+						Call::cancel_challenge { .. } |
+						Call::play_turn { .. } |
+						Call::claim_timeout { .. }
+				),
+				Error::<T>::NotRemotePlayable
+			);
+
+			let player = T::RemoteAccount::convert(location.clone());
+			if !<RemotePlayers<T>>::contains_key(&player) {
+				<RemotePlayers<T>>::insert(&player, location.clone());
+				Self::deposit_event(Event::RemotePlayerJoined(player.clone(), location));
+			}
+			let mut result = call.dispatch_bypass_filter(RawOrigin::Signed(player).into());
+			let post_info = match &mut result {
+				Ok(post_info) => post_info,
+				Err(error) => &mut error.post_info,
+			};
+			post_info.actual_weight = post_info
+				.actual_weight
+				.map(|weight| weight.saturating_add(T::DbWeight::get().reads_writes(1, 1)));
+			result
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Update nonce once used.
+	fn encode_and_update_nonce() -> Vec<u8> {
+		let nonce = <Nonce<T>>::get();
+		<Nonce<T>>::put(nonce.wrapping_add(1));
+		nonce.encode()
+	}
+
+	/// Generates a random hash out of a seed.
+	fn generate_random_hash(phrase: &[u8], sender: T::AccountId) -> T::Hash {
+		let (seed, _) = T::Randomness::random(phrase);
+		(seed, &sender, Self::encode_and_update_nonce()).using_encoded(T::Hashing::hash)
+	}
+
+	/// Bucket of the match queue a score belongs to.
+	fn score_bucket(score: i32) -> ScoreBucket {
+		score.div_euclid(QUEUE_BUCKET_SIZE)
+	}
+
+	/// Add a player to the match queue bucket for their score.
+	fn enqueue(who: &T::AccountId, score: i32) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let ticket = <NextQueueTicket<T>>::get();
+		<MatchQueue<T>>::try_mutate(Self::score_bucket(score), |queue| {
+			queue
+				.try_push((who.clone(), score, now, ticket))
+				.map_err(|_| Error::<T>::QueueFull)
+		})?;
+		<NewlyQueued<T>>::try_mutate(|newcomers| {
+			newcomers.try_push(who.clone()).map_err(|_| Error::<T>::QueueFull)
+		})?;
+		<NextQueueTicket<T>>::put(ticket.wrapping_add(1));
+		<QueuedPlayers<T>>::insert(who, score);
+		<QueueLength<T>>::mutate(|length| *length = length.saturating_add(1));
+		Self::schedule_queue_expiry(who, now)
+	}
+
+	/// Add a player to the back of the casual queue.
+	fn enqueue_casual(who: &T::AccountId) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		<CasualQueue<T>>::try_mutate(|queue| {
+			queue.try_push(who.clone()).map_err(|_| Error::<T>::QueueFull)
+		})?;
+		<NewlyQueued<T>>::try_mutate(|newcomers| {
+			newcomers.try_push(who.clone()).map_err(|_| Error::<T>::QueueFull)
+		})?;
+		<CasualQueuedAt<T>>::insert(who, now);
+		<QueueLength<T>>::mutate(|length| *length = length.saturating_add(1));
+		Self::schedule_queue_expiry(who, now)
+	}
+
+	/// List a player joining the queue at `now` for expiry after `QueueTimeout` blocks.
+	fn schedule_queue_expiry(who: &T::AccountId, now: T::BlockNumber) -> DispatchResult {
+		let timeout = T::QueueTimeout::get();
+		if timeout.is_zero() {
+			return Ok(())
+		}
+		<QueueExpiries<T>>::try_mutate(now.saturating_add(timeout), |expiring| {
+			expiring.try_push(who.clone()).map_err(|_| Error::<T>::QueueFull.into())
+		})
+	}
+
+	/// Block a player joined the ranked or the casual queue at, `None` if they aren't queued.
+	fn queued_since(who: &T::AccountId) -> Option<T::BlockNumber> {
+		match <QueuedPlayers<T>>::get(who) {
+			Some(score) => Self::enqueued_at(who, score),
+			None => <CasualQueuedAt<T>>::get(who),
+		}
+	}
+
+	/// No more boards may be played at once, see `MaxActiveBoards`.
+	fn board_limit_reached() -> bool {
+		<ActiveBoards<T>>::get() >= <MaxActiveBoards<T>>::get()
+	}
+
+	/// Boards `who` is playing, counted against `MaxGamesPerPlayer`.
+	fn games_of(who: &T::AccountId) -> u32 {
+		<PlayerBoard<T>>::contains_key(who) as u32
+	}
+
+	/// Player waits in the ranked or the casual queue.
+	fn is_queued(who: &T::AccountId) -> bool {
+		<QueuedPlayers<T>>::contains_key(who) || <CasualQueue<T>>::get().contains(who)
+	}
+
+	/// Account meets the balance, age and identity requirements of ranked matchmaking.
+	fn ensure_ranked_eligible(who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			T::Currency::free_balance(who) >= T::MinRankedBalance::get(),
+			Error::<T>::InsufficientBalance
+		);
+		if let Some(created) = <AccountCreated<T>>::get(who) {
+			let age = <frame_system::Pallet<T>>::block_number().saturating_sub(created);
+			ensure!(age >= T::MinAccountAge::get(), Error::<T>::AccountTooNew);
+		}
+		ensure!(
+			!T::RequireJudgedIdentity::get() || T::Identity::is_judged(who),
+			Error::<T>::IdentityNotJudged
+		);
+		Ok(())
+	}
+
+	/// Remove a player from the ranked or casual queue, returns `false` if they weren't
+	/// queued. The queue deposit taken by `find_game` is left to the caller.
+	fn dequeue(who: &T::AccountId) -> bool {
+		<QueuedPresets<T>>::remove(who);
+		let dequeued = match <QueuedPlayers<T>>::take(who) {
+			Some(score) => {
+				Self::remove_from_bucket(Self::score_bucket(score), who);
+				true
+			},
+			None => {
+				<CasualQueuedAt<T>>::remove(who);
+				<CasualQueue<T>>::mutate(|queue| {
+					let waiting = queue.len();
+					queue.retain(|account| account != who);
+					queue.len() < waiting
+				})
+			},
+		};
+		if dequeued {
+			<QueueLength<T>>::mutate(|length| *length = length.saturating_sub(1));
+		}
+		dequeued
+	}
+
+	/// Drop a player from a queue bucket, clearing the bucket once it's empty.
+	fn remove_from_bucket(bucket: ScoreBucket, who: &T::AccountId) {
+		<MatchQueue<T>>::mutate_exists(bucket, |queue| {
+			if let Some(players) = queue {
+				players.retain(|(account, ..)| account != who);
+				if players.is_empty() {
+					*queue = None;
+				}
+			}
+		});
+	}
+
+	/// Block a ranked player joined the queue at, `None` if they aren't queued with `score`.
+	fn enqueued_at(who: &T::AccountId, score: i32) -> Option<T::BlockNumber> {
+		<MatchQueue<T>>::get(Self::score_bucket(score))
+			.into_iter()
+			.find_map(|(account, _, enqueued, _)| (&account == who).then(|| enqueued))
+	}
+
+	/// Score tolerance at block `now` of a player queued at `enqueued`: `AcceptedDiff`, widened
+	/// by `QueueWideningStep` for every `QueueWideningPeriod` waited, up to `MaxWidenedDiff`.
+	fn widened_diff(enqueued: T::BlockNumber, now: T::BlockNumber) -> i32 {
+		let accepted_diff = <AcceptedDiff<T>>::get() as u32;
+		let period = T::QueueWideningPeriod::get();
+		if period.is_zero() {
+			return accepted_diff as i32
+		}
+		let steps: u32 = (now.saturating_sub(enqueued) / period).saturated_into();
+		let widened = steps.saturating_mul(T::QueueWideningStep::get() as u32);
+		accepted_diff
+			.saturating_add(widened)
+			.min(T::MaxWidenedDiff::get() as u32)
+			.max(accepted_diff) as i32
+	}
+
+	/// Block the score tolerance of a player queued at `enqueued` widens next, `None` once it
+	/// can't widen any further.
+	fn next_widening(enqueued: T::BlockNumber, now: T::BlockNumber) -> Option<T::BlockNumber> {
+		let period = T::QueueWideningPeriod::get();
+		if period.is_zero() ||
+			T::QueueWideningStep::get() == 0 ||
+			Self::widened_diff(enqueued, now) >= T::MaxWidenedDiff::get() as i32
+		{
+			return None
+		}
+		let steps = now.saturating_sub(enqueued) / period;
+		Some(enqueued.saturating_add(steps.saturating_add(1u32.into()).saturating_mul(period)))
+	}
+
+	/// Match `who` again at block `at`, a full schedule leaves them to be found by newcomers.
+	fn schedule_recheck(who: &T::AccountId, at: T::BlockNumber) {
+		<QueueRechecks<T>>::mutate(at, |rechecks| {
+			if !rechecks.contains(who) {
+				let _ = rechecks.try_push(who.clone());
+			}
+		});
+	}
+
+	/// Find the longest waiting player, other than `who`, within `accepted_diff` of `score`
+	/// and in the same tier, along with the variant both want to play. With
+	/// `AdjacentTierFallback` the next tier up or down is tried next. Players who blocked each
+	/// other are never paired. Only the buckets that can hold such a player are read.
+	fn find_queued_opponent(
+		who: &T::AccountId,
+		score: i32,
+		accepted_diff: i32,
+	) -> Option<(T::AccountId, BoardVariant)> {
+		let reach = (accepted_diff + QUEUE_BUCKET_SIZE - 1) / QUEUE_BUCKET_SIZE;
+		let bucket = Self::score_bucket(score);
+		let preferred = <PlayerProfile<T>>::get(who).preferred_variant;
+		let boundaries = <TierBoundaries<T>>::get();
+		let tier = Self::tier_of(&boundaries, score);
+		let max_tier_gap = if <AdjacentTierFallback<T>>::get() { 1 } else { 0 };
+		let buckets = bucket.saturating_sub(reach)..=bucket.saturating_add(reach);
+
+		(0..=max_tier_gap).find_map(|tier_gap| {
+			buckets.clone().find_map(|candidate_bucket| {
+				<MatchQueue<T>>::get(candidate_bucket).into_inner().into_iter().find_map(
+					|(account, queued_score, ..)| {
+						let in_range =
+							queued_score.saturating_sub(score).saturating_abs() <= accepted_diff;
+						let in_tier =
+							Self::tier_of(&boundaries, queued_score).abs_diff(tier) == tier_gap;
+						let eligible = in_range && in_tier && &account != who;
+						if eligible && !Self::is_blocked(who, &account) {
+							Self::paired_variant(who, preferred, &account)
+								.map(|variant| (account, variant))
+						} else {
+							None
+						}
+					},
+				)
+			})
+		})
+	}
+
+	/// Tier of `score` between `boundaries`.
+	fn tier_of(boundaries: &[i32], score: i32) -> Tier {
+		boundaries.iter().filter(|boundary| score >= **boundary).count() as Tier
+	}
+
+	/// Longest waiting casual player, other than `who`, that neither blocked nor got blocked
+	/// by `who`, along with the variant both want to play.
+	fn find_casual_opponent(who: &T::AccountId) -> Option<(T::AccountId, BoardVariant)> {
+		let preferred = <PlayerProfile<T>>::get(who).preferred_variant;
+		<CasualQueue<T>>::get().into_iter().find_map(|account| {
+			if &account != who && !Self::is_blocked(who, &account) {
+				Self::paired_variant(who, preferred, &account).map(|variant| (account, variant))
+			} else {
+				None
+			}
+		})
+	}
+
+	/// Variant the queued players `who`, preferring `preferred`, and `opponent` can be paired
+	/// on. Players that asked for a preset only meet players asking for the same one and play
+	/// its variant, no matter what their profiles prefer.
+	fn paired_variant(
+		who: &T::AccountId,
+		preferred: Option<BoardVariant>,
+		opponent: &T::AccountId,
+	) -> Option<BoardVariant> {
+		match <QueuedPresets<T>>::get(who) {
+			preset_id if preset_id != <QueuedPresets<T>>::get(opponent) => None,
+			Some(preset_id) => <Presets<T>>::get(preset_id).map(|preset| preset.variant),
+			None => Self::shared_variant(preferred, opponent),
+		}
+	}
+
+	/// Variant a player preferring `preferred` and `opponent` can be paired on, `None` if
+	/// they prefer different ones. Without any preference the default variant is played.
+	fn shared_variant(
+		preferred: Option<BoardVariant>,
+		opponent: &T::AccountId,
+	) -> Option<BoardVariant> {
+		match (preferred, <PlayerProfile<T>>::get(opponent).preferred_variant) {
+			(Some(variant), Some(other)) if variant != other => None,
+			(variant, other) => Some(variant.or(other).unwrap_or_default()),
+		}
+	}
+
+	/// Pair players who joined the queue since the last pass with a waiting opponent.
+	///
+	/// Players that were already waiting can't match each other, otherwise an earlier pass
+	/// would have paired them, so only newcomers need to be checked, along with the ranked
+	/// players whose score tolerance widened at block `now`. Unmatched ranked players are
+	/// checked again at their next widening. At most `MaxMatchesPerBlock` games are created,
+	/// leftover players wait for the next block. Casual games are created with the default
+	/// award and no handicap, as scores don't matter for them.
+	fn match_queued_players(now: T::BlockNumber) -> Weight {
+		// Players stay queued until matchmaking resumes.
+		if <Paused<T>>::get() {
+			return T::DbWeight::get().reads(1)
+		}
+		let max_matches = T::MaxMatchesPerBlock::get();
+		let mut newcomers = <NewlyQueued<T>>::take();
+		let mut rechecks = <QueueRechecks<T>>::take(now);
+		let mut matches = 0u32;
+		let mut checked = 0u32;
+
+		while matches < max_matches && !(newcomers.is_empty() && rechecks.is_empty()) {
+			let player =
+				if newcomers.is_empty() { rechecks.remove(0) } else { newcomers.remove(0) };
+			checked += 1;
+			// Skip players that left the queue or already got matched this pass.
+			let found = match <QueuedPlayers<T>>::get(&player) {
+				Some(score) => {
+					let enqueued = Self::enqueued_at(&player, score).unwrap_or(now);
+					let accepted_diff = Self::widened_diff(enqueued, now);
+					let found = Self::find_queued_opponent(&player, score, accepted_diff);
+					if found.is_none() {
+						if let Some(at) = Self::next_widening(enqueued, now) {
+							Self::schedule_recheck(&player, at);
+						}
+					}
+					found.map(|(opponent, variant)| {
+						let opponent_score = <QueuedPlayers<T>>::get(&opponent).unwrap_or(score);
+						// Handicap stones may not fit a preferred variant, the weaker player
+						// moves first then.
+						let mode = match Self::matchmaking_handicap(score, opponent_score) {
+							Some((color, handicap)) if handicap.is_valid(&variant) =>
+								GameMode::Handicapped(color, handicap),
+							Some((color, _)) => GameMode::Handicapped(color, Handicap::FirstMove),
+							None => GameMode::Standard,
+						};
+						(opponent, variant, mode, GameKind::Ranked)
+					})
+				},
+				None if <CasualQueue<T>>::get().contains(&player) =>
+					Self::find_casual_opponent(&player).map(|(opponent, variant)| {
+						(opponent, variant, GameMode::Standard, GameKind::Casual)
+					}),
+				None => continue,
+			};
+			if let Some((opponent, variant, mode, kind)) = found {
+				let (award, time_bank) =
+					match <QueuedPresets<T>>::get(&player).and_then(<Presets<T>>::get) {
+						Some(preset) => (preset.award, preset.time_bank),
+						None => (<DefaultAward<T>>::get(), T::MatchmakingTimeBank::get()),
+					};
+				// The default variant fits the limits, see `integrity_test`, preferred ones
+				// got checked by `set_profile` and preset ones by `set_preset`.
+				let created = Self::atomically(|| {
+					Self::new_game(
+						player.clone(),
+						opponent.clone(),
+						award,
+						variant,
+						time_bank,
+						mode,
+						FirstMovePolicy::Random,
+						kind,
+					)
+				});
+				if let Ok(board_id) = created {
+					<Boards<T>>::mutate(board_id, |board| {
+						if let Some(board) = board {
+							board.weighted_award = kind == GameKind::Ranked;
+						}
+					});
+					// The deposits stay reserved until both players made their first move.
+					for matched in [&player, &opponent] {
+						Self::dequeue(matched);
+						<FirstMoveDeposits<T>>::insert(matched, T::QueueDeposit::get());
+					}
+					matches += 1;
+				}
+			}
+		}
+
+		if !newcomers.is_empty() {
+			<NewlyQueued<T>>::put(newcomers);
+		}
+		for player in rechecks {
+			Self::schedule_recheck(&player, now.saturating_add(1u32.into()));
+		}
+		T::WeightInfo::match_queued_players(checked)
+	}
+
+	/// Head start matchmaking gives the weaker of red scoring `red` and blue scoring `blue`.
+	fn matchmaking_handicap(red: i32, blue: i32) -> Option<(u8, Handicap)> {
+		let gap = T::HandicapScoreGap::get();
+		let diff = red.saturating_sub(blue).unsigned_abs();
+		if gap == 0 || diff < gap {
+			return None
+		}
+		let weaker = if red < blue { PLAYER_1 } else { PLAYER_2 };
+		let handicap =
+			if diff >= gap.saturating_mul(2) { Handicap::Stones(2) } else { Handicap::FirstMove };
+		Some((weaker, handicap))
+	}
+
+	/// End the current season once it has lasted `SeasonLength` blocks.
+	/// A new season only starts after the scores of the previous one are decayed.
+	fn roll_season(now: T::BlockNumber) -> Weight {
+		let length = T::SeasonLength::get();
+		if length.is_zero() ||
+			now.saturating_sub(<SeasonStart<T>>::get()) < length ||
+			<PendingDecay<T>>::exists()
+		{
+			return T::DbWeight::get().reads(2)
+		}
+
+		let ended = <CurrentSeason<T>>::get();
+		<CurrentSeason<T>>::put(ended.saturating_add(1));
+		<SeasonStart<T>>::put(now);
+		<PendingDecay<T>>::put((ended, None::<T::AccountId>));
+
+		Self::deposit_event(Event::SeasonEnded(ended));
+		T::DbWeight::get().reads_writes(3, 3)
+	}
+
+	/// A turn taking `elapsed` on the `GameClock` counts as stalling.
+	fn is_stalling(elapsed: MomentOf<T>) -> bool {
+		let threshold = T::StallTurnTime::get();
+		!threshold.is_zero() && elapsed >= threshold
 	}
 
-	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Find randome game
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn find_game(origin: OriginFor<T>) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	/// Start the turn of `next_player` now, on the `GameClock`.
+	fn start_turn(board: &mut BoardOf<T>) {
+		let now = T::GameClock::now();
+		board.last_turn = now;
+		board.deadline = Self::move_deadline(board, now);
+	}
 
-			// Make sure players have no board open.
-			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
-			// Make sure not a challenger
-			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::ReChallengeError);
-			// Make sure gamer is not available
-			ensure!(!<MatchQueue<T>>::contains_key(&sender), Error::<T>::MatchQueueError);
+	/// Last moment on the `GameClock` `next_player` may move at if their turn starts at
+	/// `turn_start`, `None` without time banks. A move later than that loses on time.
+	fn move_deadline(board: &BoardOf<T>, turn_start: MomentOf<T>) -> Option<MomentOf<T>> {
+		board.time_banks.as_ref().map(|time_banks| {
+			let bank = if board.next_player == PLAYER_1 { time_banks.red } else { time_banks.blue };
+			turn_start.saturating_add(bank)
+		})
+	}
+
+	/// `player` forfeits `board` by stalling the current turn, which took `elapsed` so
+	/// far, after as many stalled turns as `MaxStalledTurns` allows.
+	fn stalls_out(board: &BoardOf<T>, player: u8, elapsed: MomentOf<T>) -> bool {
+		let stalled =
+			if player == PLAYER_1 { board.stalled_turns.0 } else { board.stalled_turns.1 };
+		Self::is_stalling(elapsed) && stalled.saturating_add(1) >= T::MaxStalledTurns::get()
+	}
+
+	/// Drop the results of the games that finished `ResultRetentionBlocks` blocks ago,
+	/// returning the deposits of their annotations.
+	fn prune_results(now: T::BlockNumber) -> Weight {
+		let retention = T::ResultRetentionBlocks::get();
+		if retention.is_zero() || now < retention {
+			return 0
+		}
+		let mut released = 0u32;
+		let pruned = <ResultsByBlock<T>>::drain_prefix(now.saturating_sub(retention))
+			.map(|(board_id, _)| {
+				<ArchivedGames<T>>::remove(board_id);
+				<ArchivedSnapshots<T>>::remove(board_id);
+				for (annotator, _) in <GameAnnotations<T>>::take(board_id) {
+					T::Currency::unreserve(&annotator, T::AnnotationDeposit::get());
+					released += 1;
+				}
+			})
+			.count() as u32;
+		T::WeightInfo::prune_results(pruned)
+			.saturating_add(T::WeightInfo::release_annotations(released))
+	}
+
+	/// Drop the queue entries of players waiting since `QueueTimeout` blocks before `now`,
+	/// returning their deposits.
+	fn expire_queue(now: T::BlockNumber) -> Weight {
+		let expiring = <QueueExpiries<T>>::take(now);
+		let listed = expiring.len() as u32;
+		let timeout = T::QueueTimeout::get();
+		for player in expiring {
+			// The player may have left the queue, or joined it again expiring later.
+			if Self::queued_since(&player).map(|since| since.saturating_add(timeout)) != Some(now) {
+				continue
+			}
+			Self::dequeue(&player);
+			T::Currency::unreserve(&player, T::QueueDeposit::get());
+			Self::deposit_event(Event::QueueExpired(player));
+		}
+		T::WeightInfo::expire_queue(listed)
+	}
+
+	/// Drop the private games nobody joined before block `now`, returning their stakes.
+	fn expire_private_games(now: T::BlockNumber) -> Weight {
+		let expiring = <PrivateGameExpiries<T>>::take(now);
+		let listed = expiring.len() as u32;
+		for code_hash in expiring {
+			// The code may have been used again for a game expiring later.
+			let game = match <PrivateGames<T>>::get(code_hash) {
+				Some(game) if game.expires == now => game,
+				_ => continue,
+			};
+			<PrivateGames<T>>::remove(code_hash);
+			if let Some(stake) = game.settings.stake {
+				T::Currency::unreserve(&game.creator, stake);
+			}
+			Self::deposit_event(Event::PrivateGameExpired(code_hash, game.creator));
+		}
+		T::WeightInfo::expire_private_games(listed)
+	}
+
+	/// Snapshot the scores of the players of the tournaments closing registration in block
+	/// `now`, to seed their first round from.
+	fn snapshot_seeds(now: T::BlockNumber) -> Weight {
+		let mut players = 0u32;
+		for tournament_id in <TournamentCheckIns<T>>::take(now) {
+			let registering = <Tournaments<T>>::get(tournament_id)
+				.map_or(false, |tournament| tournament.state == TournamentState::Registration);
+			if !registering {
+				continue
+			}
+			let mut seeds: Vec<(T::AccountId, i32)> =
+				<TournamentPlayers<T>>::iter_key_prefix(tournament_id)
+					.map(|player| {
+						let score = <ScoringBoard<T>>::get(&player).unwrap_or(0);
+						(player, score)
+					})
+					.collect();
+			seeds.sort_by(|a, b| b.1.cmp(&a.1));
+			players += seeds.len() as u32;
+			if let Ok(seeds) = BoundedVec::try_from(seeds) {
+				<TournamentSeeds<T>>::insert(tournament_id, seeds);
+			}
+		}
+		T::WeightInfo::snapshot_seeds(players)
+	}
+
+	/// Decay up to `MaxDecayPerBlock` scores of the ended season, recording its best players
+	/// on the way.
+	fn decay_scores() -> Weight {
+		let (season, cursor) = match <PendingDecay<T>>::get() {
+			Some(pending) => pending,
+			None => return T::DbWeight::get().reads(1),
+		};
+
+		let mut scores = match &cursor {
+			Some(last) => <ScoringBoard<T>>::iter_from(<ScoringBoard<T>>::hashed_key_for(last)),
+			None => <ScoringBoard<T>>::iter(),
+		};
+		let retention = T::SeasonScoreRetention::get();
+		let max_standings = T::MaxSeasonRewardees::get() as usize;
+		let mut standings = <SeasonStandings<T>>::get(season).into_inner();
+		let mut last = cursor;
+		let mut decayed = 0u32;
+		let mut finished = false;
+
+		while decayed < T::MaxDecayPerBlock::get() {
+			let (player, score) = match scores.next() {
+				Some(entry) => entry,
+				None => {
+					finished = true;
+					break
+				},
+			};
+			// Ties keep the player that was ranked first.
+			let rank = standings.iter().position(|(_, other)| score > *other);
+			let rank = rank.unwrap_or(standings.len());
+			if rank < max_standings {
+				standings.insert(rank, (player.clone(), score));
+				standings.truncate(max_standings);
+			}
+
+			let kept = retention.mul_floor(score.unsigned_abs()) as i32;
+			Self::set_score(&player, Some(if score < 0 { -kept } else { kept }));
+			last = Some(player);
+			decayed += 1;
+		}
+
+		if let Ok(standings) = BoundedVec::try_from(standings) {
+			<SeasonStandings<T>>::insert(season, standings);
+		}
+		if finished {
+			<PendingDecay<T>>::kill();
+		} else {
+			<PendingDecay<T>>::put((season, last));
+		}
+		T::WeightInfo::decay_scores(decayed)
+	}
+
+	/// Check up to `MaxInactivityChecks` scores, continuing after the last one checked, and
+	/// decay the positive scores of accounts that haven't finished a game in
+	/// `InactivityPeriod` blocks. Accounts the check sees for the first time count as active.
+	fn decay_inactive_scores(now: T::BlockNumber) -> Weight {
+		let period = T::InactivityPeriod::get();
+		if period.is_zero() {
+			return 0
+		}
+
+		let cursor = <InactivityCursor<T>>::get();
+		let mut scores = match &cursor {
+			Some(last) => <ScoringBoard<T>>::iter_from(<ScoringBoard<T>>::hashed_key_for(last)),
+			None => <ScoringBoard<T>>::iter(),
+		};
+		let retention = T::InactivityScoreRetention::get();
+		let mut last = cursor;
+		let mut checked = 0u32;
+
+		while checked < T::MaxInactivityChecks::get() {
+			let (player, score) = match scores.next() {
+				Some(entry) => entry,
+				None => {
+					// Start over in the next block.
+					last = None;
+					break
+				},
+			};
+			match <LastActive<T>>::get(&player) {
+				Some(active) if now.saturating_sub(active) < period => (),
+				Some(_) if score > 0 => {
+					let decayed = retention.mul_floor(score as u32) as i32;
+					Self::set_score(&player, Some(decayed));
+					<LastActive<T>>::insert(&player, now);
+					Self::deposit_event(Event::ScoreDecayed(player.clone(), score, decayed));
+				},
+				Some(_) => (),
+				None => <LastActive<T>>::insert(&player, now),
+			}
+			last = Some(player);
+			checked += 1;
+		}
+
+		match last {
+			Some(last) => <InactivityCursor<T>>::put(last),
+			None => <InactivityCursor<T>>::kill(),
+		}
+		T::WeightInfo::decay_inactive_scores(checked)
+	}
+
+	/// Board a player is currently playing on.
+	pub fn player_board_id(who: &T::AccountId) -> Option<T::Hash> {
+		<PlayerBoard<T>>::try_get(who).ok()
+	}
+
+	/// Summaries of the boards `accounts` are playing on, in the same order, `None` for
+	/// accounts that aren't playing.
+	pub fn boards_of(accounts: Vec<T::AccountId>) -> Vec<Option<BoardSummaryOf<T>>> {
+		accounts
+			.iter()
+			.map(|who| {
+				let board = <Boards<T>>::get(Self::player_board_id(who)?)?;
+				let next_player = board.account_at_turn(board.next_player).clone();
+				Some(BoardSummary {
+					id: board.id,
+					red: board.red,
+					blue: board.blue,
+					next_player,
+					moves_played: board.moves_played,
+					deadline: board.deadline,
+					kind: board.kind,
+				})
+			})
+			.collect()
+	}
+
+	/// Ids of up to `limit` boards being played, at most `MAX_BOARD_IDS_PAGE`, following
+	/// `start_key` in storage order. Pass the last id of a page to get the next one, an empty
+	/// page means all boards have been seen.
+	pub fn active_board_ids(start_key: Option<T::Hash>, limit: u32) -> Vec<T::Hash> {
+		let limit = limit.min(MAX_BOARD_IDS_PAGE) as usize;
+		match start_key {
+			Some(last) => <Boards<T>>::iter_keys_from(<Boards<T>>::hashed_key_for(last))
+				.take(limit)
+				.collect(),
+			None => <Boards<T>>::iter_keys().take(limit).collect(),
+		}
+	}
+
+	/// Row a stone of `who` dropped into `column` would land on, or why it can't be played
+	/// right now. Nothing is written, so clients can check moves before sending them.
+	pub fn dry_run_move(who: &T::AccountId, column: u8) -> Result<u8, MoveError> {
+		let board_id = Self::player_board_id(who).ok_or(MoveError::NoPlayerBoard)?;
+		let board = <Boards<T>>::get(board_id).ok_or(MoveError::NoPlayerBoard)?;
+		ensure!(board.board_state == BoardState::Running, MoveError::GameNotRunning);
+		ensure!(board.blitz.is_none(), MoveError::BlitzGame);
+		ensure!(board.account_at_turn(board.next_player) == who, MoveError::NotPlayerTurn);
+		ensure!(column < board.variant.width, MoveError::ColumnOutOfRange);
+
+		let elapsed = T::GameClock::now().saturating_sub(board.last_turn);
+		let out_of_time = board.time_banks.as_ref().map_or(false, |time_banks| {
+			elapsed > if board.next_player == PLAYER_1 { time_banks.red } else { time_banks.blue }
+		});
+		ensure!(
+			!out_of_time && !Self::stalls_out(&board, board.next_player, elapsed),
+			MoveError::OutOfTime
+		);
+		Logic::landing_row(&board.board, column).ok_or(MoveError::ColumnFull)
+	}
+
+	/// Heuristic scores of the open columns of a running board for the player at turn, see
+	/// `Logic::column_scores`.
+	pub fn evaluate_position(board_id: T::Hash) -> Option<Vec<ColumnScore>> {
+		let board = <Boards<T>>::get(board_id)?;
+		if board.board_state != BoardState::Running {
+			return None
+		}
+		Some(Logic::column_scores(&board.board, board.next_player, board.variant.connect))
+	}
+
+	/// Columns a stone can be dropped into on a running board as bits, the lowest for column
+	/// `0`, see `Logic::open_column_mask`. `0` unless the board is being played.
+	pub fn legal_moves(board_id: T::Hash) -> u32 {
+		<Boards<T>>::get(board_id)
+			.filter(|board| board.board_state == BoardState::Running)
+			.map_or(0, |board| Logic::open_column_mask(&board.board))
+	}
+
+	/// Result of the position on a board by the rules, see `Logic::outcome`. Finished games
+	/// are replayed from `ArchivedGames`, games decided on time, by forfeit or by a ruling
+	/// didn't end in a terminal position and give `None`.
+	pub fn is_terminal(board_id: T::Hash) -> Option<Outcome> {
+		if let Some(board) = <Boards<T>>::get(board_id) {
+			return Logic::outcome(&board.board, board.variant.connect)
+		}
+		let record = notation::GameRecord::from_bytes(&<ArchivedGames<T>>::get(board_id)?)?;
+		Logic::outcome(&record.replay()?, record.connect)
+	}
+
+	/// Board of an archived game after its first `moves` moves, fast-forwarded from the
+	/// latest snapshot before them.
+	pub fn archived_position(board_id: T::Hash, moves: u32) -> Option<Board> {
+		let record = notation::GameRecord::from_bytes(&<ArchivedGames<T>>::get(board_id)?)?;
+		let snapshots = <ArchivedSnapshots<T>>::get(board_id);
+		record.position(moves as usize, &snapshots, T::SnapshotInterval::get() as usize)
+	}
+
+	/// The `n` highest scores, best first, at most `MaxLeaderboardSize` of them.
+	pub fn leaderboard(n: u32) -> Vec<(T::AccountId, i32)> {
+		let mut scores = <Leaderboard<T>>::get().into_inner();
+		scores.truncate(n as usize);
+		scores
+	}
+
+	/// Same as `leaderboard`, with the identity display name of every account.
+	pub fn named_leaderboard(n: u32) -> Vec<(T::AccountId, i32, Option<Vec<u8>>)> {
+		Self::leaderboard(n)
+			.into_iter()
+			.map(|(account, score)| {
+				let name = T::Identity::display_name(&account);
+				(account, score, name)
+			})
+			.collect()
+	}
+
+	/// Match queue entry of a player, `None` if they aren't waiting for a game.
+	pub fn queue_status(who: &T::AccountId) -> Option<QueueStatus> {
+		if let Some(score) = <QueuedPlayers<T>>::get(who) {
+			let waiting =
+				<MatchQueue<T>>::decode_len(Self::score_bucket(score)).unwrap_or_default() as u32;
+			return Some(QueueStatus { score, waiting })
+		}
+		let casual_queue = <CasualQueue<T>>::get();
+		if !casual_queue.contains(who) {
+			return None
+		}
+		let score = <ScoringBoard<T>>::get(who).unwrap_or(0);
+		Some(QueueStatus { score, waiting: casual_queue.len() as u32 })
+	}
+
+	/// Place of `who` in the queue they wait in, `1` for the player waiting longest. Ranked
+	/// players are counted across all score buckets.
+	pub fn queue_position(who: &T::AccountId) -> Option<u32> {
+		if let Some(score) = <QueuedPlayers<T>>::get(who) {
+			let (.., ticket) = <MatchQueue<T>>::get(Self::score_bucket(score))
+				.into_iter()
+				.find(|(account, ..)| account == who)?;
+			let ahead = <MatchQueue<T>>::iter_values()
+				.flat_map(|queue| queue.into_inner())
+				.filter(|(.., other)| *other < ticket)
+				.count();
+			return Some(ahead as u32 + 1)
+		}
+		<CasualQueue<T>>::get()
+			.iter()
+			.position(|account| account == who)
+			.map(|index| index as u32 + 1)
+	}
+
+	/// Settle the reported game `board_id` by `verdict`, for oracles ruling on the reports
+	/// they're told about through `ReportHandler`.
+	pub fn rule_on_report(board_id: T::Hash, verdict: Verdict) -> DispatchResult {
+		let dispute = <Disputes<T>>::take(board_id).ok_or(Error::<T>::DisputeNotFound)?;
+		let board = dispute.board;
+		let winner = match (verdict, &board.board_state) {
+			(Verdict::Upheld, _) => Some(dispute.report.reporter),
+			(Verdict::Dismissed, BoardState::Finished(winner)) => winner.clone(),
+			_ => None,
+		};
+		Self::settle_result(&board, winner.as_ref(), dispute.finish);
+		let stake_fee = dispute.stake.map_or_else(Zero::zero, |stake| {
+			Self::settle_stakes(&board, winner.as_ref(), stake, dispute.asset)
+		});
+		Self::deposit_event(Event::ReportResolved(board_id, verdict, winner, stake_fee));
+		Ok(())
+	}
+
+	/// Add the win award to the winner's score and take the lose award from the loser.
+	fn settle_scores(
+		board_id: T::Hash,
+		winner: &T::AccountId,
+		loser: &T::AccountId,
+		award: &AwardState,
+	) {
+		let winner_score = <ScoringBoard<T>>::get(winner).unwrap_or(0);
+		let win = Self::provisional_award(winner, award.win);
+		Self::set_game_score(winner, Some(winner_score.saturating_add(win)), Some(board_id));
+		let loser_score = <ScoringBoard<T>>::get(loser).unwrap_or(0);
+		let lose = Self::provisional_award(loser, award.lose);
+		// A score already below the floor doesn't get raised by losing.
+		let floor = T::ScoreFloor::get().min(loser_score);
+		let lost = loser_score.saturating_sub(lose).max(floor);
+		Self::set_game_score(loser, Some(lost), Some(board_id));
+		Self::earn_reward_points(winner, award.win);
+	}
+
+	/// Add the draw award to the score of a player of a drawn game.
+	fn settle_draw(board_id: T::Hash, player: &T::AccountId, award: &AwardState) {
+		let score = <ScoringBoard<T>>::get(player).unwrap_or(0);
+		let draw = Self::provisional_award(player, award.draw);
+		Self::set_game_score(player, Some(score.saturating_add(draw)), Some(board_id));
+		Self::earn_reward_points(player, award.draw);
+	}
+
+	/// Add `points` to the reward points of `who`, they vest after `RewardVestingBlocks`.
+	fn earn_reward_points(who: &T::AccountId, points: u32) {
+		if points == 0 {
+			return
+		}
+		<RewardPoints<T>>::mutate(who, |rewards| {
+			let rewards = rewards.get_or_insert_with(Default::default);
+			Self::vest_rewards(rewards);
+			if rewards.pending == 0 {
+				rewards.vests_at =
+					<frame_system::Pallet<T>>::block_number() + T::RewardVestingBlocks::get();
+			}
+			rewards.pending = rewards.pending.saturating_add(points);
+		});
+	}
+
+	/// Make the pending points of `rewards` claimable once they vested.
+	fn vest_rewards(rewards: &mut RewardBalanceOf<T>) {
+		if rewards.vests_at <= <frame_system::Pallet<T>>::block_number() {
+			rewards.points = rewards.points.saturating_add(sp_std::mem::take(&mut rewards.pending));
+		}
+	}
+
+	/// The score of `who` is provisional, they haven't finished their placement games yet.
+	pub fn is_provisional(who: &T::AccountId) -> bool {
+		<PlayerStats<T>>::get(who).ranked < T::ProvisionalGames::get()
+	}
+
+	/// `award` for `who`, boosted during their placement games. The boost starts at
+	/// `ProvisionalBoost` and shrinks with every placement game played, so scores settle
+	/// down as they get more reliable.
+	fn provisional_award(who: &T::AccountId, award: u32) -> i32 {
+		let award = award.saturated_into::<i32>();
+		let placement = T::ProvisionalGames::get();
+		let played = <PlayerStats<T>>::get(who).ranked;
+		if played >= placement {
+			return award
+		}
+		let extra_factor = (T::ProvisionalBoost::get() as i64).saturating_sub(1).max(0);
+		let remaining = (placement - played) as i64;
+		let extra = award as i64 * extra_factor * remaining / placement as i64;
+		award.saturating_add(extra.saturated_into())
+	}
+
+	/// Store the score of `who`, `None` removes it, and keep the leaderboard in line.
+	fn set_score(who: &T::AccountId, score: Option<i32>) {
+		Self::set_game_score(who, score, None)
+	}
+
+	/// Same as `set_score`, noting the game `board_id` that changed the score in its history.
+	fn set_game_score(who: &T::AccountId, score: Option<i32>, board_id: Option<T::Hash>) {
+		let old_score = <ScoringBoard<T>>::get(who).unwrap_or(0);
+		let boundaries = <TierBoundaries<T>>::get();
+		let old_tier = Self::tier_of(&boundaries, old_score);
+		let new_tier = Self::tier_of(&boundaries, score.unwrap_or(0));
+		if old_tier != new_tier {
+			Self::deposit_event(Event::TierChanged(who.clone(), old_tier, new_tier));
+		}
+		match score {
+			Some(score) => <ScoringBoard<T>>::insert(who, score),
+			None => <ScoringBoard<T>>::remove(who),
+		}
+		Self::update_leaderboard(who, score);
+		let delta = score.unwrap_or(0).saturating_sub(old_score);
+		if delta != 0 {
+			Self::record_score_change(who, delta, board_id);
+			// Only game results count towards the guild rating.
+			if let (Some(_), Some(guild_id)) = (board_id, <PlayerGuild<T>>::get(who)) {
+				Self::add_guild_rating(guild_id, delta);
+			}
+		}
+	}
+
+	/// Append a score change to the history of `who`, dropping the oldest once it's full.
+	fn record_score_change(who: &T::AccountId, delta: i32, board_id: Option<T::Hash>) {
+		let block = <frame_system::Pallet<T>>::block_number();
+		<ScoreHistory<T>>::mutate(who, |history| {
+			if history.len() as u32 >= T::MaxScoreHistory::get() && !history.is_empty() {
+				history.remove(0);
+			}
+			// Only fails without any room for history.
+			let _ = history.try_push(ScoreChange { block, delta, board_id });
+		});
+	}
+
+	/// Move `who` to the rank matching their new score, or drop them from the leaderboard.
+	/// Accounts below the leaderboard only enter it once their own score changes, provisional
+	/// ones once they completed their placement.
+	fn update_leaderboard(who: &T::AccountId, score: Option<i32>) {
+		let score = score.filter(|_| !Self::is_provisional(who));
+		let max_size = T::MaxLeaderboardSize::get() as usize;
+		let mut leaderboard = <Leaderboard<T>>::get().into_inner();
+		let old_rank = leaderboard.iter().position(|(account, _)| account == who);
+		if let Some(rank) = old_rank {
+			leaderboard.remove(rank);
+		}
+		// Ties keep the account that got there first.
+		let new_rank = score.and_then(|score| {
+			let rank = leaderboard
+				.iter()
+				.position(|(_, other)| score > *other)
+				.unwrap_or(leaderboard.len());
+			if rank < max_size {
+				leaderboard.insert(rank, (who.clone(), score));
+				leaderboard.truncate(max_size);
+				Some(rank as u32)
+			} else {
+				None
+			}
+		});
+		if old_rank.is_none() && new_rank.is_none() {
+			return
+		}
+
+		if let Ok(leaderboard) = BoundedVec::try_from(leaderboard) {
+			<Leaderboard<T>>::put(leaderboard);
+		}
+		Self::deposit_event(Event::LeaderboardUpdated(who.clone(), new_rank));
+	}
+
+	/// Account `sender` moves for: their own unless they don't play and are the play proxy
+	/// of another account.
+	fn mover(sender: T::AccountId) -> T::AccountId {
+		if <PlayerBoard<T>>::contains_key(&sender) {
+			return sender
+		}
+		<PlayProxies<T>>::get(&sender).unwrap_or(sender)
+	}
+
+	/// Play the turn of `sender` in `column`: drop a stone, or take one out of the bottom row
+	/// if `pop` is set. Ending the game costs the full weight of the call, other moves only
+	/// pay for what they did.
+	fn take_turn(
+		sender: T::AccountId,
+		column: u8,
+		pop: bool,
+		expected_move_index: Option<u16>,
+	) -> DispatchResultWithPostInfo {
+		// TODO: should PlayerBoard storage here be optional to avoid two reads?
+		ensure!(PlayerBoard::<T>::contains_key(&sender), Error::<T>::NoPlayerBoard);
+		let board_id = Self::player_board(&sender);
+
+		// Get board from player.
+		let mut board = Self::boards(&board_id).ok_or(Error::<T>::BoardNotFound)?;
+
+		ensure!(column < board.variant.width, Error::<T>::ColumnOutOfRange);
+		ensure!(
+			expected_move_index.map_or(true, |index| index == board.moves_played),
+			Error::<T>::StaleMove
+		);
+
+		// Board is still open to play and not finished.
+		ensure!(
+			board.board_state == BoardState::Running,
+			"Board is not running, check if already finished."
+		);
+		// Blitz moves go through `commit_move` and `reveal_move`.
+		ensure!(board.blitz.is_none(), Error::<T>::BlitzGame);
+		ensure!(!matches!(board.cube, DoublingCube::Offered(_)), Error::<T>::DoublePending);
+		ensure!(!<FirstMoveDraws<T>>::contains_key(board_id), Error::<T>::FirstMoveNotDrawn);
+		ensure!(!pop || (board.variant.pop_out && board.red_mate.is_none()), Error::<T>::NotPopOut);
+		// The first mover has no stone of their own to pop, handicap stones go to the other.
+		ensure!(!pop || board.moves_played > 0, Error::<T>::CannotPop);
+
+		let current_player = board.next_player;
+		let other_player = match current_player {
+			PLAYER_1 => PLAYER_2,
+			PLAYER_2 => PLAYER_1,
+			_ => return Err(Error::<T>::WrongLogic)?,
+		};
+
+		// Check if correct player is at turn
+		let current_account = board.account_at_turn(current_player).clone();
+		let last_account = board.account_at_turn(other_player).clone();
+		board.next_player = other_player;
+
+		// Make sure current account is at turn.
+		ensure!(sender == current_account, Error::<T>::NotPlayerTurn);
+
+		// Charge the time spent on this move to the player's clock.
+		let elapsed = T::GameClock::now().saturating_sub(board.last_turn);
+		let out_of_time = match board.time_banks.as_mut() {
+			Some(time_banks) => {
+				let bank = if current_player == PLAYER_1 {
+					&mut time_banks.red
+				} else {
+					&mut time_banks.blue
+				};
+				let out_of_time = elapsed > *bank;
+				*bank = bank.saturating_sub(elapsed);
+				out_of_time
+			},
+			None => false,
+		};
+		if out_of_time {
+			// The move came too late, the opponent wins on time.
+			board.board_state = BoardState::Finished(Some(last_account));
+			Self::finish_game(board, FinishReason::Timeout);
+			return Ok(().into())
+		}
+		if Self::stalls_out(&board, current_player, elapsed) {
+			board.board_state = BoardState::Finished(Some(last_account));
+			Self::finish_game(board, FinishReason::Stalled);
+			return Ok(().into())
+		}
+		if Self::is_stalling(elapsed) {
+			let stalled = if current_player == PLAYER_1 {
+				&mut board.stalled_turns.0
+			} else {
+				&mut board.stalled_turns.1
+			};
+			*stalled = stalled.saturating_add(1);
+		}
+
+		// The rules decide which moves are left, placing the stone can't fail afterwards.
+		let mv = if pop { Move::Pop(column) } else { Move::Drop(column) };
+		ensure!(
+			Logic::legal_moves(&board.board, current_player, pop).contains(&mv),
+			if pop { Error::<T>::CannotPop } else { Error::<T>::ColumnFull }
+		);
+
+		// Showing up for the game earns the queue deposit back.
+		if let Some(deposit) = <FirstMoveDeposits<T>>::take(&sender) {
+			T::Currency::unreserve(&sender, deposit);
+		}
+		let finished = if pop {
+			Self::pop_bottom_stone(&mut board, current_account, current_player, column)?
+		} else {
+			Self::place_stone(&mut board, current_account, current_player, column)?
+		};
+		if let Some(reason) = finished {
+			Self::finish_game(board, reason);
+			return Ok(().into())
+		}
+
+		// The pallet answers right away in bot games.
+		if let Some(difficulty) = board.bot {
+			let bot_column = Self::bot_move(&board, difficulty).ok_or(Error::<T>::WrongLogic)?;
+			board.next_player = current_player;
+			if let Some(reason) = Self::place_stone(&mut board, last_account, PLAYER_2, bot_column)?
+			{
+				Self::finish_game(board, reason);
+				return Ok(().into())
+			}
+		}
+
+		let actual_weight = if board.bot.is_some() {
+			T::WeightInfo::play_turn_vs_bot()
+		} else if pop {
+			T::WeightInfo::pop_stone_mid_game()
+		} else {
+			T::WeightInfo::play_turn_mid_game()
+		};
+		// Bot games emit a snapshot for the answer of the bot as well.
+		let snapshots = match board.verbosity {
+			EventVerbosity::FullBoard => 1 + board.bot.is_some() as u64,
+			EventVerbosity::Delta => 0,
+		};
+		let actual_weight =
+			actual_weight.saturating_add(T::WeightInfo::board_snapshot().saturating_mul(snapshots));
+		Self::start_turn(&mut board);
+		// Write next board state back into the storage
+		<Boards<T>>::insert(board_id, board);
+		Ok(Some(actual_weight).into())
+	}
+
+	/// Player at turn on the board of a relayed move, if they signed it for the current turn
+	/// of a casual game.
+	fn relayed_player(
+		payload: &RelayedMoveOf<T>,
+		signature: &T::OffchainSignature,
+	) -> Result<T::AccountId, DispatchError> {
+		let board = Self::boards(payload.board_id).ok_or(Error::<T>::BoardNotFound)?;
+		ensure!(board.kind == GameKind::Casual, Error::<T>::NotCasualGame);
+		ensure!(payload.move_index == board.turns, Error::<T>::StaleMove);
+		let player = board.account_at_turn(board.next_player);
+		ensure!(signature.verify(&payload.encode()[..], player), Error::<T>::InvalidSignature);
+		Ok(player.clone())
+	}
+
+	/// Drop a stone of `player` into `column`, returns why the game ended if it did.
+	fn place_stone(
+		board: &mut BoardOf<T>,
+		account: T::AccountId,
+		player: u8,
+		column: u8,
+	) -> Result<Option<FinishReason>, DispatchError> {
+		// Check if we can successfully place a stone in that column
+		let row =
+			Logic::add_stone(&mut board.board, column, player).ok_or(Error::<T>::WrongLogic)?;
+		board.turns = board.turns.saturating_add(1);
+		board.moves_played = board.moves_played.saturating_add(1);
+		board.last_move = Some(column);
+		Self::log_move(board.id, player, Move::Drop(column));
+		board.position = Self::position_hash(&board.board, board.next_player);
+		// A pending undo request was about the previous move.
+		<UndoRequests<T>>::remove(board.id);
+		// Only games someone broadcasts are worth the extra work.
+		let evaluation = Self::is_streamed(board.id)
+			.then(|| Self::stream_move(board, notation::encode_move(player, Move::Drop(column))));
+		Self::deposit_event(Event::TurnPlayed {
+			board_id: board.id,
+			player: account.clone(),
+			column,
+			row,
+			position: board.position,
+			deadline: Self::move_deadline(board, T::GameClock::now()),
+			moves_played: board.moves_played,
+			evaluation,
+		});
+		Self::snapshot_board(board);
+
+		// Check if the last played stone gave us a winner or board is full
+		if Logic::evaluate(&board.board, player, board.variant.connect).is_some() {
+			board.board_state = BoardState::Finished(Some(account));
+			Ok(Some(FinishReason::Connected))
+		} else if Logic::full(&board.board) {
+			board.board_state = BoardState::Finished(None);
+			Ok(Some(FinishReason::BoardFull))
+		} else if board.moves_played as u32 >= T::MaxMovesPerGame::get() {
+			board.board_state = BoardState::Finished(None);
+			Ok(Some(FinishReason::MoveLimit))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Take the bottom stone of `player` out of `column`, returns why the game ended if it did.
+	/// Lines connected for both players by the stones dropping down count for the opponent.
+	fn pop_bottom_stone(
+		board: &mut BoardOf<T>,
+		account: T::AccountId,
+		player: u8,
+		column: u8,
+	) -> Result<Option<FinishReason>, DispatchError> {
+		ensure!(Logic::pop_stone(&mut board.board, column, player), Error::<T>::CannotPop);
+		board.turns = board.turns.saturating_add(1);
+		board.moves_played = board.moves_played.saturating_add(1);
+		Self::log_move(board.id, player, Move::Pop(column));
+		// Undo only takes back dropped stones.
+		board.last_move = None;
+		board.position = Self::position_hash(&board.board, board.next_player);
+		<UndoRequests<T>>::remove(board.id);
+		if Self::is_streamed(board.id) {
+			Self::stream_move(board, notation::encode_move(player, Move::Pop(column)));
+		}
+		Self::deposit_event(Event::StonePopped {
+			board_id: board.id,
+			player: account.clone(),
+			column,
+			position: board.position,
+			moves_played: board.moves_played,
+		});
+		Self::snapshot_board(board);
+
+		let opponent = if player == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+		let connect = board.variant.connect;
+		if Logic::evaluate(&board.board, opponent, connect).is_some() {
+			let winner = board.account_at_turn(opponent).clone();
+			board.board_state = BoardState::Finished(Some(winner));
+			Ok(Some(FinishReason::Connected))
+		} else if Logic::evaluate(&board.board, player, connect).is_some() {
+			board.board_state = BoardState::Finished(Some(account));
+			Ok(Some(FinishReason::Connected))
+		} else if board.moves_played as u32 >= T::MaxMovesPerGame::get() {
+			board.board_state = BoardState::Finished(None);
+			Ok(Some(FinishReason::MoveLimit))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Emit the whole board after a move if its players asked for it, with the clock of the
+	/// next turn already started.
+	fn snapshot_board(board: &BoardOf<T>) {
+		if board.verbosity == EventVerbosity::FullBoard {
+			let mut snapshot = board.clone();
+			Self::start_turn(&mut snapshot);
+			Self::deposit_event(Event::BoardSnapshot(board.id, snapshot));
+		}
+	}
+
+	/// Moves can be taken back in casual games and games without an award between people,
+	/// unless there's a stake or a prediction on the game, or both players move at once as in
+	/// blitz games.
+	fn undo_allowed(board: &BoardOf<T>) -> bool {
+		board.bot.is_none() &&
+			board.blitz.is_none() &&
+			(board.kind == GameKind::Casual ||
+				board.award == AwardState { win: 0, lose: 0, draw: 0 }) &&
+			!<BoardStakes<T>>::contains_key(board.id) &&
+			!<Predictions<T>>::contains_key(board.id) &&
+			!<TournamentBoards<T>>::contains_key(board.id)
+	}
+
+	/// The board is running and nobody moved on it for `StaleGameAge` on the `GameClock`.
+	fn is_stale(board: &BoardOf<T>) -> bool {
+		let age = T::StaleGameAge::get();
+		!age.is_zero() &&
+			board.board_state == BoardState::Running &&
+			T::GameClock::now().saturating_sub(board.last_turn) >= age
+	}
+
+	/// Color holding up a board. In blitz games that's the only player still to commit or
+	/// reveal, `None` if both are.
+	fn stalling_player(board: &BoardOf<T>) -> Option<u8> {
+		match &board.blitz {
+			Some(round) => match (round.has_moved(PLAYER_1), round.has_moved(PLAYER_2)) {
+				(true, false) => Some(PLAYER_2),
+				(false, true) => Some(PLAYER_1),
+				_ => None,
+			},
+			None => Some(board.next_player),
+		}
+	}
+
+	/// Submit an unsigned `expire_stale_game` for stale boards, at most `MAX_STALE_EXPIRIES`
+	/// per run.
+	fn submit_stale_expiries() {
+		if T::StaleGameAge::get().is_zero() {
+			return
+		}
+		let stale = <Boards<T>>::iter_values()
+			.filter(|board| Self::is_stale(board))
+			.take(MAX_STALE_EXPIRIES);
+		for board in stale {
+			let call = Call::expire_stale_game { board_id: board.id };
+			// A rejected submission is retried on the next run.
+			let _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+		}
+	}
+
+	/// Pair the next round of a tournament and start its games. The first round is seeded
+	/// from `TournamentSeeds`, later rounds pair players with similar points.
+	///
+	/// Players that are busy on another board when the round starts forfeit their game.
+	fn start_round(tournament_id: u32, tournament: &mut TournamentOf<T>) -> DispatchResult {
+		let mut standings: Vec<(T::AccountId, SwissStandingOf<T>)> =
+			<TournamentPlayers<T>>::iter_prefix(tournament_id).collect();
+		let seeds = if tournament.round == 0 {
+			<TournamentSeeds<T>>::take(tournament_id)
+		} else {
+			Default::default()
+		};
+		// Tournaments created before seeding was introduced have no snapshot.
+		let round = if seeds.is_empty() {
+			let points: Vec<u32> = standings.iter().map(|(_, standing)| standing.points).collect();
+			Swiss::pair(
+				&points,
+				|a, b| standings[a].1.opponents.contains(&standings[b].0),
+				|player| standings[player].1.had_bye,
+			)
+		} else {
+			let ratings: Vec<i32> = standings
+				.iter()
+				.map(|(player, _)| {
+					seeds.iter().find(|(seeded, _)| seeded == player).map_or(0, |(_, score)| *score)
+				})
+				.collect();
+			Swiss::seed(&ratings)
+		};
+
+		tournament.round += 1;
+		let mut pairings = BoundedVec::default();
+		let mut boards = BoundedVec::default();
+		for (a, b) in round.pairs {
+			let (red, blue) = (standings[a].0.clone(), standings[b].0.clone());
+			for (player, opponent) in [(a, &blue), (b, &red)] {
+				standings[player]
+					.1
+					.opponents
+					.try_push(opponent.clone())
+					.map_err(|_| Error::<T>::InvalidRounds)?;
+			}
+			match (<PlayerBoard<T>>::contains_key(&red), <PlayerBoard<T>>::contains_key(&blue)) {
+				(false, false) => {
+					boards
+						.try_push((red.clone(), blue.clone()))
+						.map_err(|_| Error::<T>::TournamentFull)?;
+					tournament.pending += 1;
+				},
+				(true, false) => standings[b].1.points += swiss::WIN_POINTS,
+				(false, true) => standings[a].1.points += swiss::WIN_POINTS,
+				(true, true) => {},
+			}
+			pairings.try_push((red, blue)).map_err(|_| Error::<T>::TournamentFull)?;
+		}
+		let bye = round.bye.map(|player| {
+			standings[player].1.points += swiss::WIN_POINTS;
+			standings[player].1.had_bye = true;
+			standings[player].0.clone()
+		});
+
+		for (player, standing) in standings {
+			<TournamentPlayers<T>>::insert(tournament_id, player, standing);
+		}
+		if !seeds.is_empty() {
+			Self::deposit_event(Event::TournamentSeeded(tournament_id, pairings.clone()));
+		}
+		<TournamentRounds<T>>::insert(tournament_id, tournament.round, pairings);
+		// Boards of a large round may not fit into one block, see `create_pending_boards`.
+		<PendingBoardCreations<T>>::insert(tournament_id, boards);
+		Self::deposit_event(Event::TournamentRoundStarted(tournament_id, tournament.round, bye));
+		Ok(())
+	}
+
+	/// Create the boards of paired tournament games, oldest tournament first, until
+	/// `MaxBoardCreationsPerBlock` boards are created.
+	fn create_pending_boards() -> Weight {
+		// Games wait for new games to be allowed again.
+		if <Paused<T>>::get() {
+			return T::DbWeight::get().reads(1)
+		}
+		let max_boards = T::MaxBoardCreationsPerBlock::get();
+		let mut created = 0u32;
+		// Games also wait for running ones to free up boards.
+		while created < max_boards && !Self::board_limit_reached() {
+			let (tournament_id, mut pairs) = match <PendingBoardCreations<T>>::iter().next() {
+				Some(pending) => pending,
+				None => break,
+			};
+			let mut tournament = match <Tournaments<T>>::get(tournament_id) {
+				Some(tournament) => tournament,
+				None => {
+					<PendingBoardCreations<T>>::remove(tournament_id);
+					continue
+				},
+			};
+			while created < max_boards && !pairs.is_empty() {
+				let (red, blue) = pairs.remove(0);
+				Self::create_tournament_board(tournament_id, &mut tournament, red, blue);
+				created += 1;
+			}
+			if pairs.is_empty() {
+				<PendingBoardCreations<T>>::remove(tournament_id);
+			} else {
+				<PendingBoardCreations<T>>::insert(tournament_id, pairs);
+			}
+			<Tournaments<T>>::insert(tournament_id, tournament);
+		}
+		T::WeightInfo::create_pending_boards(created)
+	}
+
+	/// Start a paired tournament game. A player that got onto another board since the round
+	/// was paired forfeits, the same as if they were busy at the pairing.
+	fn create_tournament_board(
+		tournament_id: u32,
+		tournament: &mut TournamentOf<T>,
+		red: T::AccountId,
+		blue: T::AccountId,
+	) {
+		let busy = |who: &T::AccountId| <PlayerBoard<T>>::contains_key(who);
+		let winner = match (busy(&red), busy(&blue)) {
+			(false, false) => {
+				let award = tournament.award.clone();
+				let created =
+					Self::create_game(red, blue, award, tournament.variant, tournament.time_bank);
+				match created {
+					Ok(board_id) => {
+						<TournamentBoards<T>>::insert(board_id, tournament_id);
+						return
+					},
+					// Not expected for a checked variant, the game is dropped without a result.
+					Err(_) => None,
+				}
+			},
+			(true, false) => Some(blue),
+			(false, true) => Some(red),
+			(true, true) => None,
+		};
+		tournament.pending = tournament.pending.saturating_sub(1);
+		if let Some(winner) = winner {
+			<TournamentPlayers<T>>::mutate(tournament_id, winner, |standing| {
+				if let Some(standing) = standing {
+					standing.points += swiss::WIN_POINTS;
+				}
+			});
+		}
+	}
+
+	/// Break ties of the final standings by Buchholz score and name the winner. Entry fees
+	/// are returned.
+	fn finish_tournament(tournament_id: u32, tournament: &mut TournamentOf<T>) {
+		let standings: Vec<(T::AccountId, SwissStandingOf<T>)> =
+			<TournamentPlayers<T>>::iter_prefix(tournament_id).collect();
+		let points: Vec<u32> = standings.iter().map(|(_, standing)| standing.points).collect();
+		let opponents: Vec<Vec<usize>> = standings
+			.iter()
+			.map(|(_, standing)| {
+				standing
+					.opponents
+					.iter()
+					.filter_map(|opponent| standings.iter().position(|(who, _)| who == opponent))
+					.collect()
+			})
+			.collect();
+		let tie_breaks = Swiss::buchholz(&points, &opponents);
+		let ranking = Swiss::ranking(&points, &tie_breaks);
+
+		tournament.state = TournamentState::Finished;
+		tournament.claim_deadline =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::PrizeClaimPeriod::get());
+		let winner = ranking.first().map(|first| standings[*first].0.clone());
+		for ((player, mut standing), tie_break) in standings.into_iter().zip(tie_breaks) {
+			let prize =
+				if winner.as_ref() == Some(&player) { tournament.prize_pool } else { Zero::zero() };
+			<TournamentPrizes<T>>::insert(tournament_id, &player, prize);
+			standing.tie_break = tie_break;
+			<TournamentPlayers<T>>::insert(tournament_id, player, standing);
+		}
+		if let Some(winner) = winner {
+			Self::deposit_event(Event::TournamentFinished(tournament_id, winner));
+		}
+	}
+
+	/// Add the result of a finished tournament game to the standings.
+	fn record_tournament_result(board: &BoardOf<T>, winner: Option<&T::AccountId>) {
+		let tournament_id = match <TournamentBoards<T>>::take(board.id) {
+			Some(tournament_id) => tournament_id,
+			None => return,
+		};
+		let results = match winner {
+			Some(winner) => vec![(winner, swiss::WIN_POINTS)],
+			None => vec![(&board.red, swiss::DRAW_POINTS), (&board.blue, swiss::DRAW_POINTS)],
+		};
+		for (player, points) in results {
+			<TournamentPlayers<T>>::mutate(tournament_id, player, |standing| {
+				if let Some(standing) = standing {
+					standing.points = standing.points.saturating_add(points);
+				}
+			});
+		}
+		<Tournaments<T>>::mutate(tournament_id, |tournament| {
+			if let Some(tournament) = tournament {
+				tournament.pending = tournament.pending.saturating_sub(1);
+			}
+		});
+	}
+
+	/// Guild `who` is the founder of.
+	fn founded_guild(who: &T::AccountId) -> Result<u32, DispatchError> {
+		let guild_id = <PlayerGuild<T>>::get(who).ok_or(Error::<T>::NotInGuild)?;
+		let guild = <Guilds<T>>::get(guild_id).ok_or(Error::<T>::GuildNotFound)?;
+		ensure!(&guild.founder == who, Error::<T>::OnlyGuildFounder);
+		Ok(guild_id)
+	}
+
+	/// Check that `lineup` holds distinct members of the guild and fits a guild match.
+	fn guild_lineup(
+		guild_id: u32,
+		lineup: Vec<T::AccountId>,
+	) -> Result<BoundedVec<T::AccountId, T::MaxGuildMatchPlayers>, DispatchError> {
+		ensure!(!lineup.is_empty(), Error::<T>::InvalidLineup);
+		for (i, player) in lineup.iter().enumerate() {
+			ensure!(
+				<PlayerGuild<T>>::get(player) == Some(guild_id) && !lineup[..i].contains(player),
+				Error::<T>::InvalidLineup
+			);
+		}
+		lineup.try_into().map_err(|_| Error::<T>::InvalidLineup.into())
+	}
+
+	/// Count the result of a finished guild match game. Once all its boards finished, the
+	/// guild winning the majority of them gains the award and the other loses it, even
+	/// results are a draw.
+	fn record_guild_match_result(board: &BoardOf<T>, winner: Option<&T::AccountId>) {
+		let match_id = match <GuildMatchBoards<T>>::take(board.id) {
+			Some(match_id) => match_id,
+			None => return,
+		};
+		let mut guild_match = match <GuildMatches<T>>::get(match_id) {
+			Some(guild_match) => guild_match,
+			None => return,
+		};
+		// The challenging guild's players play red.
+		match winner {
+			Some(winner) if winner == &board.red =>
+				guild_match.challenger_wins = guild_match.challenger_wins.saturating_add(1),
+			Some(_) => guild_match.opponent_wins = guild_match.opponent_wins.saturating_add(1),
+			None => {},
+		}
+		guild_match.pending = guild_match.pending.saturating_sub(1);
+		if guild_match.pending > 0 {
+			<GuildMatches<T>>::insert(match_id, guild_match);
+			return
+		}
+
+		<GuildMatches<T>>::remove(match_id);
+		let award = guild_match.award;
+		let (challenger, opponent) = (guild_match.challenger, guild_match.opponent);
+		let winner = match guild_match.challenger_wins.cmp(&guild_match.opponent_wins) {
+			Ordering::Greater => Some((challenger, opponent)),
+			Ordering::Less => Some((opponent, challenger)),
+			Ordering::Equal => None,
+		};
+		match winner {
+			Some((winner, loser)) => {
+				Self::add_guild_rating(winner, award.win.saturated_into());
+				Self::add_guild_rating(loser, award.lose.saturated_into::<i32>().saturating_neg());
+			},
+			None => {
+				Self::add_guild_rating(challenger, award.draw.saturated_into());
+				Self::add_guild_rating(opponent, award.draw.saturated_into());
+			},
+		}
+		let winner = winner.map(|(winner, _)| winner);
+		Self::deposit_event(Event::GuildMatchFinished(match_id, winner));
+	}
+
+	/// Add `change` to the rating of a guild, if it still exists.
+	fn add_guild_rating(guild_id: u32, change: i32) {
+		<Guilds<T>>::mutate(guild_id, |guild| {
+			if let Some(guild) = guild {
+				guild.rating = guild.rating.saturating_add(change);
+			}
+		});
+	}
+
+	/// Account of the pallet, which plays blue in bot games.
+	pub fn bot_account() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Column the pallet plays in a bot game.
+	fn bot_move(board: &BoardOf<T>, difficulty: BotDifficulty) -> Option<u8> {
+		let connect = board.variant.connect;
+		let hash = Self::generate_random_hash(b"bot", board.red.clone());
+		let seed = u32::decode(&mut TrailingZeroInput::new(hash.as_ref())).unwrap_or_default();
+		let best = match difficulty {
+			BotDifficulty::Easy => None,
+			BotDifficulty::Hard => Logic::winning_move(&board.board, PLAYER_2, connect)
+				.or_else(|| Logic::winning_move(&board.board, PLAYER_1, connect)),
+		};
+		best.or_else(|| Logic::random_move(&board.board, seed))
+	}
+
+	/// Remove a finished board, settle scores of ranked games and give both players the
+	/// chance to ask for a rematch. Bot games are casual and get no rematch.
+	fn finish_game(board: BoardOf<T>, reason: FinishReason) {
+		let winner = match &board.board_state {
+			BoardState::Finished(winner) => winner.clone(),
+			_ => None,
+		};
+		let doubles = board.red_mate.is_some();
+		let now = <frame_system::Pallet<T>>::block_number();
+		for player in
+			[Some(&board.red), Some(&board.blue), board.red_mate.as_ref(), board.blue_mate.as_ref()]
+				.into_iter()
+				.flatten()
+		{
+			<PlayerBoard<T>>::remove(player);
+			<LastActive<T>>::insert(player, now);
+		}
+		<Boards<T>>::remove(board.id);
+		<ActiveBoards<T>>::mutate(|active| *active = active.saturating_sub(1));
+		<Spectators<T>>::remove(board.id);
+		<UndoRequests<T>>::remove(board.id);
+		<FirstMoveDraws<T>>::remove(board.id);
+		// Its moves live on in the archive, the game trie is cleared once blocks have room.
+		if <StreamedGames<T>>::take(board.id).is_some() {
+			<GameTrieCleanups<T>>::insert(board.id, ());
+		}
 
-			let finder_score = match <ScoringBoard<T>>::get(&sender) {
-				Some(val) => val,
-				None => 0,
+		let asset = <BoardStakeAssets<T>>::take(board.id);
+		let stake = <BoardStakes<T>>::take(board.id);
+		// A double nobody answered is off, its part of the stake goes back.
+		if let (DoublingCube::Offered(player), Some(stake)) = (board.cube, stake) {
+			let offerer = if player == PLAYER_1 { &board.red } else { &board.blue };
+			Self::release_stake(asset, offerer, stake);
+		}
+		let mut stake_fee = Zero::zero();
+		if let Some(report) = <Reports<T>>::take(board.id) {
+			// Stakes stay reserved until the verdict is in.
+			let dispute = Dispute { board: board.clone(), report, finish: reason, stake, asset };
+			<Disputes<T>>::insert(board.id, dispute);
+		} else {
+			Self::settle_result(&board, winner.as_ref(), reason);
+			if let Some(stake) = stake {
+				stake_fee = Self::settle_stakes(&board, winner.as_ref(), stake, asset);
+			}
+		}
+		// Rematches are only offered between two players that played the game out.
+		if board.bot.is_none() && !doubles && reason != FinishReason::Aborted {
+			Self::offer_rematches(&board);
+		}
+		// Players that never made a move keep their deposit reserved until now, it's lost
+		// if they abandon the game. The waiting player gets the `AbandonPenalty` of it.
+		let abandoned = matches!(reason, FinishReason::Timeout | FinishReason::Stalled);
+		for player in [&board.red, &board.blue] {
+			if let Some(deposit) = <FirstMoveDeposits<T>>::take(player) {
+				match &winner {
+					Some(waiting) if abandoned && waiting != player =>
+						Self::compensate_abandon(&board, player, waiting, deposit),
+					_ => {
+						T::Currency::unreserve(player, deposit);
+					},
+				}
+			}
+		}
+		Self::settle_predictions(&board, winner.as_ref(), reason);
+		Self::settle_sponsorships(&board, winner.as_ref());
+		Self::record_stats(&board, winner.as_ref(), reason);
+		Self::record_tournament_result(&board, winner.as_ref());
+		Self::record_guild_match_result(&board, winner.as_ref());
+		let game =
+			<MoveLogs<T>>::take(board.id).and_then(|moves| Self::archived_notation(&board, &moves));
+		let record = game.as_ref().and_then(|game| notation::GameRecord::from_bytes(game));
+		if let Some(winner) = &winner {
+			Self::unlock_achievements(&board, winner, record.as_ref());
+		}
+		if !T::ResultRetentionBlocks::get().is_zero() {
+			if let Some(game) = game {
+				let snapshots = record
+					.and_then(|record| record.snapshots(T::SnapshotInterval::get() as usize))
+					.and_then(|snapshots| ArchivedSnapshotsOf::<T>::try_from(snapshots).ok())
+					.unwrap_or_default();
+				if !snapshots.is_empty() {
+					<ArchivedSnapshots<T>>::insert(board.id, snapshots);
+				}
+				<ArchivedGames<T>>::insert(board.id, game);
+			}
+			let result = GameResult {
+				red: board.red.clone(),
+				blue: board.blue.clone(),
+				winner: winner.clone(),
+				reason,
+				moves: Logic::stones(&board.board),
 			};
+			<ResultsByBlock<T>>::insert(now, board.id, result);
+		}
+		let win_line = match (&winner, reason) {
+			(Some(winner), FinishReason::Connected) => board
+				.team_of(winner)
+				.and_then(|player| Logic::evaluate(&board.board, player, board.variant.connect))
+				.unwrap_or_default(),
+			_ => Vec::new(),
+		};
+		Self::deposit_event(Event::GameFinished {
+			board_id: board.id,
+			winner,
+			reason,
+			win_line,
+			stake_fee,
+		});
+	}
 
-			for (account_id, score) in <MatchQueue<T>>::iter() {
-				let opponent = account_id;
-				if i32::abs(score - finder_score) as u8 <= ACCEPTED_DIFF {
-					let award = AwardState { win: 10, lose: 5 };
+	/// Tell the players of `board` that play from other chains how the game ended.
+	fn notify_remote_players(
+		board: &BoardOf<T>,
+		winner: Option<&T::AccountId>,
+		reason: FinishReason,
+	) {
+		for player in
+			[Some(&board.red), Some(&board.blue), board.red_mate.as_ref(), board.blue_mate.as_ref()]
+				.into_iter()
+				.flatten()
+		{
+			if let Some(location) = <RemotePlayers<T>>::get(player) {
+				let won = winner.map(|winner| board.team_of(winner) == board.team_of(player));
+				T::RemoteNotifier::notify_result(&location, board.id, won, reason);
+			}
+		}
+	}
 
-					<MatchQueue<T>>::remove(opponent.clone());
-					<MatchQueue<T>>::remove(sender.clone());
-					let _board_id = Self::create_game(sender.clone(), opponent, award);
-					break;
+	/// Settle the scores of a finished ranked board, other boards leave scores alone, and tell
+	/// the `GameFinishedHandler` about the result.
+	fn settle_result(board: &BoardOf<T>, winner: Option<&T::AccountId>, reason: FinishReason) {
+		let loser = winner.map(|winner| {
+			if board.team_of(winner) == Some(PLAYER_1) {
+				&board.blue
+			} else {
+				&board.red
+			}
+		});
+		T::GameFinishedHandler::on_game_finished(
+			board.id,
+			&board.red,
+			&board.blue,
+			winner,
+			loser,
+			board.kind,
+			reason,
+		);
+		Self::notify_remote_players(board, winner, reason);
+		if board.kind == GameKind::Ranked {
+			if let Some(winner) = winner {
+				// Team leads and teammates settle against each other.
+				let (winners, losers) = if board.team_of(winner) == Some(PLAYER_1) {
+					((&board.red, &board.red_mate), (&board.blue, &board.blue_mate))
+				} else {
+					((&board.blue, &board.blue_mate), (&board.red, &board.red_mate))
+				};
+				let award = if board.weighted_award {
+					T::AwardCalculator::award(
+						&board.award,
+						<ScoringBoard<T>>::get(winners.0).unwrap_or(0),
+						<ScoringBoard<T>>::get(losers.0).unwrap_or(0),
+					)
+				} else {
+					board.award.clone()
+				};
+				Self::settle_scores(board.id, winners.0, losers.0, &award);
+				if let (Some(winner_mate), Some(loser_mate)) = (winners.1, losers.1) {
+					Self::settle_scores(board.id, winner_mate, loser_mate, &award);
+				}
+				Self::award_trophy(winner, board.id, Logic::stones(&board.board));
+			} else if reason.is_draw() {
+				// Holding out until the board is full or the move limit earns everyone the draw
+				// award.
+				for player in [
+					Some(&board.red),
+					Some(&board.blue),
+					board.red_mate.as_ref(),
+					board.blue_mate.as_ref(),
+				]
+				.into_iter()
+				.flatten()
+				{
+					Self::settle_draw(board.id, player, &board.award);
 				}
 			}
-			<MatchQueue<T>>::insert(sender, finder_score);
-			Ok(())
 		}
+	}
 
-		/// Cancel Challenge
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cancel_queue(origin: OriginFor<T>) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	/// Drop both revealed stones of a blitz round and start the next one.
+	fn play_blitz_round(mut board: BoardOf<T>, red_column: u8, blue_column: u8) -> DispatchResult {
+		let first = board.next_player;
+		let second = if first == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+		// The second player drops first next round, both stones are hashed with that position.
+		board.next_player = second;
+		for player in [first, second] {
+			let (account, column) = if player == PLAYER_1 {
+				(board.red.clone(), red_column)
+			} else {
+				(board.blue.clone(), blue_column)
+			};
+			// The first stone can fill a column both players picked, the second one is lost.
+			if !Logic::open_columns(&board.board).contains(&column) {
+				continue
+			}
+			if let Some(reason) = Self::place_stone(&mut board, account, player, column)? {
+				Self::finish_game(board, reason);
+				return Ok(())
+			}
+		}
 
-			// Make sure players have no board open.
-			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
-			// Make sure challenger in the storage
-			ensure!(<MatchQueue<T>>::contains_key(&sender), Error::<T>::NotFound);
+		board.blitz = Some(BlitzRound::default());
+		Self::start_turn(&mut board);
+		<Boards<T>>::insert(board.id, board);
+		Ok(())
+	}
 
-			<MatchQueue<T>>::remove(sender.clone());
-			Self::deposit_event(Event::CancelQueue(sender));
-			Ok(())
+	/// Pay the `AbandonPenalty` of the reserved `deposit` of `abandoner` to `waiting` and
+	/// slash the rest.
+	fn compensate_abandon(
+		board: &BoardOf<T>,
+		abandoner: &T::AccountId,
+		waiting: &T::AccountId,
+		deposit: BalanceOf<T>,
+	) {
+		let compensation = <AbandonPenalty<T>>::get(board.kind) * deposit;
+		let unpaid =
+			T::Currency::repatriate_reserved(abandoner, waiting, compensation, BalanceStatus::Free)
+				.unwrap_or(compensation);
+		let compensation = compensation.saturating_sub(unpaid);
+		let _ = T::Currency::slash_reserved(abandoner, deposit.saturating_sub(compensation));
+		if !compensation.is_zero() {
+			Self::deposit_event(Event::AbandonCompensated {
+				board_id: board.id,
+				abandoner: abandoner.clone(),
+				player: waiting.clone(),
+				compensation,
+			});
 		}
+	}
 
-		/// Challenge player
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn challenge(
-			origin: OriginFor<T>,
-			opponent: T::AccountId,
-			win: u32,
-			lose: u32,
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-			// Don't allow playing against yourself.
-			ensure!(sender != opponent, Error::<T>::NoFakePlay);
+	/// Someone broadcasts the game on `board_id`: it's spectated or part of a tournament.
+	fn is_streamed(board_id: T::Hash) -> bool {
+		<Spectators<T>>::contains_key(board_id) || <TournamentBoards<T>>::contains_key(board_id)
+	}
 
-			// Make sure players have no board open.
-			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
-			ensure!(!PlayerBoard::<T>::contains_key(&opponent), Error::<T>::PlayerBoardExists);
+	/// Child trie of the game on `board_id`, holding a `StreamedMove` for every move of a
+	/// streamed game while it's being played. Light clients prove single moves of a game
+	/// against its root without touching the rest of the state.
+	pub fn game_trie(board_id: T::Hash) -> ChildInfo {
+		ChildInfo::new_default(&[GAME_TRIE_PREFIX, board_id.as_ref()].concat())
+	}
 
-			// Make sure responder is not also a challenger
-			ensure!(!<Challenges<T>>::contains_key(&opponent), Error::<T>::WrongChallengeTurn);
-			// Make sure challenger doesn't re-challenge
-			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::ReChallengeError);
+	/// Move number `move_number` of a running streamed game, counting from `1`.
+	pub fn streamed_move(board_id: T::Hash, move_number: u16) -> Option<StreamedMoveOf<T>> {
+		child::get(&Self::game_trie(board_id), &move_number.encode())
+	}
 
-			let challenge_state = AwardState { win, lose };
+	/// Write the position after the latest move, given in `notation`, to the game trie and
+	/// return its evaluation.
+	fn stream_move(board: &BoardOf<T>, notation: u8) -> ThreatCounts {
+		let evaluation = Logic::threat_counts(&board.board, board.variant.connect);
+		let entry = StreamedMove {
+			notation,
+			clocks: board.time_banks.as_ref().map(|banks| (banks.red, banks.blue)),
+			evaluation,
+		};
+		child::put(&Self::game_trie(board.id), &board.moves_played.encode(), &entry);
+		<StreamedGames<T>>::insert(board.id, ());
+		evaluation
+	}
 
-			<Challenges<T>>::insert(sender.clone(), challenge_state.clone());
-			Self::deposit_event(Event::AcceptChallenge(sender, opponent, challenge_state));
-			Ok(())
+	/// Clear the game tries in `GameTrieCleanups` within `limit` weight, a trie too large for
+	/// it is continued in a later block.
+	fn clear_game_tries(limit: Weight) -> Weight {
+		let base = T::WeightInfo::clear_game_trie(0);
+		let per_move = T::WeightInfo::clear_game_trie(1).saturating_sub(base).max(1);
+		let mut weight: Weight = 0;
+		loop {
+			let room = limit.saturating_sub(weight).saturating_sub(base) / per_move;
+			if room == 0 {
+				return weight
+			}
+			let board_id = match <GameTrieCleanups<T>>::iter_keys().next() {
+				Some(board_id) => board_id,
+				None => return weight.saturating_add(T::DbWeight::get().reads(1)),
+			};
+			let moves = room.min(T::MaxMovesPerGame::get() as Weight) as u32;
+			let result = child::clear_storage(&Self::game_trie(board_id), Some(moves), None);
+			weight = weight.saturating_add(T::WeightInfo::clear_game_trie(result.loops));
+			if result.maybe_cursor.is_some() {
+				return weight
+			}
+			<GameTrieCleanups<T>>::remove(board_id);
 		}
+	}
 
-		/// Response hallenge player
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn resp_challenge(
-			origin: OriginFor<T>,
-			opponent: T::AccountId,
-			accepted: bool,
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-			// Don't allow playing against yourself.
-			ensure!(sender != opponent, Error::<T>::NoFakePlay);
-
-			// Make sure players have no board open.
-			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
-			ensure!(!PlayerBoard::<T>::contains_key(&opponent), Error::<T>::PlayerBoardExists);
-
-			// Make sure responder is not also a challenger
-			ensure!(!<Challenges<T>>::contains_key(&sender), Error::<T>::WrongChallengeTurn);
+	/// Add a move to the log of `board_id`, a log that grows too long is dropped.
+	fn log_move(board_id: T::Hash, player: u8, mv: Move) {
+		<MoveLogs<T>>::mutate_exists(board_id, |log| {
+			if let Some(moves) = log {
+				if moves.try_push(notation::encode_move(player, mv)).is_err() {
+					*log = None;
+				}
+			}
+		});
+	}
 
-			let award = Self::challenges(opponent.clone()).unwrap();
+	/// `moves` of `board` in `notation`, headed by its board variant and handicap.
+	fn archived_notation(board: &BoardOf<T>, moves: &[u8]) -> Option<ArchivedGameOf<T>> {
+		let handicap = match board.handicap {
+			Some((player, Handicap::Stones(stones))) => Some((player, stones)),
+			_ => None,
+		};
+		let variant = &board.variant;
+		let mut game = notation::header(
+			variant.width,
+			variant.height,
+			variant.connect,
+			variant.pop_out,
+			handicap,
+		)
+		.to_vec();
+		game.extend_from_slice(moves);
+		game.try_into().ok()
+	}
 
-			if accepted {
-				// Create new game
-				let _board_id = Self::create_game(sender, opponent.clone(), award);
+	/// Release the stakes of a finished board, the winner takes the loser's stake less the
+	/// `StakeFee`, which is returned. Stakes in other assets than the native token are paid
+	/// out without a fee, `StakeFeeDestination` only takes the native token.
+	fn settle_stakes(
+		board: &BoardOf<T>,
+		winner: Option<&T::AccountId>,
+		stake: BalanceOf<T>,
+		asset: Option<AssetIdOf<T>>,
+	) -> BalanceOf<T> {
+		if let Some(asset) = asset {
+			if let Some(winner) = winner {
+				let loser = if *winner == board.red { &board.blue } else { &board.red };
+				let _ = T::Assets::transfer_held(asset, loser, winner, stake, true, false);
+				Self::release_stake(Some(asset), winner, stake);
 			} else {
-				// Remove challenge
-				Self::deposit_event(Event::RejectChallenge(sender, opponent.clone(), award));
+				Self::release_stake(Some(asset), &board.red, stake);
+				Self::release_stake(Some(asset), &board.blue, stake);
 			}
-			<Challenges<T>>::remove(opponent);
+			return Zero::zero()
+		}
+		if let Some(winner) = winner {
+			let loser = if *winner == board.red { &board.blue } else { &board.red };
+			let (fee, _) = T::Currency::slash_reserved(loser, <StakeFee<T>>::get() * stake);
+			let fee_amount = fee.peek();
+			T::StakeFeeDestination::on_unbalanced(fee);
+			let winnings = stake.saturating_sub(fee_amount);
+			let _ = T::Currency::repatriate_reserved(loser, winner, winnings, BalanceStatus::Free);
+			T::Currency::unreserve(winner, stake);
+			fee_amount
+		} else {
+			T::Currency::unreserve(&board.red, stake);
+			T::Currency::unreserve(&board.blue, stake);
+			Zero::zero()
+		}
+	}
 
-			Ok(())
+	/// Reserve a stake of `amount` in `asset` from `who`, in the native token for `None`.
+	fn hold_stake(
+		asset: Option<AssetIdOf<T>>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match asset {
+			Some(asset) => T::Assets::hold(asset, who, amount),
+			None => T::Currency::reserve(who, amount),
 		}
+	}
 
-		/// Cancel Challenge
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cancel_challenge(origin: OriginFor<T>) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	/// Release a stake reserved by `hold_stake`.
+	fn release_stake(asset: Option<AssetIdOf<T>>, who: &T::AccountId, amount: BalanceOf<T>) {
+		match asset {
+			Some(asset) => {
+				let _ = T::Assets::release(asset, who, amount, true);
+			},
+			None => {
+				T::Currency::unreserve(who, amount);
+			},
+		}
+	}
 
-			// Make sure players have no board open.
-			ensure!(!PlayerBoard::<T>::contains_key(&sender), Error::<T>::PlayerBoardExists);
-			// Make sure challenger in the storage
-			ensure!(<Challenges<T>>::contains_key(&sender), Error::<T>::NotFound);
+	/// Pay out the predictions on a finished board. Correct predictions are returned and
+	/// share the losing stakes, what's left after rounding goes to the house with the fee.
+	fn settle_predictions(board: &BoardOf<T>, winner: Option<&T::AccountId>, reason: FinishReason) {
+		let predictions = <Predictions<T>>::take(board.id);
+		if predictions.is_empty() {
+			return
+		}
+		let winning_team = winner.and_then(|winner| board.team_of(winner));
+		let correct = |prediction: &PredictionOf<T>| {
+			prediction.winner.as_ref().and_then(|winner| board.team_of(winner)) == winning_team
+		};
+		let winning_stake = predictions
+			.iter()
+			.filter(|prediction| correct(prediction))
+			.fold(BalanceOf::<T>::zero(), |total, prediction| {
+				total.saturating_add(prediction.stake)
+			});
+		// Like in the stats, games ended without a winner only count as a draw once the board
+		// is full or the move limit is reached.
+		if winning_stake.is_zero() || (winner.is_none() && !reason.is_draw()) {
+			for prediction in &predictions {
+				T::Currency::unreserve(&prediction.predictor, prediction.stake);
+			}
+			Self::deposit_event(Event::PredictionsRefunded(board.id));
+			return
+		}
 
-			<Challenges<T>>::remove(sender.clone());
-			Self::deposit_event(Event::CancelChallenge(sender));
-			Ok(())
+		let mut pool = NegativeImbalanceOf::<T>::zero();
+		for prediction in &predictions {
+			if correct(prediction) {
+				T::Currency::unreserve(&prediction.predictor, prediction.stake);
+			} else {
+				pool.subsume(
+					T::Currency::slash_reserved(&prediction.predictor, prediction.stake).0,
+				);
+			}
+		}
+		let total = pool.peek();
+		let fee = <PredictionFee<T>>::get() * total;
+		let (house, mut pool) = pool.split(fee);
+		let winnings = pool.peek();
+		for prediction in predictions.iter().filter(|prediction| correct(prediction)) {
+			let share = Perbill::from_rational(prediction.stake, winning_stake) * winnings;
+			let (payout, rest) = pool.split(share);
+			T::Currency::resolve_creating(&prediction.predictor, payout);
+			pool = rest;
 		}
+		T::PredictionFeeDestination::on_unbalanced(house.merge(pool));
+		Self::deposit_event(Event::PredictionsSettled(board.id, total, fee));
+	}
 
-		/// Create game for two players
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn play_turn(origin: OriginFor<T>, column: u8) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	/// Pay the sponsorships of a finished board to its winner, or refund them if there's none.
+	fn settle_sponsorships(board: &BoardOf<T>, winner: Option<&T::AccountId>) {
+		for (sponsor, amount) in <BoardSponsors<T>>::take(board.id) {
+			match winner {
+				Some(winner) => {
+					let unpaid = T::Currency::repatriate_reserved(
+						&sponsor,
+						winner,
+						amount,
+						BalanceStatus::Free,
+					)
+					.unwrap_or(amount);
+					T::Currency::unreserve(&sponsor, unpaid);
+					Self::deposit_event(Event::SponsorshipPaid(
+						board.id,
+						sponsor,
+						winner.clone(),
+						amount.saturating_sub(unpaid),
+					));
+				},
+				None => {
+					T::Currency::unreserve(&sponsor, amount);
+					Self::deposit_event(Event::SponsorshipRefunded(board.id, sponsor, amount));
+				},
+			}
+		}
+	}
 
-			ensure!(column < 8, "Game only allows columns smaller then 8");
+	/// Stones the players placed on a board, handicap stones left out.
+	fn placed_stones(board: &BoardOf<T>) -> u32 {
+		let handicap = match &board.handicap {
+			Some((_, Handicap::Stones(stones))) => u32::from(*stones),
+			_ => 0,
+		};
+		Logic::stones(&board.board).saturating_sub(handicap)
+	}
 
-			// TODO: should PlayerBoard storage here be optional to avoid two reads?
-			ensure!(PlayerBoard::<T>::contains_key(&sender), Error::<T>::NoPlayerBoard);
-			let board_id = Self::player_board(&sender);
+	/// Count the result of a finished game for everyone who played it, other than the bot.
+	/// Games ended without a winner only count as a draw if `FinishReason::is_draw`.
+	fn record_stats(board: &BoardOf<T>, winner: Option<&T::AccountId>, reason: FinishReason) {
+		if winner.is_none() && !reason.is_draw() {
+			return
+		}
+		let winning_team = winner.and_then(|winner| board.team_of(winner));
+		let ranked = board.kind == GameKind::Ranked;
+		let blue = if board.bot.is_none() { Some(&board.blue) } else { None };
+		for player in [Some(&board.red), blue, board.red_mate.as_ref(), board.blue_mate.as_ref()]
+			.into_iter()
+			.flatten()
+		{
+			let stats = <PlayerStats<T>>::mutate(player, |stats| {
+				stats.played = stats.played.saturating_add(1);
+				stats.ranked = stats.ranked.saturating_add(ranked as u32);
+				match winning_team {
+					None => {
+						stats.draws = stats.draws.saturating_add(1);
+						stats.current_streak = 0;
+					},
+					Some(team) if board.team_of(player) == Some(team) => {
+						stats.wins = stats.wins.saturating_add(1);
+						stats.current_streak = stats.current_streak.saturating_add(1);
+						stats.best_streak = stats.best_streak.max(stats.current_streak);
+					},
+					Some(_) => {
+						stats.losses = stats.losses.saturating_add(1);
+						stats.current_streak = 0;
+					},
+				}
+				stats.clone()
+			});
+			let placed = ranked && stats.ranked == T::ProvisionalGames::get();
+			Self::deposit_event(Event::StatsUpdated(player.clone(), stats));
+			if placed {
+				let score = <ScoringBoard<T>>::get(player);
+				Self::update_leaderboard(player, score);
+				Self::deposit_event(Event::PlacementCompleted(player.clone(), score.unwrap_or(0)));
+			}
+		}
+	}
 
-			// Get board from player.
-			ensure!(Boards::<T>::contains_key(&board_id), "No board found");
-			let mut board = Self::boards(&board_id).unwrap();
+	/// Unlock the achievements the winning team earned with a finished board, `game` being its
+	/// move history. Needs the stats to count the game already.
+	fn unlock_achievements(
+		board: &BoardOf<T>,
+		winner: &T::AccountId,
+		game: Option<&notation::GameRecord>,
+	) {
+		let team = match board.team_of(winner) {
+			Some(team) => team,
+			None => return,
+		};
+		let blue = if board.bot.is_none() { Some(&board.blue) } else { None };
+		for player in [Some(&board.red), blue, board.red_mate.as_ref(), board.blue_mate.as_ref()]
+			.into_iter()
+			.flatten()
+			.filter(|player| board.team_of(player) == Some(team))
+		{
+			let stats = <PlayerStats<T>>::get(player);
+			for achievement in achievements::earned(&stats, &board.board, game, team) {
+				let unlocked = <Achievements<T>>::get(player);
+				if unlocked & achievement.bit() == 0 {
+					<Achievements<T>>::insert(player, unlocked | achievement.bit());
+					Self::deposit_event(Event::AchievementUnlocked(player.clone(), achievement));
+				}
+			}
+		}
+	}
 
-			// Board is still open to play and not finished.
-			ensure!(
-				board.board_state == BoardState::Running,
-				"Board is not running, check if already finished."
+	/// Give both players of a finished board the chance to ask for a rematch.
+	fn offer_rematches(board: &BoardOf<T>) {
+		let expires = <frame_system::Pallet<T>>::block_number() + T::RematchPeriod::get();
+		for (player, opponent, was_red) in
+			[(&board.red, &board.blue, true), (&board.blue, &board.red, false)]
+		{
+			<Rematches<T>>::insert(
+				player,
+				RematchStruct {
+					opponent: opponent.clone(),
+					award: board.award.clone(),
+					variant: board.variant,
+					time_bank: board.time_banks.map(|banks| banks.initial),
+					kind: board.kind,
+					was_red,
+					moved_first: board.first_player == if was_red { PLAYER_1 } else { PLAYER_2 },
+					requested: false,
+					expires,
+				},
 			);
+		}
+	}
 
-			let current_player = board.next_player;
-			let current_account;
-			let last_account;
-
-			// Check if correct player is at turn
-			if current_player == PLAYER_1 {
-				current_account = board.red.clone();
-				last_account = board.blue.clone();
-				board.next_player = PLAYER_2;
-			} else if current_player == PLAYER_2 {
-				current_account = board.blue.clone();
-				last_account = board.red.clone();
-				board.next_player = PLAYER_1;
+	/// Run `f` in its own storage layer, so its writes are dropped if it fails. Extrinsics
+	/// already run that way, hooks and failures an extrinsic recovers from don't.
+	fn atomically<R>(f: impl FnOnce() -> Result<R, DispatchError>) -> Result<R, DispatchError> {
+		with_transaction(|| {
+			let result = f();
+			if result.is_ok() {
+				TransactionOutcome::Commit(result)
 			} else {
-				return Err(Error::<T>::WrongLogic)?;
+				TransactionOutcome::Rollback(result)
 			}
+		})
+	}
 
-			// Make sure current account is at turn.
-			ensure!(sender == current_account, Error::<T>::NotPlayerTurn);
+	/// Mint a trophy for a won game. A failing minter doesn't block the game from finishing.
+	fn award_trophy(winner: &T::AccountId, board_id: T::Hash, moves: u32) {
+		let trophy_id = <NextTrophyId<T>>::get();
+		let minted =
+			Self::atomically(|| T::Trophies::mint_trophy(trophy_id, winner, board_id, moves));
+		if minted.is_ok() {
+			<NextTrophyId<T>>::put(trophy_id.wrapping_add(1));
+			Self::deposit_event(Event::TrophyAwarded(winner.clone(), board_id, trophy_id));
+		}
+	}
 
-			// Check if we can successfully place a stone in that column
-			if !Logic::add_stone(&mut board.board, column, current_player) {
-				return Err(Error::<T>::WrongLogic)?;
-			}
+	/// Store a challenge of `challenger` after checking its settings.
+	fn place_challenge(challenger: &T::AccountId, challenge: ChallengeOf<T>) -> DispatchResult {
+		ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+		// Both players play on the board described by the challenge.
+		ensure!(
+			challenge.variant.is_valid(T::MaxBoardWidth::get(), T::MaxBoardHeight::get()),
+			Error::<T>::InvalidVariant
+		);
+		ensure!(
+			challenge.time_bank.map_or(true, |bank| !bank.is_zero()),
+			Error::<T>::InvalidTimeBank
+		);
+		ensure!(
+			challenge
+				.handicap
+				.map_or(true, |handicap| handicap.is_valid(&challenge.variant)),
+			Error::<T>::InvalidHandicap
+		);
 
-			let red = board.red.clone();
-			let blue = board.blue.clone();
-			let win_award = board.award.win;
-			let lose_award = board.award.lose;
+		if let Some(opponent) = &challenge.opponent {
+			ensure!(!Self::is_blocked(challenger, opponent), Error::<T>::PlayerBlocked);
+		}
 
-			// Check if the last played stone gave us a winner or board is full
-			if Logic::evaluate(board.board.clone(), current_player) {
-				match <ScoringBoard<T>>::try_get(&current_account) {
-					Ok(score) => {
-						let new_score = score + win_award as i32;
-						<ScoringBoard<T>>::mutate(&current_account, |score| {
-							*score = Some(new_score);
-						});
-					},
-					Err(_e) => {
-						<ScoringBoard<T>>::insert(&current_account, win_award as i32);
-					},
-				};
+		// Make sure challenger has no board open.
+		ensure!(!PlayerBoard::<T>::contains_key(challenger), Error::<T>::PlayerBoardExists);
+		// Make sure challenger doesn't re-challenge
+		ensure!(!<Challenges<T>>::contains_key(challenger), Error::<T>::ReChallengeError);
+		Self::count_challenge(challenger)?;
 
-				match <ScoringBoard<T>>::try_get(&last_account) {
-					Ok(score) => {
-						let new_score = score - lose_award as i32;
-						<ScoringBoard<T>>::mutate(&last_account, |score| {
-							*score = Some(new_score);
-						});
-					},
-					Err(_e) => {
-						<ScoringBoard<T>>::insert(&last_account, 0 - lose_award as i32);
-					},
-				};
-				board.board_state = BoardState::Finished(Some(current_account));
-				Self::deposit_event(Event::GameState(board));
-				<Boards<T>>::remove(board_id);
-				<PlayerBoard<T>>::remove(red);
-				<PlayerBoard<T>>::remove(blue);
-			} else if Logic::full(board.board.clone()) {
-				board.board_state = BoardState::Finished(None);
-				Self::deposit_event(Event::GameState(board));
-				<Boards<T>>::remove(board_id);
-				<PlayerBoard<T>>::remove(red);
-				<PlayerBoard<T>>::remove(blue);
-			} else {
-				// get current blocknumber
-				let last_turn = <frame_system::Pallet<T>>::block_number();
-				board.last_turn = last_turn;
-				// Write next board state back into the storage
-				<Boards<T>>::insert(board_id, board.clone());
-				Self::deposit_event(Event::GameState(board));
-			}
+		if let Some(stake) = challenge.stake {
+			T::Currency::reserve(challenger, stake)?;
+		}
+		Self::deposit_event(Event::ChallengeCreated {
+			challenger: challenger.clone(),
+			challenge: challenge.clone(),
+		});
+		<Challenges<T>>::insert(challenger, challenge);
+		Ok(())
+	}
 
-			Ok(())
+	/// Count a new challenge of `challenger`, unless it comes too soon after the last one or
+	/// exceeds the challenges of the current era.
+	fn count_challenge(challenger: &T::AccountId) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let era = now.checked_div(&T::ChallengeEra::get()).unwrap_or_else(Zero::zero);
+		let (last, count) = match <ChallengeRates<T>>::get(challenger) {
+			Some(rate) => (Some(rate.last), if rate.era == era { rate.count } else { 0 }),
+			None => (None, 0),
+		};
+		ensure!(
+			last.map_or(true, |last| now >= last.saturating_add(T::ChallengeCooldown::get())),
+			Error::<T>::ChallengeRateLimited
+		);
+		let max = T::MaxChallengesPerEra::get();
+		ensure!(max == 0 || count < max, Error::<T>::ChallengeRateLimited);
+		<ChallengeRates<T>>::insert(
+			challenger,
+			ChallengeRate { last: now, era, count: count.saturating_add(1) },
+		);
+		Ok(())
+	}
+
+	/// Last block a challenge placed now can be accepted in.
+	fn challenge_expiry() -> T::BlockNumber {
+		<frame_system::Pallet<T>>::block_number().saturating_add(T::ChallengeLifetime::get())
+	}
+
+	/// Return the stake of a challenge that won't be played.
+	fn release_challenge_stake(challenger: &T::AccountId, challenge: &ChallengeOf<T>) {
+		if let Some(stake) = challenge.stake {
+			T::Currency::unreserve(challenger, stake);
 		}
 	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// Update nonce once used.
-	fn encode_and_update_nonce() -> Vec<u8> {
-		let nonce = <Nonce<T>>::get();
-		<Nonce<T>>::put(nonce.wrapping_add(1));
-		nonce.encode()
+	/// Hand out the next challenge id.
+	fn take_challenge_id() -> u32 {
+		let id = <NextChallengeId<T>>::get();
+		<NextChallengeId<T>>::put(id.wrapping_add(1));
+		id
 	}
 
-	/// Generates a random hash out of a seed.
-	fn generate_random_hash(phrase: &[u8], sender: T::AccountId) -> T::Hash {
-		let (seed, _) = T::Randomness::random(phrase);
-		let seed = <[u8; 32]>::decode(&mut TrailingZeroInput::new(seed.as_ref()))
-			.expect("input is padded with zeroes; qed");
-		return (seed, &sender, Self::encode_and_update_nonce()).using_encoded(T::Hashing::hash);
+	/// Create the game of a challenge of `challenger` accepted by `responder`, who plays red.
+	fn start_challenge_game(
+		responder: T::AccountId,
+		challenger: T::AccountId,
+		challenge: ChallengeOf<T>,
+	) -> Result<T::Hash, DispatchError> {
+		// The responder plays red, so a handicap goes to red.
+		let mode = match (challenge.blitz, challenge.handicap) {
+			(true, _) => GameMode::Blitz,
+			(false, Some(handicap)) => GameMode::Handicapped(PLAYER_1, handicap),
+			(false, None) => GameMode::Standard,
+		};
+		// The challenger's stake is reserved since the challenge got placed.
+		let stake = challenge.stake.filter(|stake| !stake.is_zero());
+		if let Some(stake) = stake {
+			T::Currency::reserve(&responder, stake)?;
+		}
+		let board_id = Self::new_game(
+			responder,
+			challenger,
+			challenge.award,
+			challenge.variant,
+			challenge.time_bank,
+			mode,
+			challenge.first_move,
+			challenge.kind,
+		)?;
+		if let Some(stake) = stake {
+			<BoardStakes<T>>::insert(board_id, stake);
+		}
+		Ok(board_id)
+	}
+
+	/// `opponent` accepts the challenge of `challenger` without answering it: nothing is at
+	/// stake, as no tokens are staked and the game is casual or can't cost the opponent any
+	/// score, and the scores are within the opponent's `auto_accept_within`.
+	fn auto_accepts(
+		opponent: &T::AccountId,
+		challenger: &T::AccountId,
+		challenge: &ChallengeOf<T>,
+	) -> bool {
+		let within = match <PlayerProfile<T>>::get(opponent).auto_accept_within {
+			Some(within) => within,
+			None => return false,
+		};
+		// The opponent commits to their salt of a commit-reveal draw when accepting.
+		let no_stake = challenge.stake.is_none() &&
+			(challenge.kind == GameKind::Casual || challenge.award.lose == 0) &&
+			challenge.first_move != FirstMovePolicy::CommitReveal;
+		let gap = <ScoringBoard<T>>::get(opponent)
+			.unwrap_or(0)
+			.saturating_sub(<ScoringBoard<T>>::get(challenger).unwrap_or(0))
+			.unsigned_abs();
+		no_stake && gap <= within
 	}
 
-	/// Generate a new game between two players.
-	fn create_game(red: T::AccountId, blue: T::AccountId, award: AwardState) -> T::Hash {
-		// get a random hash as board id
-		let board_id = Self::generate_random_hash(b"create", red.clone());
+	/// Either of the two accounts has the other on their block list.
+	fn is_blocked(a: &T::AccountId, b: &T::AccountId) -> bool {
+		<Blocked<T>>::get(a).contains(b) || <Blocked<T>>::get(b).contains(a)
+	}
 
-		// calculate plyer to start the first turn, with the first byte of the board_id random hash
-		let next_player = if board_id.as_ref()[0] < 128 { PLAYER_1 } else { PLAYER_2 };
+	/// Rematch entry of a player, as long as it hasn't expired.
+	fn valid_rematch(who: &T::AccountId) -> Result<RematchOf<T>, Error<T>> {
+		let rematch = <Rematches<T>>::get(who).ok_or(Error::<T>::NoRematch)?;
+		ensure!(
+			rematch.expires >= <frame_system::Pallet<T>>::block_number(),
+			Error::<T>::RematchExpired
+		);
+		Ok(rematch)
+	}
+
+	/// Generate a new ranked game between two players.
+	fn create_game(
+		red: T::AccountId,
+		blue: T::AccountId,
+		award: AwardState,
+		variant: BoardVariant,
+		time_bank: Option<MomentOf<T>>,
+	) -> Result<T::Hash, DispatchError> {
+		Self::new_game(
+			red,
+			blue,
+			award,
+			variant,
+			time_bank,
+			GameMode::Standard,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+		)
+	}
 
-		// get current blocknumber
-		let block_number = <frame_system::Pallet<T>>::block_number();
+	/// Generate a new game of the given mode. For `first_move`, red is the player who
+	/// accepted the challenge.
+	#[allow(clippy::too_many_arguments)]
+	fn new_game(
+		red: T::AccountId,
+		blue: T::AccountId,
+		award: AwardState,
+		variant: BoardVariant,
+		time_bank: Option<MomentOf<T>>,
+		mode: GameMode<T::AccountId>,
+		first_move: FirstMovePolicy,
+		kind: GameKind,
+	) -> Result<T::Hash, DispatchError> {
+		Self::seeded_game(red, blue, award, variant, time_bank, mode, first_move, kind, None)
+	}
+
+	/// Generate a new game like `new_game`, with the board id derived from `seed` instead of
+	/// `Randomness` if there is one, so the same seed and players set up the same game.
+	#[allow(clippy::too_many_arguments)]
+	fn seeded_game(
+		red: T::AccountId,
+		blue: T::AccountId,
+		award: AwardState,
+		variant: BoardVariant,
+		time_bank: Option<MomentOf<T>>,
+		mode: GameMode<T::AccountId>,
+		first_move: FirstMovePolicy,
+		kind: GameKind,
+		seed: Option<T::Hash>,
+	) -> Result<T::Hash, DispatchError> {
+		// Every way of starting a game ends up here.
+		ensure!(!<Paused<T>>::get(), Error::<T>::Paused);
+		ensure!(!Self::board_limit_reached(), Error::<T>::BoardLimitReached);
+		let (bot, mates, blitz, handicap) = match mode {
+			GameMode::Standard => (None, None, None, None),
+			GameMode::Bot(difficulty) => (Some(difficulty), None, None, None),
+			GameMode::Doubles(red_mate, blue_mate) =>
+				(None, Some((red_mate, blue_mate)), None, None),
+			GameMode::Blitz => (None, None, Some(BlitzRound::default()), None),
+			GameMode::Handicapped(player, handicap) => (None, None, None, Some((player, handicap))),
+		};
+		// Players are on one board at a time, the bot plays any number of boards.
+		let players = [
+			Some(&red),
+			if bot.is_none() { Some(&blue) } else { None },
+			mates.as_ref().map(|(red_mate, _)| red_mate),
+			mates.as_ref().map(|(_, blue_mate)| blue_mate),
+		];
+		ensure!(
+			players
+				.into_iter()
+				.flatten()
+				.all(|player| !<PlayerBoard<T>>::contains_key(player)),
+			Error::<T>::PlayerBoardExists
+		);
+		let max_games = <MaxGamesPerPlayer<T>>::get();
+		ensure!(
+			players.into_iter().flatten().all(|player| Self::games_of(player) < max_games),
+			Error::<T>::TooManyGames
+		);
+		let mut cells = Self::empty_board(&variant).ok_or(Error::<T>::InvalidVariant)?;
+		if let Some((player, Handicap::Stones(stones))) = handicap {
+			ensure!(
+				Logic::valid_handicap(variant.width, variant.connect, stones) &&
+					Logic::place_handicap(&mut cells, player, stones),
+				Error::<T>::InvalidHandicap
+			);
+		}
+
+		// The first mover of a commit-reveal game is drawn once both players revealed their salt.
+		let draw = match (first_move, handicap, &bot) {
+			(FirstMovePolicy::CommitReveal, None, None) => Some(FirstMoveDraw {
+				red_commit: <FirstMoveCommits<T>>::get(&red)
+					.ok_or(Error::<T>::FirstMoveNotCommitted)?,
+				blue_commit: <FirstMoveCommits<T>>::get(&blue)
+					.ok_or(Error::<T>::FirstMoveNotCommitted)?,
+				red_salt: None,
+				blue_salt: None,
+			}),
+			_ => None,
+		};
+
+		// Nothing can fail from here on, so no storage gets written for a game that isn't
+		// created, even when called from a hook.
+		// get a random hash as board id, seeded ids can't be taken as both players are free
+		let board_id = match seed {
+			Some(seed) => (b"create", seed, &red, &blue).using_encoded(T::Hashing::hash),
+			None => Self::generate_random_hash(b"create", red.clone()),
+		};
+
+		// calculate plyer to start the first turn, with the first byte of the board_id random hash
+		// The bot only ever replies, so the human starts bot games.
+		let next_player = match handicap {
+			Some((player, Handicap::FirstMove)) => player,
+			// Pre-placed stones make up for the first move, the stronger player starts.
+			Some((player, Handicap::Stones(_))) =>
+				if player == PLAYER_1 {
+					PLAYER_2
+				} else {
+					PLAYER_1
+				},
+			None if bot.is_some() => PLAYER_1,
+			None => {
+				let random = if board_id.as_ref()[0] < 128 { PLAYER_1 } else { PLAYER_2 };
+				match first_move {
+					// Stands in for commit-reveal until the draw, see `reveal_first_move`.
+					FirstMovePolicy::Random |
+					FirstMovePolicy::CommitReveal |
+					FirstMovePolicy::PieRule => random,
+					FirstMovePolicy::ResponderFirst => PLAYER_1,
+					FirstMovePolicy::ChallengerFirst => PLAYER_2,
+					// The last game is known from the rematch entry it left behind.
+					FirstMovePolicy::AlternateFromLastGame => Self::valid_rematch(&red)
+						.ok()
+						.filter(|last| last.opponent == blue)
+						.map_or(random, |last| if last.moved_first { PLAYER_2 } else { PLAYER_1 }),
+				}
+			},
+		};
 
 		// create a new empty game
-		let board = BoardStruct {
+		let position = Self::position_hash(&cells, next_player);
+		let mut board = BoardStruct {
 			id: board_id,
 			red: red.clone(),
 			blue: blue.clone(),
-			board: [[0u8; 6]; 7],
-			last_turn: block_number,
+			board: cells,
+			variant,
+			last_turn: T::GameClock::now(),
+			time_banks: time_bank.map(|bank| TimeBanks { initial: bank, red: bank, blue: bank }),
+			deadline: None,
 			next_player,
 			board_state: BoardState::Running,
 			award,
+			bot,
+			red_mate: mates.as_ref().map(|(red_mate, _)| red_mate.clone()),
+			blue_mate: mates.as_ref().map(|(_, blue_mate)| blue_mate.clone()),
+			blitz,
+			last_move: None,
+			handicap,
+			first_move,
+			first_player: next_player,
+			weighted_award: false,
+			stalled_turns: (0, 0),
+			position,
+			kind,
+			turns: 0,
+			cube: DoublingCube::Centered,
+			moves_played: 0,
+			sides_swapped: false,
+			verbosity: T::DefaultEventVerbosity::get(),
 		};
+		Self::start_turn(&mut board);
+		let deadline = board.deadline;
 
 		// insert the new board into the storage
 		<Boards<T>>::insert(board_id, board);
+		<ActiveBoards<T>>::mutate(|active| *active = active.saturating_add(1));
+		<MoveLogs<T>>::insert(board_id, BoundedVec::default());
+		if let Some(draw) = draw {
+			<FirstMoveCommits<T>>::remove(&red);
+			<FirstMoveCommits<T>>::remove(&blue);
+			<FirstMoveDraws<T>>::insert(board_id, draw);
+		}
 
-		// Add board to the players playing it.
+		// Add board to the players playing it, the bot plays any number of boards.
 		<PlayerBoard<T>>::insert(red, board_id);
-		<PlayerBoard<T>>::insert(blue, board_id);
+		if bot.is_none() {
+			<PlayerBoard<T>>::insert(blue, board_id);
+		}
+		if let Some((red_mate, blue_mate)) = mates {
+			<PlayerBoard<T>>::insert(red_mate, board_id);
+			<PlayerBoard<T>>::insert(blue_mate, board_id);
+		}
 
 		// emit event for a new board creation
 		// Emit an event.
-		Self::deposit_event(Event::NewBoard(board_id));
+		Self::deposit_event(Event::NewBoard(board_id, deadline));
+
+		Ok(board_id)
+	}
+
+	/// Hash of a position, for light clients to check they follow the same game without
+	/// storage proofs: `Hashing::hash_of(&(board, next_player))`, with the board encoded as
+	/// its size followed by the bitboards of both players.
+	pub fn position_hash(board: &Board, next_player: u8) -> T::Hash {
+		T::Hashing::hash_of(&(board, next_player))
+	}
+
+	/// Empty board of a variant, `None` if it exceeds the board size limits.
+	fn empty_board(variant: &BoardVariant) -> Option<Board> {
+		if variant.width > T::MaxBoardWidth::get() || variant.height > T::MaxBoardHeight::get() {
+			return None
+		}
+		Logic::new_board(variant.width, variant.height)
+	}
+
+	/// Check the storage invariants of the pallet.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		for (player, board_id) in <PlayerBoard<T>>::iter() {
+			let board =
+				<Boards<T>>::get(board_id).ok_or("PlayerBoard points at a missing board")?;
+			ensure!(
+				board.board_state == BoardState::Running,
+				"PlayerBoard points at a finished board"
+			);
+			ensure!(board.team_of(&player).is_some(), "Player is not part of their board");
+		}
+		for (board_id, board) in <Boards<T>>::iter() {
+			ensure!(board.id == board_id, "Board stored under a different id");
+			ensure!(
+				board.position == Self::position_hash(&board.board, board.next_player),
+				"Board position hash is out of date"
+			);
+			ensure!(
+				<PlayerBoard<T>>::try_get(&board.red).ok() == Some(board_id) &&
+					(board.bot.is_some() ||
+						<PlayerBoard<T>>::try_get(&board.blue).ok() == Some(board_id)),
+				"Board players don't point at their board"
+			);
+			ensure!(
+				board.board.width() == board.variant.width &&
+					board.board.height() == board.variant.height,
+				"Board size doesn't match the board variant"
+			);
+			// Every move adds a stone, only pops take one away.
+			let stones = Self::placed_stones(&board);
+			let moves = board.moves_played as u32;
+			ensure!(stones % 2 == moves % 2, "Moves played and stones differ in parity");
+			ensure!(
+				board.variant.pop_out || stones == moves,
+				"Moves played don't match the stones on the board"
+			);
+		}
+		let leaderboard = <Leaderboard<T>>::get();
+		ensure!(
+			leaderboard.windows(2).all(|pair| pair[0].1 >= pair[1].1),
+			"Leaderboard is not sorted"
+		);
+		for (account, score) in leaderboard {
+			ensure!(
+				<ScoringBoard<T>>::get(&account) == Some(score),
+				"Leaderboard score differs from the scoring board"
+			);
+			ensure!(!Self::is_provisional(&account), "Provisional score on the leaderboard");
+		}
+		for player in <FirstMoveDeposits<T>>::iter_keys() {
+			ensure!(
+				<PlayerBoard<T>>::contains_key(&player),
+				"First move deposit kept for a player without a board"
+			);
+		}
+		for board_id in <BoardStakes<T>>::iter_keys() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Stake kept for a missing board");
+		}
+		for board_id in <BoardStakeAssets<T>>::iter_keys() {
+			ensure!(<BoardStakes<T>>::contains_key(board_id), "Stake asset kept without a stake");
+		}
+		for (board_id, board) in <Boards<T>>::iter() {
+			ensure!(
+				board.cube == DoublingCube::Centered || <BoardStakes<T>>::contains_key(board_id),
+				"Doubling cube used without a stake"
+			);
+		}
+		for board_id in <Reports<T>>::iter_keys() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Report kept for a missing board");
+		}
+		for board_id in <MoveLogs<T>>::iter_keys() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Move log kept for a missing board");
+		}
+		for board_id in <Spectators<T>>::iter_keys() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Spectators kept for a missing board");
+		}
+		for board_id in <Predictions<T>>::iter_keys() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Predictions kept for a missing board");
+		}
+		for board_id in <BoardSponsors<T>>::iter_keys() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Sponsors kept for a missing board");
+		}
+		for (board_id, tournament_id) in <TournamentBoards<T>>::iter() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Tournament game for a missing board");
+			ensure!(
+				<Tournaments<T>>::get(tournament_id)
+					.map_or(false, |tournament| tournament.state == TournamentState::Running),
+				"Tournament game outside of a running tournament"
+			);
+		}
+		ensure!(
+			<ActiveBoards<T>>::get() as usize == <Boards<T>>::iter_keys().count(),
+			"ActiveBoards differs from the boards being played"
+		);
+		for (player, score) in <QueuedPlayers<T>>::iter() {
+			ensure!(
+				<MatchQueue<T>>::get(Self::score_bucket(score))
+					.iter()
+					.any(|(who, ..)| *who == player),
+				"Queued player is missing from their bucket"
+			);
+		}
+		for player in <CasualQueue<T>>::get() {
+			ensure!(
+				!<QueuedPlayers<T>>::contains_key(&player),
+				"Player waits in both the ranked and the casual queue"
+			);
+		}
+		for player in <CasualQueuedAt<T>>::iter_keys() {
+			ensure!(
+				<CasualQueue<T>>::get().contains(&player),
+				"Queue join block kept for a player outside the casual queue"
+			);
+		}
+		ensure!(
+			<QueueLength<T>>::get() as usize ==
+				<QueuedPlayers<T>>::iter_keys().count() +
+					<CasualQueue<T>>::decode_len().unwrap_or(0),
+			"QueueLength differs from the queued players"
+		);
+		for (code_hash, game) in <PrivateGames<T>>::iter() {
+			ensure!(
+				<PrivateGameExpiries<T>>::get(game.expires).contains(&code_hash),
+				"Private game missing from its expiry block"
+			);
+		}
+		for (player, guild_id) in <PlayerGuild<T>>::iter() {
+			ensure!(
+				<Guilds<T>>::get(guild_id).map_or(false, |guild| guild.members.contains(&player)),
+				"Guild member missing from their guild"
+			);
+		}
+		for (_, guild) in <Guilds<T>>::iter() {
+			ensure!(guild.members.contains(&guild.founder), "Guild founder isn't a member");
+		}
+		for (board_id, match_id) in <GuildMatchBoards<T>>::iter() {
+			ensure!(<Boards<T>>::contains_key(board_id), "Guild match game for a missing board");
+			ensure!(<GuildMatches<T>>::contains_key(match_id), "Guild match game without a match");
+		}
+		for board_id in <FirstMoveDraws<T>>::iter_keys() {
+			let board =
+				<Boards<T>>::get(board_id).ok_or("First mover drawn for a missing board")?;
+			ensure!(board.turns == 0, "Moves played before the first mover was drawn");
+		}
+		Ok(())
+	}
+}
+
+/// Track when accounts are created, so ranked matchmaking can require a minimum account age.
+impl<T: Config> OnNewAccount<T::AccountId> for Pallet<T> {
+	fn on_new_account(who: &T::AccountId) {
+		<AccountCreated<T>>::insert(who, <frame_system::Pallet<T>>::block_number());
+	}
+}
 
-		return board_id;
+impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
+	fn on_killed_account(who: &T::AccountId) {
+		<AccountCreated<T>>::remove(who);
 	}
 }
@@ -0,0 +1,210 @@
+//! Storage migrations of the pallet, run from `on_runtime_upgrade`.
+//!
+//! Every layout change bumps `STORAGE_VERSION` and adds a module translating the storage of the
+//! previous version. Version 1 translates straight to the current layout.
+
+use frame_support::{
+	traits::{Get, GetStorageVersion, PalletInfoAccess, Time},
+	weights::Weight,
+};
+
+use crate::{Config, Pallet};
+
+/// Bring the storage of the pallet up to `STORAGE_VERSION` from version 0.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+	}
+	weight
+}
+
+/// Checks run before `migrate` by try-runtime.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		v1::pre_upgrade::<T>()?;
+	}
+	Ok(())
+}
+
+/// Checks run after `migrate` by try-runtime.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+	frame_support::ensure!(
+		Pallet::<T>::on_chain_storage_version() == Pallet::<T>::current_storage_version(),
+		"Storage version wasn't updated"
+	);
+	v1::post_upgrade::<T>()
+}
+
+/// Version 1 replaced the fixed 7x6 boards with sized bitboards and challenges with challenge
+/// settings, and moved the match queue into score buckets.
+pub mod v1 {
+	use super::*;
+	use crate::{
+		gameplay::{Board, Logic},
+		ActiveBoards, AwardState, BoardOf, BoardState, BoardStruct, BoardVariant, Boards,
+		ChallengeStruct, Challenges, DoublingCube, EventVerbosity, FirstMovePolicy, GameKind,
+		NextChallengeId, PlayerBoard,
+	};
+	use codec::{Decode, Encode};
+	use sp_std::vec::Vec;
+
+	/// Award as stored before version 1, draws didn't count.
+	#[derive(Encode, Decode)]
+	pub struct OldAwardState {
+		win: u32,
+		lose: u32,
+	}
+
+	impl From<OldAwardState> for AwardState {
+		fn from(old: OldAwardState) -> Self {
+			AwardState { win: old.win, lose: old.lose, draw: 0 }
+		}
+	}
+
+	/// Board as stored before version 1, always 7 columns of 6 rows.
+	#[derive(Encode, Decode)]
+	pub struct OldBoardStruct<Hash, AccountId, BlockNumber> {
+		id: Hash,
+		red: AccountId,
+		blue: AccountId,
+		board: [[u8; 6]; 7],
+		_last_turn: BlockNumber,
+		next_player: u8,
+		board_state: BoardState<AccountId>,
+		award: OldAwardState,
+	}
+
+	type OldBoardOf<T> = OldBoardStruct<
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	fn translate_board<T: Config>(old: OldBoardOf<T>) -> Option<BoardOf<T>> {
+		let board = Board::from_cells(&old.board)?;
+		let position = Pallet::<T>::position_hash(&board, old.next_player);
+		Some(BoardStruct {
+			id: old.id,
+			red: old.red,
+			blue: old.blue,
+			board,
+			variant: BoardVariant::default(),
+			// Old games had no time banks, only staleness is timed from the migration on.
+			last_turn: T::GameClock::now(),
+			time_banks: None,
+			deadline: None,
+			next_player: old.next_player,
+			board_state: old.board_state,
+			award: old.award.into(),
+			bot: None,
+			red_mate: None,
+			blue_mate: None,
+			blitz: None,
+			last_move: None,
+			handicap: None,
+			first_move: FirstMovePolicy::Random,
+			first_player: old.next_player,
+			weighted_award: false,
+			stalled_turns: (0, 0),
+			position,
+			kind: GameKind::Ranked,
+			turns: Logic::stones(&board),
+			cube: DoublingCube::Centered,
+			moves_played: Logic::stones(&board) as u16,
+			sides_swapped: false,
+			verbosity: EventVerbosity::Delta,
+		})
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut translated = 0u64;
+
+		// Boards with stones floating above empty cells can't be translated and are dropped.
+		let mut boards = 0u32;
+		let mut dropped = Vec::new();
+		<Boards<T>>::translate::<OldBoardOf<T>, _>(|board_id, old| {
+			translated += 1;
+			let players = [old.red.clone(), old.blue.clone()];
+			let board = translate_board::<T>(old);
+			match board {
+				Some(_) => boards += 1,
+				None => dropped.push((board_id, players)),
+			}
+			board
+		});
+		// Running boards count against `MaxActiveBoards` like the ones created from now on.
+		<ActiveBoards<T>>::put(boards);
+		// Players of the dropped ones are free to start new games.
+		let (mut checked, mut released) = (0u64, 0u64);
+		for (board_id, players) in dropped {
+			for player in players {
+				checked += 1;
+				if <PlayerBoard<T>>::get(&player) == board_id {
+					<PlayerBoard<T>>::remove(&player);
+					released += 1;
+				}
+			}
+		}
+		// Old challenges named no opponent, so anyone may accept them.
+		let mut challenge_id = 0u32;
+		let expires =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::ChallengeLifetime::get());
+		<Challenges<T>>::translate::<OldAwardState, _>(|_, award| {
+			translated += 1;
+			let id = challenge_id;
+			challenge_id += 1;
+			Some(ChallengeStruct {
+				id,
+				opponent: None,
+				award: award.into(),
+				variant: BoardVariant::default(),
+				time_bank: None,
+				blitz: false,
+				handicap: None,
+				first_move: FirstMovePolicy::Random,
+				kind: GameKind::Ranked,
+				stake: None,
+				expires,
+			})
+		});
+		// Queued players were keyed by account, they have to join the bucketed queue again.
+		#[allow(deprecated)]
+		frame_support::storage::migration::remove_storage_prefix(
+			Pallet::<T>::name().as_bytes(),
+			b"MatchQueue",
+			&[],
+		);
+
+		<NextChallengeId<T>>::put(challenge_id);
+		Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated + checked, translated + released + 4)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+		let old_layout = <Boards<T>>::iter_keys().all(|board_id| {
+			frame_support::storage::unhashed::get::<OldBoardOf<T>>(&<Boards<T>>::hashed_key_for(
+				board_id,
+			))
+			.is_some()
+		});
+		frame_support::ensure!(old_layout, "Boards don't use the old layout");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			<Boards<T>>::iter_keys().all(|board_id| <Boards<T>>::get(board_id).is_some()),
+			"Boards left in the old layout"
+		);
+		frame_support::ensure!(
+			<Challenges<T>>::iter_keys().all(|account| <Challenges<T>>::get(account).is_some()),
+			"Challenges left in the old layout"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,472 @@
+use crate as pallet_connectfour;
+use codec::Encode;
+use frame_support::{
+	parameter_types,
+	storage::unhashed,
+	traits::{
+		tokens::{
+			fungibles::{Inspect, InspectHold, MutateHold, Transfer},
+			DepositConsequence, WithdrawConsequence,
+		},
+		ConstI32, ConstU16, ConstU32, ConstU64, ConstU8, Currency, OnUnbalanced, Randomness, Time,
+	},
+	PalletId,
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Convert, Hash, IdentityLookup},
+	DispatchError, DispatchResult, Perbill, TokenError,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		ConnectFour: pallet_connectfour::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ConnectFour;
+	type OnKilledAccount = ConnectFour;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	Call: From<C>,
+{
+	type Extrinsic = TestXt<Call, ()>;
+	type OverarchingCall = Call;
+}
+
+/// Predictable randomness, the subject hashed together with the current block number.
+pub struct TestRandomness;
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		(BlakeTwo256::hash_of(&(subject, block_number)), block_number)
+	}
+}
+
+/// Account receiving the house fee of prediction pools.
+pub const TREASURY: u64 = 100;
+
+/// Pays the house fees of prediction pools and staked games to `TREASURY`.
+pub struct ToTreasury;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for ToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&TREASURY, amount);
+	}
+}
+
+/// Free and held balances of assets, kept in the test externalities.
+pub struct TestAssets;
+
+impl TestAssets {
+	fn get(asset: u32, who: &u64) -> (u64, u64) {
+		unhashed::get_or_default(&(b"assets", asset, who).encode())
+	}
+
+	fn put(asset: u32, who: &u64, balances: (u64, u64)) {
+		unhashed::put(&(b"assets", asset, who).encode(), &balances);
+	}
+
+	pub fn set_balance(asset: u32, who: &u64, free: u64) {
+		let (_, held) = Self::get(asset, who);
+		Self::put(asset, who, (free, held));
+	}
+}
+
+impl Inspect<u64> for TestAssets {
+	type AssetId = u32;
+	type Balance = u64;
+
+	fn total_issuance(_: u32) -> u64 {
+		0
+	}
+
+	fn minimum_balance(_: u32) -> u64 {
+		1
+	}
+
+	fn balance(asset: u32, who: &u64) -> u64 {
+		Self::get(asset, who).0
+	}
+
+	fn reducible_balance(asset: u32, who: &u64, _: bool) -> u64 {
+		Self::get(asset, who).0
+	}
+
+	fn can_deposit(_: u32, _: &u64, _: u64, _: bool) -> DepositConsequence {
+		DepositConsequence::Success
+	}
+
+	fn can_withdraw(asset: u32, who: &u64, amount: u64) -> WithdrawConsequence<u64> {
+		if Self::balance(asset, who) >= amount {
+			WithdrawConsequence::Success
+		} else {
+			WithdrawConsequence::NoFunds
+		}
+	}
+}
+
+impl InspectHold<u64> for TestAssets {
+	fn balance_on_hold(asset: u32, who: &u64) -> u64 {
+		Self::get(asset, who).1
+	}
+
+	fn can_hold(asset: u32, who: &u64, amount: u64) -> bool {
+		Self::balance(asset, who) >= amount
+	}
+}
+
+impl Transfer<u64> for TestAssets {
+	fn transfer(
+		asset: u32,
+		source: &u64,
+		dest: &u64,
+		amount: u64,
+		_: bool,
+	) -> Result<u64, DispatchError> {
+		let (free, held) = Self::get(asset, source);
+		let free = free.checked_sub(amount).ok_or(TokenError::NoFunds)?;
+		Self::put(asset, source, (free, held));
+		let (dest_free, dest_held) = Self::get(asset, dest);
+		Self::put(asset, dest, (dest_free + amount, dest_held));
+		Ok(amount)
+	}
+}
+
+impl MutateHold<u64> for TestAssets {
+	fn hold(asset: u32, who: &u64, amount: u64) -> DispatchResult {
+		let (free, held) = Self::get(asset, who);
+		let free = free.checked_sub(amount).ok_or(TokenError::NoFunds)?;
+		Self::put(asset, who, (free, held + amount));
+		Ok(())
+	}
+
+	fn release(
+		asset: u32,
+		who: &u64,
+		amount: u64,
+		best_effort: bool,
+	) -> Result<u64, DispatchError> {
+		let (free, held) = Self::get(asset, who);
+		if !best_effort && held < amount {
+			return Err(TokenError::NoFunds.into())
+		}
+		let amount = amount.min(held);
+		Self::put(asset, who, (free + amount, held - amount));
+		Ok(amount)
+	}
+
+	fn transfer_held(
+		asset: u32,
+		source: &u64,
+		dest: &u64,
+		amount: u64,
+		best_effort: bool,
+		on_hold: bool,
+	) -> Result<u64, DispatchError> {
+		let amount = Self::release(asset, source, amount, best_effort)?;
+		Self::transfer(asset, source, dest, amount, false)?;
+		if on_hold {
+			Self::hold(asset, dest, amount)?;
+		}
+		Ok(amount)
+	}
+}
+
+/// Accounts below 10 have an identity named after them, only odd ones are judged.
+pub struct TestIdentity;
+impl pallet_connectfour::IdentityProvider<u64> for TestIdentity {
+	fn display_name(who: &u64) -> Option<Vec<u8>> {
+		(*who < 10).then(|| format!("player{}", who).into_bytes())
+	}
+
+	fn is_judged(who: &u64) -> bool {
+		*who < 10 && who % 2 == 1
+	}
+}
+
+/// Keeps the trophies it mints in `MintedTrophies`, fails while `FailTrophyMints` is set.
+pub struct RecordTrophies;
+impl pallet_connectfour::TrophyMinter<u64, H256> for RecordTrophies {
+	fn mint_trophy(trophy_id: u32, winner: &u64, board_id: H256, moves: u32) -> DispatchResult {
+		if FailTrophyMints::get() {
+			return Err(DispatchError::Other("mint failed"))
+		}
+		let mut trophies = MintedTrophies::get();
+		trophies.push((trophy_id, *winner, board_id, moves));
+		MintedTrophies::set(trophies);
+		Ok(())
+	}
+}
+
+/// Keeps the results it's told about in `GameResults`.
+pub struct RecordResults;
+impl pallet_connectfour::OnGameFinished<u64, H256> for RecordResults {
+	fn on_game_finished(
+		board_id: H256,
+		_: &u64,
+		_: &u64,
+		winner: Option<&u64>,
+		loser: Option<&u64>,
+		kind: pallet_connectfour::GameKind,
+		reason: pallet_connectfour::FinishReason,
+	) {
+		let mut results = GameResults::get();
+		results.push((winner.copied(), loser.copied(), board_id, kind, reason));
+		GameResults::set(results);
+	}
+}
+
+/// Signed origins of accounts from 100 on stand in for players on other chains, located at
+/// their account. They play as the account 1000 above it, see `RemoteAccount`.
+pub struct EnsureRemote;
+impl frame_support::traits::EnsureOrigin<Origin> for EnsureRemote {
+	type Success = u64;
+
+	fn try_origin(origin: Origin) -> Result<u64, Origin> {
+		let origin: Result<system::RawOrigin<u64>, Origin> = origin.into();
+		origin.and_then(|origin| match origin {
+			system::RawOrigin::Signed(location) if location >= 100 => Ok(location),
+			origin => Err(Origin::from(origin)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(100)
+	}
+}
+
+pub struct RemoteAccount;
+impl Convert<u64, u64> for RemoteAccount {
+	fn convert(location: u64) -> u64 {
+		location + 1000
+	}
+}
+
+/// Keeps the results sent to players on other chains in `RemoteResults`.
+pub struct RecordRemoteResults;
+impl pallet_connectfour::NotifyRemotePlayer<u64, H256> for RecordRemoteResults {
+	fn notify_result(
+		location: &u64,
+		board_id: H256,
+		won: Option<bool>,
+		reason: pallet_connectfour::FinishReason,
+	) {
+		let mut results = RemoteResults::get();
+		results.push((*location, board_id, won, reason));
+		RemoteResults::set(results);
+	}
+}
+
+/// Ticks `ClockSpeed` times per block, a speed of 1 counts blocks like `BlockClock`.
+pub struct TestClock;
+impl Time for TestClock {
+	type Moment = u64;
+
+	fn now() -> u64 {
+		System::block_number() * ClockSpeed::get()
+	}
+}
+
+/// Test signatures are the signer's account and the message.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_connectfour::BenchmarkHelper<UintAuthorityId, u64, TestSignature, u64>
+	for TestBenchmarkHelper
+{
+	fn signer() -> (UintAuthorityId, u64) {
+		(UintAuthorityId(1), 1)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+
+	fn advance_clock(by: u64) {
+		let speed = ClockSpeed::get();
+		System::set_block_number(System::block_number() + (by + speed - 1) / speed);
+	}
+}
+
+parameter_types! {
+	pub static RequireJudgedIdentity: bool = false;
+	pub static ProvisionalGames: u32 = 0;
+	pub static MaxMovesPerGame: u32 = 1000;
+	pub static ClockSpeed: u64 = 1;
+	pub static InactivityPeriod: u64 = 0;
+	pub static ChallengeCooldown: u64 = 0;
+	pub static MaxChallengesPerEra: u32 = 0;
+	pub static MinAccountAge: u64 = 0;
+	pub static QueueTimeout: u64 = 0;
+	pub static GameResults: Vec<(
+		Option<u64>,
+		Option<u64>,
+		H256,
+		pallet_connectfour::GameKind,
+		pallet_connectfour::FinishReason,
+	)> = Vec::new();
+	pub static MintedTrophies: Vec<(u32, u64, H256, u32)> = Vec::new();
+	pub static FailTrophyMints: bool = false;
+	pub static RemoteResults: Vec<(u64, H256, Option<bool>, pallet_connectfour::FinishReason)> =
+		Vec::new();
+	pub const MatchmakingTimeBank: Option<u64> = None;
+	pub const SeasonScoreRetention: Perbill = Perbill::from_percent(50);
+	pub const DefaultMatchAward: pallet_connectfour::AwardState =
+		pallet_connectfour::AwardState { win: 10, lose: 5, draw: 2 };
+	pub static DefaultEventVerbosity: pallet_connectfour::EventVerbosity =
+		pallet_connectfour::EventVerbosity::Delta;
+	pub const ConnectFourPalletId: PalletId = PalletId(*b"py/cnct4");
+}
+
+impl pallet_connectfour::Config for Test {
+	type Proposal = Call;
+	type Event = Event;
+	type Randomness = TestRandomness;
+	type MaxBoardWidth = ConstU8<10>;
+	type MaxBoardHeight = ConstU8<10>;
+	type MaxQueueLength = ConstU32<16>;
+	type MaxMatchesPerBlock = ConstU32<4>;
+	type RematchPeriod = ConstU64<10>;
+	type MatchmakingTimeBank = MatchmakingTimeBank;
+	type Trophies = RecordTrophies;
+	type MaxSpectators = ConstU32<4>;
+	type Currency = Balances;
+	type MinRankedBalance = ConstU64<10>;
+	type QueueDeposit = ConstU64<5>;
+	type MinAccountAge = MinAccountAge;
+	type SeasonLength = ConstU64<100>;
+	type SeasonScoreRetention = SeasonScoreRetention;
+	type MaxDecayPerBlock = ConstU32<4>;
+	type MaxSeasonRewardees = ConstU32<3>;
+	type SeasonRewardOrigin = frame_system::EnsureRoot<u64>;
+	type PalletId = ConnectFourPalletId;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type DefaultAcceptedDiff = ConstU8<10>;
+	type QueueWideningPeriod = ConstU64<5>;
+	type QueueWideningStep = ConstU8<10>;
+	type MaxWidenedDiff = ConstU8<30>;
+	type QueueTimeout = QueueTimeout;
+	type DefaultMatchAward = DefaultMatchAward;
+	type DefaultMaxActiveBoards = ConstU32<100>;
+	type DefaultMaxGamesPerPlayer = ConstU32<1>;
+	type MaxLeaderboardSize = ConstU32<4>;
+	type GameClock = TestClock;
+	type GameFinishedHandler = RecordResults;
+	type RemoteLocation = u64;
+	type RemoteOrigin = EnsureRemote;
+	type RemoteAccount = RemoteAccount;
+	type RemoteNotifier = RecordRemoteResults;
+	type ReportHandler = ();
+	type MaxOpenLobbies = ConstU32<4>;
+	type HandicapScoreGap = ConstU32<5>;
+	type ScoreFloor = ConstI32<0>;
+	type ProvisionalGames = ProvisionalGames;
+	type ProvisionalBoost = ConstU8<3>;
+	type MaxTournamentPlayers = ConstU32<8>;
+	type MaxTournamentRounds = ConstU32<4>;
+	type MaxTournamentsPerBlock = ConstU32<2>;
+	type StaleGameAge = ConstU64<20>;
+	type UnsignedPriority = ConstU64<100>;
+	type AwardCalculator = ();
+	type MaxFriends = ConstU32<4>;
+	type MaxBlocked = ConstU32<4>;
+	type ResultRetentionBlocks = ConstU64<10>;
+	type MaxArchivedMoves = ConstU32<64>;
+	type StallTurnTime = ConstU64<5>;
+	type MaxStalledTurns = ConstU8<2>;
+	type MaxDisplayNameLength = ConstU32<8>;
+	type MaxPredictions = ConstU32<4>;
+	type PredictionFeeDestination = ToTreasury;
+	type StakeFeeDestination = ToTreasury;
+	type MaxCleanupAccounts = ConstU32<4>;
+	type CleanupReward = ConstU64<1>;
+	type Identity = TestIdentity;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TestBenchmarkHelper;
+	type RequireJudgedIdentity = RequireJudgedIdentity;
+	type MaxBoardCreationsPerBlock = ConstU32<2>;
+	type MaxMovesPerGame = MaxMovesPerGame;
+	type RewardPointValue = ConstU64<2>;
+	type RewardVestingBlocks = ConstU64<5>;
+	type InactivityPeriod = InactivityPeriod;
+	type InactivityScoreRetention = SeasonScoreRetention;
+	type MaxInactivityChecks = ConstU32<2>;
+	type Assets = TestAssets;
+	type EmoteCooldown = ConstU64<3>;
+	type ChallengeLifetime = ConstU64<20>;
+	type PrivateGameLifetime = ConstU64<10>;
+	type MaxPrivateGamesPerBlock = ConstU32<2>;
+	type MaxScoreHistory = ConstU32<3>;
+	type MaxGuildMembers = ConstU32<3>;
+	type MaxGuildMatchPlayers = ConstU32<2>;
+	type SnapshotInterval = ConstU32<4>;
+	type PrizeClaimPeriod = ConstU64<10>;
+	type MaxPrizeSweeps = ConstU32<2>;
+	type UnclaimedPrizeDestination = ToTreasury;
+	type ChallengeCooldown = ChallengeCooldown;
+	type ChallengeEra = ConstU64<20>;
+	type MaxChallengesPerEra = MaxChallengesPerEra;
+	type MaxSponsors = ConstU32<2>;
+	type AnnotationDeposit = ConstU64<3>;
+	type MaxAnnotations = ConstU32<2>;
+	type MaxAnnotationLength = ConstU32<8>;
+	type DefaultEventVerbosity = DefaultEventVerbosity;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+}
@@ -0,0 +1,120 @@
+//! Swiss-system pairing of tournament rounds.
+//!
+//! Players are given by their index in the standings. Every round pairs players with equal or
+//! close points who haven't met yet, and the final ranking breaks ties by Buchholz score.
+
+use sp_std::{vec, vec::Vec};
+
+/// Points for a win or a bye, scores are kept in half points so a draw is worth one.
+pub const WIN_POINTS: u32 = 2;
+
+/// Points for a draw.
+pub const DRAW_POINTS: u32 = 1;
+
+/// Pairings tried before rematches are accepted, keeps pairing cheap when avoiding them gets hard.
+const MAX_PAIRING_STEPS: u32 = 10_000;
+
+/// Pairings of a single round.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Round {
+	pub pairs: Vec<(usize, usize)>,
+	/// Player sitting out an odd round, who gets the points of a win.
+	pub bye: Option<usize>,
+}
+
+pub struct Swiss {}
+
+impl Swiss {
+	/// Pair the next round of players with the given `points`.
+	///
+	/// `played` tells if two players already met and `had_bye` if a player already sat out a
+	/// round. Rematches are avoided as long as a pairing without them can be found.
+	pub fn pair(
+		points: &[u32],
+		played: impl Fn(usize, usize) -> bool,
+		had_bye: impl Fn(usize) -> bool,
+	) -> Round {
+		let mut order = Self::ranking(points, &[]);
+		// The lowest ranked player without a bye sits out an odd round.
+		let bye = if order.len() % 2 == 1 {
+			let position =
+				order.iter().rposition(|player| !had_bye(*player)).unwrap_or(order.len() - 1);
+			Some(order.remove(position))
+		} else {
+			None
+		};
+
+		let mut paired = vec![false; order.len()];
+		let mut pairs = Vec::new();
+		let mut steps = MAX_PAIRING_STEPS;
+		if !Self::pair_from(&order, &mut paired, &mut pairs, &played, &mut steps) {
+			// Neighbours in the ranking meet again.
+			pairs = order.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+		}
+		Round { pairs, bye }
+	}
+
+	/// Pair the first round of players with the given `ratings`: the best seed meets the worst,
+	/// the second best the second worst and so on. The worst seed sits out an odd round.
+	pub fn seed(ratings: &[i32]) -> Round {
+		let mut order: Vec<usize> = (0..ratings.len()).collect();
+		order.sort_by(|a, b| ratings[*b].cmp(&ratings[*a]).then(a.cmp(b)));
+		let bye = if order.len() % 2 == 1 { order.pop() } else { None };
+		let pairs = (0..order.len() / 2).map(|i| (order[i], order[order.len() - 1 - i])).collect();
+		Round { pairs, bye }
+	}
+
+	/// Pair the highest ranked unpaired player with the next opponent they haven't met,
+	/// backtracking if the rest of the players can't be paired afterwards.
+	fn pair_from(
+		order: &[usize],
+		paired: &mut [bool],
+		pairs: &mut Vec<(usize, usize)>,
+		played: &impl Fn(usize, usize) -> bool,
+		steps: &mut u32,
+	) -> bool {
+		let first = match paired.iter().position(|done| !done) {
+			Some(first) => first,
+			None => return true,
+		};
+		paired[first] = true;
+		for second in first + 1..order.len() {
+			if paired[second] || played(order[first], order[second]) {
+				continue
+			}
+			if *steps == 0 {
+				break
+			}
+			*steps -= 1;
+			paired[second] = true;
+			pairs.push((order[first], order[second]));
+			if Self::pair_from(order, paired, pairs, played, steps) {
+				return true
+			}
+			pairs.pop();
+			paired[second] = false;
+		}
+		paired[first] = false;
+		false
+	}
+
+	/// Buchholz score of every player, the sum of the points of their opponents.
+	pub fn buchholz(points: &[u32], opponents: &[Vec<usize>]) -> Vec<u32> {
+		opponents
+			.iter()
+			.map(|met| met.iter().filter_map(|opponent| points.get(*opponent)).sum())
+			.collect()
+	}
+
+	/// Players ordered by points, then by `tie_breaks`, then by index.
+	pub fn ranking(points: &[u32], tie_breaks: &[u32]) -> Vec<usize> {
+		let mut order: Vec<usize> = (0..points.len()).collect();
+		order.sort_by(|a, b| {
+			points[*b]
+				.cmp(&points[*a])
+				.then(tie_breaks.get(*b).cmp(&tie_breaks.get(*a)))
+				.then(a.cmp(b))
+		});
+		order
+	}
+}
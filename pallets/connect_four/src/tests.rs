@@ -0,0 +1,4773 @@
+use crate::{
+	achievements::Achievement,
+	gameplay::{
+		notation::{self, GameRecord},
+		Logic, Move, Outcome, ThreatCounts,
+	},
+	mock::*,
+	AbortReason, AwardCalculator, AwardState, BlitzPhase, BlitzRound, BoardSponsors, BoardState,
+	BoardVariant, Boards, BotDifficulty, Challenges, CheckMove, Emote, Error, EventVerbosity,
+	FinishReason, FirstMovePolicy, Friends, GameAnnotations, GameKind, GamePreset, GameStats,
+	GameTrieCleanups, Handicap, Leaderboard, MatchQueue, MoveError, PendingBoardCreations,
+	PlayerBoard, PlayerProfile, Predictions, PrivateGameSettings, QueueRechecks, QueueStatus,
+	QueuedPresets, RelayedMove, RemotePlayers, ReportReason, ResultsByBlock, ScoreChange,
+	ScoreWeightedAward, ScoringBoard, SponsoredGame, StreamedMove, TierBoundariesOf, TimeBanks,
+	TournamentPlayers, TournamentRounds, TournamentState, Tournaments, Verdict, WeightInfo,
+	PLAYER_1, PLAYER_2,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{
+		tokens::fungibles::{Inspect, InspectHold},
+		Currency, GenesisBuild, GetStorageVersion, Hooks, StorageInfoTrait, StorageVersion,
+	},
+	weights::{GetDispatchInfo, Pays},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::TestSignature,
+	traits::{BlakeTwo256, Hash, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidity},
+	DispatchError, Perbill, TokenError,
+};
+
+/// Award of most challenges in these tests.
+const AWARD: AwardState = AwardState { win: 10, lose: 5, draw: 2 };
+
+#[test]
+fn challenges_pick_the_board_size_and_connect_length() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Boards beyond `MaxBoardWidth` and lines longer than the board are refused.
+		let too_wide = BoardVariant { width: 11, ..Default::default() };
+		assert_noop!(
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				too_wide,
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			),
+			Error::<Test>::InvalidVariant
+		);
+		let too_long = BoardVariant { connect: 7, ..Default::default() };
+		assert_noop!(
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				too_long,
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			),
+			Error::<Test>::InvalidVariant
+		);
+
+		let connect_five = BoardVariant { width: 8, height: 8, connect: 5, pop_out: false };
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			connect_five,
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.variant, connect_five);
+		let (first, second) = if board.next_player == PLAYER_1 {
+			(board.red, board.blue)
+		} else {
+			(board.blue, board.red)
+		};
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(first), 8, None),
+			Error::<Test>::ColumnOutOfRange
+		);
+
+		// Four in a row don't win a game of connect five.
+		for column in 0..4 {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(first), column, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(second), 7, None));
+		}
+		assert!(Boards::<Test>::contains_key(board_id));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 4, None));
+		assert!(!Boards::<Test>::contains_key(board_id));
+		assert_eq!(ScoringBoard::<Test>::get(first), Some(10));
+		assert_eq!(ScoringBoard::<Test>::get(second), Some(0));
+	});
+}
+
+#[test]
+fn calls_are_charged_their_benchmarked_weight() {
+	new_test_ext().execute_with(|| {
+		let weight_of = |call: crate::Call<Test>| call.get_dispatch_info().weight;
+		// Finding a game only queues the caller, at a constant weight.
+		assert_eq!(
+			weight_of(crate::Call::<Test>::find_game { kind: GameKind::Ranked }),
+			<() as WeightInfo>::find_game()
+		);
+		assert_eq!(
+			weight_of(crate::Call::<Test>::cancel_queue {}),
+			<() as WeightInfo>::cancel_queue()
+		);
+		assert_eq!(
+			weight_of(crate::Call::<Test>::challenge {
+				opponent: 2,
+				win: 10,
+				lose: 5,
+				draw: 2,
+				variant: BoardVariant::default(),
+				time_bank: None,
+				handicap: None,
+				first_move: FirstMovePolicy::Random,
+				kind: GameKind::Ranked,
+				stake: None
+			}),
+			<() as WeightInfo>::challenge()
+		);
+		assert_eq!(
+			weight_of(crate::Call::<Test>::resp_challenge {
+				opponent: 1,
+				accepted: true,
+				award: AWARD
+			}),
+			<() as WeightInfo>::resp_challenge()
+		);
+		assert_eq!(
+			weight_of(crate::Call::<Test>::cancel_challenge {}),
+			<() as WeightInfo>::cancel_challenge()
+		);
+		// Moves are charged upfront for the winning move, the worst case.
+		let play_turn =
+			weight_of(crate::Call::<Test>::play_turn { column: 0, expected_move_index: None });
+		assert!(play_turn >= <() as WeightInfo>::play_turn());
+		assert!(<() as WeightInfo>::play_turn() > <() as WeightInfo>::play_turn_mid_game());
+	});
+}
+
+#[test]
+fn full_match_queue_turns_players_away() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in 10..=26 {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		for player in 10..26 {
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		assert_eq!(MatchQueue::<Test>::get(0).len(), 16);
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(26), GameKind::Ranked),
+			Error::<Test>::QueueFull
+		);
+	});
+}
+
+#[test]
+fn queued_players_are_paired_a_few_games_per_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in 1..=10 {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		// Finding a game only queues the player.
+		assert_eq!(Boards::<Test>::iter().count(), 0);
+		assert_eq!(ConnectFour::newly_queued().len(), 10);
+
+		// No more than `MaxMatchesPerBlock` games start in a block, the rest wait their turn.
+		ConnectFour::on_initialize(2);
+		assert_eq!(Boards::<Test>::iter().count(), 4);
+		assert_eq!(ConnectFour::newly_queued().into_inner(), vec![9, 10]);
+		assert_eq!(PlayerBoard::<Test>::get(1), PlayerBoard::<Test>::get(2));
+
+		ConnectFour::on_initialize(3);
+		assert_eq!(Boards::<Test>::iter().count(), 5);
+		assert_eq!(PlayerBoard::<Test>::get(9), PlayerBoard::<Test>::get(10));
+		assert!(MatchQueue::<Test>::get(0).is_empty());
+		assert_eq!(ConnectFour::queued_players(9), None);
+	});
+}
+
+#[test]
+fn rematches_swap_colors_until_the_offer_expires() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let variant = BoardVariant { width: 8, ..Default::default() };
+		let play_out = |challenger, responder| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(challenger),
+				responder,
+				10,
+				5,
+				2,
+				variant,
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(
+				Origin::signed(responder),
+				challenger,
+				true,
+				AWARD
+			));
+			let board = Boards::<Test>::get(PlayerBoard::<Test>::get(challenger)).unwrap();
+			let (first, second) = if board.next_player == PLAYER_1 {
+				(board.red, board.blue)
+			} else {
+				(board.blue, board.red)
+			};
+			for _ in 0..3 {
+				assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+				assert_ok!(ConnectFour::play_turn(Origin::signed(second), 1, None));
+			}
+			assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+		};
+
+		play_out(1, 2);
+		assert!(!PlayerBoard::<Test>::contains_key(1));
+		assert_noop!(
+			ConnectFour::accept_rematch(Origin::signed(1)),
+			Error::<Test>::RematchNotRequested
+		);
+		assert_ok!(ConnectFour::request_rematch(Origin::signed(2)));
+		System::assert_last_event(crate::Event::RematchRequested(2, 1).into());
+		assert_ok!(ConnectFour::accept_rematch(Origin::signed(1)));
+		// Red responded to the first game, the challenger plays red now.
+		let board = Boards::<Test>::get(PlayerBoard::<Test>::get(1)).unwrap();
+		assert_eq!((board.red, board.blue), (1, 2));
+		assert_eq!(
+			(board.award, board.variant),
+			(AwardState { win: 10, lose: 5, draw: 2 }, variant)
+		);
+		assert!(ConnectFour::rematches(1).is_none() && ConnectFour::rematches(2).is_none());
+
+		// Offers run out after `RematchPeriod` blocks.
+		play_out(3, 4);
+		System::set_block_number(12);
+		assert_noop!(
+			ConnectFour::request_rematch(Origin::signed(3)),
+			Error::<Test>::RematchExpired
+		);
+		assert_noop!(ConnectFour::request_rematch(Origin::signed(5)), Error::<Test>::NoRematch);
+	});
+}
+
+#[test]
+fn moves_after_the_time_bank_ran_out_lose_on_time() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				Some(0),
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			),
+			Error::<Test>::InvalidTimeBank
+		);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			Some(10),
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let (first, second) = if board.next_player == PLAYER_1 {
+			(board.red, board.blue)
+		} else {
+			(board.blue, board.red)
+		};
+
+		// Each player's bank only runs down on their own turns.
+		System::set_block_number(5);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 3, None));
+		System::set_block_number(7);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(second), 3, None));
+		let time_banks = Boards::<Test>::get(board_id).unwrap().time_banks;
+		let (red, blue) = if first == board.red { (6, 8) } else { (8, 6) };
+		assert_eq!(time_banks, Some(TimeBanks { initial: 10, red, blue }));
+		assert_noop!(
+			ConnectFour::claim_timeout(Origin::signed(second)),
+			Error::<Test>::ClockNotExpired
+		);
+
+		// The first mover took 7 blocks with 6 left, the move isn't played and they lose on time.
+		System::set_block_number(14);
+		assert_noop!(
+			ConnectFour::claim_timeout(Origin::signed(first)),
+			Error::<Test>::CannotClaimOwnTimeout
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 4, None));
+		assert!(!Boards::<Test>::contains_key(board_id));
+		assert_eq!(ScoringBoard::<Test>::get(second), Some(10));
+		assert_eq!(ScoringBoard::<Test>::get(first), Some(0));
+	});
+}
+
+#[test]
+fn winners_are_minted_a_trophy() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let win_game = || {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			let board_id = PlayerBoard::<Test>::get(1);
+			let board = Boards::<Test>::get(board_id).unwrap();
+			let (first, second) = if board.next_player == PLAYER_1 {
+				(board.red, board.blue)
+			} else {
+				(board.blue, board.red)
+			};
+			for _ in 0..3 {
+				assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+				assert_ok!(ConnectFour::play_turn(Origin::signed(second), 1, None));
+			}
+			assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+			(board_id, first)
+		};
+
+		let (board_id, winner) = win_game();
+		assert_eq!(MintedTrophies::get(), vec![(0, winner, board_id, 7)]);
+		System::assert_has_event(crate::Event::TrophyAwarded(winner, board_id, 0).into());
+		assert_eq!(ConnectFour::next_trophy_id(), 1);
+
+		// A failing minter doesn't keep the game from finishing, the id isn't used up.
+		FailTrophyMints::set(true);
+		let (board_id, _) = win_game();
+		assert!(!Boards::<Test>::contains_key(board_id));
+		assert_eq!(MintedTrophies::get().len(), 1);
+		assert_eq!(ConnectFour::next_trophy_id(), 1);
+	});
+}
+
+#[test]
+fn front_ends_read_boards_scores_and_queue_entries() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(ConnectFour::player_board_id(&2), Some(board_id));
+		assert_eq!(ConnectFour::player_board_id(&3), None);
+		assert_eq!(ConnectFour::boards(board_id).map(|board| board.red), Some(2));
+
+		Leaderboard::<Test>::put(vec![(11, 300), (3, 200), (4, 100)].try_into().unwrap());
+		assert_eq!(ConnectFour::leaderboard(2), vec![(11, 300), (3, 200)]);
+		assert_eq!(ConnectFour::leaderboard(10).len(), 3);
+
+		ScoringBoard::<Test>::insert(3, 200);
+		Balances::make_free_balance_be(&3, 100);
+		assert_eq!(ConnectFour::queue_status(&3), None);
+		assert_ok!(ConnectFour::find_game(Origin::signed(3), GameKind::Ranked));
+		assert_eq!(ConnectFour::queue_status(&3), Some(QueueStatus { score: 200, waiting: 1 }));
+	});
+}
+
+#[test]
+fn genesis_scores_seed_the_scoring_board() {
+	new_test_ext().execute_with(|| {
+		let config = crate::GenesisConfig::<Test> { scores: vec![(1, 10), (2, 30)] };
+		GenesisBuild::<Test>::build(&config);
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(10));
+		assert_eq!(ScoringBoard::<Test>::get(2), Some(30));
+		assert_eq!(ConnectFour::leaderboard(2), vec![(2, 30), (1, 10)]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "Account has more than one genesis score.")]
+fn genesis_scores_reject_duplicate_accounts() {
+	new_test_ext().execute_with(|| {
+		let config = crate::GenesisConfig::<Test> { scores: vec![(1, 10), (1, 30)] };
+		GenesisBuild::<Test>::build(&config);
+	});
+}
+
+#[test]
+fn every_storage_item_is_bounded() {
+	new_test_ext().execute_with(|| {
+		for info in ConnectFour::storage_info() {
+			let name = String::from_utf8_lossy(&info.storage_name);
+			assert!(info.max_size.is_some(), "{} is unbounded", name);
+		}
+	});
+}
+
+#[test]
+fn try_state_catches_dangling_board_pointers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert_ok!(ConnectFour::do_try_state());
+
+		let board_id = PlayerBoard::<Test>::get(1);
+		Boards::<Test>::remove(board_id);
+		assert_eq!(ConnectFour::do_try_state(), Err("PlayerBoard points at a missing board"));
+	});
+}
+
+#[test]
+fn spectators_follow_a_board_until_it_finishes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_noop!(
+			ConnectFour::watch_board(Origin::signed(3), H256::repeat_byte(1)),
+			Error::<Test>::BoardNotFound
+		);
+
+		for spectator in 3..7 {
+			assert_ok!(ConnectFour::watch_board(Origin::signed(spectator), board_id));
+		}
+		assert_noop!(
+			ConnectFour::watch_board(Origin::signed(3), board_id),
+			Error::<Test>::AlreadyWatching
+		);
+		assert_noop!(
+			ConnectFour::watch_board(Origin::signed(7), board_id),
+			Error::<Test>::TooManySpectators
+		);
+		assert_ok!(ConnectFour::unwatch_board(Origin::signed(3), board_id));
+		assert_noop!(
+			ConnectFour::unwatch_board(Origin::signed(3), board_id),
+			Error::<Test>::NotWatching
+		);
+		assert_ok!(ConnectFour::watch_board(Origin::signed(7), board_id));
+		assert_eq!(ConnectFour::spectators(board_id).into_inner(), vec![4, 5, 6, 7]);
+
+		// Every stone is announced, a finished game has nothing left to follow.
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let (first, second) = if board.next_player == PLAYER_1 {
+			(board.red, board.blue)
+		} else {
+			(board.blue, board.red)
+		};
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+		System::assert_last_event(
+			crate::Event::TurnPlayed {
+				board_id,
+				player: first,
+				column: 0,
+				row: 5,
+				position: Boards::<Test>::get(board_id).unwrap().position,
+				deadline: None,
+				moves_played: 1,
+				evaluation: Some(ThreatCounts { red: 0, blue: 0 }),
+			}
+			.into(),
+		);
+		for _ in 0..3 {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(second), 1, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+		}
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: Some(first),
+				reason: FinishReason::Connected,
+				win_line: vec![(0, 5), (0, 4), (0, 3), (0, 2)],
+				stake_fee: 0,
+			}
+			.into(),
+		);
+		assert!(ConnectFour::spectators(board_id).is_empty());
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn only_the_challenged_account_responds_unless_the_challenge_is_open() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_eq!(Challenges::<Test>::get(1).unwrap().opponent, Some(2));
+		for accepted in [true, false] {
+			assert_noop!(
+				ConnectFour::resp_challenge(Origin::signed(3), 1, accepted, AWARD),
+				Error::<Test>::NotChallenged
+			);
+		}
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+
+		// Anyone may take up an open challenge, nobody can turn it down.
+		assert_ok!(ConnectFour::open_challenge(
+			Origin::signed(3),
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			GameKind::Ranked
+		));
+		assert_eq!(Challenges::<Test>::get(3).unwrap().opponent, None);
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(4), 3, false, AWARD),
+			Error::<Test>::NotChallenged
+		);
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(4), 3, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(3);
+		assert_eq!(PlayerBoard::<Test>::get(4), board_id);
+		assert!(!Challenges::<Test>::contains_key(3));
+	});
+}
+
+#[test]
+fn ranked_matchmaking_keeps_throwaway_accounts_out() {
+	new_test_ext().execute_with(|| {
+		MinAccountAge::set(10);
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 9);
+		Balances::make_free_balance_be(&2, 100);
+
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(1), GameKind::Ranked),
+			Error::<Test>::InsufficientBalance
+		);
+		System::set_block_number(5);
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(2), GameKind::Ranked),
+			Error::<Test>::AccountTooNew
+		);
+
+		System::set_block_number(11);
+		assert_ok!(ConnectFour::find_game(Origin::signed(2), GameKind::Ranked));
+		assert_eq!(Balances::reserved_balance(&2), 5);
+		assert_ok!(ConnectFour::cancel_queue(Origin::signed(2)));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn seasons_decay_scores_and_reward_the_best_players() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (player, score) in [(1, 100), (2, 60), (3, 40), (4, -20), (5, 10), (6, 8)] {
+			ScoringBoard::<Test>::insert(player, score);
+		}
+
+		ConnectFour::on_initialize(99);
+		assert_eq!(ConnectFour::current_season(), 0);
+		// The season ends after `SeasonLength` blocks, its scores decay a few per block.
+		ConnectFour::on_initialize(100);
+		System::assert_has_event(crate::Event::SeasonEnded(0).into());
+		assert_eq!(ConnectFour::current_season(), 1);
+		assert_noop!(
+			ConnectFour::distribute_season_rewards(Origin::root(), 0, 30),
+			Error::<Test>::SeasonNotSettled
+		);
+		ConnectFour::on_initialize(101);
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(50));
+		assert_eq!(ScoringBoard::<Test>::get(4), Some(-10));
+		assert_eq!(ScoringBoard::<Test>::get(6), Some(4));
+		assert_eq!(ConnectFour::season_standings(0).into_inner(), vec![(1, 100), (2, 60), (3, 40)]);
+
+		assert_noop!(
+			ConnectFour::distribute_season_rewards(Origin::signed(1), 0, 30),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ConnectFour::distribute_season_rewards(Origin::root(), 0, 30));
+		System::assert_last_event(crate::Event::SeasonRewarded(0, 30).into());
+		for player in [1, 2, 3] {
+			assert_eq!(Balances::free_balance(&player), 10);
+		}
+		assert_eq!(Balances::free_balance(&4), 0);
+		assert_noop!(
+			ConnectFour::distribute_season_rewards(Origin::root(), 0, 30),
+			Error::<Test>::NoSeasonStandings
+		);
+	});
+}
+
+#[test]
+fn the_bot_replies_right_away_and_hard_bots_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::play_vs_bot(Origin::signed(1), BotDifficulty::Easy));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(
+			(board.blue, board.bot),
+			(ConnectFour::bot_account(), Some(BotDifficulty::Easy))
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 3, None));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((Logic::stones(&board.board), board.next_player), (2, PLAYER_1));
+
+		// Red threatens to connect in the bottom row, the hard bot takes the last cell.
+		assert_ok!(ConnectFour::play_vs_bot(Origin::signed(2), BotDifficulty::Hard));
+		let board_id = PlayerBoard::<Test>::get(2);
+		Boards::<Test>::mutate(board_id, |board| {
+			let board = board.as_mut().unwrap();
+			for column in 0..3 {
+				Logic::add_stone(&mut board.board, column, PLAYER_1);
+			}
+			Logic::add_stone(&mut board.board, 6, PLAYER_2);
+			Logic::add_stone(&mut board.board, 6, PLAYER_2);
+		});
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 6, None));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.board.cell(3, 5), PLAYER_2);
+		assert_eq!(board.next_player, PLAYER_1);
+	});
+}
+
+#[test]
+fn doubles_teammates_alternate_and_share_the_result() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in 1..=4 {
+			ScoringBoard::<Test>::insert(player, 50);
+		}
+		assert_ok!(ConnectFour::challenge_team(
+			Origin::signed(1),
+			3,
+			2,
+			4,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None
+		));
+		// The game starts once the other three accepted.
+		for player in [3, 2] {
+			assert_ok!(ConnectFour::resp_team_challenge(Origin::signed(player), 1, true));
+			assert!(!PlayerBoard::<Test>::contains_key(1));
+		}
+		assert_noop!(
+			ConnectFour::resp_team_challenge(Origin::signed(5), 1, true),
+			Error::<Test>::NotChallenged
+		);
+		assert_ok!(ConnectFour::resp_team_challenge(Origin::signed(4), 1, true));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(
+			(board.red, board.red_mate, board.blue, board.blue_mate),
+			(1, Some(3), 2, Some(4))
+		);
+
+		// Leads move first, their teammates take every other turn of their side.
+		let (first, second) =
+			if board.next_player == PLAYER_1 { ([1, 3], [2, 4]) } else { ([2, 4], [1, 3]) };
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(first[1]), 0, None),
+			Error::<Test>::NotPlayerTurn
+		);
+		// The first side stacks column 0 and connects four with its seventh stone.
+		for player in [first[0], second[0], first[1], second[1], first[0], second[0], first[1]] {
+			let column = if first.contains(&player) { 0 } else { 1 };
+			assert_ok!(ConnectFour::play_turn(Origin::signed(player), column, None));
+		}
+
+		// Both teammates of the winning side gain, both of the other side lose.
+		assert!(!Boards::<Test>::contains_key(board_id));
+		for player in first {
+			assert_eq!(ScoringBoard::<Test>::get(player), Some(60));
+			assert!(!PlayerBoard::<Test>::contains_key(player));
+		}
+		for player in second {
+			assert_eq!(ScoringBoard::<Test>::get(player), Some(45));
+			assert!(!PlayerBoard::<Test>::contains_key(player));
+		}
+	});
+}
+
+#[test]
+fn only_the_admin_origin_ends_games_and_resets_scores() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+
+		assert_noop!(
+			ConnectFour::force_end_game(Origin::signed(1), board_id, Some(1)),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_noop!(
+			ConnectFour::force_end_game(Origin::root(), H256::repeat_byte(1), None),
+			Error::<Test>::BoardNotFound
+		);
+		assert_noop!(
+			ConnectFour::force_end_game(Origin::root(), board_id, Some(3)),
+			Error::<Test>::NotOnBoard
+		);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(10));
+
+		assert_noop!(
+			ConnectFour::reset_score(Origin::signed(2), 1),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::reset_score(Origin::root(), 1));
+		System::assert_last_event(crate::Event::ScoreReset(1).into());
+		assert_eq!(ScoringBoard::<Test>::get(1), None);
+
+		let award = AwardState { win: 20, lose: 10, draw: 5 };
+		assert_noop!(
+			ConnectFour::set_matchmaking_params(Origin::signed(1), 30, award.clone()),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::set_matchmaking_params(Origin::root(), 30, award.clone()));
+		System::assert_last_event(crate::Event::MatchmakingParamsSet(30, award.clone()).into());
+		assert_eq!((ConnectFour::accepted_diff(), ConnectFour::default_award()), (30, award));
+	});
+}
+
+#[test]
+fn matchmaking_starts_from_the_configured_tolerance_and_award() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ConnectFour::accepted_diff(), 10);
+		assert_eq!(ConnectFour::default_award(), DefaultMatchAward::get());
+
+		System::set_block_number(1);
+		ScoringBoard::<Test>::insert(2, 10);
+		ScoringBoard::<Test>::insert(3, 21);
+		for player in [1, 2, 3] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		// 1 and 2 are within the tolerance, 3 is one point too far from 2.
+		ConnectFour::on_initialize(2);
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(PlayerBoard::<Test>::get(2), board_id);
+		assert!(!PlayerBoard::<Test>::contains_key(3));
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().award, DefaultMatchAward::get());
+	});
+}
+
+#[test]
+fn leaderboard_keeps_the_best_scores_as_they_change() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (player, score) in [(1, 10), (2, 30), (3, 20), (4, 5)] {
+			ConnectFour::set_score(&player, Some(score));
+		}
+		assert_eq!(ConnectFour::leaderboard(4), vec![(2, 30), (3, 20), (1, 10), (4, 5)]);
+
+		// A full leaderboard only takes scores that beat its last entry.
+		System::reset_events();
+		ConnectFour::set_score(&5, Some(1));
+		assert!(System::events().is_empty());
+		ConnectFour::set_score(&5, Some(25));
+		System::assert_last_event(crate::Event::LeaderboardUpdated(5, Some(1)).into());
+		assert_eq!(ConnectFour::leaderboard(4), vec![(2, 30), (5, 25), (3, 20), (1, 10)]);
+
+		ConnectFour::set_score(&1, Some(40));
+		System::assert_last_event(crate::Event::LeaderboardUpdated(1, Some(0)).into());
+		assert_ok!(ConnectFour::reset_score(Origin::root(), 3));
+		System::assert_has_event(crate::Event::LeaderboardUpdated(3, None).into());
+		assert_eq!(ConnectFour::leaderboard(4), vec![(1, 40), (2, 30), (5, 25)]);
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn blitz_rounds_reveal_both_columns_and_drop_the_player_at_turn_first() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge_blitz(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			GameKind::Ranked
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let salt = [7; 32];
+		assert_noop!(
+			ConnectFour::reveal_move(Origin::signed(1), 3, salt),
+			Error::<Test>::WrongBlitzPhase
+		);
+
+		for player in [1, 2] {
+			assert_ok!(ConnectFour::commit_move(
+				Origin::signed(player),
+				BlakeTwo256::hash_of(&(3u8, salt))
+			));
+		}
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.blitz.as_ref().map(|round| round.phase), Some(BlitzPhase::Reveal));
+		assert_noop!(
+			ConnectFour::reveal_move(Origin::signed(1), 4, salt),
+			Error::<Test>::CommitmentMismatch
+		);
+		assert_ok!(ConnectFour::reveal_move(Origin::signed(1), 3, salt));
+		assert_noop!(
+			ConnectFour::reveal_move(Origin::signed(1), 3, salt),
+			Error::<Test>::AlreadyMoved
+		);
+		assert_ok!(ConnectFour::reveal_move(Origin::signed(2), 3, salt));
+
+		// Both picked the same column, the player at turn got the bottom cell.
+		let first = board.next_player;
+		let second = if first == PLAYER_1 { PLAYER_2 } else { PLAYER_1 };
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((board.board.cell(3, 5), board.board.cell(3, 4)), (first, second));
+		assert_eq!(board.next_player, second);
+		assert_eq!(board.blitz, Some(BlitzRound::default()));
+	});
+}
+
+#[test]
+fn finished_games_point_out_the_winning_line() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let (first, second) = if board.next_player == PLAYER_1 {
+			(board.red, board.blue)
+		} else {
+			(board.blue, board.red)
+		};
+		for _ in 0..3 {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(first), 1, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(second), 0, None));
+		}
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 2, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(second), 0, None));
+
+		// Lines run from the stone closest to the bottom left.
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: Some(second),
+				reason: FinishReason::Connected,
+				win_line: vec![(0, 5), (0, 4), (0, 3), (0, 2)],
+				stake_fee: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn players_take_back_moves_their_opponent_agrees_to() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let start_game = |challenger, responder, win| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(challenger),
+				responder,
+				win,
+				0,
+				0,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(
+				Origin::signed(responder),
+				challenger,
+				true,
+				AwardState { win, lose: 0, draw: 0 }
+			));
+			let board = Boards::<Test>::get(PlayerBoard::<Test>::get(challenger)).unwrap();
+			let color = board.next_player;
+			let (first, second) =
+				if color == PLAYER_1 { (board.red, board.blue) } else { (board.blue, board.red) };
+			assert_ok!(ConnectFour::play_turn(Origin::signed(first), 3, None));
+			(board.id, first, second, color)
+		};
+
+		// Games with scores at stake are played as they are.
+		let (_, first, _, _) = start_game(3, 4, 10);
+		assert_noop!(
+			ConnectFour::request_undo(Origin::signed(first)),
+			Error::<Test>::UndoNotAllowed
+		);
+
+		let (board_id, first, second, color) = start_game(1, 2, 0);
+		assert_noop!(
+			ConnectFour::request_undo(Origin::signed(second)),
+			Error::<Test>::NothingToUndo
+		);
+		assert_noop!(
+			ConnectFour::respond_undo(Origin::signed(second), true),
+			Error::<Test>::NoUndoRequest
+		);
+		assert_ok!(ConnectFour::request_undo(Origin::signed(first)));
+		assert_noop!(
+			ConnectFour::request_undo(Origin::signed(first)),
+			Error::<Test>::UndoAlreadyRequested
+		);
+		assert_noop!(
+			ConnectFour::respond_undo(Origin::signed(first), true),
+			Error::<Test>::NoUndoRequest
+		);
+		assert_ok!(ConnectFour::respond_undo(Origin::signed(second), false));
+		System::assert_last_event(crate::Event::UndoRejected(board_id, second).into());
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().board.cell(3, 5), color);
+
+		assert_ok!(ConnectFour::request_undo(Origin::signed(first)));
+		assert_ok!(ConnectFour::respond_undo(Origin::signed(second), true));
+		System::assert_last_event(crate::Event::MoveUndone(board_id, 3).into());
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.board.cell(3, 5), 0);
+		assert_eq!((board.next_player, board.last_move), (color, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 4, None));
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn migration_to_v1_translates_the_original_layout() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		StorageVersion::new(0).put::<ConnectFour>();
+		// A 7x6 board with a red stone in the middle column and blue at turn.
+		let board_id = H256::repeat_byte(1);
+		let mut cells = [[0u8; 6]; 7];
+		cells[3][5] = PLAYER_1;
+		let old_board = |board_id: H256, red: u64, blue: u64, cells: [[u8; 6]; 7]| {
+			let old = (
+				board_id,
+				red,
+				blue,
+				cells,
+				0u64,
+				PLAYER_2,
+				BoardState::<u64>::Running,
+				(10u32, 5u32),
+			);
+			unhashed::put(&Boards::<Test>::hashed_key_for(board_id), &old);
+			PlayerBoard::<Test>::insert(red, board_id);
+			PlayerBoard::<Test>::insert(blue, board_id);
+		};
+		old_board(board_id, 1, 2, cells);
+		// A stone floating at the top of an empty column can't be put on a bitboard.
+		let floating_id = H256::repeat_byte(2);
+		let mut floating = [[0u8; 6]; 7];
+		floating[0][0] = PLAYER_1;
+		old_board(floating_id, 5, 6, floating);
+		unhashed::put(&Challenges::<Test>::hashed_key_for(3), &(20u32, 10u32));
+		unhashed::put(&MatchQueue::<Test>::hashed_key_for(0), &4u64);
+
+		crate::migrations::migrate::<Test>();
+		assert_ok!(ConnectFour::do_try_state());
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((board.red, board.blue, board.next_player), (1, 2, PLAYER_2));
+		assert_eq!(ConnectFour::active_boards(), 1);
+		assert_eq!(PlayerBoard::<Test>::get(1), board_id);
+		// The dropped board takes the pointers of its players along.
+		assert!(!Boards::<Test>::contains_key(floating_id));
+		assert!(!PlayerBoard::<Test>::contains_key(5) && !PlayerBoard::<Test>::contains_key(6));
+		assert_eq!(board.board.cell(3, 5), PLAYER_1);
+		assert_eq!(board.variant, BoardVariant::default());
+		assert_eq!(board.award, AwardState { win: 10, lose: 5, draw: 0 });
+		// Old challenges are open to anyone, old queue entries are dropped.
+		let challenge = Challenges::<Test>::get(3).unwrap();
+		assert_eq!(challenge.opponent, None);
+		assert_eq!(challenge.award, AwardState { win: 20, lose: 10, draw: 0 });
+		assert!(!MatchQueue::<Test>::contains_key(0));
+		assert_eq!(ConnectFour::on_chain_storage_version(), ConnectFour::current_storage_version());
+	});
+}
+
+#[test]
+fn player_stats_count_results_and_streaks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let start_game = |time_bank| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				time_bank,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Casual,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			PlayerBoard::<Test>::get(1)
+		};
+
+		for _ in 0..2 {
+			let board_id = start_game(None);
+			assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+		}
+		let winner = GameStats {
+			played: 2,
+			wins: 2,
+			current_streak: 2,
+			best_streak: 2,
+			..Default::default()
+		};
+		assert_eq!(ConnectFour::player_stats(1), winner);
+		System::assert_has_event(crate::Event::StatsUpdated(1, winner.clone()).into());
+		assert_eq!(
+			ConnectFour::player_stats(2),
+			GameStats { played: 2, losses: 2, ..Default::default() }
+		);
+
+		// Void games don't count.
+		let board_id = start_game(None);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+		assert_eq!(ConnectFour::player_stats(1), winner);
+
+		// A loss on time ends the streak, the best one is kept.
+		let board_id = start_game(Some(10));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		if board.team_of(&1) == Some(board.next_player) {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(1), 3, None));
+		}
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+		System::set_block_number(12);
+		assert_ok!(ConnectFour::claim_timeout(Origin::signed(2)));
+		let stats = ConnectFour::player_stats(1);
+		assert_eq!(
+			(stats.played, stats.losses, stats.current_streak, stats.best_streak),
+			(3, 1, 0, 2)
+		);
+		assert_eq!(ConnectFour::player_stats(2).wins, 1);
+	});
+}
+
+#[test]
+fn public_tables_are_joined_by_the_first_other_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in 1..=6 {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 10, None));
+		System::assert_last_event(crate::Event::LobbyOpened(0, 1, AWARD, 10, None).into());
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_noop!(
+			ConnectFour::create_open_game(Origin::signed(1), AWARD, 10, None),
+			Error::<Test>::AlreadyInLobby
+		);
+		assert_noop!(
+			ConnectFour::join_open_game(Origin::signed(1), 0, None),
+			Error::<Test>::NoFakePlay
+		);
+		assert_noop!(
+			ConnectFour::join_open_game(Origin::signed(2), 7, None),
+			Error::<Test>::LobbyNotFound
+		);
+
+		// Only a few tables are open at a time.
+		for player in 2..=4 {
+			assert_ok!(ConnectFour::create_open_game(Origin::signed(player), AWARD, 10, None));
+		}
+		assert_noop!(
+			ConnectFour::create_open_game(Origin::signed(5), AWARD, 10, None),
+			Error::<Test>::TooManyLobbies
+		);
+
+		// Leaving a table releases the stake.
+		assert_ok!(ConnectFour::close_open_game(Origin::signed(2)));
+		System::assert_last_event(crate::Event::LobbyClosed(1).into());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_noop!(ConnectFour::close_open_game(Origin::signed(2)), Error::<Test>::LobbyNotFound);
+
+		// Joining starts the game right away with both stakes held.
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(6), 0, None));
+		System::assert_last_event(crate::Event::LobbyClosed(0).into());
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(PlayerBoard::<Test>::get(6), board_id);
+		assert_eq!(ConnectFour::board_stakes(board_id), Some(10));
+		assert_eq!(Balances::reserved_balance(6), 10);
+		assert_eq!(
+			ConnectFour::open_lobbies().iter().map(|lobby| lobby.id).collect::<Vec<_>>(),
+			vec![2, 3]
+		);
+		assert_eq!(ConnectFour::next_lobby_id(), 4);
+	});
+}
+
+#[test]
+fn queue_deposits_are_held_until_the_first_move() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(3), GameKind::Casual),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+
+		// Leaving the queue hands the deposit back.
+		assert_ok!(ConnectFour::find_game(Origin::signed(1), GameKind::Ranked));
+		assert_eq!(Balances::reserved_balance(1), 5);
+		assert_ok!(ConnectFour::cancel_queue(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		System::set_block_number(2);
+		for player in [1, 2] {
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		ConnectFour::on_initialize(3);
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let (first, second) = if board.next_player == PLAYER_1 {
+			(board.red, board.blue)
+		} else {
+			(board.blue, board.red)
+		};
+		for player in [first, second] {
+			assert_eq!(ConnectFour::first_move_deposits(player), Some(5));
+			assert_eq!(Balances::reserved_balance(player), 5);
+		}
+
+		// Only the player that showed up gets theirs back.
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+		assert_eq!(ConnectFour::first_move_deposits(first), None);
+		assert_eq!(Balances::reserved_balance(first), 0);
+		assert_eq!(Balances::reserved_balance(second), 5);
+
+		// A game ending otherwise releases it as well.
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+		assert_eq!(ConnectFour::first_move_deposits(second), None);
+		assert_eq!(Balances::free_balance(second), 100);
+	});
+}
+
+#[test]
+fn weaker_players_get_a_handicap() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				Some(Handicap::Stones(3)),
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			),
+			Error::<Test>::InvalidHandicap
+		);
+		// The responder plays red and starts with two stones around the center column.
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			Some(Handicap::Stones(2)),
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.red, 2);
+		assert_eq!(board.handicap, Some((PLAYER_1, Handicap::Stones(2))));
+		assert_eq!(board.next_player, PLAYER_2);
+		assert_eq!(Logic::player_stones(&board.board, PLAYER_1), 2);
+		assert_eq!(board.board.cell(3, 5), PLAYER_1);
+		assert_eq!(board.board.cell(2, 5), PLAYER_1);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+
+		// Matchmaking hands out handicaps by the score gap.
+		assert_eq!(ConnectFour::matchmaking_handicap(0, 4), None);
+		assert_eq!(ConnectFour::matchmaking_handicap(6, 0), Some((PLAYER_2, Handicap::FirstMove)));
+		assert_eq!(ConnectFour::matchmaking_handicap(0, 10), Some((PLAYER_1, Handicap::Stones(2))));
+		for (player, score) in [(3, 0), (4, 10), (5, 30), (6, 36)] {
+			Balances::make_free_balance_be(&player, 100);
+			ScoringBoard::<Test>::insert(player, score);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		ConnectFour::on_initialize(2);
+		let board = Boards::<Test>::get(PlayerBoard::<Test>::get(3)).unwrap();
+		let weaker = if board.red == 3 { PLAYER_1 } else { PLAYER_2 };
+		assert_eq!(board.handicap, Some((weaker, Handicap::Stones(2))));
+		assert_eq!(Logic::player_stones(&board.board, weaker), 2);
+		assert_ne!(board.next_player, weaker);
+		let board = Boards::<Test>::get(PlayerBoard::<Test>::get(5)).unwrap();
+		let weaker = if board.red == 5 { PLAYER_1 } else { PLAYER_2 };
+		assert_eq!(board.handicap, Some((weaker, Handicap::FirstMove)));
+		assert_eq!(Logic::stones(&board.board), 0);
+		assert_eq!(board.next_player, weaker);
+	});
+}
+
+#[test]
+fn resp_challenge_without_challenge_fails() {
+	new_test_ext().execute_with(|| {
+		// Responding to a challenge that was never placed used to panic.
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(1), 2, true, AWARD),
+			Error::<Test>::ChallengeNotFound
+		);
+	});
+}
+
+#[test]
+fn resp_challenge_after_cancel_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD),
+			Error::<Test>::ChallengeNotFound
+		);
+	});
+}
+
+#[test]
+fn cancel_challenge_without_challenge_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConnectFour::cancel_challenge(Origin::signed(1)),
+			Error::<Test>::ChallengeNotFound
+		);
+	});
+}
+
+#[test]
+fn challenge_events_carry_the_challenge_id() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let challenge = |challenger, opponent| {
+			ConnectFour::challenge(
+				Origin::signed(challenger),
+				opponent,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None,
+			)
+		};
+
+		assert_ok!(challenge(1, 2));
+		System::assert_last_event(
+			crate::Event::ChallengeCreated {
+				challenger: 1,
+				challenge: ConnectFour::challenges(1).unwrap(),
+			}
+			.into(),
+		);
+		assert_eq!(ConnectFour::challenges(1).unwrap().id, 0);
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+		System::assert_last_event(
+			crate::Event::CancelChallenge { challenge_id: 0, challenger: 1 }.into(),
+		);
+
+		assert_ok!(challenge(1, 2));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, false, AWARD));
+		System::assert_last_event(
+			crate::Event::RejectChallenge { challenge_id: 1, challenger: 1, responder: 2 }.into(),
+		);
+
+		assert_ok!(challenge(1, 2));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		System::assert_last_event(
+			crate::Event::AcceptChallenge {
+				challenge_id: 2,
+				challenger: 1,
+				opponent: 2,
+				board_id: PlayerBoard::<Test>::get(1),
+			}
+			.into(),
+		);
+		assert_eq!(ConnectFour::next_challenge_id(), 3);
+	});
+}
+
+#[test]
+fn challenges_carry_their_stake_and_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		let challenge = || {
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				Some(30),
+			)
+		};
+
+		assert_ok!(challenge());
+		let placed = ConnectFour::challenges(1).unwrap();
+		assert_eq!((placed.stake, placed.expires), (Some(30), 21));
+		System::assert_last_event(
+			crate::Event::ChallengeCreated { challenger: 1, challenge: placed }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 30);
+		System::set_block_number(22);
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD),
+			Error::<Test>::ChallengeExpired
+		);
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// The stakes go to the winner of the accepted challenge.
+		assert_ok!(challenge());
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(Balances::reserved_balance(2), 30);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert_eq!(Balances::free_balance(1), 70);
+		assert_eq!(Balances::free_balance(2), 130);
+	});
+}
+
+#[test]
+fn private_games_start_with_the_invite_code_or_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		let settings = PrivateGameSettings {
+			award: AWARD,
+			variant: BoardVariant::default(),
+			time_bank: None,
+			kind: GameKind::Casual,
+			stake: Some(30),
+		};
+		let code_hash = BlakeTwo256::hash(b"secret");
+
+		assert_ok!(ConnectFour::create_private_game(
+			Origin::signed(1),
+			code_hash,
+			settings.clone()
+		));
+		System::assert_last_event(crate::Event::PrivateGameCreated(code_hash, 1, 11).into());
+		assert_eq!(Balances::reserved_balance(1), 30);
+		assert_noop!(
+			ConnectFour::create_private_game(Origin::signed(2), code_hash, settings.clone()),
+			Error::<Test>::PrivateGameExists
+		);
+		assert_noop!(
+			ConnectFour::join_private_game(Origin::signed(2), b"guess".to_vec()),
+			Error::<Test>::PrivateGameNotFound
+		);
+		assert_ok!(ConnectFour::join_private_game(Origin::signed(2), b"secret".to_vec()));
+		let board_id = PlayerBoard::<Test>::get(2);
+		System::assert_last_event(crate::Event::PrivateGameJoined(code_hash, board_id).into());
+		assert_eq!(PlayerBoard::<Test>::get(1), board_id);
+		assert_eq!(Balances::reserved_balance(2), 30);
+		assert!(ConnectFour::private_games(code_hash).is_none());
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+
+		// Unclaimed games are dropped and their stake returned.
+		assert_ok!(ConnectFour::create_private_game(Origin::signed(1), code_hash, settings));
+		ConnectFour::on_initialize(11);
+		System::assert_has_event(crate::Event::PrivateGameExpired(code_hash, 1).into());
+		assert!(ConnectFour::private_games(code_hash).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn withdraw_all_leaves_every_pending_state() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None,
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+		assert_noop!(
+			ConnectFour::withdraw_all(Origin::signed(3)),
+			Error::<Test>::NothingToWithdraw
+		);
+
+		assert_ok!(ConnectFour::find_game(Origin::signed(1), GameKind::Ranked));
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			3,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			Some(30),
+		));
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 20, None));
+		assert_eq!(Balances::reserved_balance(1), 55);
+
+		assert_ok!(ConnectFour::withdraw_all(Origin::signed(1)));
+		System::assert_last_event(
+			crate::Event::WithdrawnFromAll {
+				account: 1,
+				queue: true,
+				challenge_id: Some(1),
+				lobby_id: Some(0),
+				rematch: true,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(ConnectFour::queue_length(), 0);
+		assert!(ConnectFour::rematches(1).is_none());
+		assert_noop!(ConnectFour::accept_rematch(Origin::signed(2)), Error::<Test>::NoRematch);
+		assert_noop!(
+			ConnectFour::withdraw_all(Origin::signed(1)),
+			Error::<Test>::NothingToWithdraw
+		);
+	});
+}
+
+#[test]
+fn resp_team_challenge_without_challenge_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConnectFour::resp_team_challenge(Origin::signed(1), 2, true),
+			Error::<Test>::ChallengeNotFound
+		);
+	});
+}
+
+#[test]
+fn play_turn_without_board_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(1), 0, None),
+			Error::<Test>::NoPlayerBoard
+		);
+	});
+}
+
+#[test]
+fn play_turn_on_missing_board_fails() {
+	new_test_ext().execute_with(|| {
+		// A player pointing at a board that is gone used to panic.
+		PlayerBoard::<Test>::insert(1, H256::repeat_byte(1));
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(1), 0, None),
+			Error::<Test>::BoardNotFound
+		);
+	});
+}
+
+#[test]
+fn accepting_needs_the_award_of_the_challenge() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			20,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		// The terms got worse since the challenged player last looked.
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD),
+			Error::<Test>::AwardMismatch
+		);
+		let award = AwardState { win: 10, lose: 20, draw: 2 };
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, award));
+		assert!(PlayerBoard::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn accepted_challenges_get_distinct_boards() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (challenger, opponent) in [(1, 2), (3, 4)] {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(challenger),
+				opponent,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(
+				Origin::signed(opponent),
+				challenger,
+				true,
+				AWARD
+			));
+		}
+		// Board ids are random hashes, generating them must not fail within a block.
+		let first = PlayerBoard::<Test>::get(1);
+		let second = PlayerBoard::<Test>::get(3);
+		assert_ne!(first, second);
+		assert!(Boards::<Test>::contains_key(first));
+		assert!(Boards::<Test>::contains_key(second));
+	});
+}
+
+#[test]
+fn presets_set_up_challenges_and_matchmade_games() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let variant = BoardVariant { width: 6, height: 5, connect: 4, pop_out: false };
+		let award = AwardState { win: 7, lose: 3, draw: 1 };
+		let preset =
+			GamePreset { variant, time_bank: Some(20), award, min_stake: 0, max_stake: 10 };
+		assert_noop!(
+			ConnectFour::set_preset(Origin::signed(1), 1, Some(preset.clone())),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_noop!(
+			ConnectFour::set_preset(
+				Origin::root(),
+				1,
+				Some(GamePreset { min_stake: 20, ..preset.clone() })
+			),
+			Error::<Test>::StakeOutOfBounds
+		);
+		assert_ok!(ConnectFour::set_preset(Origin::root(), 1, Some(preset)));
+		System::assert_last_event(crate::Event::PresetSet(1).into());
+
+		let challenge = |stake, preset_id| {
+			ConnectFour::challenge_preset(
+				Origin::signed(1),
+				2,
+				preset_id,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				stake,
+			)
+		};
+		assert_noop!(challenge(None, 2), Error::<Test>::PresetNotFound);
+		assert_noop!(challenge(Some(20), 1), Error::<Test>::StakeOutOfBounds);
+		assert_ok!(challenge(None, 1));
+		let placed = Challenges::<Test>::get(1).unwrap();
+		assert_eq!((placed.variant, placed.time_bank, placed.award), (variant, Some(20), award));
+
+		// Only players asking for the same preset are paired, and play with its settings.
+		for player in [3, 4, 5] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::find_preset_game(Origin::signed(3), GameKind::Casual, 1));
+		assert_ok!(ConnectFour::find_game(Origin::signed(5), GameKind::Casual));
+		assert_ok!(ConnectFour::find_preset_game(Origin::signed(4), GameKind::Casual, 1));
+		ConnectFour::on_initialize(2);
+		let board = Boards::<Test>::get(PlayerBoard::<Test>::get(3)).unwrap();
+		assert_eq!(PlayerBoard::<Test>::get(4), board.id);
+		assert_eq!((board.variant, board.award), (variant, award));
+		assert_eq!(board.time_banks.map(|banks| banks.initial), Some(20));
+		assert!(!PlayerBoard::<Test>::contains_key(5));
+		assert_eq!(QueuedPresets::<Test>::get(3), None);
+
+		assert_ok!(ConnectFour::set_preset(Origin::root(), 1, None));
+		System::assert_last_event(crate::Event::PresetRemoved(1).into());
+		assert_noop!(
+			ConnectFour::set_preset(Origin::root(), 1, None),
+			Error::<Test>::PresetNotFound
+		);
+	});
+}
+
+#[test]
+fn seeded_games_are_reproducible() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let seed = H256::repeat_byte(7);
+		let create = |origin, first_move| {
+			ConnectFour::create_game_deterministic(
+				origin,
+				1,
+				2,
+				AWARD,
+				BoardVariant::default(),
+				seed,
+				first_move,
+				GameKind::Casual,
+			)
+		};
+		assert_noop!(
+			create(Origin::signed(1), FirstMovePolicy::Random),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(create(Origin::root(), FirstMovePolicy::Random));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let random = if board_id.as_ref()[0] < 128 { PLAYER_1 } else { PLAYER_2 };
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().next_player, random);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+
+		// The same seed sets up the same board, this time with blue to move first.
+		assert_ok!(create(Origin::root(), FirstMovePolicy::ChallengerFirst));
+		assert_eq!(PlayerBoard::<Test>::get(1), board_id);
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().next_player, PLAYER_2);
+	});
+}
+
+#[test]
+fn paused_pallet_blocks_new_games() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_noop!(
+			ConnectFour::set_paused(Origin::signed(1), true),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::set_paused(Origin::root(), true));
+
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(3), GameKind::Ranked),
+			Error::<Test>::Paused
+		);
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD),
+			Error::<Test>::Paused
+		);
+		// Pending challenges can still be withdrawn.
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+
+		assert_ok!(ConnectFour::set_paused(Origin::root(), false));
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert!(PlayerBoard::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn new_games_wait_for_a_free_board() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (challenger, responder) in [(1, 2), (3, 4)] {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(challenger),
+				responder,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			));
+		}
+		assert_noop!(
+			ConnectFour::set_max_active_boards(Origin::signed(1), 1),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::set_max_active_boards(Origin::root(), 1));
+		System::assert_last_event(crate::Event::MaxActiveBoardsSet(1).into());
+
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert_eq!(ConnectFour::active_boards(), 1);
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(4), 3, true, AWARD),
+			Error::<Test>::BoardLimitReached
+		);
+
+		// A finished game frees its board.
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+		assert_eq!(ConnectFour::active_boards(), 0);
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(4), 3, true, AWARD));
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn players_play_up_to_the_configured_number_of_games() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(ConnectFour::max_games_per_player(), 1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Casual,
+			None
+		));
+		assert_noop!(
+			ConnectFour::set_max_games_per_player(Origin::signed(1), 0),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::set_max_games_per_player(Origin::root(), 0));
+		System::assert_last_event(crate::Event::MaxGamesPerPlayerSet(0).into());
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD),
+			Error::<Test>::TooManyGames
+		);
+
+		assert_ok!(ConnectFour::set_max_games_per_player(Origin::root(), 1));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert_eq!(PlayerBoard::<Test>::get(1), PlayerBoard::<Test>::get(2));
+	});
+}
+
+#[test]
+fn swiss_tournament_avoids_rematches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::create_tournament(
+			Origin::signed(1),
+			2,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			None,
+			0,
+			2,
+			3,
+			true
+		));
+		for player in 1..=4 {
+			assert_ok!(ConnectFour::join_tournament(Origin::signed(player), 0));
+		}
+		System::set_block_number(2);
+		for player in 1..=4 {
+			assert_ok!(ConnectFour::check_in(Origin::signed(player), 0));
+		}
+		System::set_block_number(3);
+		assert_noop!(
+			ConnectFour::start_tournament(Origin::signed(2), 0),
+			Error::<Test>::NotTournamentCreator
+		);
+		assert_ok!(ConnectFour::start_tournament(Origin::signed(1), 0));
+
+		for round in 1..=2 {
+			ConnectFour::on_initialize(round);
+			assert_noop!(
+				ConnectFour::advance_tournament(Origin::signed(5), 0),
+				Error::<Test>::RoundNotFinished
+			);
+			// Red wins every game.
+			for (red, _) in TournamentRounds::<Test>::get(0, round) {
+				let board_id = PlayerBoard::<Test>::get(red);
+				assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(red)));
+			}
+			assert_ok!(ConnectFour::advance_tournament(Origin::signed(5), 0));
+		}
+
+		let first = TournamentRounds::<Test>::get(0, 1);
+		let second = TournamentRounds::<Test>::get(0, 2);
+		assert_eq!(first.len(), 2);
+		for (red, blue) in second.iter() {
+			assert!(!first.contains(&(*red, *blue)) && !first.contains(&(*blue, *red)));
+		}
+		let tournament = Tournaments::<Test>::get(0).unwrap();
+		assert_eq!(tournament.state, TournamentState::Finished);
+		let winner = second[0].0;
+		assert_eq!(
+			TournamentPlayers::<Test>::get(0, winner).map(|standing| standing.points),
+			Some(4)
+		);
+		System::assert_last_event(crate::Event::TournamentFinished(0, winner).into());
+	});
+}
+
+#[test]
+fn tournaments_are_seeded_from_scores_at_registration_close() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::create_tournament(
+			Origin::signed(1),
+			1,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			None,
+			0,
+			2,
+			3,
+			true
+		));
+		for player in 1..=5 {
+			ScoringBoard::<Test>::insert(player, 60 - 10 * player as i32);
+			assert_ok!(ConnectFour::join_tournament(Origin::signed(player), 0));
+		}
+		System::set_block_number(2);
+		ConnectFour::on_initialize(2);
+		assert_eq!(
+			ConnectFour::tournament_seeds(0).into_inner(),
+			[(1, 50), (2, 40), (3, 30), (4, 20), (5, 10)]
+		);
+		for player in 1..=5 {
+			assert_ok!(ConnectFour::check_in(Origin::signed(player), 0));
+		}
+
+		// Tanking after registration closed doesn't change the seeds.
+		ScoringBoard::<Test>::insert(2, -100);
+		System::set_block_number(3);
+		assert_ok!(ConnectFour::start_tournament(Origin::signed(1), 0));
+		let pairings = TournamentRounds::<Test>::get(0, 1);
+		assert_eq!(pairings.clone().into_inner(), [(1, 4), (2, 3)]);
+		System::assert_has_event(crate::Event::TournamentSeeded(0, pairings).into());
+		System::assert_last_event(crate::Event::TournamentRoundStarted(0, 1, Some(5)).into());
+		assert!(ConnectFour::tournament_seeds(0).is_empty());
+	});
+}
+
+#[test]
+fn move_deadlines_follow_the_time_banks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			Some(10),
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		System::assert_has_event(crate::Event::NewBoard(board_id, Some(11)).into());
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().deadline, Some(11));
+
+		// Red spends 4 blocks of its bank, blue still has all of its own.
+		System::set_block_number(5);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.deadline, Some(15));
+		System::assert_last_event(
+			crate::Event::TurnPlayed {
+				board_id,
+				player: 2,
+				column: 3,
+				row: 5,
+				position: board.position,
+				deadline: Some(15),
+				moves_played: 1,
+				evaluation: None,
+			}
+			.into(),
+		);
+
+		System::set_block_number(7);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 3, None));
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().deadline, Some(13));
+	});
+}
+
+#[test]
+fn time_banks_run_on_the_game_clock() {
+	new_test_ext().execute_with(|| {
+		// A clock in milliseconds with 6 second blocks, the time banks hold a minute.
+		ClockSpeed::set(6_000);
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			Some(60_000),
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((board.last_turn, board.deadline), (6_000, Some(66_000)));
+
+		// Red spends 24 seconds of its bank.
+		System::set_block_number(5);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.time_banks.map(|banks| banks.red), Some(36_000));
+		assert_eq!(board.deadline, Some(90_000));
+
+		System::set_block_number(15);
+		assert_noop!(ConnectFour::claim_timeout(Origin::signed(2)), Error::<Test>::ClockNotExpired);
+		System::set_block_number(16);
+		assert_ok!(ConnectFour::claim_timeout(Origin::signed(2)));
+		assert!(!Boards::<Test>::contains_key(board_id));
+	});
+}
+
+#[test]
+fn relayed_moves_need_the_signature_of_the_player_at_turn() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Casual,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		let sign = |signer, payload: &RelayedMove<H256>| TestSignature(signer, payload.encode());
+
+		// The sponsor relays the first move of red.
+		let payload = RelayedMove { board_id, column: 3, move_index: 0 };
+		assert_noop!(
+			ConnectFour::play_turn_signed(Origin::signed(9), payload.clone(), sign(1, &payload)),
+			Error::<Test>::InvalidSignature
+		);
+		assert_ok!(ConnectFour::play_turn_signed(
+			Origin::signed(9),
+			payload.clone(),
+			sign(2, &payload)
+		));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((board.turns, board.last_move), (1, Some(3)));
+
+		// The signed move can't be played again.
+		assert_noop!(
+			ConnectFour::play_turn_signed(Origin::signed(9), payload.clone(), sign(2, &payload)),
+			Error::<Test>::StaleMove
+		);
+		let payload = RelayedMove { board_id, column: 3, move_index: 1 };
+		assert_ok!(ConnectFour::play_turn_signed(
+			Origin::signed(9),
+			payload.clone(),
+			sign(1, &payload)
+		));
+		assert_eq!(Boards::<Test>::get(board_id).map(|board| board.turns), Some(2));
+	});
+}
+
+#[test]
+fn play_proxies_move_for_their_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::set_play_proxy(Origin::signed(2), 9));
+		System::assert_last_event(crate::Event::PlayProxySet(2, 9).into());
+		assert_noop!(
+			ConnectFour::set_play_proxy(Origin::signed(1), 1),
+			Error::<Test>::InvalidPlayProxy
+		);
+		assert_noop!(
+			ConnectFour::set_play_proxy(Origin::signed(1), 9),
+			Error::<Test>::PlayProxyTaken
+		);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Casual,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+
+		// The delegate plays red's moves, but only on red's turn.
+		assert_ok!(ConnectFour::play_turn(Origin::signed(9), 3, None));
+		assert_eq!(Boards::<Test>::get(board_id).map(|board| board.last_move), Some(Some(3)));
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(9), 3, None),
+			Error::<Test>::NotPlayerTurn
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 3, None));
+
+		assert_noop!(
+			ConnectFour::remove_play_proxy(Origin::signed(1), 9),
+			Error::<Test>::NoPlayProxy
+		);
+		assert_ok!(ConnectFour::remove_play_proxy(Origin::signed(2), 9));
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(9), 3, None),
+			Error::<Test>::NoPlayerBoard
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+	});
+}
+
+#[test]
+fn alternate_policy_swaps_first_player() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let play = |first_move| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				0,
+				0,
+				0,
+				BoardVariant::default(),
+				None,
+				None,
+				first_move,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(
+				Origin::signed(2),
+				1,
+				true,
+				AwardState { win: 0, lose: 0, draw: 0 }
+			));
+			let board = Boards::<Test>::get(PlayerBoard::<Test>::get(1)).unwrap();
+			assert_ok!(ConnectFour::force_end_game(Origin::root(), board.id, None));
+			board.first_player
+		};
+
+		// The responder plays red.
+		assert_eq!(play(FirstMovePolicy::ResponderFirst), PLAYER_1);
+		assert_eq!(play(FirstMovePolicy::AlternateFromLastGame), PLAYER_2);
+		assert_eq!(play(FirstMovePolicy::AlternateFromLastGame), PLAYER_1);
+		assert_eq!(play(FirstMovePolicy::ChallengerFirst), PLAYER_2);
+	});
+}
+
+#[test]
+fn commit_reveal_draws_the_first_player() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let challenge = || {
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::CommitReveal,
+				GameKind::Ranked,
+				None,
+			)
+		};
+		let (challenger_salt, responder_salt) = ([1; 32], [2; 32]);
+		assert_noop!(challenge(), Error::<Test>::FirstMoveNotCommitted);
+		assert_ok!(ConnectFour::commit_first_move(
+			Origin::signed(1),
+			BlakeTwo256::hash_of(&challenger_salt)
+		));
+		assert_ok!(challenge());
+		assert_noop!(
+			ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD),
+			Error::<Test>::FirstMoveNotCommitted
+		);
+		assert_ok!(ConnectFour::commit_first_move(
+			Origin::signed(2),
+			BlakeTwo256::hash_of(&responder_salt)
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert!(ConnectFour::first_move_commits(1).is_none());
+
+		// Nobody moves before both salts are revealed, the responder plays red.
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(1), 0, None),
+			Error::<Test>::FirstMoveNotDrawn
+		);
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(2), 0, None),
+			Error::<Test>::FirstMoveNotDrawn
+		);
+		assert_noop!(
+			ConnectFour::reveal_first_move(Origin::signed(2), board_id, challenger_salt),
+			Error::<Test>::CommitmentMismatch
+		);
+		assert_ok!(ConnectFour::reveal_first_move(Origin::signed(2), board_id, responder_salt));
+		System::assert_last_event(crate::Event::FirstMoveRevealed(board_id, 2).into());
+		assert_noop!(
+			ConnectFour::reveal_first_move(Origin::signed(2), board_id, responder_salt),
+			Error::<Test>::AlreadyMoved
+		);
+		assert_ok!(ConnectFour::reveal_first_move(Origin::signed(1), board_id, challenger_salt));
+
+		let seed = BlakeTwo256::hash_of(&(responder_salt, challenger_salt));
+		let (first, player) = if seed.as_ref()[0] < 128 { (2, PLAYER_1) } else { (1, PLAYER_2) };
+		System::assert_last_event(crate::Event::FirstMoveDrawn(board_id, first).into());
+		assert_eq!(Boards::<Test>::get(board_id).map(|board| board.first_player), Some(player));
+		assert_noop!(
+			ConnectFour::reveal_first_move(Origin::signed(1), board_id, challenger_salt),
+			Error::<Test>::NoFirstMoveDraw
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+	});
+}
+
+#[test]
+fn pie_rule_lets_the_second_player_swap_sides() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			Some(20),
+			None,
+			FirstMovePolicy::PieRule,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let (color, first) = (board.next_player, *board.account_at_turn(board.next_player));
+		let second = if first == 1 { 2 } else { 1 };
+		assert_noop!(
+			ConnectFour::swap_sides(Origin::signed(second), board_id),
+			Error::<Test>::CannotSwapSides
+		);
+
+		System::set_block_number(3);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 3, None));
+		assert_noop!(
+			ConnectFour::swap_sides(Origin::signed(first), board_id),
+			Error::<Test>::NotPlayerTurn
+		);
+		// The opening stone changes hands, each player keeps their own time bank.
+		System::set_block_number(8);
+		assert_ok!(ConnectFour::swap_sides(Origin::signed(second), board_id));
+		System::assert_last_event(crate::Event::SidesSwapped(board_id, second, Some(26)).into());
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!(board.team_of(&second), Some(color));
+		assert_eq!(board.account_at_turn(board.next_player), &first);
+		let banks = board.time_banks.unwrap();
+		let (second_bank, first_bank) =
+			if color == PLAYER_1 { (banks.red, banks.blue) } else { (banks.blue, banks.red) };
+		assert_eq!((first_bank, second_bank), (18, 15));
+
+		assert_noop!(
+			ConnectFour::swap_sides(Origin::signed(first), board_id),
+			Error::<Test>::CannotSwapSides
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 3, None));
+	});
+}
+
+#[test]
+fn sides_cant_be_swapped_once_the_stakes_were_doubled() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::PieRule,
+			GameKind::Ranked,
+			Some(20),
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let first = *board.account_at_turn(board.next_player);
+		let second = if first == 1 { 2 } else { 1 };
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 3, None));
+
+		// Swapping would hand the cube to the player that doubled against it.
+		assert_ok!(ConnectFour::offer_double(Origin::signed(second), board_id));
+		assert_noop!(
+			ConnectFour::swap_sides(Origin::signed(second), board_id),
+			Error::<Test>::CannotSwapSides
+		);
+		assert_ok!(ConnectFour::respond_double(Origin::signed(first), board_id, true));
+		assert_noop!(
+			ConnectFour::swap_sides(Origin::signed(second), board_id),
+			Error::<Test>::CannotSwapSides
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(second), 3, None));
+	});
+}
+
+#[test]
+fn challenges_are_rate_limited() {
+	new_test_ext().execute_with(|| {
+		ChallengeCooldown::set(5);
+		MaxChallengesPerEra::set(2);
+		let challenge = |block| {
+			System::set_block_number(block);
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None,
+			)
+		};
+
+		assert_ok!(challenge(1));
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+		assert_noop!(challenge(5), Error::<Test>::ChallengeRateLimited);
+		assert_ok!(challenge(6));
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+
+		// Two challenges per era of 20 blocks, the count starts over in the next era.
+		assert_noop!(challenge(11), Error::<Test>::ChallengeRateLimited);
+		assert_ok!(challenge(20));
+		assert_eq!(ConnectFour::challenge_rates(1).map(|rate| rate.count), Some(1));
+	});
+}
+
+#[test]
+fn stale_games_expire_as_timeout() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+
+		System::set_block_number(20);
+		assert_noop!(
+			ConnectFour::expire_stale_game(Origin::none(), board_id),
+			Error::<Test>::GameNotStale
+		);
+
+		// Red was at turn and never moved, blue wins.
+		System::set_block_number(21);
+		assert_ok!(ConnectFour::expire_stale_game(Origin::none(), board_id));
+		assert!(!Boards::<Test>::contains_key(board_id));
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: Some(1),
+				reason: FinishReason::Timeout,
+				win_line: Vec::new(),
+				stake_fee: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn unplayable_moves_are_rejected_before_dispatch() {
+	new_test_ext().execute_with(|| {
+		let validate = |who: u64, column: u8, expected_move_index: Option<u16>| {
+			let call = Call::ConnectFour(crate::Call::play_turn { column, expected_move_index });
+			CheckMove::<Test>::new().validate(&who, &call, &Default::default(), 0)
+		};
+		let rejected = |error: MoveError| -> TransactionValidity {
+			Err(InvalidTransaction::Custom(error as u8).into())
+		};
+
+		assert_eq!(validate(1, 0, None), rejected(MoveError::NoPlayerBoard));
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert_eq!(validate(1, 0, None), rejected(MoveError::NotPlayerTurn));
+		assert_eq!(validate(2, 7, None), rejected(MoveError::ColumnOutOfRange));
+
+		for _ in 0..3 {
+			assert_ok!(validate(2, 0, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(1), 0, None));
+		}
+		assert_eq!(validate(2, 0, None), rejected(MoveError::ColumnFull));
+		assert_eq!(validate(2, 1, Some(5)), Err(InvalidTransaction::Stale.into()));
+		assert_ok!(validate(2, 1, Some(6)));
+
+		// Other calls aren't checked.
+		let call = Call::ConnectFour(crate::Call::find_game { kind: GameKind::Casual });
+		assert_ok!(CheckMove::<Test>::new().validate(&3, &call, &Default::default(), 0));
+	});
+}
+
+#[test]
+fn score_weighted_award_favors_upsets() {
+	type Weighted = ScoreWeightedAward<frame_support::traits::ConstU32<10>>;
+	let base = AwardState { win: 10, lose: 6, draw: 2 };
+
+	assert_eq!(Weighted::award(&base, 50, 50), base);
+	// Beating a stronger opponent wins more, and they lose more.
+	assert_eq!(Weighted::award(&base, 0, 40), AwardState { win: 14, lose: 10, draw: 2 });
+	// Beating a weaker opponent wins less, and they lose less.
+	assert_eq!(Weighted::award(&base, 20, 0), AwardState { win: 8, lose: 4, draw: 2 });
+	// Big gaps are capped at half and double the base award.
+	assert_eq!(Weighted::award(&base, 0, 1000), AwardState { win: 20, lose: 12, draw: 2 });
+	assert_eq!(Weighted::award(&base, 1000, 0), AwardState { win: 5, lose: 3, draw: 2 });
+	assert_eq!(<() as AwardCalculator>::award(&base, 0, 1000), base);
+}
+
+#[test]
+fn blocked_players_are_never_paired() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::block_player(Origin::signed(2), 1));
+		assert_noop!(
+			ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			),
+			Error::<Test>::PlayerBlocked
+		);
+
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		ConnectFour::on_initialize(2);
+		assert!(!PlayerBoard::<Test>::contains_key(1));
+		assert!(!PlayerBoard::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn challenge_friend_needs_both_friend_lists() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::add_friend(Origin::signed(1), 2));
+		assert_noop!(
+			ConnectFour::challenge_friend(Origin::signed(1), 2, GameKind::Ranked),
+			Error::<Test>::NotFriends
+		);
+
+		assert_ok!(ConnectFour::add_friend(Origin::signed(2), 1));
+		assert_ok!(ConnectFour::challenge_friend(Origin::signed(1), 2, GameKind::Ranked));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board = Boards::<Test>::get(PlayerBoard::<Test>::get(1)).unwrap();
+		assert_eq!(board.award, ConnectFour::default_award());
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board.id, None));
+
+		// Blocking a friend ends the friendship.
+		assert_ok!(ConnectFour::block_player(Origin::signed(2), 1));
+		assert!(!Friends::<Test>::get(2).contains(&1));
+		assert_noop!(
+			ConnectFour::challenge_friend(Origin::signed(1), 2, GameKind::Ranked),
+			Error::<Test>::NotFriends
+		);
+	});
+}
+
+#[test]
+fn results_are_kept_for_the_retention_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+
+		System::set_block_number(3);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+		let result = ResultsByBlock::<Test>::get(3, board_id).unwrap();
+		assert_eq!((result.red, result.blue, result.winner), (2, 1, Some(1)));
+		assert_eq!(result.reason, FinishReason::Forced);
+
+		ConnectFour::on_initialize(12);
+		assert!(ResultsByBlock::<Test>::contains_key(3, board_id));
+		ConnectFour::on_initialize(13);
+		assert!(!ResultsByBlock::<Test>::contains_key(3, board_id));
+	});
+}
+
+#[test]
+fn finished_games_are_archived_in_compact_notation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+
+		// Red drops into the first column until it connects four.
+		for _ in 0..3 {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		}
+		assert!(ConnectFour::archived_game(board_id).is_none());
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+
+		let game = GameRecord::from_bytes(&ConnectFour::archived_game(board_id).unwrap()).unwrap();
+		assert_eq!(game.moves.len(), 7);
+		assert_eq!(game.moves[1], (PLAYER_2, Move::Drop(1)));
+		assert_eq!(game.column_string().as_deref(), Some("1212121"));
+		assert_eq!(
+			Logic::evaluate(&game.replay().unwrap(), PLAYER_1, 4).map(|line| line.len()),
+			Some(4)
+		);
+
+		// Positions fast-forward from the snapshot after the fourth move.
+		assert_eq!(ConnectFour::archived_snapshots(board_id).len(), 1);
+		for moves in 0..=7 {
+			let position = ConnectFour::archived_position(board_id, moves).unwrap();
+			assert_eq!(Logic::stones(&position), moves);
+		}
+		assert_eq!(ConnectFour::archived_position(board_id, 8), None);
+
+		// The archive goes with the result.
+		ConnectFour::on_initialize(11);
+		assert!(ConnectFour::archived_game(board_id).is_none());
+		assert!(ConnectFour::archived_snapshots(board_id).is_empty());
+	});
+}
+
+#[test]
+fn archived_games_can_be_annotated() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		for _ in 0..3 {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		}
+		assert_noop!(
+			ConnectFour::annotate_game(Origin::signed(3), board_id, b"cid".to_vec()),
+			Error::<Test>::GameNotArchived
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+
+		for annotator in [3, 4, 5] {
+			Balances::make_free_balance_be(&annotator, 100);
+		}
+		assert_noop!(
+			ConnectFour::annotate_game(Origin::signed(3), board_id, b"too long".repeat(2)),
+			Error::<Test>::AnnotationTooLong
+		);
+		assert_ok!(ConnectFour::annotate_game(Origin::signed(3), board_id, b"cid".to_vec()));
+		System::assert_last_event(
+			crate::Event::GameAnnotated(board_id, 3, b"cid".to_vec().try_into().unwrap()).into(),
+		);
+		assert_ok!(ConnectFour::annotate_game(Origin::signed(4), board_id, b"cid2".to_vec()));
+		assert_eq!(Balances::reserved_balance(&3), 3);
+		assert_noop!(
+			ConnectFour::annotate_game(Origin::signed(5), board_id, b"cid3".to_vec()),
+			Error::<Test>::TooManyAnnotations
+		);
+		let annotators: Vec<_> =
+			GameAnnotations::<Test>::get(board_id).into_iter().map(|(who, _)| who).collect();
+		assert_eq!(annotators, [3, 4]);
+
+		// Pruning the archive returns the deposits.
+		ConnectFour::on_initialize(11);
+		assert!(!GameAnnotations::<Test>::contains_key(board_id));
+		assert_eq!(Balances::reserved_balance(&3), 0);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+	});
+}
+
+#[test]
+fn stalling_players_forfeit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+
+		// Red stalls the first turn, blue doesn't.
+		System::set_block_number(6);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		System::set_block_number(7);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().stalled_turns, (1, 0));
+
+		// The game has no clock, but red is about to stall a second time.
+		System::set_block_number(11);
+		assert_noop!(ConnectFour::claim_timeout(Origin::signed(1)), Error::<Test>::NoTimeControl);
+		System::set_block_number(12);
+		assert_ok!(ConnectFour::claim_timeout(Origin::signed(1)));
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: Some(1),
+				reason: FinishReason::Stalled,
+				win_line: Vec::new(),
+				stake_fee: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn abandoners_compensate_the_waiting_player() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ConnectFour::set_abandon_penalty(
+				Origin::signed(1),
+				GameKind::Ranked,
+				Perbill::from_percent(60)
+			),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::set_abandon_penalty(
+			Origin::root(),
+			GameKind::Ranked,
+			Perbill::from_percent(60)
+		));
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		ConnectFour::on_initialize(2);
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let (abandoner, waiting) = if board.next_player == PLAYER_1 {
+			(board.red, board.blue)
+		} else {
+			(board.blue, board.red)
+		};
+
+		// Nobody moved, the player at turn lets the game go stale.
+		System::set_block_number(22);
+		assert_ok!(ConnectFour::expire_stale_game(Origin::none(), board_id));
+		System::assert_has_event(
+			crate::Event::AbandonCompensated {
+				board_id,
+				abandoner,
+				player: waiting,
+				compensation: 3,
+			}
+			.into(),
+		);
+		// 3 of the deposit of 5 go to the waiting player, the rest is slashed.
+		assert_eq!(Balances::total_balance(&abandoner), 95);
+		assert_eq!(Balances::free_balance(&waiting), 103);
+		assert_eq!(Balances::reserved_balance(&waiting), 0);
+	});
+}
+
+#[test]
+fn inactive_scores_decay_a_few_accounts_per_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		InactivityPeriod::set(10);
+		for (player, score) in [(1, 40), (2, 20), (3, -10)] {
+			ScoringBoard::<Test>::insert(player, score);
+		}
+		// The first pass only starts the clock, two accounts per block.
+		ConnectFour::on_initialize(1);
+		assert_eq!(ConnectFour::last_active(2), Some(1));
+		assert_eq!(ConnectFour::last_active(3), None);
+		ConnectFour::on_initialize(2);
+		assert_eq!(ConnectFour::last_active(3), Some(2));
+
+		ConnectFour::on_initialize(11);
+		System::assert_has_event(crate::Event::ScoreDecayed(1, 40, 20).into());
+		assert_eq!(ScoringBoard::<Test>::get(2), Some(10));
+		assert_eq!(ConnectFour::last_active(1), Some(11));
+		// Negative scores don't rise by staying away.
+		ConnectFour::on_initialize(12);
+		assert_eq!(ScoringBoard::<Test>::get(3), Some(-10));
+	});
+}
+
+#[test]
+fn queued_players_see_their_place_in_the_queue() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (player, score) in [(1, 0), (2, 500), (3, 1000), (4, 0)] {
+			ScoringBoard::<Test>::insert(player, score);
+			Balances::make_free_balance_be(&player, 100);
+		}
+		for player in [1, 2, 3] {
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		assert_ok!(ConnectFour::find_game(Origin::signed(4), GameKind::Casual));
+		// Ranked places count across score buckets, the casual queue is separate.
+		assert_eq!(ConnectFour::queue_position(&3), Some(3));
+		assert_eq!(ConnectFour::queue_position(&4), Some(1));
+		assert_eq!(ConnectFour::queue_length(), 4);
+
+		assert_ok!(ConnectFour::cancel_queue(Origin::signed(1)));
+		assert_eq!(ConnectFour::queue_position(&1), None);
+		assert_eq!(ConnectFour::queue_position(&2), Some(1));
+		assert_eq!(ConnectFour::queue_position(&3), Some(2));
+		assert_eq!(ConnectFour::queue_length(), 3);
+	});
+}
+
+#[test]
+fn queued_players_are_either_matched_or_left_waiting() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ScoringBoard::<Test>::insert(3, 1000);
+		for player in [1, 2, 3] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+
+		ConnectFour::on_initialize(2);
+		// A matched player leaves the queue along with the opponent.
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(PlayerBoard::<Test>::get(2), board_id);
+		for player in [1, 2] {
+			assert!(ConnectFour::queue_status(&player).is_none());
+			assert!(!ConnectFour::newly_queued().contains(&player));
+		}
+		assert!(!MatchQueue::<Test>::contains_key(0));
+		// Without a candidate the player waits, queued exactly once.
+		assert!(!PlayerBoard::<Test>::contains_key(3));
+		let bucket = MatchQueue::<Test>::get(100);
+		assert_eq!(bucket.iter().filter(|(account, ..)| *account == 3).count(), 1);
+		assert_eq!(ConnectFour::queue_length(), 1);
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn stale_queue_entries_expire() {
+	new_test_ext().execute_with(|| {
+		QueueTimeout::set(10);
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::find_game(Origin::signed(1), GameKind::Ranked));
+		assert_ok!(ConnectFour::find_game(Origin::signed(2), GameKind::Casual));
+		// Joining the queue again restarts the timeout.
+		System::set_block_number(5);
+		assert_ok!(ConnectFour::cancel_queue(Origin::signed(2)));
+		assert_ok!(ConnectFour::find_game(Origin::signed(2), GameKind::Casual));
+
+		ConnectFour::on_initialize(11);
+		System::assert_has_event(crate::Event::QueueExpired(1).into());
+		assert!(ConnectFour::queue_status(&1).is_none());
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(ConnectFour::queue_position(&2), Some(1));
+
+		ConnectFour::on_initialize(15);
+		System::assert_has_event(crate::Event::QueueExpired(2).into());
+		assert_eq!(ConnectFour::queue_length(), 0);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn score_tolerance_widens_while_waiting() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ScoringBoard::<Test>::insert(2, 25);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+
+		// The tolerance starts at 10 and widens by 10 every 5 blocks.
+		ConnectFour::on_initialize(2);
+		assert!(!PlayerBoard::<Test>::contains_key(1));
+		assert_eq!(QueueRechecks::<Test>::get(6).into_inner(), vec![1, 2]);
+		ConnectFour::on_initialize(6);
+		assert!(!PlayerBoard::<Test>::contains_key(1));
+		assert!(!QueueRechecks::<Test>::contains_key(6));
+		assert_eq!(QueueRechecks::<Test>::get(11).into_inner(), vec![1, 2]);
+
+		ConnectFour::on_initialize(11);
+		assert!(PlayerBoard::<Test>::contains_key(1));
+		assert_eq!(PlayerBoard::<Test>::get(1), PlayerBoard::<Test>::get(2));
+		assert!(ConnectFour::queue_status(&1).is_none());
+	});
+}
+
+#[test]
+fn ranked_players_are_paired_within_their_tier() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let boundaries: TierBoundariesOf = vec![20].try_into().unwrap();
+		assert_noop!(
+			ConnectFour::set_tier_boundaries(
+				Origin::root(),
+				vec![20, 20].try_into().unwrap(),
+				false
+			),
+			Error::<Test>::InvalidTierBoundaries
+		);
+		assert_ok!(ConnectFour::set_tier_boundaries(Origin::root(), boundaries.clone(), false));
+		ConnectFour::set_score(&1, Some(25));
+		System::assert_last_event(crate::Event::TierChanged(1, 0, 1).into());
+		ConnectFour::set_score(&1, Some(15));
+		ScoringBoard::<Test>::insert(2, 22);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+
+		// The scores are close enough, but on both sides of the boundary.
+		ConnectFour::on_initialize(2);
+		assert!(!PlayerBoard::<Test>::contains_key(1));
+
+		assert_ok!(ConnectFour::set_tier_boundaries(Origin::root(), boundaries, true));
+		ConnectFour::on_initialize(6);
+		assert!(PlayerBoard::<Test>::contains_key(1));
+		assert_eq!(PlayerBoard::<Test>::get(1), PlayerBoard::<Test>::get(2));
+	});
+}
+
+#[test]
+fn matchmaking_skips_players_busy_on_a_board() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Ranked));
+		}
+		// A queued player can still accept a challenge.
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(3),
+			1,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(1), 3, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+
+		// Pairing them again used to leave the first board without its player.
+		ConnectFour::on_initialize(2);
+		assert_eq!(PlayerBoard::<Test>::get(1), board_id);
+		assert!(!PlayerBoard::<Test>::contains_key(2));
+		assert_eq!(Boards::<Test>::iter().count(), 1);
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn pop_out_stones_drop_and_double_lines_go_to_the_opponent() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pop_out = BoardVariant { pop_out: true, ..Default::default() };
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			pop_out,
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+
+		// Red plays first, blue stacks on top.
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 0, None));
+		assert_noop!(ConnectFour::pop_stone(Origin::signed(2), 1), Error::<Test>::CannotPop);
+		assert_ok!(ConnectFour::pop_stone(Origin::signed(2), 0));
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().board.cell(0, 5), PLAYER_2);
+
+		// Blue's pop completes a red line in the bottom row and a blue one above it.
+		Boards::<Test>::mutate(board_id, |board| {
+			let cells = &mut board.as_mut().unwrap().board;
+			for column in 1..4 {
+				Logic::add_stone(cells, column, PLAYER_1);
+				Logic::add_stone(cells, column, PLAYER_2);
+			}
+			Logic::add_stone(cells, 0, PLAYER_1);
+			Logic::add_stone(cells, 0, PLAYER_2);
+		});
+		assert_ok!(ConnectFour::pop_stone(Origin::signed(1), 0));
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: Some(2),
+				reason: FinishReason::Connected,
+				win_line: vec![(0, 5), (1, 5), (2, 5), (3, 5)],
+				stake_fee: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn pop_stone_needs_pop_out_board() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		assert_noop!(ConnectFour::pop_stone(Origin::signed(2), 0), Error::<Test>::NotPopOut);
+	});
+}
+
+#[test]
+fn turns_publish_the_position_hash() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+
+		// A client replaying the move gets to the same hash.
+		let mut board = Logic::new_board(7, 6).unwrap();
+		Logic::add_stone(&mut board, 3, PLAYER_1);
+		let position = BlakeTwo256::hash_of(&(board, PLAYER_2));
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().position, position);
+		System::assert_last_event(
+			crate::Event::TurnPlayed {
+				board_id,
+				player: 2,
+				column: 3,
+				row: 5,
+				position,
+				deadline: None,
+				moves_played: 1,
+				evaluation: None,
+			}
+			.into(),
+		);
+		assert_ok!(ConnectFour::do_try_state());
+
+		// Spectated games carry the threats of both sides.
+		assert_ok!(ConnectFour::watch_board(Origin::signed(3), board_id));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 3, None));
+		System::assert_last_event(
+			crate::Event::TurnPlayed {
+				board_id,
+				player: 1,
+				column: 3,
+				row: 4,
+				position: Boards::<Test>::get(board_id).unwrap().position,
+				deadline: None,
+				moves_played: 2,
+				evaluation: Some(ThreatCounts { red: 0, blue: 0 }),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn casual_games_ignore_scores() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ScoringBoard::<Test>::insert(1, 500);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Casual));
+		}
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(1), GameKind::Ranked),
+			Error::<Test>::MatchQueueError
+		);
+
+		// Far apart in score, but casual players take whoever waits.
+		ConnectFour::on_initialize(2);
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(PlayerBoard::<Test>::get(2), board_id);
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().kind, GameKind::Casual);
+		assert!(ConnectFour::casual_queue().is_empty());
+
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(500));
+		assert_eq!(ScoringBoard::<Test>::get(2), None);
+		assert!(ConnectFour::top_scores().is_empty());
+	});
+}
+
+#[test]
+fn profiles_steer_matchmaking_and_tables() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let big = BoardVariant { width: 9, height: 8, connect: 5, pop_out: false };
+		assert_noop!(
+			ConnectFour::set_profile(
+				Origin::signed(1),
+				Some(big),
+				None,
+				true,
+				None,
+				b"far too long".to_vec()
+			),
+			Error::<Test>::DisplayNameTooLong
+		);
+		assert_ok!(ConnectFour::set_profile(
+			Origin::signed(1),
+			Some(big),
+			None,
+			true,
+			None,
+			b"one".to_vec()
+		));
+		assert_ok!(ConnectFour::set_profile(
+			Origin::signed(3),
+			Some(big),
+			Some(5),
+			true,
+			None,
+			Vec::new()
+		));
+		assert_ok!(ConnectFour::set_profile(
+			Origin::signed(2),
+			Some(BoardVariant::default()),
+			None,
+			true,
+			None,
+			Vec::new()
+		));
+		for player in [1, 2, 3] {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::find_game(Origin::signed(player), GameKind::Casual));
+		}
+
+		// Player 2 waited longer, but only player 3 wants the same board as player 1.
+		ConnectFour::on_initialize(2);
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(PlayerBoard::<Test>::get(3), board_id);
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().variant, big);
+		assert!(!PlayerBoard::<Test>::contains_key(2));
+
+		// Back to the default profile, without a stake limit but closed to public tables.
+		assert_ok!(ConnectFour::set_profile(Origin::signed(1), None, None, true, None, Vec::new()));
+		assert!(!PlayerProfile::<Test>::contains_key(1));
+		assert_ok!(ConnectFour::set_profile(
+			Origin::signed(2),
+			None,
+			None,
+			false,
+			None,
+			Vec::new()
+		));
+		assert_ok!(ConnectFour::cancel_queue(Origin::signed(2)));
+		Balances::make_free_balance_be(&4, 100);
+		assert_ok!(ConnectFour::create_open_game(
+			Origin::signed(4),
+			AwardState { win: 10, lose: 5, draw: 2 },
+			10,
+			None
+		));
+		assert_noop!(
+			ConnectFour::join_open_game(Origin::signed(2), 0, None),
+			Error::<Test>::OpenChallengesDisallowed
+		);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, None));
+		assert_noop!(
+			ConnectFour::join_open_game(Origin::signed(3), 0, None),
+			Error::<Test>::StakeTooHigh
+		);
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(1), 0, None));
+	});
+}
+
+#[test]
+fn tables_can_be_staked_in_other_assets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::set_stake_fee(Origin::root(), Perbill::from_percent(10)));
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+			TestAssets::set_balance(7, &player, 100);
+		}
+		assert_noop!(
+			ConnectFour::create_open_game(Origin::signed(1), AWARD, 200, Some(7)),
+			TokenError::NoFunds
+		);
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 50, Some(7)));
+		assert_eq!(TestAssets::balance_on_hold(7, &1), 50);
+		// Only players staking the same asset are paired.
+		assert_noop!(
+			ConnectFour::join_open_game(Origin::signed(2), 0, None),
+			Error::<Test>::StakeAssetMismatch
+		);
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(2), 0, Some(7)));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(ConnectFour::board_stake_asset(board_id), Some(7));
+
+		// The winner takes the whole stake, the fee only applies to the native token.
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert_eq!(TestAssets::balance(7, &2), 150);
+		assert_eq!(TestAssets::balance(7, &1), 50);
+		assert_eq!(TestAssets::balance_on_hold(7, &2), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(ConnectFour::board_stake_asset(board_id), None);
+	});
+}
+
+#[test]
+fn staked_games_can_be_doubled() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 20, None));
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(2), 0, None));
+		let board_id = PlayerBoard::<Test>::get(1);
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let first = *board.account_at_turn(board.next_player);
+		let second = if first == 1 { 2 } else { 1 };
+
+		// Only the player at turn doubles, the game waits for the answer.
+		assert_noop!(
+			ConnectFour::offer_double(Origin::signed(second), board_id),
+			Error::<Test>::CannotDouble
+		);
+		assert_ok!(ConnectFour::offer_double(Origin::signed(first), board_id));
+		assert_eq!(Balances::reserved_balance(first), 40);
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(first), 0, None),
+			Error::<Test>::DoublePending
+		);
+		assert_noop!(
+			ConnectFour::respond_double(Origin::signed(first), board_id, true),
+			Error::<Test>::NoDoubleOffer
+		);
+		assert_ok!(ConnectFour::respond_double(Origin::signed(second), board_id, true));
+		System::assert_last_event(crate::Event::DoubleAccepted(board_id, second, 40).into());
+		assert_eq!(ConnectFour::board_stakes(board_id), Some(40));
+
+		// The cube belongs to the player that accepted.
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(second), 1, None));
+		assert_noop!(
+			ConnectFour::offer_double(Origin::signed(first), board_id),
+			Error::<Test>::CannotDouble
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(first), 0, None));
+		assert_ok!(ConnectFour::offer_double(Origin::signed(second), board_id));
+		assert_eq!(Balances::reserved_balance(second), 80);
+
+		// Declining loses the game at the current stakes.
+		assert_ok!(ConnectFour::respond_double(Origin::signed(first), board_id, false));
+		assert!(!Boards::<Test>::contains_key(board_id));
+		assert_eq!(Balances::free_balance(second), 140);
+		assert_eq!(Balances::free_balance(first), 60);
+		assert_eq!(Balances::reserved_balance(second), 0);
+	});
+}
+
+#[test]
+fn games_can_be_aborted_before_the_second_move() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		let play_first_move = || {
+			let board_id = PlayerBoard::<Test>::get(1);
+			let board = Boards::<Test>::get(board_id).unwrap();
+			let player = *board.account_at_turn(board.next_player);
+			assert_ok!(ConnectFour::play_turn(Origin::signed(player), 0, None));
+			board_id
+		};
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 50, None));
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(2), 0, None));
+		let board_id = play_first_move();
+
+		assert_ok!(ConnectFour::abort_game(Origin::signed(2), AbortReason::WrongOpponent));
+		System::assert_last_event(
+			crate::Event::GameAborted(board_id, 2, AbortReason::WrongOpponent).into(),
+		);
+		assert!(!Boards::<Test>::contains_key(board_id));
+		for player in [1, 2] {
+			assert_eq!(Balances::free_balance(player), 100);
+			assert_eq!(ScoringBoard::<Test>::get(player), None);
+			assert_eq!(ConnectFour::player_stats(player).played, 0);
+		}
+		assert!(ConnectFour::rematches(1).is_none());
+
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 50, None));
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(2), 1, None));
+		play_first_move();
+		play_first_move();
+		assert_noop!(
+			ConnectFour::abort_game(Origin::signed(1), AbortReason::Other),
+			Error::<Test>::AbortNotAllowed
+		);
+	});
+}
+
+#[test]
+fn undone_moves_leave_the_moves_played() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let award = AwardState { win: 0, lose: 0, draw: 0 };
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Casual,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, award));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 3, None));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((board.moves_played, board.turns), (2, 2));
+
+		// The undone move reopens the window to abort the game, it still counts as a turn.
+		assert_ok!(ConnectFour::request_undo(Origin::signed(1)));
+		assert_ok!(ConnectFour::respond_undo(Origin::signed(2), true));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		assert_eq!((board.moves_played, board.turns), (1, 2));
+		assert_ok!(ConnectFour::do_try_state());
+		assert_ok!(ConnectFour::abort_game(Origin::signed(1), AbortReason::Other));
+	});
+}
+
+#[test]
+fn spectated_games_are_streamed_to_their_game_trie() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let award = AwardState { win: 0, lose: 0, draw: 0 };
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			Some(10),
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Casual,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, award));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+		assert_eq!(ConnectFour::streamed_move(board_id, 1), None);
+
+		// Blue spends 2 blocks of its bank on the first streamed move.
+		assert_ok!(ConnectFour::watch_board(Origin::signed(3), board_id));
+		System::set_block_number(3);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 4, None));
+		let board = Boards::<Test>::get(board_id).unwrap();
+		let streamed = StreamedMove {
+			notation: notation::encode_move(PLAYER_2, Move::Drop(4)),
+			clocks: Some((10, 8)),
+			evaluation: Logic::threat_counts(&board.board, 4),
+		};
+		assert_eq!(ConnectFour::streamed_move(board_id, 2), Some(streamed));
+
+		// Undone moves leave the trie, finished games drop it once blocks have room.
+		assert_ok!(ConnectFour::request_undo(Origin::signed(1)));
+		assert_ok!(ConnectFour::respond_undo(Origin::signed(2), true));
+		assert_eq!(ConnectFour::streamed_move(board_id, 2), None);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 4, None));
+		assert!(ConnectFour::streamed_move(board_id, 2).is_some());
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert!(GameTrieCleanups::<Test>::contains_key(board_id));
+		assert_eq!(ConnectFour::on_idle(3, 0), 0);
+		assert!(ConnectFour::streamed_move(board_id, 2).is_some());
+		assert!(ConnectFour::on_idle(3, u64::MAX) >= <() as WeightInfo>::clear_game_trie(0));
+		assert_eq!(ConnectFour::streamed_move(board_id, 2), None);
+		assert!(!GameTrieCleanups::<Test>::contains_key(board_id));
+	});
+}
+
+#[test]
+fn winners_and_losers_reach_the_result_handler() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let start_game = |kind| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				kind,
+				None,
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			PlayerBoard::<Test>::get(1)
+		};
+
+		let board_id = start_game(GameKind::Casual);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		let forced = (Some(2), Some(1), board_id, GameKind::Casual, FinishReason::Forced);
+		assert_eq!(GameResults::get(), vec![forced]);
+
+		// Games without a winner are passed on without one.
+		let board_id = start_game(GameKind::Ranked);
+		assert_ok!(ConnectFour::abort_game(Origin::signed(1), AbortReason::Other));
+		let aborted = (None, None, board_id, GameKind::Ranked, FinishReason::Aborted);
+		assert_eq!(GameResults::get(), vec![forced, aborted]);
+	});
+}
+
+#[test]
+fn winners_unlock_achievements_once() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let start_game = || {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::ResponderFirst,
+				GameKind::Ranked,
+				None,
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			PlayerBoard::<Test>::get(1)
+		};
+
+		// Both players pass up a win before red connects four.
+		start_game();
+		for _ in 0..3 {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+			assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		}
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 5, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 6, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		System::assert_has_event(
+			crate::Event::AchievementUnlocked(2, Achievement::Comeback).into(),
+		);
+		assert_eq!(
+			ConnectFour::achievements(2),
+			Achievement::FirstWin.bit() | Achievement::Comeback.bit()
+		);
+		assert_eq!(ConnectFour::achievements(1), 0);
+
+		// Achievements are only unlocked once.
+		System::reset_events();
+		let board_id = start_game();
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert!(System::events().iter().all(|record| !matches!(
+			record.event,
+			Event::ConnectFour(crate::Event::AchievementUnlocked(..))
+		)));
+		assert_eq!(ConnectFour::player_stats(2).wins, 2);
+
+		let board_id = start_game();
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+		System::assert_has_event(
+			crate::Event::AchievementUnlocked(1, Achievement::FirstWin).into(),
+		);
+		assert_eq!(ConnectFour::achievements(1), Achievement::FirstWin.bit());
+	});
+}
+
+#[test]
+fn score_changes_are_kept_in_a_bounded_history() {
+	new_test_ext().execute_with(|| {
+		let mut boards = Vec::new();
+		for block in 1..=3 {
+			System::set_block_number(block);
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None,
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			let board_id = PlayerBoard::<Test>::get(1);
+			assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+			boards.push(board_id);
+		}
+		let history = ConnectFour::score_history(2);
+		assert_eq!(
+			history.iter().map(|change| (change.block, change.delta)).collect::<Vec<_>>(),
+			[(1, 10), (2, 10), (3, 10)]
+		);
+		assert_eq!(history[2].board_id, Some(boards[2]));
+		// Scores held at the floor don't change.
+		assert!(ConnectFour::score_history(1).is_empty());
+
+		// The oldest change makes room, resets aren't tied to a game.
+		System::set_block_number(4);
+		assert_ok!(ConnectFour::reset_score(Origin::root(), 2));
+		let history = ConnectFour::score_history(2);
+		assert_eq!(history.len(), 3);
+		assert_eq!(history[0].block, 2);
+		assert_eq!(history[2], ScoreChange { block: 4, delta: -30, board_id: None });
+	});
+}
+
+#[test]
+fn reported_games_are_settled_by_the_verdict() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 50, None));
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(2), 0, None));
+		let board_id = PlayerBoard::<Test>::get(1);
+
+		assert_noop!(
+			ConnectFour::report_opponent(Origin::signed(3), board_id, ReportReason::Abuse),
+			Error::<Test>::NotOnBoard
+		);
+		assert_ok!(ConnectFour::report_opponent(
+			Origin::signed(2),
+			board_id,
+			ReportReason::EngineAssistance
+		));
+		System::assert_last_event(
+			crate::Event::GameReported(board_id, 2, ReportReason::EngineAssistance).into(),
+		);
+		assert_noop!(
+			ConnectFour::report_opponent(Origin::signed(1), board_id, ReportReason::Other),
+			Error::<Test>::AlreadyReported
+		);
+		assert_noop!(
+			ConnectFour::resolve_report(Origin::root(), board_id, Verdict::Upheld),
+			Error::<Test>::DisputeNotFound
+		);
+
+		// The stakes stay reserved until the verdict is in.
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+		assert!(ConnectFour::disputes(board_id).is_some());
+		for player in [1, 2] {
+			assert_eq!(Balances::free_balance(player), 50);
+		}
+
+		assert_noop!(
+			ConnectFour::resolve_report(Origin::signed(2), board_id, Verdict::Upheld),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::resolve_report(Origin::root(), board_id, Verdict::Upheld));
+		System::assert_last_event(
+			crate::Event::ReportResolved(board_id, Verdict::Upheld, Some(2), 0).into(),
+		);
+		assert_eq!(Balances::free_balance(1), 50);
+		assert_eq!(Balances::free_balance(2), 150);
+		assert!(ConnectFour::disputes(board_id).is_none());
+	});
+}
+
+#[test]
+fn stake_fee_goes_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ConnectFour::set_stake_fee(Origin::signed(1), Perbill::from_percent(10)),
+			Error::<Test>::OnlyFounderAllowed
+		);
+		assert_ok!(ConnectFour::set_stake_fee(Origin::root(), Perbill::from_percent(10)));
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_ok!(ConnectFour::create_open_game(Origin::signed(1), AWARD, 50, None));
+		assert_ok!(ConnectFour::join_open_game(Origin::signed(2), 0, None));
+		let board_id = PlayerBoard::<Test>::get(1);
+
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: Some(1),
+				reason: FinishReason::Forced,
+				win_line: Vec::new(),
+				stake_fee: 5,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(1), 145);
+		assert_eq!(Balances::free_balance(2), 50);
+		assert_eq!(Balances::free_balance(TREASURY), 5);
+	});
+}
+
+#[test]
+fn challenges_within_the_profile_limits_start_right_away() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ScoringBoard::<Test>::insert(2, 5);
+		ScoringBoard::<Test>::insert(3, 100);
+		assert_ok!(ConnectFour::set_profile(
+			Origin::signed(2),
+			None,
+			None,
+			true,
+			Some(10),
+			Vec::new()
+		));
+		let challenge = |challenger, kind| {
+			ConnectFour::challenge(
+				Origin::signed(challenger),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				kind,
+				None,
+			)
+		};
+
+		// Score is at stake in a ranked game.
+		assert_ok!(challenge(1, GameKind::Ranked));
+		assert!(!PlayerBoard::<Test>::contains_key(2));
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(1)));
+		// The scores are too far apart.
+		assert_ok!(challenge(3, GameKind::Casual));
+		assert!(!PlayerBoard::<Test>::contains_key(2));
+		assert_ok!(ConnectFour::cancel_challenge(Origin::signed(3)));
+
+		assert_ok!(challenge(1, GameKind::Casual));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_eq!(PlayerBoard::<Test>::get(1), board_id);
+		assert!(ConnectFour::challenges(1).is_none());
+		System::assert_last_event(crate::Event::AutoAccepted(2, 1, 2, board_id).into());
+	});
+}
+
+#[test]
+fn moves_expecting_an_older_position_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, Some(0)));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, Some(1)));
+
+		// A retry of the first move can't be played as a second one.
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(2), 0, Some(0)),
+			Error::<Test>::StaleMove
+		);
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(2), 0, Some(3)),
+			Error::<Test>::StaleMove
+		);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, Some(2)));
+		assert_eq!(ConnectFour::boards(PlayerBoard::<Test>::get(2)).unwrap().moves_played, 3);
+	});
+}
+
+#[test]
+fn moves_that_keep_the_game_going_are_refunded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		for _ in 0..3 {
+			for (player, column) in [(2, 0), (1, 1)] {
+				let info = ConnectFour::play_turn(Origin::signed(player), column, None).unwrap();
+				assert_eq!(info.actual_weight, Some(<() as WeightInfo>::play_turn_mid_game()));
+			}
+		}
+
+		// The winning move pays for settling the game.
+		let info = ConnectFour::play_turn(Origin::signed(2), 0, None).unwrap();
+		assert_eq!(info.actual_weight, None);
+		assert!(!PlayerBoard::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn correct_predictions_share_the_losing_stakes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::set_prediction_fee(Origin::root(), Perbill::from_percent(10)));
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		for spectator in 3..=6 {
+			Balances::make_free_balance_be(&spectator, 100);
+		}
+
+		assert_noop!(
+			ConnectFour::predict(Origin::signed(1), board_id, Some(1), 10),
+			Error::<Test>::PlayerCannotPredict
+		);
+		assert_ok!(ConnectFour::predict(Origin::signed(3), board_id, Some(2), 10));
+		assert_ok!(ConnectFour::predict(Origin::signed(4), board_id, Some(1), 40));
+		assert_ok!(ConnectFour::predict(Origin::signed(5), board_id, Some(2), 30));
+		assert_eq!(Balances::reserved_balance(&4), 40);
+
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		assert_noop!(
+			ConnectFour::predict(Origin::signed(6), board_id, None, 10),
+			Error::<Test>::PredictionsClosed
+		);
+
+		// The fee is a tenth of the losing stake, the rest is split three to one.
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert_eq!(Balances::free_balance(&3), 109);
+		assert_eq!(Balances::free_balance(&4), 60);
+		assert_eq!(Balances::free_balance(&5), 127);
+		assert_eq!(Balances::free_balance(&TREASURY), 4);
+		assert!(!Predictions::<Test>::contains_key(board_id));
+	});
+}
+
+#[test]
+fn sponsorships_go_to_the_winner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let start = || {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::ResponderFirst,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			PlayerBoard::<Test>::get(1)
+		};
+		for account in 1..=5 {
+			Balances::make_free_balance_be(&account, 100);
+		}
+		let board_id = start();
+		let board = SponsoredGame::Board(board_id);
+		assert_ok!(ConnectFour::sponsor_game(Origin::signed(3), board, 30));
+		System::assert_last_event(crate::Event::GameSponsored(board, 3, 30).into());
+		assert_ok!(ConnectFour::sponsor_game(Origin::signed(4), board, 20));
+		assert_noop!(
+			ConnectFour::sponsor_game(Origin::signed(5), board, 10),
+			Error::<Test>::TooManySponsors
+		);
+		assert_eq!(Balances::reserved_balance(&3), 30);
+
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		System::assert_has_event(crate::Event::SponsorshipPaid(board_id, 3, 2, 30).into());
+		System::assert_has_event(crate::Event::SponsorshipPaid(board_id, 4, 2, 20).into());
+		assert_eq!(Balances::free_balance(&2), 150);
+		assert_eq!(Balances::reserved_balance(&3), 0);
+		assert!(!BoardSponsors::<Test>::contains_key(board_id));
+
+		// A game called off pays nobody, the sponsor gets their tokens back.
+		let board_id = start();
+		assert_ok!(ConnectFour::sponsor_game(
+			Origin::signed(5),
+			SponsoredGame::Board(board_id),
+			10
+		));
+		assert_ok!(ConnectFour::abort_game(Origin::signed(1), AbortReason::Other));
+		System::assert_has_event(crate::Event::SponsorshipRefunded(board_id, 5, 10).into());
+		assert_eq!(Balances::free_balance(&5), 100);
+	});
+}
+
+#[test]
+fn moves_can_be_checked_without_playing_them() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+
+		// Stones land on the bottom row, the sixth.
+		assert_eq!(ConnectFour::dry_run_move(&2, 0), Ok(5));
+		assert_eq!(ConnectFour::dry_run_move(&1, 0), Err(MoveError::NotPlayerTurn));
+		assert_eq!(ConnectFour::dry_run_move(&3, 0), Err(MoveError::NoPlayerBoard));
+		assert_eq!(ConnectFour::dry_run_move(&2, 7), Err(MoveError::ColumnOutOfRange));
+		for player in [2, 1, 2, 1, 2, 1] {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(player), 0, None));
+		}
+		assert_eq!(ConnectFour::dry_run_move(&2, 0), Err(MoveError::ColumnFull));
+
+		// Failed checks don't pay, nothing is played either way.
+		let error = ConnectFour::check_move(Origin::signed(2), 0).unwrap_err();
+		assert_eq!(error.post_info.pays_fee, Pays::No);
+		assert_eq!(error.error, Error::<Test>::ColumnFull.into());
+		assert_ok!(ConnectFour::check_move(Origin::signed(2), 1));
+		assert_eq!(ConnectFour::dry_run_move(&2, 1), Ok(5));
+
+		// Playing them fails the same way.
+		assert_noop!(ConnectFour::play_turn(Origin::signed(2), 0, None), Error::<Test>::ColumnFull);
+		assert_noop!(
+			ConnectFour::play_turn(Origin::signed(2), 7, None),
+			Error::<Test>::ColumnOutOfRange
+		);
+
+		// The full column is left out of the evaluation, the center column scores best.
+		let board_id = PlayerBoard::<Test>::get(2);
+		let scores = ConnectFour::evaluate_position(board_id).unwrap();
+		assert_eq!(scores.iter().map(|score| score.column).collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6]);
+		assert_eq!(
+			scores.iter().max_by_key(|score| score.score).map(|score| score.column),
+			Some(3)
+		);
+		assert_eq!(ConnectFour::evaluate_position(H256::zero()), None);
+		assert_eq!(ConnectFour::legal_moves(board_id), 0b111_1110);
+		assert_eq!(ConnectFour::legal_moves(H256::zero()), 0);
+		assert_eq!(ConnectFour::is_terminal(board_id), None);
+
+		// Finished games are judged from the archive.
+		for player in [2, 1, 2, 1, 2, 1, 2] {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(player), 1 + player as u8 % 2, None));
+		}
+		assert_eq!(ConnectFour::legal_moves(board_id), 0);
+		assert_eq!(ConnectFour::is_terminal(board_id), Some(Outcome::Win(PLAYER_1)));
+	});
+}
+
+#[test]
+fn running_boards_can_be_queried_in_bulk() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (challenger, opponent) in [(1, 2), (3, 4)] {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(challenger),
+				opponent,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::ResponderFirst,
+				GameKind::Casual,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(
+				Origin::signed(opponent),
+				challenger,
+				true,
+				AWARD
+			));
+		}
+		let first = PlayerBoard::<Test>::get(1);
+		let second = PlayerBoard::<Test>::get(3);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 3, None));
+
+		let summaries = ConnectFour::boards_of(vec![1, 5, 4]);
+		assert_eq!(summaries.len(), 3);
+		let summary = summaries[0].as_ref().unwrap();
+		assert_eq!((summary.id, summary.red, summary.blue), (first, 2, 1));
+		assert_eq!((summary.next_player, summary.moves_played), (1, 1));
+		assert_eq!(summary.kind, GameKind::Casual);
+		assert_eq!(summaries[1], None);
+		assert_eq!(summaries[2].as_ref().map(|summary| summary.id), Some(second));
+
+		// Pages continue after the last id of the previous one.
+		let page = ConnectFour::active_board_ids(None, 1);
+		assert_eq!(page.len(), 1);
+		let next = ConnectFour::active_board_ids(page.last().copied(), 5);
+		assert_eq!(next.len(), 1);
+		assert!(ConnectFour::active_board_ids(next.last().copied(), 5).is_empty());
+		let mut ids = [page[0], next[0]];
+		ids.sort();
+		let mut expected = [first, second];
+		expected.sort();
+		assert_eq!(ids, expected);
+	});
+}
+
+#[test]
+fn full_board_draws_earn_the_draw_award() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let variant = BoardVariant { width: 4, height: 4, connect: 4, pop_out: false };
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			variant,
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let columns = [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 3, 3, 3, 2, 3];
+		for (column, player) in columns.into_iter().zip([2, 1].into_iter().cycle()) {
+			assert_ok!(ConnectFour::play_turn(Origin::signed(player), column, None));
+		}
+
+		assert!(!PlayerBoard::<Test>::contains_key(1));
+		for player in [1, 2] {
+			assert_eq!(ScoringBoard::<Test>::get(player), Some(2));
+			assert_eq!(ConnectFour::player_stats(player).draws, 1);
+		}
+	});
+}
+
+#[test]
+fn games_reaching_the_move_limit_end_in_a_draw() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxMovesPerGame::set(3);
+		let pop_out = BoardVariant { pop_out: true, ..Default::default() };
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			pop_out,
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		assert_ok!(ConnectFour::pop_stone(Origin::signed(2), 0));
+
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: None,
+				reason: FinishReason::MoveLimit,
+				win_line: vec![],
+				stake_fee: 0,
+			}
+			.into(),
+		);
+		assert!(!Boards::<Test>::contains_key(board_id));
+		for player in [1, 2] {
+			assert_eq!(ScoringBoard::<Test>::get(player), Some(2));
+			assert_eq!(ConnectFour::player_stats(player).draws, 1);
+		}
+	});
+}
+
+#[test]
+fn undone_moves_dont_count_towards_the_move_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxMovesPerGame::set(3);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Casual,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		assert_ok!(ConnectFour::request_undo(Origin::signed(1)));
+		assert_ok!(ConnectFour::respond_undo(Origin::signed(2), true));
+
+		// The third move made counts as the second one.
+		assert_ok!(ConnectFour::play_turn(Origin::signed(1), 1, None));
+		assert_eq!(Boards::<Test>::get(board_id).unwrap().moves_played, 2);
+		assert_ok!(ConnectFour::play_turn(Origin::signed(2), 0, None));
+		System::assert_has_event(
+			crate::Event::GameFinished {
+				board_id,
+				winner: None,
+				reason: FinishReason::MoveLimit,
+				win_line: vec![],
+				stake_fee: 0,
+			}
+			.into(),
+		);
+		assert!(!Boards::<Test>::contains_key(board_id));
+	});
+}
+
+#[test]
+fn reward_points_vest_before_they_can_be_claimed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1)));
+
+		assert_eq!(ConnectFour::reward_points(1).map(|rewards| rewards.pending), Some(10));
+		assert_eq!(ConnectFour::reward_points(2), None);
+		assert_noop!(
+			ConnectFour::claim_rewards(Origin::signed(1)),
+			Error::<Test>::NoRewardsToClaim
+		);
+
+		System::set_block_number(6);
+		assert_ok!(ConnectFour::claim_rewards(Origin::signed(1)));
+		System::assert_last_event(crate::Event::RewardsClaimed(1, 10, 20).into());
+		assert_eq!(Balances::free_balance(1), 120);
+		assert_eq!(ConnectFour::reward_points(1), None);
+		// Claiming leaves the score alone.
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(10));
+	});
+}
+
+#[test]
+fn provisional_scores_move_faster_and_stop_at_the_floor() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ProvisionalGames::set(1);
+		ScoringBoard::<Test>::insert(2, 3);
+		let play = |winner| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				GameKind::Ranked,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			let board_id = PlayerBoard::<Test>::get(1);
+			assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(winner)));
+		};
+
+		// The first game counts three times, the loser stops at the floor of 0.
+		play(1);
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(30));
+		assert_eq!(ScoringBoard::<Test>::get(2), Some(0));
+
+		play(2);
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(25));
+		assert_eq!(ScoringBoard::<Test>::get(2), Some(10));
+	});
+}
+
+#[test]
+fn placement_games_taper_the_boost_and_keep_players_off_the_leaderboard() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ProvisionalGames::set(2);
+		let play = |winner, kind| {
+			assert_ok!(ConnectFour::challenge(
+				Origin::signed(1),
+				2,
+				10,
+				5,
+				2,
+				BoardVariant::default(),
+				None,
+				None,
+				FirstMovePolicy::Random,
+				kind,
+				None
+			));
+			assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+			let board_id = PlayerBoard::<Test>::get(1);
+			assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(winner)));
+		};
+
+		// Casual games don't count towards the placement.
+		play(1, GameKind::Casual);
+		assert!(ConnectFour::is_provisional(&1));
+
+		// The first placement game counts three times, the second twice.
+		play(1, GameKind::Ranked);
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(30));
+		assert!(ConnectFour::leaderboard(4).is_empty());
+		play(1, GameKind::Ranked);
+		assert_eq!(ScoringBoard::<Test>::get(1), Some(50));
+		assert_eq!(ConnectFour::player_stats(1).ranked, 2);
+		System::assert_has_event(crate::Event::PlacementCompleted(1, 50).into());
+		System::assert_has_event(crate::Event::PlacementCompleted(2, 0).into());
+		assert!(!ConnectFour::is_provisional(&2));
+		assert_eq!(ConnectFour::leaderboard(4), vec![(1, 50), (2, 0)]);
+
+		// Placed players play for the plain awards.
+		play(2, GameKind::Ranked);
+		assert_eq!(ConnectFour::leaderboard(4), vec![(1, 45), (2, 10)]);
+		assert_ok!(ConnectFour::do_try_state());
+	});
+}
+
+#[test]
+fn players_and_spectators_send_emotes_at_a_limited_rate() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_ok!(ConnectFour::watch_board(Origin::signed(3), board_id));
+
+		assert_noop!(
+			ConnectFour::send_emote(Origin::signed(4), board_id, Emote::Wow),
+			Error::<Test>::NotAtBoard
+		);
+		assert_ok!(ConnectFour::send_emote(Origin::signed(3), board_id, Emote::Wow));
+		System::assert_last_event(crate::Event::BoardEmote(board_id, 3, Emote::Wow).into());
+		assert_ok!(ConnectFour::send_emote(Origin::signed(2), board_id, Emote::GoodLuck));
+		assert_noop!(
+			ConnectFour::send_emote(Origin::signed(3), board_id, Emote::Thinking),
+			Error::<Test>::EmoteTooSoon
+		);
+		System::set_block_number(4);
+		assert_ok!(ConnectFour::send_emote(Origin::signed(3), board_id, Emote::Thinking));
+	});
+}
+
+#[test]
+fn full_board_events_are_opt_in_and_charged_to_the_mover() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(1),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::ResponderFirst,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1);
+		assert_eq!(ConnectFour::boards(board_id).unwrap().verbosity, EventVerbosity::Delta);
+		assert_ok!(ConnectFour::watch_board(Origin::signed(3), board_id));
+		assert_noop!(
+			ConnectFour::set_event_verbosity(
+				Origin::signed(3),
+				board_id,
+				EventVerbosity::FullBoard
+			),
+			Error::<Test>::NotAtBoard
+		);
+
+		assert_ok!(ConnectFour::set_event_verbosity(
+			Origin::signed(1),
+			board_id,
+			EventVerbosity::FullBoard
+		));
+		System::assert_last_event(
+			crate::Event::EventVerbositySet(board_id, EventVerbosity::FullBoard).into(),
+		);
+		let info = ConnectFour::play_turn(Origin::signed(2), 0, None).unwrap();
+		let snapshot = <() as WeightInfo>::board_snapshot();
+		assert_eq!(
+			info.actual_weight,
+			Some(<() as WeightInfo>::play_turn_mid_game().saturating_add(snapshot))
+		);
+		let board = ConnectFour::boards(board_id).unwrap();
+		assert_eq!(board.moves_played, 1);
+		System::assert_has_event(crate::Event::BoardSnapshot(board_id, board).into());
+
+		// Back to plain moves.
+		assert_ok!(ConnectFour::set_event_verbosity(
+			Origin::signed(2),
+			board_id,
+			EventVerbosity::Delta
+		));
+		let events = System::events().len();
+		let info = ConnectFour::play_turn(Origin::signed(1), 1, None).unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::play_turn_mid_game()));
+		assert_eq!(System::events().len(), events + 1);
+
+		// New boards start with the configured verbosity.
+		DefaultEventVerbosity::set(EventVerbosity::FullBoard);
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(4),
+			5,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(5), 4, true, AWARD));
+		let board = ConnectFour::boards(PlayerBoard::<Test>::get(4)).unwrap();
+		assert_eq!(board.verbosity, EventVerbosity::FullBoard);
+	});
+}
+
+#[test]
+fn cleanup_removes_orphaned_entries_and_pays_a_reward() {
+	new_test_ext().execute_with(|| {
+		let orphaned = BlakeTwo256::hash_of(&"gone");
+		PlayerBoard::<Test>::insert(3, orphaned);
+		let accounts = vec![2, 3].try_into().unwrap();
+		let balance = Balances::free_balance(&1);
+
+		assert_ok!(ConnectFour::cleanup(Origin::signed(1), accounts));
+		assert!(!PlayerBoard::<Test>::contains_key(3));
+		assert_eq!(Balances::free_balance(&1), balance + 1);
+
+		let accounts = vec![2, 3].try_into().unwrap();
+		assert_noop!(
+			ConnectFour::cleanup(Origin::signed(1), accounts),
+			Error::<Test>::NothingToCleanUp
+		);
+	});
+}
+
+#[test]
+fn ranked_play_can_require_a_judged_identity() {
+	new_test_ext().execute_with(|| {
+		RequireJudgedIdentity::set(true);
+		for player in [1, 2] {
+			Balances::make_free_balance_be(&player, 100);
+		}
+		assert_noop!(
+			ConnectFour::find_game(Origin::signed(2), GameKind::Ranked),
+			Error::<Test>::IdentityNotJudged
+		);
+		assert_ok!(ConnectFour::find_game(Origin::signed(2), GameKind::Casual));
+		assert_ok!(ConnectFour::find_game(Origin::signed(1), GameKind::Ranked));
+
+		ConnectFour::set_score(&3, Some(7));
+		ConnectFour::set_score(&12, Some(5));
+		assert_eq!(
+			ConnectFour::named_leaderboard(2),
+			vec![(3, 7, Some(b"player3".to_vec())), (12, 5, None)]
+		);
+	});
+}
+
+#[test]
+fn tournament_drops_players_that_did_not_check_in() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ConnectFour::create_tournament(
+				Origin::signed(1),
+				1,
+				0,
+				0,
+				0,
+				BoardVariant::default(),
+				None,
+				10,
+				5,
+				5,
+				false
+			),
+			Error::<Test>::InvalidCheckInWindow
+		);
+		assert_ok!(ConnectFour::create_tournament(
+			Origin::signed(1),
+			1,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			None,
+			10,
+			5,
+			10,
+			false
+		));
+		for player in 1..=4 {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::join_tournament(Origin::signed(player), 0));
+			assert_eq!(Balances::reserved_balance(player), 10);
+		}
+		assert_noop!(ConnectFour::check_in(Origin::signed(1), 0), Error::<Test>::CheckInClosed);
+
+		System::set_block_number(5);
+		Balances::make_free_balance_be(&5, 100);
+		assert_noop!(
+			ConnectFour::join_tournament(Origin::signed(5), 0),
+			Error::<Test>::RegistrationClosed
+		);
+		assert_noop!(ConnectFour::check_in(Origin::signed(5), 0), Error::<Test>::NotRegistered);
+		for player in 1..=3 {
+			assert_ok!(ConnectFour::check_in(Origin::signed(player), 0));
+		}
+		assert_noop!(ConnectFour::check_in(Origin::signed(1), 0), Error::<Test>::AlreadyCheckedIn);
+		assert_noop!(
+			ConnectFour::start_tournament(Origin::signed(1), 0),
+			Error::<Test>::CheckInNotOver
+		);
+
+		// Player 4 never checked in and loses the entry fee, the round pairs the other three.
+		System::set_block_number(10);
+		assert_ok!(ConnectFour::start_tournament(Origin::signed(1), 0));
+		System::assert_has_event(crate::Event::TournamentNoShow(0, 4, false).into());
+		assert!(!TournamentPlayers::<Test>::contains_key(0, 4));
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(4), 90);
+		assert_eq!(Tournaments::<Test>::get(0).map(|tournament| tournament.players), Some(3));
+		assert_eq!(TournamentRounds::<Test>::get(0, 1).len(), 1);
+
+		// The others claim their entry fee back once the tournament finished, the winner gets
+		// the fee of the no-show on top.
+		ConnectFour::on_initialize(10);
+		let (red, _) = TournamentRounds::<Test>::get(0, 1)[0];
+		let board_id = PlayerBoard::<Test>::get(red);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(red)));
+		assert_ok!(ConnectFour::advance_tournament(Origin::signed(5), 0));
+		System::assert_last_event(crate::Event::TournamentFinished(0, red).into());
+		for player in 1..=3 {
+			assert_eq!(Balances::reserved_balance(player), 10);
+			assert_ok!(ConnectFour::claim_prize(Origin::signed(player), 0));
+			assert_eq!(Balances::reserved_balance(player), 0);
+			let prize = if player == red { 10 } else { 0 };
+			System::assert_last_event(crate::Event::PrizeClaimed(0, player, prize).into());
+			assert_eq!(Balances::free_balance(player), 100 + prize);
+		}
+		assert_noop!(ConnectFour::claim_prize(Origin::signed(1), 0), Error::<Test>::NoPrize);
+	});
+}
+
+#[test]
+fn unclaimed_tournament_prizes_are_swept() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::create_tournament(
+			Origin::signed(1),
+			1,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			None,
+			10,
+			5,
+			10,
+			false
+		));
+		for player in 1..=4 {
+			Balances::make_free_balance_be(&player, 100);
+			assert_ok!(ConnectFour::join_tournament(Origin::signed(player), 0));
+		}
+		System::set_block_number(5);
+		for player in 1..=4 {
+			assert_ok!(ConnectFour::check_in(Origin::signed(player), 0));
+		}
+		System::set_block_number(10);
+		assert_ok!(ConnectFour::start_tournament(Origin::signed(1), 0));
+		ConnectFour::on_initialize(10);
+		for (red, _) in TournamentRounds::<Test>::get(0, 1) {
+			let board_id = PlayerBoard::<Test>::get(red);
+			assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(red)));
+		}
+		assert_noop!(
+			ConnectFour::sweep_prizes(Origin::signed(5), 0),
+			Error::<Test>::WrongTournamentState
+		);
+		assert_ok!(ConnectFour::advance_tournament(Origin::signed(5), 0));
+		assert_ok!(ConnectFour::claim_prize(Origin::signed(1), 0));
+
+		// Prizes stay claimable for `PrizeClaimPeriod`, the rest is swept two at a time.
+		assert_noop!(
+			ConnectFour::sweep_prizes(Origin::signed(5), 0),
+			Error::<Test>::ClaimPeriodNotOver
+		);
+		System::set_block_number(20);
+		assert_ok!(ConnectFour::sweep_prizes(Origin::signed(5), 0));
+		System::assert_last_event(crate::Event::PrizesSwept(0, 2, 20).into());
+		assert_ok!(ConnectFour::sweep_prizes(Origin::signed(5), 0));
+		System::assert_last_event(crate::Event::PrizesSwept(0, 1, 10).into());
+		assert_noop!(ConnectFour::sweep_prizes(Origin::signed(5), 0), Error::<Test>::NoPrize);
+		assert_eq!(Balances::free_balance(TREASURY), 30);
+		for player in 2..=4 {
+			assert_eq!(Balances::reserved_balance(player), 0);
+			assert_eq!(Balances::free_balance(player), 90);
+			assert_noop!(
+				ConnectFour::claim_prize(Origin::signed(player), 0),
+				Error::<Test>::NoPrize
+			);
+		}
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn tournament_boards_are_created_over_several_blocks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::create_tournament(
+			Origin::signed(1),
+			1,
+			0,
+			0,
+			0,
+			BoardVariant::default(),
+			None,
+			0,
+			2,
+			3,
+			true
+		));
+		for player in 1..=6 {
+			assert_ok!(ConnectFour::join_tournament(Origin::signed(player), 0));
+		}
+		System::set_block_number(2);
+		for player in 1..=6 {
+			assert_ok!(ConnectFour::check_in(Origin::signed(player), 0));
+		}
+		System::set_block_number(3);
+		assert_ok!(ConnectFour::start_tournament(Origin::signed(1), 0));
+		assert_eq!(PendingBoardCreations::<Test>::get(0).len(), 3);
+		assert_eq!(Tournaments::<Test>::get(0).map(|tournament| tournament.pending), Some(3));
+
+		// Two boards fit into a block, the last one waits for the next.
+		ConnectFour::on_initialize(2);
+		assert_eq!(PendingBoardCreations::<Test>::get(0).len(), 1);
+		let (red, blue) = PendingBoardCreations::<Test>::get(0)[0];
+		assert!(!PlayerBoard::<Test>::contains_key(red));
+
+		// Meanwhile red starts another game and forfeits the tournament game.
+		assert_ok!(ConnectFour::play_vs_bot(Origin::signed(red), BotDifficulty::Easy));
+		ConnectFour::on_initialize(3);
+		assert!(!PendingBoardCreations::<Test>::contains_key(0));
+		assert!(!PlayerBoard::<Test>::contains_key(blue));
+		assert_eq!(Tournaments::<Test>::get(0).map(|tournament| tournament.pending), Some(2));
+		assert_eq!(
+			TournamentPlayers::<Test>::get(0, blue).map(|standing| standing.points),
+			Some(2)
+		);
+	});
+}
+
+#[test]
+fn guilds_gain_their_members_results_and_guild_matches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ConnectFour::create_guild(Origin::signed(1)));
+		System::assert_last_event(crate::Event::GuildCreated(0, 1).into());
+		assert_ok!(ConnectFour::join_guild(Origin::signed(2), 0));
+		assert_ok!(ConnectFour::create_guild(Origin::signed(3)));
+		assert_ok!(ConnectFour::join_guild(Origin::signed(4), 1));
+		assert_noop!(ConnectFour::join_guild(Origin::signed(2), 1), Error::<Test>::AlreadyInGuild);
+		assert_noop!(ConnectFour::join_guild(Origin::signed(5), 9), Error::<Test>::GuildNotFound);
+
+		// Only founders field lineups of their own members.
+		assert_noop!(
+			ConnectFour::challenge_guild(Origin::signed(2), 1, 10, 5, 2, vec![1, 2]),
+			Error::<Test>::OnlyGuildFounder
+		);
+		assert_noop!(
+			ConnectFour::challenge_guild(Origin::signed(1), 1, 10, 5, 2, vec![1, 3]),
+			Error::<Test>::InvalidLineup
+		);
+		assert_ok!(ConnectFour::challenge_guild(Origin::signed(1), 1, 10, 5, 2, vec![1, 2]));
+		assert_ok!(ConnectFour::resp_guild_challenge(Origin::signed(3), 0, false, vec![]));
+		System::assert_last_event(crate::Event::GuildChallengeRejected(0, 1).into());
+
+		assert_ok!(ConnectFour::challenge_guild(Origin::signed(1), 1, 10, 5, 2, vec![1, 2]));
+		assert_noop!(
+			ConnectFour::resp_guild_challenge(Origin::signed(3), 0, true, vec![3]),
+			Error::<Test>::InvalidLineup
+		);
+		assert_ok!(ConnectFour::resp_guild_challenge(Origin::signed(3), 0, true, vec![3, 4]));
+		System::assert_last_event(crate::Event::GuildMatchStarted(0, 0, 1).into());
+
+		// One win and one draw take the match.
+		let first = PlayerBoard::<Test>::get(1);
+		let second = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), first, Some(1)));
+		assert_eq!(ConnectFour::guilds(0).map(|guild| guild.rating), Some(0));
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), second, None));
+		System::assert_has_event(crate::Event::GuildMatchFinished(0, Some(0)).into());
+		assert_eq!(ConnectFour::guilds(0).map(|guild| guild.rating), Some(10));
+		assert_eq!(ConnectFour::guilds(1).map(|guild| guild.rating), Some(-5));
+
+		// Ranked games of the members count towards the guild rating.
+		assert_ok!(ConnectFour::challenge(
+			Origin::signed(5),
+			2,
+			10,
+			5,
+			2,
+			BoardVariant::default(),
+			None,
+			None,
+			FirstMovePolicy::Random,
+			GameKind::Ranked,
+			None,
+		));
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 5, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(2);
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(2)));
+		assert_eq!(ConnectFour::guilds(0).map(|guild| guild.rating), Some(20));
+
+		// The oldest member takes over, the last one disbands the guild.
+		assert_ok!(ConnectFour::leave_guild(Origin::signed(1)));
+		assert_eq!(ConnectFour::guilds(0).map(|guild| guild.founder), Some(2));
+		assert_ok!(ConnectFour::leave_guild(Origin::signed(2)));
+		assert_eq!(ConnectFour::guilds(0), None);
+		assert_noop!(ConnectFour::leave_guild(Origin::signed(2)), Error::<Test>::NotInGuild);
+	});
+}
+
+#[test]
+fn remote_players_play_through_their_local_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let challenge = crate::Call::<Test>::challenge {
+			opponent: 2,
+			win: 0,
+			lose: 0,
+			draw: 0,
+			variant: BoardVariant::default(),
+			time_bank: None,
+			handicap: None,
+			first_move: FirstMovePolicy::ResponderFirst,
+			kind: GameKind::Casual,
+			stake: None,
+		};
+		// Only origins of other chains may call in.
+		assert_noop!(
+			ConnectFour::remote_call(Origin::signed(1), Box::new(challenge.clone())),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ConnectFour::remote_call(Origin::signed(100), Box::new(challenge)));
+		assert_eq!(RemotePlayers::<Test>::get(1100), Some(100));
+		System::assert_has_event(crate::Event::RemotePlayerJoined(1100, 100).into());
+
+		assert_ok!(ConnectFour::resp_challenge(Origin::signed(2), 1100, true, AWARD));
+		let board_id = PlayerBoard::<Test>::get(1100);
+		assert_noop!(
+			ConnectFour::remote_call(
+				Origin::signed(100),
+				Box::new(crate::Call::<Test>::watch_board { board_id })
+			),
+			Error::<Test>::NotRemotePlayable
+		);
+
+		// The result goes back to the chain the player came from.
+		assert_ok!(ConnectFour::force_end_game(Origin::root(), board_id, Some(1100)));
+		assert_eq!(RemoteResults::get(), vec![(100, board_id, Some(true), FinishReason::Forced)]);
+	});
+}
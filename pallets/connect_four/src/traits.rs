@@ -0,0 +1,284 @@
+//! Integration points for other pallets, passed in through `Config`.
+
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{
+		tokens::{
+			fungibles::{Inspect, InspectHold, MutateHold, Transfer},
+			nonfungibles::Mutate,
+			AssetId, Balance, DepositConsequence, WithdrawConsequence,
+		},
+		Get, Time,
+	},
+};
+use sp_runtime::{DispatchError, TokenError};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use crate::{AwardState, FinishReason, GameKind, ReportReason};
+
+/// Hands out trophies to the winners of games.
+pub trait TrophyMinter<AccountId, Hash> {
+	/// Mint trophy `trophy_id` to `winner` for the game on `board_id`, which took `moves` moves.
+	fn mint_trophy(
+		trophy_id: u32,
+		winner: &AccountId,
+		board_id: Hash,
+		moves: u32,
+	) -> DispatchResult;
+}
+
+/// No trophies.
+impl<AccountId, Hash> TrophyMinter<AccountId, Hash> for () {
+	fn mint_trophy(_: u32, _: &AccountId, _: Hash, _: u32) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Identities of players, e.g. from `pallet_identity`.
+pub trait IdentityProvider<AccountId> {
+	/// Display name of the identity set by `who`, if any.
+	fn display_name(who: &AccountId) -> Option<Vec<u8>>;
+
+	/// A registrar gave the identity of `who` a positive judgement.
+	fn is_judged(who: &AccountId) -> bool;
+}
+
+/// No identities.
+impl<AccountId> IdentityProvider<AccountId> for () {
+	fn display_name(_: &AccountId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn is_judged(_: &AccountId) -> bool {
+		false
+	}
+}
+
+/// Creates signing keys and signatures for benchmarking `play_turn_signed`.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, AccountId, Signature, Moment> {
+	/// A new key to sign moves with, along with its account.
+	fn signer() -> (Public, AccountId);
+
+	/// Sign `message` with the key of `signer`.
+	fn sign(signer: &Public, message: &[u8]) -> Signature;
+
+	/// Move the game clock `by` forward.
+	fn advance_clock(by: Moment);
+}
+
+/// Notified about the result of every game, e.g. pallets handing out rewards, achievements or
+/// guild standings, or reporting results to the chain a remote player plays from. Implemented
+/// for tuples, so several handlers can be told, `()` tells nobody.
+pub trait OnGameFinished<AccountId, Hash> {
+	/// The `kind` game on `board_id` between `red` and `blue` ended for `reason`. `winner`
+	/// beat `loser`, both are `None` on a draw or a void game. Doubles teams are represented
+	/// by their leads.
+	fn on_game_finished(
+		board_id: Hash,
+		red: &AccountId,
+		blue: &AccountId,
+		winner: Option<&AccountId>,
+		loser: Option<&AccountId>,
+		kind: GameKind,
+		reason: FinishReason,
+	);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, Hash: Copy> OnGameFinished<AccountId, Hash> for Tuple {
+	fn on_game_finished(
+		board_id: Hash,
+		red: &AccountId,
+		blue: &AccountId,
+		winner: Option<&AccountId>,
+		loser: Option<&AccountId>,
+		kind: GameKind,
+		reason: FinishReason,
+	) {
+		for_tuples!( #(
+			Tuple::on_game_finished(board_id, red, blue, winner, loser, kind, reason);
+		)* );
+	}
+}
+
+/// Sends the results of their games to players on other chains, e.g. as an XCM message to the
+/// chain at their location.
+pub trait NotifyRemotePlayer<Location, Hash> {
+	/// The game on `board_id` of the player at `location` ended for `reason`. `won` tells
+	/// whether their team won, it's `None` on a draw or a void game.
+	fn notify_result(location: &Location, board_id: Hash, won: Option<bool>, reason: FinishReason);
+}
+
+/// No remote players.
+impl<Location, Hash> NotifyRemotePlayer<Location, Hash> for () {
+	fn notify_result(_: &Location, _: Hash, _: Option<bool>, _: FinishReason) {}
+}
+
+/// Notified about fair-play reports, e.g. an oracle pallet that rules on them through
+/// `Pallet::rule_on_report` once the game ended.
+pub trait OnGameReported<AccountId, Hash> {
+	/// `reporter` reported `reported` on the running board `board_id` for `reason`.
+	fn on_game_reported(
+		board_id: Hash,
+		reporter: &AccountId,
+		reported: &AccountId,
+		reason: ReportReason,
+	);
+}
+
+/// Reports are left to the admin origin.
+impl<AccountId, Hash> OnGameReported<AccountId, Hash> for () {
+	fn on_game_reported(_: Hash, _: &AccountId, _: &AccountId, _: ReportReason) {}
+}
+
+/// Award of a matchmade game, settled once the winner is known.
+pub trait AwardCalculator {
+	/// Award for a player scoring `winner_score` beating one scoring `loser_score`, `base` is
+	/// the matchmaking default.
+	fn award(base: &AwardState, winner_score: i32, loser_score: i32) -> AwardState;
+}
+
+/// Flat award, always `base`.
+impl AwardCalculator for () {
+	fn award(base: &AwardState, _: i32, _: i32) -> AwardState {
+		base.clone()
+	}
+}
+
+/// Shifts the base award by the score gap divided by `Divisor`: beating a stronger opponent
+/// wins more and losing to one costs less, and the other way around for weaker opponents.
+///
+/// Both win and loss stay between half and double their base amount, draws aren't weighed.
+pub struct ScoreWeightedAward<Divisor>(PhantomData<Divisor>);
+
+impl<Divisor: Get<u32>> AwardCalculator for ScoreWeightedAward<Divisor> {
+	fn award(base: &AwardState, winner_score: i32, loser_score: i32) -> AwardState {
+		let divisor = Divisor::get() as i64;
+		if divisor == 0 {
+			return base.clone()
+		}
+		let shift = (loser_score as i64 - winner_score as i64) / divisor;
+		let weigh = |amount: u32| {
+			let amount = amount as i64;
+			(amount + shift).clamp(amount / 2, amount * 2) as u32
+		};
+		AwardState { win: weigh(base.win), lose: weigh(base.lose), draw: base.draw }
+	}
+}
+
+/// Mints trophies as items of a `nonfungibles` collection, e.g. `pallet_uniques`.
+///
+/// The board id and move count are stored as the `board` and `moves` item attributes.
+pub struct NonfungibleTrophies<Nft, Collection>(PhantomData<(Nft, Collection)>);
+
+impl<AccountId, Hash, Nft, Collection> TrophyMinter<AccountId, Hash>
+	for NonfungibleTrophies<Nft, Collection>
+where
+	Hash: AsRef<[u8]>,
+	Nft: Mutate<AccountId>,
+	Nft::ItemId: From<u32>,
+	Collection: Get<Nft::CollectionId>,
+{
+	fn mint_trophy(
+		trophy_id: u32,
+		winner: &AccountId,
+		board_id: Hash,
+		moves: u32,
+	) -> DispatchResult {
+		let collection = Collection::get();
+		let item = trophy_id.into();
+		Nft::mint_into(&collection, &item, winner)?;
+		Nft::set_attribute(&collection, &item, b"board", board_id.as_ref())?;
+		Nft::set_attribute(&collection, &item, b"moves", &moves.encode())
+	}
+}
+
+/// Game clock counting blocks, turn deadlines and time banks are block numbers then. Use
+/// `pallet_timestamp` instead to time games in milliseconds.
+pub struct BlockClock<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> Time for BlockClock<T> {
+	type Moment = T::BlockNumber;
+
+	fn now() -> T::BlockNumber {
+		frame_system::Pallet::<T>::block_number()
+	}
+}
+
+/// No assets besides the native token, for chains without an assets pallet. Every asset is
+/// unknown and has no balance.
+pub struct NoAssets<Id, Amount>(PhantomData<(Id, Amount)>);
+
+impl<AccountId, Id: AssetId, Amount: Balance> Inspect<AccountId> for NoAssets<Id, Amount> {
+	type AssetId = Id;
+	type Balance = Amount;
+
+	fn total_issuance(_: Id) -> Amount {
+		Amount::default()
+	}
+
+	fn minimum_balance(_: Id) -> Amount {
+		Amount::default()
+	}
+
+	fn balance(_: Id, _: &AccountId) -> Amount {
+		Amount::default()
+	}
+
+	fn reducible_balance(_: Id, _: &AccountId, _: bool) -> Amount {
+		Amount::default()
+	}
+
+	fn can_deposit(_: Id, _: &AccountId, _: Amount, _: bool) -> DepositConsequence {
+		DepositConsequence::UnknownAsset
+	}
+
+	fn can_withdraw(_: Id, _: &AccountId, _: Amount) -> WithdrawConsequence<Amount> {
+		WithdrawConsequence::UnknownAsset
+	}
+}
+
+impl<AccountId, Id: AssetId, Amount: Balance> InspectHold<AccountId> for NoAssets<Id, Amount> {
+	fn balance_on_hold(_: Id, _: &AccountId) -> Amount {
+		Amount::default()
+	}
+
+	fn can_hold(_: Id, _: &AccountId, _: Amount) -> bool {
+		false
+	}
+}
+
+impl<AccountId, Id: AssetId, Amount: Balance> Transfer<AccountId> for NoAssets<Id, Amount> {
+	fn transfer(
+		_: Id,
+		_: &AccountId,
+		_: &AccountId,
+		_: Amount,
+		_: bool,
+	) -> Result<Amount, DispatchError> {
+		Err(TokenError::UnknownAsset.into())
+	}
+}
+
+impl<AccountId, Id: AssetId, Amount: Balance> MutateHold<AccountId> for NoAssets<Id, Amount> {
+	fn hold(_: Id, _: &AccountId, _: Amount) -> DispatchResult {
+		Err(TokenError::UnknownAsset.into())
+	}
+
+	fn release(_: Id, _: &AccountId, _: Amount, _: bool) -> Result<Amount, DispatchError> {
+		Err(TokenError::UnknownAsset.into())
+	}
+
+	fn transfer_held(
+		_: Id,
+		_: &AccountId,
+		_: &AccountId,
+		_: Amount,
+		_: bool,
+		_: bool,
+	) -> Result<Amount, DispatchError> {
+		Err(TokenError::UnknownAsset.into())
+	}
+}
@@ -0,0 +1,1830 @@
+//! Weights for pallet_connectfour
+//!
+//! These are conservative estimates until the benchmarks are run on reference hardware.
+//! Regenerate this file with:
+//!
+//! ./target/release/node-template benchmark pallet --chain dev --execution wasm --wasm-execution
+//! compiled --pallet pallet_connectfour --extrinsic '*' --steps 50 --repeat 20
+//! --output ./pallets/connect_four/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_connectfour.
+pub trait WeightInfo {
+	fn find_game() -> Weight;
+	fn match_queued_players(c: u32, ) -> Weight;
+	fn cancel_queue() -> Weight;
+	fn challenge() -> Weight;
+	fn resp_challenge() -> Weight;
+	fn cancel_challenge() -> Weight;
+	fn play_turn() -> Weight;
+	fn claim_timeout() -> Weight;
+	fn request_rematch() -> Weight;
+	fn accept_rematch() -> Weight;
+	fn watch_board() -> Weight;
+	fn unwatch_board() -> Weight;
+	fn decay_scores(c: u32, ) -> Weight;
+	fn distribute_season_rewards(n: u32, ) -> Weight;
+	fn play_vs_bot() -> Weight;
+	fn play_turn_vs_bot() -> Weight;
+	fn challenge_team() -> Weight;
+	fn resp_team_challenge() -> Weight;
+	fn force_end_game() -> Weight;
+	fn reset_score() -> Weight;
+	fn set_matchmaking_params() -> Weight;
+	fn commit_move() -> Weight;
+	fn reveal_move() -> Weight;
+	fn request_undo() -> Weight;
+	fn respond_undo() -> Weight;
+	fn create_open_game() -> Weight;
+	fn join_open_game() -> Weight;
+	fn close_open_game() -> Weight;
+	fn set_paused() -> Weight;
+	fn create_tournament() -> Weight;
+	fn join_tournament() -> Weight;
+	fn start_tournament(n: u32, ) -> Weight;
+	fn advance_tournament(n: u32, ) -> Weight;
+	fn expire_stale_game() -> Weight;
+	fn challenge_friend() -> Weight;
+	fn add_friend() -> Weight;
+	fn remove_friend() -> Weight;
+	fn block_player() -> Weight;
+	fn unblock_player() -> Weight;
+	fn prune_results(n: u32, ) -> Weight;
+	fn pop_stone() -> Weight;
+	fn set_profile() -> Weight;
+	fn play_turn_mid_game() -> Weight;
+	fn pop_stone_mid_game() -> Weight;
+	fn predict() -> Weight;
+	fn set_prediction_fee() -> Weight;
+	fn settle_predictions(p: u32, ) -> Weight;
+	fn check_move() -> Weight;
+	fn cleanup(n: u32, ) -> Weight;
+	fn create_pending_boards(c: u32, ) -> Weight;
+	fn check_in() -> Weight;
+	fn verify_relayed_move() -> Weight;
+	fn set_stake_fee() -> Weight;
+	fn set_abandon_penalty() -> Weight;
+	fn set_tier_boundaries() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn decay_inactive_scores(c: u32, ) -> Weight;
+	fn send_emote() -> Weight;
+	fn create_game_deterministic() -> Weight;
+	fn abort_game() -> Weight;
+	fn report_opponent() -> Weight;
+	fn resolve_report() -> Weight;
+	fn create_private_game() -> Weight;
+	fn join_private_game() -> Weight;
+	fn expire_private_games(n: u32, ) -> Weight;
+	fn withdraw_all() -> Weight;
+	fn create_guild() -> Weight;
+	fn join_guild() -> Weight;
+	fn leave_guild() -> Weight;
+	fn challenge_guild() -> Weight;
+	fn resp_guild_challenge(n: u32, ) -> Weight;
+	fn set_play_proxy() -> Weight;
+	fn remove_play_proxy() -> Weight;
+	fn offer_double() -> Weight;
+	fn respond_double() -> Weight;
+	fn claim_prize() -> Weight;
+	fn sweep_prizes(n: u32, ) -> Weight;
+	fn commit_first_move() -> Weight;
+	fn reveal_first_move() -> Weight;
+	fn sponsor_game() -> Weight;
+	fn settle_sponsorships(s: u32, ) -> Weight;
+	fn expire_queue(n: u32, ) -> Weight;
+	fn set_max_active_boards() -> Weight;
+	fn set_max_games_per_player() -> Weight;
+	fn swap_sides() -> Weight;
+	fn annotate_game() -> Weight;
+	fn release_annotations(a: u32, ) -> Weight;
+	fn snapshot_seeds(n: u32, ) -> Weight;
+	fn find_preset_game() -> Weight;
+	fn challenge_preset() -> Weight;
+	fn set_preset() -> Weight;
+	fn set_event_verbosity() -> Weight;
+	fn board_snapshot() -> Weight;
+	fn clear_game_trie(k: u32, ) -> Weight;
+}
+
+/// Weights for pallet_connectfour using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Challenges (r:1 w:0)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:1 w:0)
+	// Storage: ConnectFour MatchQueue (r:1 w:1)
+	// Storage: ConnectFour NewlyQueued (r:1 w:1)
+	// Storage: ConnectFour AccountCreated (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour CasualQueue (r:1 w:0)
+	// Storage: ConnectFour NextQueueTicket (r:1 w:1)
+	// Storage: ConnectFour QueueLength (r:1 w:1)
+	// Storage: ConnectFour QueueExpiries (r:1 w:1)
+	// Storage: ConnectFour CasualQueuedAt (r:0 w:1)
+	fn find_game() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ConnectFour NewlyQueued (r:1 w:1)
+	// Storage: ConnectFour QueuedPlayers (r:3 w:2)
+	// Storage: ConnectFour MatchQueue (r:6 w:2)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:0 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour Blocked (r:2 w:0)
+	// Storage: ConnectFour CasualQueue (r:1 w:1)
+	// Storage: ConnectFour PlayerProfile (r:2 w:0)
+	// Storage: ConnectFour QueueRechecks (r:1 w:1)
+	// Storage: ConnectFour TierBoundaries (r:1 w:0)
+	// Storage: ConnectFour AdjacentTierFallback (r:1 w:0)
+	// Storage: ConnectFour QueueLength (r:1 w:1)
+	// Storage: ConnectFour QueuedPresets (r:2 w:2)
+	// Storage: ConnectFour Presets (r:1 w:0)
+	fn match_queued_players(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			// Standard Error: 20_000
+			.saturating_add((78_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((24 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((13 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:1)
+	// Storage: ConnectFour MatchQueue (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour CasualQueue (r:1 w:1)
+	// Storage: ConnectFour QueueLength (r:1 w:1)
+	// Storage: ConnectFour CasualQueuedAt (r:0 w:1)
+	// Storage: ConnectFour QueuedPresets (r:0 w:1)
+	fn cancel_queue() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Challenges (r:2 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour Blocked (r:2 w:0)
+	// Storage: ConnectFour PlayerProfile (r:1 w:0)
+	// Storage: ConnectFour ScoringBoard (r:2 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Rematches (r:1 w:0)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour NextChallengeId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour FirstMoveCommits (r:1 w:0)
+	// Storage: ConnectFour ChallengeRates (r:1 w:1)
+	fn challenge() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Challenges (r:2 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour Rematches (r:1 w:0)
+	// Storage: ConnectFour PlayerProfile (r:1 w:0)
+	// Storage: ConnectFour MoveLogs (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour BoardStakes (r:0 w:1)
+	// Storage: ConnectFour FirstMoveCommits (r:2 w:2)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour MaxActiveBoards (r:1 w:0)
+	// Storage: ConnectFour MaxGamesPerPlayer (r:1 w:0)
+	fn resp_challenge() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Challenges (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_challenge() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:1 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour AbandonPenalty (r:1 w:0)
+	// Storage: ConnectFour MoveLogs (r:1 w:1)
+	// Storage: ConnectFour ArchivedGames (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour ArchivedSnapshots (r:0 w:1)
+	// Storage: ConnectFour PlayProxies (r:1 w:0)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour GameTrie (r:0 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn play_turn() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(38 as Weight))
+			.saturating_add(T::DbWeight::get().writes(40 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:2)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour AbandonPenalty (r:1 w:0)
+	// Storage: ConnectFour MoveLogs (r:1 w:1)
+	// Storage: ConnectFour ArchivedGames (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour ArchivedSnapshots (r:0 w:1)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn claim_timeout() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(35 as Weight))
+			.saturating_add(T::DbWeight::get().writes(39 as Weight))
+	}
+	// Storage: ConnectFour Rematches (r:1 w:1)
+	fn request_rematch() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Rematches (r:2 w:2)
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour QueuedPlayers (r:2 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour CasualQueue (r:2 w:0)
+	fn accept_rematch() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour Spectators (r:1 w:1)
+	fn watch_board() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Spectators (r:1 w:1)
+	fn unwatch_board() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PendingDecay (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:1 w:1)
+	// Storage: ConnectFour SeasonStandings (r:1 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:1 w:1)
+	fn decay_scores(c: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((12_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ConnectFour PendingDecay (r:1 w:0)
+	// Storage: ConnectFour SeasonStandings (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn distribute_season_rewards(n: u32, ) -> Weight {
+		(15_000_000 as Weight)
+			// Standard Error: 10_000
+			.saturating_add((25_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:1)
+	// Storage: ConnectFour Challenges (r:1 w:0)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour CasualQueue (r:1 w:0)
+	fn play_vs_bot() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:1 w:1)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour MoveLogs (r:1 w:1)
+	// Storage: ConnectFour ArchivedGames (r:0 w:1)
+	// Storage: ConnectFour ArchivedSnapshots (r:0 w:1)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:0)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour Spectators (r:1 w:0)
+	// Storage: ConnectFour TournamentBoards (r:1 w:0)
+	// Storage: ConnectFour GameTrie (r:0 w:1)
+	// Storage: ConnectFour StreamedGames (r:0 w:1)
+	fn play_turn_vs_bot() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Challenges (r:1 w:0)
+	// Storage: ConnectFour TeamChallenges (r:1 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour NextChallengeId (r:1 w:1)
+	fn challenge_team() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour TeamChallenges (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:4 w:4)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	fn resp_team_challenge() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour NextTrophyId (r:1 w:1)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn force_end_game() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(32 as Weight))
+			.saturating_add(T::DbWeight::get().writes(37 as Weight))
+	}
+	// Storage: ConnectFour ScoringBoard (r:0 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:1 w:1)
+	fn reset_score() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour AcceptedDiff (r:0 w:1)
+	// Storage: ConnectFour DefaultAward (r:0 w:1)
+	fn set_matchmaking_params() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	fn commit_move() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:2)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn reveal_move() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(31 as Weight))
+			.saturating_add(T::DbWeight::get().writes(34 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour UndoRequests (r:1 w:1)
+	// Storage: ConnectFour TournamentBoards (r:1 w:0)
+	fn request_undo() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour UndoRequests (r:1 w:1)
+	// Storage: ConnectFour MoveLogs (r:1 w:1)
+	// Storage: ConnectFour GameTrie (r:0 w:1)
+	fn respond_undo() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour OpenLobbies (r:1 w:1)
+	// Storage: ConnectFour NextLobbyId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	fn create_open_game() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour OpenLobbies (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour BoardStakes (r:0 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:0 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour PlayerProfile (r:1 w:0)
+	fn join_open_game() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ConnectFour OpenLobbies (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn close_open_game() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour Paused (r:0 w:1)
+	fn set_paused() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour NextTournamentId (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:0 w:1)
+	// Storage: ConnectFour TournamentCheckIns (r:1 w:1)
+	fn create_tournament() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn join_tournament() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour TournamentRounds (r:0 w:1)
+	// Storage: ConnectFour PendingBoardCreations (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour TournamentSeeds (r:1 w:1)
+	fn start_tournament(n: u32, ) -> Weight {
+		(24_000_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((17_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour TournamentRounds (r:0 w:1)
+	// Storage: ConnectFour PendingBoardCreations (r:0 w:1)
+	// Storage: ConnectFour TournamentPrizes (r:0 w:1)
+	fn advance_tournament(n: u32, ) -> Weight {
+		(26_000_000 as Weight)
+			// Standard Error: 16_000
+			.saturating_add((17_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour PlayerBoard (r:0 w:2)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour AbandonPenalty (r:1 w:0)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn expire_stale_game() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(32 as Weight))
+			.saturating_add(T::DbWeight::get().writes(36 as Weight))
+	}
+	// Storage: ConnectFour Friends (r:2 w:0)
+	// Storage: ConnectFour PlayerBoard (r:2 w:0)
+	// Storage: ConnectFour Challenges (r:2 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour DefaultAward (r:1 w:0)
+	// Storage: ConnectFour Blocked (r:2 w:0)
+	// Storage: ConnectFour NextChallengeId (r:1 w:1)
+	// Storage: ConnectFour ChallengeRates (r:1 w:1)
+	fn challenge_friend() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour Blocked (r:1 w:0)
+	// Storage: ConnectFour Friends (r:1 w:1)
+	fn add_friend() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Friends (r:1 w:1)
+	fn remove_friend() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Blocked (r:1 w:1)
+	// Storage: ConnectFour Friends (r:1 w:1)
+	fn block_player() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour Blocked (r:1 w:1)
+	fn unblock_player() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour ResultsByBlock (r:1 w:1)
+	// Storage: ConnectFour ArchivedGames (r:0 w:1)
+	// Storage: ConnectFour ArchivedSnapshots (r:0 w:1)
+	// Storage: ConnectFour GameAnnotations (r:1 w:1)
+	fn prune_results(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((4_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour AbandonPenalty (r:1 w:0)
+	// Storage: ConnectFour MoveLogs (r:1 w:1)
+	// Storage: ConnectFour ArchivedGames (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour ArchivedSnapshots (r:0 w:1)
+	// Storage: ConnectFour PlayProxies (r:1 w:0)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour GameTrie (r:0 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn pop_stone() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(37 as Weight))
+			.saturating_add(T::DbWeight::get().writes(40 as Weight))
+	}
+	// Storage: ConnectFour PlayerProfile (r:0 w:1)
+	fn set_profile() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:1 w:1)
+	// Storage: ConnectFour UndoRequests (r:0 w:1)
+	// Storage: ConnectFour PlayProxies (r:1 w:0)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:0)
+	// Storage: ConnectFour Spectators (r:1 w:0)
+	// Storage: ConnectFour TournamentBoards (r:1 w:0)
+	// Storage: ConnectFour GameTrie (r:0 w:1)
+	// Storage: ConnectFour StreamedGames (r:0 w:1)
+	fn play_turn_mid_game() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:1 w:1)
+	// Storage: ConnectFour UndoRequests (r:0 w:1)
+	// Storage: ConnectFour PlayProxies (r:1 w:0)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:0)
+	// Storage: ConnectFour Spectators (r:1 w:0)
+	// Storage: ConnectFour TournamentBoards (r:1 w:0)
+	// Storage: ConnectFour GameTrie (r:0 w:1)
+	// Storage: ConnectFour StreamedGames (r:0 w:1)
+	fn pop_stone_mid_game() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour Predictions (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn predict() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour PredictionFee (r:0 w:1)
+	fn set_prediction_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Predictions (r:1 w:1)
+	// Storage: ConnectFour PredictionFee (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn settle_predictions(p: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Boards (r:1 w:0)
+	fn check_move() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: ConnectFour PlayerBoard (r:1 w:1)
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:1)
+	// Storage: ConnectFour MatchQueue (r:1 w:1)
+	// Storage: ConnectFour CasualQueue (r:1 w:1)
+	// Storage: ConnectFour Rematches (r:1 w:1)
+	// Storage: ConnectFour Challenges (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn cleanup(n: u32, ) -> Weight {
+		(9_000_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((18_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour PendingBoardCreations (r:2 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour TournamentBoards (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour MaxActiveBoards (r:1 w:0)
+	// Storage: ConnectFour MaxGamesPerPlayer (r:1 w:0)
+	fn create_pending_boards(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((19_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ConnectFour Tournaments (r:1 w:0)
+	// Storage: ConnectFour TournamentPlayers (r:1 w:1)
+	fn check_in() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:0)
+	fn verify_relayed_move() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	// Storage: ConnectFour StakeFee (r:0 w:1)
+	fn set_stake_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour AbandonPenalty (r:0 w:1)
+	fn set_abandon_penalty() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour TierBoundaries (r:0 w:1)
+	// Storage: ConnectFour AdjacentTierFallback (r:0 w:1)
+	fn set_tier_boundaries() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn claim_rewards() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour InactivityCursor (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:1 w:1)
+	// Storage: ConnectFour TierBoundaries (r:1 w:0)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:1 w:1)
+	fn decay_inactive_scores(c: u32, ) -> Weight {
+		(8_000_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((11_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour Spectators (r:1 w:0)
+	// Storage: ConnectFour LastEmote (r:1 w:1)
+	fn send_emote() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour MoveLogs (r:0 w:1)
+	// Storage: ConnectFour Rematches (r:1 w:0)
+	// Storage: ConnectFour FirstMoveCommits (r:2 w:2)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour MaxActiveBoards (r:1 w:0)
+	// Storage: ConnectFour MaxGamesPerPlayer (r:1 w:0)
+	fn create_game_deterministic() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:2)
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour TournamentBoards (r:1 w:0)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour UndoRequests (r:0 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: ConnectFour MoveLogs (r:1 w:1)
+	// Storage: ConnectFour ArchivedGames (r:0 w:1)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour ArchivedSnapshots (r:0 w:1)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn abort_game() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(24 as Weight))
+			.saturating_add(T::DbWeight::get().writes(27 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	fn report_opponent() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Disputes (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour NextTrophyId (r:1 w:1)
+	// Storage: ConnectFour StakeFee (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn resolve_report() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour PrivateGames (r:1 w:1)
+	// Storage: ConnectFour PrivateGameExpiries (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create_private_game() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour PrivateGames (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Blocked (r:2 w:0)
+	// Storage: ConnectFour PlayerProfile (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour BoardStakes (r:0 w:1)
+	// Storage: ConnectFour MoveLogs (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour MaxActiveBoards (r:1 w:0)
+	// Storage: ConnectFour MaxGamesPerPlayer (r:1 w:0)
+	fn join_private_game() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: ConnectFour PrivateGameExpiries (r:1 w:1)
+	// Storage: ConnectFour PrivateGames (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn expire_private_games(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:1)
+	// Storage: ConnectFour MatchQueue (r:1 w:1)
+	// Storage: ConnectFour CasualQueue (r:1 w:1)
+	// Storage: ConnectFour QueueLength (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour Challenges (r:1 w:1)
+	// Storage: ConnectFour TeamChallenges (r:1 w:1)
+	// Storage: ConnectFour OpenLobbies (r:1 w:1)
+	// Storage: ConnectFour Rematches (r:1 w:1)
+	// Storage: ConnectFour CasualQueuedAt (r:0 w:1)
+	// Storage: ConnectFour QueuedPresets (r:0 w:1)
+	fn withdraw_all() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: ConnectFour PlayerGuild (r:1 w:1)
+	// Storage: ConnectFour NextGuildId (r:1 w:1)
+	// Storage: ConnectFour Guilds (r:0 w:1)
+	fn create_guild() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour PlayerGuild (r:1 w:1)
+	// Storage: ConnectFour Guilds (r:1 w:1)
+	fn join_guild() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour PlayerGuild (r:1 w:1)
+	// Storage: ConnectFour Guilds (r:1 w:1)
+	// Storage: ConnectFour GuildChallenges (r:0 w:1)
+	fn leave_guild() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour PlayerGuild (r:1 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:0)
+	// Storage: ConnectFour GuildChallenges (r:1 w:1)
+	fn challenge_guild() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PlayerGuild (r:1 w:0)
+	// Storage: ConnectFour Guilds (r:1 w:0)
+	// Storage: ConnectFour GuildChallenges (r:1 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour NextGuildMatchId (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour GuildMatchBoards (r:0 w:1)
+	// Storage: ConnectFour GuildMatches (r:0 w:1)
+	fn resp_guild_challenge(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour PlayProxies (r:1 w:1)
+	fn set_play_proxy() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PlayProxies (r:1 w:1)
+	fn remove_play_proxy() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour BoardStakes (r:1 w:0)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn offer_double() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour PlayerBoard (r:0 w:2)
+	// Storage: ConnectFour Spectators (r:0 w:1)
+	// Storage: ConnectFour ScoringBoard (r:2 w:2)
+	// Storage: ConnectFour RewardPoints (r:1 w:1)
+	// Storage: ConnectFour LastActive (r:0 w:2)
+	// Storage: ConnectFour NextTrophyId (r:1 w:1)
+	// Storage: ConnectFour Rematches (r:0 w:2)
+	// Storage: ConnectFour Leaderboard (r:1 w:1)
+	// Storage: ConnectFour PlayerStats (r:2 w:2)
+	// Storage: ConnectFour BoardStakes (r:1 w:1)
+	// Storage: ConnectFour BoardStakeAssets (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDeposits (r:2 w:2)
+	// Storage: ConnectFour TournamentBoards (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:1)
+	// Storage: ConnectFour TournamentPlayers (r:2 w:2)
+	// Storage: ConnectFour ResultsByBlock (r:0 w:1)
+	// Storage: ConnectFour Reports (r:1 w:1)
+	// Storage: ConnectFour Disputes (r:0 w:1)
+	// Storage: ConnectFour Achievements (r:1 w:1)
+	// Storage: ConnectFour ScoreHistory (r:2 w:2)
+	// Storage: ConnectFour GuildMatchBoards (r:1 w:1)
+	// Storage: ConnectFour GuildMatches (r:1 w:1)
+	// Storage: ConnectFour PlayerGuild (r:2 w:0)
+	// Storage: ConnectFour Guilds (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDraws (r:0 w:1)
+	// Storage: ConnectFour ActiveBoards (r:1 w:1)
+	// Storage: ConnectFour StreamedGames (r:1 w:1)
+	// Storage: ConnectFour GameTrieCleanups (r:0 w:1)
+	// Storage: ConnectFour RemotePlayers (r:4 w:0)
+	fn respond_double() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(33 as Weight))
+			.saturating_add(T::DbWeight::get().writes(38 as Weight))
+	}
+	// Storage: ConnectFour Tournaments (r:1 w:0)
+	// Storage: ConnectFour TournamentPrizes (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_prize() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour Tournaments (r:1 w:0)
+	// Storage: ConnectFour TournamentPrizes (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn sweep_prizes(n: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour FirstMoveCommits (r:0 w:1)
+	fn commit_first_move() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:1)
+	// Storage: ConnectFour FirstMoveDraws (r:1 w:1)
+	fn reveal_first_move() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:0)
+	// Storage: ConnectFour BoardSponsors (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn sponsor_game() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConnectFour BoardSponsors (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn settle_sponsorships(s: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((27_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: ConnectFour QueueExpiries (r:1 w:1)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:1)
+	// Storage: ConnectFour MatchQueue (r:1 w:1)
+	// Storage: ConnectFour CasualQueuedAt (r:1 w:1)
+	// Storage: ConnectFour CasualQueue (r:1 w:1)
+	// Storage: ConnectFour QueueLength (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour QueuedPresets (r:0 w:1)
+	fn expire_queue(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: ConnectFour MaxActiveBoards (r:0 w:1)
+	fn set_max_active_boards() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour MaxGamesPerPlayer (r:0 w:1)
+	fn set_max_games_per_player() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:1)
+	fn swap_sides() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour ArchivedGames (r:1 w:0)
+	// Storage: ConnectFour GameAnnotations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn annotate_game() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	fn release_annotations(a: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((21_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: ConnectFour TournamentCheckIns (r:1 w:1)
+	// Storage: ConnectFour Tournaments (r:1 w:0)
+	// Storage: ConnectFour TournamentPlayers (r:1 w:0)
+	// Storage: ConnectFour ScoringBoard (r:1 w:0)
+	// Storage: ConnectFour TournamentSeeds (r:0 w:1)
+	fn snapshot_seeds(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:1 w:0)
+	// Storage: ConnectFour Challenges (r:1 w:0)
+	// Storage: ConnectFour QueuedPlayers (r:1 w:1)
+	// Storage: ConnectFour ScoringBoard (r:1 w:0)
+	// Storage: ConnectFour MatchQueue (r:1 w:1)
+	// Storage: ConnectFour NewlyQueued (r:1 w:1)
+	// Storage: ConnectFour AccountCreated (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour CasualQueue (r:1 w:0)
+	// Storage: ConnectFour NextQueueTicket (r:1 w:1)
+	// Storage: ConnectFour QueueLength (r:1 w:1)
+	// Storage: ConnectFour QueueExpiries (r:1 w:1)
+	// Storage: ConnectFour CasualQueuedAt (r:0 w:1)
+	// Storage: ConnectFour Presets (r:1 w:0)
+	// Storage: ConnectFour QueuedPresets (r:0 w:1)
+	fn find_preset_game() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: ConnectFour PlayerBoard (r:2 w:2)
+	// Storage: ConnectFour Challenges (r:2 w:1)
+	// Storage: ConnectFour Paused (r:1 w:0)
+	// Storage: ConnectFour Blocked (r:2 w:0)
+	// Storage: ConnectFour PlayerProfile (r:1 w:0)
+	// Storage: ConnectFour ScoringBoard (r:2 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ConnectFour Nonce (r:1 w:1)
+	// Storage: ConnectFour Rematches (r:1 w:0)
+	// Storage: ConnectFour Boards (r:0 w:1)
+	// Storage: ConnectFour NextChallengeId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ConnectFour FirstMoveCommits (r:1 w:0)
+	// Storage: ConnectFour ChallengeRates (r:1 w:1)
+	// Storage: ConnectFour Presets (r:1 w:0)
+	fn challenge_preset() -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(18 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ConnectFour Presets (r:0 w:1)
+	fn set_preset() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConnectFour Boards (r:1 w:1)
+	fn set_event_verbosity() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn board_snapshot() -> Weight {
+		(6_000_000 as Weight)
+	}
+	// Storage: ConnectFour GameTrieCleanups (r:1 w:1)
+	// Storage: unknown [0x3a6368696c645f73746f726167653a64656661756c743a] (r:0 w:1)
+	fn clear_game_trie(k: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_000_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn find_game() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn match_queued_players(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((78_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((24 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((13 as Weight).saturating_mul(c as Weight)))
+	}
+	fn cancel_queue() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn challenge() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn resp_challenge() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn cancel_challenge() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn play_turn() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(38 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(40 as Weight))
+	}
+	fn claim_timeout() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(35 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(39 as Weight))
+	}
+	fn request_rematch() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accept_rematch() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn watch_board() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unwatch_board() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn decay_scores(c: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((12_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	fn distribute_season_rewards(n: u32, ) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add((25_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn play_vs_bot() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn play_turn_vs_bot() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn challenge_team() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn resp_team_challenge() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn force_end_game() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(32 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(37 as Weight))
+	}
+	fn reset_score() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_matchmaking_params() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn commit_move() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_move() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(31 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(34 as Weight))
+	}
+	fn request_undo() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn respond_undo() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn create_open_game() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn join_open_game() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn close_open_game() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_paused() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn create_tournament() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn join_tournament() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn start_tournament(n: u32, ) -> Weight {
+		(24_000_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((17_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn advance_tournament(n: u32, ) -> Weight {
+		(26_000_000 as Weight)
+			// Standard Error: 16_000
+			.saturating_add((17_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn expire_stale_game() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(32 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(36 as Weight))
+	}
+	fn challenge_friend() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn add_friend() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_friend() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn block_player() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn unblock_player() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn prune_results(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn pop_stone() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(37 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(40 as Weight))
+	}
+	fn set_profile() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn play_turn_mid_game() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn pop_stone_mid_game() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn predict() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_prediction_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn settle_predictions(p: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn check_move() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+	fn cleanup(n: u32, ) -> Weight {
+		(9_000_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((18_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn create_pending_boards(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((19_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	fn check_in() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn verify_relayed_move() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn set_stake_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_abandon_penalty() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_tier_boundaries() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn decay_inactive_scores(c: u32, ) -> Weight {
+		(8_000_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((11_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	fn send_emote() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn create_game_deterministic() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn abort_game() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(24 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(27 as Weight))
+	}
+	fn report_opponent() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resolve_report() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn create_private_game() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn join_private_game() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn expire_private_games(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn withdraw_all() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn create_guild() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn join_guild() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn leave_guild() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn challenge_guild() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resp_guild_challenge(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_play_proxy() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_play_proxy() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn offer_double() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn respond_double() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(33 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(38 as Weight))
+	}
+	fn claim_prize() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn sweep_prizes(n: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn commit_first_move() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_first_move() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn sponsor_game() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn settle_sponsorships(s: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((27_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+	}
+	fn expire_queue(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_max_active_boards() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_max_games_per_player() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn swap_sides() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn annotate_game() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn release_annotations(a: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((21_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	fn snapshot_seeds(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn find_preset_game() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn challenge_preset() -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(18 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn set_preset() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_event_verbosity() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn board_snapshot() -> Weight {
+		(6_000_000 as Weight)
+	}
+	fn clear_game_trie(k: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+	}
+}
@@ -15,7 +15,7 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -27,14 +27,14 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Nothing, Randomness,
-		StorageInfo,
+		ConstBool, ConstI32, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Nothing,
+		Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -197,9 +197,9 @@ impl frame_system::Config for Runtime {
 	/// This type is being generated by `construct_runtime!`.
 	type PalletInfo = PalletInfo;
 	/// What to do if a new account is created.
-	type OnNewAccount = ();
+	type OnNewAccount = ConnectFour;
 	/// What to do if an account is fully reaped from the system.
-	type OnKilledAccount = ();
+	type OnKilledAccount = ConnectFour;
 	/// The data to be stored in an account.
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// Weight information for the extrinsics of this pallet.
@@ -317,11 +317,166 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MatchmakingTimeBank: Option<BlockNumber> = Some(10 * MINUTES);
+	pub const SeasonScoreRetention: Perbill = Perbill::from_percent(50);
+	pub const InactivityScoreRetention: Perbill = Perbill::from_percent(90);
+	pub const DefaultMatchAward: pallet_connectfour::AwardState =
+		pallet_connectfour::AwardState { win: 10, lose: 5, draw: 2 };
+	pub const DefaultEventVerbosity: pallet_connectfour::EventVerbosity =
+		pallet_connectfour::EventVerbosity::Delta;
+	pub const ConnectFourPalletId: PalletId = PalletId(*b"py/cnct4");
+	pub const ConnectFourRewardsPalletId: PalletId = PalletId(*b"py/c4rwd");
+	pub const ConnectFourUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+/// Signs relayed moves with a fresh sr25519 key from the benchmark keystore.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ConnectFourBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_connectfour::BenchmarkHelper<sp_runtime::MultiSigner, AccountId, Signature, BlockNumber>
+	for ConnectFourBenchmarkHelper
+{
+	fn signer() -> (sp_runtime::MultiSigner, AccountId) {
+		let signer: sp_runtime::MultiSigner =
+			sp_io::crypto::sr25519_generate(0.into(), None).into();
+		(signer.clone(), signer.into_account())
+	}
+
+	fn sign(signer: &sp_runtime::MultiSigner, message: &[u8]) -> Signature {
+		match signer {
+			sp_runtime::MultiSigner::Sr25519(public) =>
+				sp_io::crypto::sr25519_sign(0.into(), public, message)
+					.expect("the key was generated by `signer`")
+					.into(),
+			_ => unreachable!("`signer` only creates sr25519 keys"),
+		}
+	}
+
+	fn advance_clock(by: BlockNumber) {
+		System::set_block_number(System::block_number() + by);
+	}
+}
+
 impl pallet_connectfour::Config for Runtime {
 	type Proposal = Call;
-    type Event = Event;
-    type Randomness = RandomnessCollectiveFlip;
+	type Event = Event;
+	type Randomness = RandomnessCollectiveFlip;
+	type MaxBoardWidth = ConstU8<10>;
+	type MaxBoardHeight = ConstU8<10>;
+	type MaxQueueLength = ConstU32<64>;
+	type MaxMatchesPerBlock = ConstU32<16>;
+	type RematchPeriod = ConstU32<{ 10 * MINUTES }>;
+	type MatchmakingTimeBank = MatchmakingTimeBank;
+	type Trophies = ();
+	type MaxSpectators = ConstU32<32>;
+	type Currency = Balances;
+	type MinRankedBalance = ConstU128<50_000>;
+	type QueueDeposit = ConstU128<500>;
+	type MinAccountAge = ConstU32<DAYS>;
+	type SeasonLength = ConstU32<{ 28 * DAYS }>;
+	type SeasonScoreRetention = SeasonScoreRetention;
+	type MaxDecayPerBlock = ConstU32<100>;
+	type MaxSeasonRewardees = ConstU32<10>;
+	type SeasonRewardOrigin = frame_system::EnsureRoot<AccountId>;
+	type PalletId = ConnectFourPalletId;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type DefaultAcceptedDiff = ConstU8<10>;
+	type QueueWideningPeriod = ConstU32<10>;
+	type QueueWideningStep = ConstU8<5>;
+	type MaxWidenedDiff = ConstU8<100>;
+	type QueueTimeout = ConstU32<{ HOURS }>;
+	type DefaultMatchAward = DefaultMatchAward;
+	type DefaultMaxActiveBoards = ConstU32<10_000>;
+	type DefaultMaxGamesPerPlayer = ConstU32<1>;
+	type MaxLeaderboardSize = ConstU32<100>;
+	// Turns are timed in blocks, `Timestamp` would time them in milliseconds.
+	type GameClock = pallet_connectfour::BlockClock<Runtime>;
+	type GameFinishedHandler = ConnectFourRewards;
+	// A standalone chain has no players on other chains.
+	type RemoteLocation = AccountId;
+	type RemoteOrigin = frame_system::EnsureNever<AccountId>;
+	type RemoteAccount = sp_runtime::traits::Identity;
+	type RemoteNotifier = ();
+	type ReportHandler = ();
+	type MaxOpenLobbies = ConstU32<50>;
+	type HandicapScoreGap = ConstU32<5>;
+	type ScoreFloor = ConstI32<0>;
+	type ProvisionalGames = ConstU32<10>;
+	type ProvisionalBoost = ConstU8<2>;
+	type MaxTournamentPlayers = ConstU32<64>;
+	type MaxTournamentRounds = ConstU32<10>;
+	type MaxTournamentsPerBlock = ConstU32<16>;
+	type StaleGameAge = ConstU32<{ 7 * DAYS }>;
+	type UnsignedPriority = ConnectFourUnsignedPriority;
+	type AwardCalculator = pallet_connectfour::ScoreWeightedAward<ConstU32<10>>;
+	type MaxFriends = ConstU32<100>;
+	type MaxBlocked = ConstU32<100>;
+	type ResultRetentionBlocks = ConstU32<{ 7 * DAYS }>;
+	type MaxArchivedMoves = ConstU32<256>;
+	type StallTurnTime = ConstU32<{ 2 * MINUTES }>;
+	type MaxStalledTurns = ConstU8<3>;
+	type MaxDisplayNameLength = ConstU32<32>;
+	type MaxPredictions = ConstU32<100>;
+	// There's no treasury yet, the house fee is burned like the transaction fees.
+	type PredictionFeeDestination = ();
+	type StakeFeeDestination = ();
+	type MaxCleanupAccounts = ConstU32<32>;
+	type CleanupReward = ConstU128<50>;
+	type Identity = ();
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ConnectFourBenchmarkHelper;
+	type RequireJudgedIdentity = ConstBool<false>;
+	type MaxBoardCreationsPerBlock = ConstU32<16>;
+	type MaxMovesPerGame = ConstU32<200>;
+	type RewardPointValue = ConstU128<10>;
+	type RewardVestingBlocks = ConstU32<{ DAYS }>;
+	type InactivityPeriod = ConstU32<{ 30 * DAYS }>;
+	type InactivityScoreRetention = InactivityScoreRetention;
+	type MaxInactivityChecks = ConstU32<50>;
+	type Assets = pallet_connectfour::NoAssets<u32, Balance>;
+	type EmoteCooldown = ConstU32<5>;
+	type ChallengeLifetime = ConstU32<{ DAYS }>;
+	type PrivateGameLifetime = ConstU32<{ DAYS }>;
+	type MaxPrivateGamesPerBlock = ConstU32<50>;
+	type MaxScoreHistory = ConstU32<100>;
+	type MaxGuildMembers = ConstU32<50>;
+	type MaxGuildMatchPlayers = ConstU32<5>;
+	type SnapshotInterval = ConstU32<8>;
+	type PrizeClaimPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxPrizeSweeps = ConstU32<32>;
+	type UnclaimedPrizeDestination = ();
+	type ChallengeCooldown = ConstU32<{ MINUTES }>;
+	type ChallengeEra = ConstU32<{ HOURS }>;
+	type MaxChallengesPerEra = ConstU32<20>;
+	type MaxSponsors = ConstU32<10>;
+	type AnnotationDeposit = ConstU128<100>;
+	type MaxAnnotations = ConstU32<16>;
+	type MaxAnnotationLength = ConstU32<64>;
+	type DefaultEventVerbosity = DefaultEventVerbosity;
+	type WeightInfo = pallet_connectfour::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_connectfour_rewards::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = ConnectFourRewardsPalletId;
+	type BaseReward = ConstU128<{ CENTS }>;
+	type StreakBonus = ConstU128<{ CENTS / 2 }>;
+	type MaxBonusStreak = ConstU32<10>;
+	type WeightInfo = pallet_connectfour_rewards::weights::SubstrateWeight<Runtime>;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
 }
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -340,7 +495,8 @@ construct_runtime!(
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
 		Contracts: pallet_contracts,
-		ConnectFour: pallet_connectfour
+		ConnectFour: pallet_connectfour,
+		ConnectFourRewards: pallet_connectfour_rewards
 	}
 );
 
@@ -360,6 +516,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_connectfour::CheckMove<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -386,6 +543,8 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
+		[pallet_connectfour, ConnectFour]
+		[pallet_connectfour_rewards, ConnectFourRewards]
 	);
 }
 
@@ -627,4 +786,84 @@ impl_runtime_apis! {
 	 Contracts::get_storage(address, key)
 	 }
   }
+
+	impl connectfour_runtime_api::ConnectFourApi<Block, AccountId, Hash, BlockNumber, BlockNumber>
+		for Runtime
+	{
+		fn get_board(board_id: Hash) -> Option<pallet_connectfour::BoardOf<Runtime>> {
+			ConnectFour::boards(board_id)
+		}
+
+		fn get_player_board(account: AccountId) -> Option<Hash> {
+			ConnectFour::player_board_id(&account)
+		}
+
+		fn boards_of(
+			accounts: Vec<AccountId>,
+		) -> Vec<Option<pallet_connectfour::BoardSummaryOf<Runtime>>> {
+			ConnectFour::boards_of(accounts)
+		}
+
+		fn active_board_ids(start_key: Option<Hash>, limit: u32) -> Vec<Hash> {
+			ConnectFour::active_board_ids(start_key, limit)
+		}
+
+		fn get_leaderboard(n: u32) -> Vec<(AccountId, i32)> {
+			ConnectFour::leaderboard(n)
+		}
+
+		fn get_named_leaderboard(n: u32) -> Vec<(AccountId, i32, Option<Vec<u8>>)> {
+			ConnectFour::named_leaderboard(n)
+		}
+
+		fn get_queue_status(account: AccountId) -> Option<pallet_connectfour::QueueStatus> {
+			ConnectFour::queue_status(&account)
+		}
+
+		fn get_queue_position(account: AccountId) -> Option<u32> {
+			ConnectFour::queue_position(&account)
+		}
+
+		fn get_queue_length() -> u32 {
+			ConnectFour::queue_length()
+		}
+
+		fn check_move(
+			account: AccountId,
+			column: u8,
+		) -> Result<u8, pallet_connectfour::MoveError> {
+			ConnectFour::dry_run_move(&account, column)
+		}
+
+		fn get_archived_game(board_id: Hash) -> Option<Vec<u8>> {
+			ConnectFour::archived_game(board_id).map(Into::into)
+		}
+
+		fn get_archived_position(
+			board_id: Hash,
+			moves: u32,
+		) -> Option<pallet_connectfour::gameplay::Board> {
+			ConnectFour::archived_position(board_id, moves)
+		}
+
+		fn evaluate_position(
+			board_id: Hash,
+		) -> Option<Vec<pallet_connectfour::gameplay::ColumnScore>> {
+			ConnectFour::evaluate_position(board_id)
+		}
+
+		fn legal_moves(board_id: Hash) -> u32 {
+			ConnectFour::legal_moves(board_id)
+		}
+
+		fn is_terminal(board_id: Hash) -> Option<pallet_connectfour::gameplay::Outcome> {
+			ConnectFour::is_terminal(board_id)
+		}
+
+		fn get_score_history(
+			account: AccountId,
+		) -> Vec<pallet_connectfour::ScoreChange<BlockNumber, Hash>> {
+			ConnectFour::score_history(&account).into_inner()
+		}
+	}
 }